@@ -0,0 +1,384 @@
+//! 買い方戦略のバックテスト
+//!
+//! 宣言的な戦略（対象レースの条件・賭式・買い目・賭け金ルール）を、SQLite に保存済みの
+//! レース結果と払戻（race_payouts）で古い順に再生し、的中率・回収率・最大ドローダウン・
+//! 最長連敗・競艇場別の成績を集計する。
+//!
+//! Tauri コマンド `run_backtest` と同じ処理をライブラリからも利用できる。
+//!
+//! ```no_run
+//! use bort_scraping_lib::backtest::{self, BacktestStrategy, CombinationRule, SearchParams, StakeRule};
+//!
+//! # async fn example() {
+//! let strategy = BacktestStrategy {
+//!     filters: SearchParams { venue_code: Some("01".to_string()), ..Default::default() },
+//!     bet_type: "trifecta".to_string(),
+//!     combinations: CombinationRule::Formation { first: vec![1], second: vec![2, 3], third: vec![2, 3, 4] },
+//!     stake: StakeRule::Flat { amount: 100 },
+//!     initial_bankroll: None,
+//! };
+//! let report = backtest::run_backtest(None, &strategy, Some("20250101"), Some("20251231")).await;
+//! # }
+//! ```
+
+use crate::error::AppError;
+use crate::models::open_api::BET_TYPES;
+use crate::services::open_api_service::OpenApiService;
+use std::collections::BTreeMap;
+
+pub use crate::models::backtest::{
+    BacktestRace, BacktestReport, BacktestStrategy, CombinationRule, StakeRule, VenueBacktestStats,
+};
+pub use crate::models::open_api::SearchParams;
+
+/// DB（db_path 省略時は data/open_api.db）の保存済みデータで戦略を検証（日付は YYYYMMDD）
+pub async fn run_backtest(
+    db_path: Option<&str>,
+    strategy: &BacktestStrategy,
+    date_from: Option<&str>,
+    date_to: Option<&str>,
+) -> Result<BacktestReport, AppError> {
+    OpenApiService::new(db_path)
+        .await?
+        .run_backtest(strategy, date_from, date_to)
+        .await
+}
+
+/// 戦略を検証済みの買い目に展開し、レースを再生する
+pub struct BacktestEngine {
+    strategy: BacktestStrategy,
+    combinations: Vec<String>,
+}
+
+impl BacktestEngine {
+    /// 賭式・買い目・賭け金ルールを検証して買い目を展開
+    pub fn new(strategy: &BacktestStrategy) -> Result<Self, AppError> {
+        let bet_type = strategy.bet_type.as_str();
+        if !BET_TYPES.contains(&bet_type) {
+            return Err(AppError::invalid_input(
+                "bet_type",
+                format!("Invalid bet_type: '{}'. Expected one of {:?}", bet_type, BET_TYPES),
+            ));
+        }
+
+        match strategy.stake {
+            StakeRule::Flat { amount } if amount <= 0 || amount % 100 != 0 => {
+                return Err(AppError::invalid_input(
+                    "stake",
+                    format!("Flat stake must be a positive multiple of 100 yen: {}", amount),
+                ));
+            }
+            StakeRule::Proportional { fraction } => {
+                if !(fraction > 0.0 && fraction <= 1.0) {
+                    return Err(AppError::invalid_input(
+                        "stake",
+                        format!("Proportional fraction must be in (0, 1]: {}", fraction),
+                    ));
+                }
+                if strategy.initial_bankroll.unwrap_or(0) <= 0 {
+                    return Err(AppError::invalid_input(
+                        "initial_bankroll",
+                        "Proportional stake requires a positive initial_bankroll",
+                    ));
+                }
+            }
+            StakeRule::Flat { .. } => {}
+        }
+
+        let combinations = expand_combinations(bet_type, &strategy.combinations)
+            .map_err(|message| AppError::invalid_input("combinations", message))?;
+
+        Ok(Self {
+            strategy: strategy.clone(),
+            combinations,
+        })
+    }
+
+    /// 1レースあたりの買い目
+    pub fn combinations(&self) -> &[String] {
+        &self.combinations
+    }
+
+    /// レースを順に再生して集計（races は古い順）
+    pub fn run(&self, races: &[BacktestRace]) -> BacktestReport {
+        let tickets = self.combinations.len() as i64;
+        let mut report = BacktestReport {
+            bet_type: self.strategy.bet_type.clone(),
+            combinations: self.combinations.clone(),
+            ..Default::default()
+        };
+        let mut venues: BTreeMap<&str, VenueBacktestStats> = BTreeMap::new();
+
+        let mut bankroll = self.strategy.initial_bankroll.unwrap_or(0);
+        let mut peak = bankroll;
+        let mut losing_streak = 0;
+
+        for race in races {
+            // 払戻がない（不成立・データ欠損）レースは購入しない
+            if race.payouts.is_empty() {
+                report.skipped_race_count += 1;
+                continue;
+            }
+
+            let stake_per_ticket = match self.strategy.stake {
+                StakeRule::Flat { amount } => amount,
+                StakeRule::Proportional { fraction } => {
+                    let budget = bankroll as f64 * fraction / tickets as f64;
+                    (budget / 100.0).floor() as i64 * 100
+                }
+            };
+            if stake_per_ticket <= 0 {
+                report.skipped_race_count += 1;
+                continue;
+            }
+
+            let race_stake = stake_per_ticket * tickets;
+            let race_return: i64 = race
+                .payouts
+                .iter()
+                .filter(|(combination, _)| self.combinations.contains(combination))
+                .map(|(_, payout)| *payout as i64 * stake_per_ticket / 100)
+                .sum();
+            let hit = race_return > 0;
+
+            report.race_count += 1;
+            report.bet_count += tickets;
+            report.total_stake += race_stake;
+            report.total_return += race_return;
+            if hit {
+                report.hit_count += 1;
+                losing_streak = 0;
+            } else {
+                losing_streak += 1;
+                report.longest_losing_streak = report.longest_losing_streak.max(losing_streak);
+            }
+
+            bankroll += race_return - race_stake;
+            peak = peak.max(bankroll);
+            let drawdown = peak - bankroll;
+            report.max_drawdown = report.max_drawdown.max(drawdown);
+            if self.strategy.initial_bankroll.is_some() && peak > 0 {
+                let rate = drawdown as f64 / peak as f64;
+                report.max_drawdown_rate = Some(report.max_drawdown_rate.unwrap_or(0.0).max(rate));
+            }
+
+            let venue = venues.entry(race.venue_code.as_str()).or_insert_with(|| VenueBacktestStats {
+                venue_code: race.venue_code.clone(),
+                ..Default::default()
+            });
+            venue.race_count += 1;
+            venue.total_stake += race_stake;
+            venue.total_return += race_return;
+            if hit {
+                venue.hit_count += 1;
+            }
+        }
+
+        report.hit_rate = rate(report.hit_count, report.race_count).unwrap_or(0.0);
+        report.profit = report.total_return - report.total_stake;
+        report.roi = rate(report.total_return, report.total_stake);
+        report.final_bankroll = self.strategy.initial_bankroll.map(|_| bankroll);
+        report.venues = venues
+            .into_values()
+            .map(|mut venue| {
+                venue.hit_rate = rate(venue.hit_count, venue.race_count).unwrap_or(0.0);
+                venue.roi = rate(venue.total_return, venue.total_stake);
+                venue
+            })
+            .collect();
+        report
+    }
+}
+
+fn rate(numerator: i64, denominator: i64) -> Option<f64> {
+    (denominator > 0).then(|| numerator as f64 / denominator as f64)
+}
+
+/// 買い目を race_payouts の組番形式（着順あり "1-2-3"、着順なし "1=2=3"）に展開
+fn expand_combinations(bet_type: &str, rule: &CombinationRule) -> Result<Vec<String>, String> {
+    let (arity, ordered) = match bet_type {
+        "win" | "place" => (1, true),
+        "exacta" => (2, true),
+        "quinella" | "quinella_place" => (2, false),
+        "trifecta" => (3, true),
+        _ => (3, false), // trio
+    };
+
+    let candidates: Vec<Vec<u8>> = match rule {
+        CombinationRule::Fixed { combinations } => combinations
+            .iter()
+            .map(|combination| {
+                let boats = combination
+                    .split(['-', '='])
+                    .map(|boat| boat.trim().parse::<u8>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|_| format!("Invalid combination: '{}'", combination))?;
+                if boats.len() != arity {
+                    return Err(format!(
+                        "Combination '{}' must have {} boat(s) for {}",
+                        combination, arity, bet_type
+                    ));
+                }
+                Ok(boats)
+            })
+            .collect::<Result<_, _>>()?,
+        CombinationRule::Box { boats } => {
+            let positions = vec![boats.clone(); arity];
+            cartesian(&positions)
+        }
+        CombinationRule::Formation { first, second, third } => {
+            let positions = [first.clone(), second.clone(), third.clone()];
+            if positions[..arity].iter().any(Vec::is_empty) {
+                return Err(format!("Formation needs boats for the first {} place(s)", arity));
+            }
+            cartesian(&positions[..arity])
+        }
+    };
+
+    let mut combinations: Vec<String> = Vec::new();
+    for mut boats in candidates {
+        if let Some(boat) = boats.iter().find(|boat| !(1..=6).contains(*boat)) {
+            return Err(format!("Boat number must be 1-6: {}", boat));
+        }
+        let mut unique = boats.clone();
+        unique.sort_unstable();
+        unique.dedup();
+        if unique.len() != boats.len() {
+            continue; // 同じ艇を複数の着順に含む組み合わせは買えない
+        }
+        if !ordered {
+            boats = unique;
+        }
+        let separator = if ordered { "-" } else { "=" };
+        let combination = boats.iter().map(u8::to_string).collect::<Vec<_>>().join(separator);
+        if !combinations.contains(&combination) {
+            combinations.push(combination);
+        }
+    }
+
+    if combinations.is_empty() {
+        return Err("No valid combinations to buy".to_string());
+    }
+    Ok(combinations)
+}
+
+/// 着順ごとの候補艇の直積
+fn cartesian(positions: &[Vec<u8>]) -> Vec<Vec<u8>> {
+    positions.iter().fold(vec![Vec::new()], |acc, boats| {
+        acc.iter()
+            .flat_map(|prefix| {
+                boats.iter().map(move |&boat| {
+                    let mut next = prefix.clone();
+                    next.push(boat);
+                    next
+                })
+            })
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strategy(bet_type: &str, combinations: CombinationRule, stake: StakeRule) -> BacktestStrategy {
+        BacktestStrategy {
+            filters: SearchParams::default(),
+            bet_type: bet_type.to_string(),
+            combinations,
+            stake,
+            initial_bankroll: None,
+        }
+    }
+
+    fn race(id: i64, venue_code: &str, payouts: &[(&str, i32)]) -> BacktestRace {
+        BacktestRace {
+            race_id: id,
+            race_date: "20251228".to_string(),
+            venue_code: venue_code.to_string(),
+            race_number: id as i32,
+            payouts: payouts.iter().map(|(c, p)| (c.to_string(), *p)).collect(),
+        }
+    }
+
+    #[test]
+    fn test_expand_combinations() {
+        let boxed = expand_combinations("trifecta", &CombinationRule::Box { boats: vec![1, 2, 3] }).unwrap();
+        assert_eq!(boxed.len(), 6);
+        assert_eq!(boxed[0], "1-2-3");
+
+        let boxed = expand_combinations("trio", &CombinationRule::Box { boats: vec![3, 1, 2, 4] }).unwrap();
+        assert_eq!(boxed, vec!["1=2=3", "1=3=4", "2=3=4", "1=2=4"]);
+
+        let formation = CombinationRule::Formation { first: vec![1], second: vec![2, 3], third: vec![2, 3, 4] };
+        let formation = expand_combinations("trifecta", &formation).unwrap();
+        assert_eq!(formation, vec!["1-2-3", "1-2-4", "1-3-2", "1-3-4"]);
+
+        let fixed = CombinationRule::Fixed { combinations: vec!["6=2".to_string(), "2-6".to_string()] };
+        assert_eq!(expand_combinations("quinella_place", &fixed).unwrap(), vec!["2=6"]);
+
+        let invalid = CombinationRule::Fixed { combinations: vec!["1-7".to_string()] };
+        assert!(expand_combinations("exacta", &invalid).is_err());
+        let invalid = CombinationRule::Fixed { combinations: vec!["1-2".to_string()] };
+        assert!(expand_combinations("trifecta", &invalid).is_err());
+    }
+
+    #[test]
+    fn test_replay_metrics() {
+        let engine = BacktestEngine::new(&strategy(
+            "win",
+            CombinationRule::Fixed { combinations: vec!["1".to_string()] },
+            StakeRule::Flat { amount: 200 },
+        ))
+        .unwrap();
+        let races = vec![
+            race(1, "01", &[("1", 150)]),
+            race(2, "01", &[("3", 900)]),
+            race(3, "02", &[("4", 1200)]),
+            race(4, "02", &[]),
+            race(5, "02", &[("2", 300)]),
+            race(6, "01", &[("1", 500)]),
+        ];
+        let report = engine.run(&races);
+
+        assert_eq!(report.race_count, 5);
+        assert_eq!(report.skipped_race_count, 1);
+        assert_eq!(report.hit_count, 2);
+        assert_eq!(report.total_stake, 1000);
+        assert_eq!(report.total_return, 300 + 1000);
+        assert_eq!(report.profit, 300);
+        assert_eq!(report.roi, Some(1.3));
+        // +100 → -100 → -300 → -500 → +300
+        assert_eq!(report.max_drawdown, 600);
+        assert_eq!(report.longest_losing_streak, 3);
+        assert_eq!(report.final_bankroll, None);
+
+        assert_eq!(report.venues.len(), 2);
+        assert_eq!(report.venues[0].venue_code, "01");
+        assert_eq!(report.venues[0].hit_count, 2);
+        assert_eq!(report.venues[1].roi, Some(0.0));
+    }
+
+    #[test]
+    fn test_proportional_stake_and_validation() {
+        let mut proportional = strategy(
+            "exacta",
+            CombinationRule::Box { boats: vec![1, 2] },
+            StakeRule::Proportional { fraction: 0.1 },
+        );
+        assert!(BacktestEngine::new(&proportional).is_err(), "初期資金が必要");
+
+        proportional.initial_bankroll = Some(10_000);
+        let engine = BacktestEngine::new(&proportional).unwrap();
+        let report = engine.run(&[race(1, "01", &[("1-2", 500)]), race(2, "01", &[("3-4", 2000)])]);
+        // 1R: 資金の10%を2点に配分して500円ずつ → 2500円の払戻、2R: 11500円の10%を2点で575円 → 500円に切り捨て
+        assert_eq!(report.total_stake, 1000 + 1000);
+        assert_eq!(report.final_bankroll, Some(10_000 + 1500 - 1000));
+        assert_eq!(report.max_drawdown_rate, Some(1000.0 / 11_500.0));
+
+        let invalid = strategy("trifecta_box", CombinationRule::Box { boats: vec![1] }, StakeRule::Flat { amount: 100 });
+        assert!(matches!(BacktestEngine::new(&invalid), Err(AppError::InvalidInput { .. })));
+        let invalid = strategy("win", CombinationRule::Box { boats: vec![1] }, StakeRule::Flat { amount: 150 });
+        assert!(BacktestEngine::new(&invalid).is_err());
+    }
+}
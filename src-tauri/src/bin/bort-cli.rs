@@ -0,0 +1,4 @@
+// GUI を使わずにサービス層を実行する CLI（cron などからの定期収集用）
+fn main() {
+    bort_scraping_lib::cli::run()
+}
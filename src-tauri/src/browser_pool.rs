@@ -0,0 +1,259 @@
+use headless_chrome::{Browser, LaunchOptions, Tab};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, OnceLock};
+use std::time::Duration;
+
+/// 同時に開けるタブ数の上限（デフォルト）
+const DEFAULT_MAX_TABS: usize = 4;
+
+/// 無通信状態でブラウザを維持する時間（headless_chromeのデフォルトは30秒）
+const IDLE_BROWSER_TIMEOUT: Duration = Duration::from_secs(600);
+
+static GLOBAL_POOL: OnceLock<BrowserPool> = OnceLock::new();
+
+/// ヘッドレスChromeを使い回すためのブラウザプール
+///
+/// - ブラウザは初回利用時に起動し、以降は同じプロセスを再利用する
+/// - 使い終わったタブは待機リストに戻し、次のフェッチで再利用する
+/// - ブラウザがクラッシュした場合は破棄して次回利用時に再起動する
+/// - 同時に使用中のタブ数は `max_tabs` で制限する
+pub struct BrowserPool {
+    max_tabs: usize,
+    state: Mutex<PoolState>,
+    tab_released: Condvar,
+}
+
+struct PoolState {
+    browser: Option<Browser>,
+    idle_tabs: Vec<Arc<Tab>>,
+    active_tabs: usize,
+    // ブラウザを再起動するたびに増える世代番号（旧ブラウザのタブを待機リストに戻さないため）
+    generation: u64,
+}
+
+struct PooledTab {
+    tab: Arc<Tab>,
+    generation: u64,
+}
+
+impl BrowserPool {
+    pub fn new(max_tabs: usize) -> Self {
+        BrowserPool {
+            max_tabs: max_tabs.max(1),
+            state: Mutex::new(PoolState {
+                browser: None,
+                idle_tabs: Vec::new(),
+                active_tabs: 0,
+                generation: 0,
+            }),
+            tab_released: Condvar::new(),
+        }
+    }
+
+    /// アプリ全体で共有するプール
+    pub fn global() -> &'static BrowserPool {
+        GLOBAL_POOL.get_or_init(|| BrowserPool::new(DEFAULT_MAX_TABS))
+    }
+
+    /// タブを1つ借りて処理を実行する
+    ///
+    /// 上限までタブが使用中の場合は空きが出るまで待機する。
+    /// 処理が失敗したタブは閉じ、ブラウザが応答しなければ再起動対象にする。
+    /// 処理がパニックした場合も失敗として扱い、タブの枠は必ず返す。
+    pub fn with_tab<T, F>(&self, f: F) -> Result<T, Box<dyn std::error::Error>>
+    where
+        F: FnOnce(&Tab) -> Result<T, Box<dyn std::error::Error>>,
+    {
+        let mut lease = self.acquire_slot();
+        let opened = {
+            let mut state = self.lock_state();
+            Self::open_tab(&mut state)
+        };
+        let tab = Arc::clone(&lease.tab.insert(opened?).tab);
+
+        let result = f(&tab);
+        lease.succeeded = result.is_ok();
+        result
+    }
+
+    fn lock_state(&self) -> MutexGuard<'_, PoolState> {
+        // パニックしたスレッドがあってもプールの状態自体は壊れないため、そのまま使う
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// 使用中タブの枠を1つ確保する（上限に達していれば空くまで待つ）
+    fn acquire_slot(&self) -> TabLease<'_> {
+        let mut state = self.lock_state();
+        while state.active_tabs >= self.max_tabs {
+            state = self
+                .tab_released
+                .wait(state)
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+        state.active_tabs += 1;
+
+        TabLease {
+            pool: self,
+            tab: None,
+            succeeded: false,
+        }
+    }
+
+    fn open_tab(state: &mut PoolState) -> Result<PooledTab, Box<dyn std::error::Error>> {
+        let browser = Self::ensure_browser(state)?;
+
+        if let Some(tab) = state.idle_tabs.pop() {
+            return Ok(PooledTab {
+                tab,
+                generation: state.generation,
+            });
+        }
+
+        let tab = browser.new_tab()?;
+        Ok(PooledTab {
+            tab,
+            generation: state.generation,
+        })
+    }
+
+    /// 稼働中のブラウザを返す。未起動または応答しない場合は起動し直す
+    fn ensure_browser(state: &mut PoolState) -> Result<Browser, Box<dyn std::error::Error>> {
+        if let Some(browser) = &state.browser {
+            if browser.get_version().is_ok() {
+                return Ok(browser.clone());
+            }
+            eprintln!("⚠️ ブラウザが応答しないため再起動します");
+            Self::recycle(state);
+        }
+
+        eprintln!("🚀 ヘッドレスブラウザを起動します");
+        let browser = Browser::new(LaunchOptions {
+            idle_browser_timeout: IDLE_BROWSER_TIMEOUT,
+            ..LaunchOptions::default()
+        })?;
+        state.browser = Some(browser.clone());
+        Ok(browser)
+    }
+
+    fn recycle(state: &mut PoolState) {
+        state.browser = None;
+        state.idle_tabs.clear();
+        state.generation += 1;
+    }
+
+    /// 借りたタブを待機リストに戻す（失敗したタブは閉じる）
+    ///
+    /// タブを閉じる処理とブラウザの応答確認はロックの外で行い、
+    /// 応答しないブラウザがあっても他のタブの確保・返却を止めないようにする。
+    fn return_tab(&self, pooled: PooledTab, succeeded: bool) {
+        let browser = {
+            let mut state = self.lock_state();
+            if pooled.generation != state.generation {
+                return;
+            }
+            if succeeded {
+                state.idle_tabs.push(pooled.tab);
+                return;
+            }
+            state.browser.clone()
+        };
+
+        let _ = pooled.tab.close(false);
+        let browser_alive = browser.is_some_and(|browser| browser.get_version().is_ok());
+        if !browser_alive {
+            let mut state = self.lock_state();
+            // 確認中に別のスレッドが再起動していれば、新しいブラウザは破棄しない
+            if state.generation == pooled.generation {
+                Self::recycle(&mut state);
+            }
+        }
+    }
+}
+
+/// 確保したタブの枠。drop 時に枠を返し、タブがあれば待機リストに戻す
+struct TabLease<'a> {
+    pool: &'a BrowserPool,
+    tab: Option<PooledTab>,
+    succeeded: bool,
+}
+
+impl Drop for TabLease<'_> {
+    fn drop(&mut self) {
+        if let Some(pooled) = self.tab.take() {
+            self.pool.return_tab(pooled, self.succeeded);
+        }
+        self.pool.lock_state().active_tabs -= 1;
+        self.pool.tab_released.notify_one();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    fn active_tabs(pool: &BrowserPool) -> usize {
+        pool.lock_state().active_tabs
+    }
+
+    #[test]
+    fn test_acquire_and_release_slot() {
+        let pool = BrowserPool::new(2);
+        let first = pool.acquire_slot();
+        let second = pool.acquire_slot();
+        assert_eq!(active_tabs(&pool), 2);
+
+        drop(first);
+        assert_eq!(active_tabs(&pool), 1);
+        drop(second);
+        assert_eq!(active_tabs(&pool), 0);
+    }
+
+    #[test]
+    fn test_slot_released_on_panic() {
+        let pool = BrowserPool::new(1);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _lease = pool.acquire_slot();
+            panic!("スクレイピング処理のパニック");
+        }));
+        assert!(result.is_err());
+        assert_eq!(active_tabs(&pool), 0);
+
+        // 枠が戻っているので待たずに確保できる
+        let _lease = pool.acquire_slot();
+        assert_eq!(active_tabs(&pool), 1);
+    }
+
+    #[test]
+    fn test_acquire_waits_for_max_tabs() {
+        let pool = Arc::new(BrowserPool::new(1));
+        let held = pool.acquire_slot();
+
+        let (sender, receiver) = mpsc::channel();
+        let waiter = {
+            let pool = Arc::clone(&pool);
+            std::thread::spawn(move || {
+                let _lease = pool.acquire_slot();
+                sender.send(()).unwrap();
+            })
+        };
+
+        // 上限に達している間は確保できない
+        assert!(receiver.recv_timeout(Duration::from_millis(200)).is_err());
+        drop(held);
+        receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+        waiter.join().unwrap();
+        assert_eq!(active_tabs(&pool), 0);
+    }
+
+    #[test]
+    fn test_recycle_discards_browser_state() {
+        let pool = BrowserPool::new(1);
+        let mut state = pool.lock_state();
+        BrowserPool::recycle(&mut state);
+        assert!(state.browser.is_none());
+        assert!(state.idle_tabs.is_empty());
+        assert_eq!(state.generation, 1);
+    }
+}
@@ -0,0 +1,161 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
+use std::time::Duration;
+
+/// キャッシュするデータの種類（LocalDb のキーの末尾と同じ）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheKind {
+    /// レースデータ（出走表・選手成績）
+    RaceData,
+    /// 単勝・複勝オッズ
+    Odds,
+}
+
+impl CacheKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CacheKind::RaceData => "data",
+            CacheKind::Odds => "odds",
+        }
+    }
+}
+
+/// キャッシュの鮮度ポリシー
+///
+/// - レースデータ: レース日より後に取得したものは以後変わらないため期限なし。レース日当日までは
+///   直前情報で更新されるため `race_data_ttl` で再取得し、レース日を過ぎたら一度だけ再取得する。
+/// - オッズ: 締切前は `odds_ttl` で再取得し、締切後に取得したもの（確定オッズ）は期限なし。
+///
+/// 取得時刻のない旧キャッシュは、レース日を過ぎていれば新しいものとして扱う。
+#[derive(Debug, Clone)]
+pub struct CachePolicy {
+    pub race_data_ttl: Duration,
+    pub odds_ttl: Duration,
+}
+
+impl Default for CachePolicy {
+    fn default() -> Self {
+        CachePolicy {
+            race_data_ttl: Duration::from_secs(30 * 60),
+            odds_ttl: Duration::from_secs(60),
+        }
+    }
+}
+
+impl CachePolicy {
+    /// キャッシュがまだ使えるか（時刻はすべて日本時間）
+    ///
+    /// `closes_at` はオッズの締切時刻。不明な場合はレース日の終わりを締切とみなす。
+    pub fn is_fresh(
+        &self,
+        kind: CacheKind,
+        race_date: NaiveDate,
+        fetched_at: Option<NaiveDateTime>,
+        closes_at: Option<NaiveDateTime>,
+        now: NaiveDateTime,
+    ) -> bool {
+        let Some(fetched_at) = fetched_at else {
+            return now.date() > race_date;
+        };
+        let within = |ttl: Duration| {
+            (now - fetched_at)
+                .to_std()
+                .map(|elapsed| elapsed < ttl)
+                .unwrap_or(true)
+        };
+
+        match kind {
+            CacheKind::RaceData => {
+                if fetched_at.date() > race_date {
+                    true
+                } else if now.date() > race_date {
+                    false
+                } else {
+                    within(self.race_data_ttl)
+                }
+            }
+            CacheKind::Odds => {
+                let closes_at = closes_at
+                    .unwrap_or_else(|| race_date.and_time(NaiveTime::MIN) + chrono::Duration::days(1));
+                if fetched_at >= closes_at {
+                    true
+                } else if now >= closes_at {
+                    false
+                } else {
+                    within(self.odds_ttl)
+                }
+            }
+        }
+    }
+}
+
+/// RFC3339 の取得時刻を日本時間に変換
+pub fn fetched_at_jst(fetched_at: &str) -> Option<NaiveDateTime> {
+    let jst = FixedOffset::east_opt(9 * 3600)?;
+    DateTime::parse_from_rfc3339(fetched_at)
+        .ok()
+        .map(|time| time.with_timezone(&jst).naive_local())
+}
+
+/// キャッシュの日付（YYYY-MM-DD / YYYYMMDD）を解釈
+pub fn parse_cache_date(date: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(&date.replace('-', ""), "%Y%m%d").ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn test_race_data_freshness() {
+        let policy = CachePolicy::default();
+        let race_date = parse_cache_date("2025-12-28").unwrap();
+        let fresh = |fetched: Option<&str>, now: &str| {
+            policy.is_fresh(CacheKind::RaceData, race_date, fetched.map(at), None, at(now))
+        };
+
+        // 当日は30分で期限切れ
+        assert!(fresh(Some("2025-12-28 09:00:00"), "2025-12-28 09:20:00"));
+        assert!(!fresh(Some("2025-12-28 09:00:00"), "2025-12-28 10:00:00"));
+        // 当日に取得したものはレース日を過ぎたら再取得、翌日以降に取得したものは期限なし
+        assert!(!fresh(Some("2025-12-28 20:00:00"), "2025-12-29 08:00:00"));
+        assert!(fresh(Some("2025-12-29 08:00:00"), "2026-03-01 00:00:00"));
+        // 取得時刻のない旧キャッシュ
+        assert!(!fresh(None, "2025-12-28 09:00:00"));
+        assert!(fresh(None, "2025-12-29 09:00:00"));
+    }
+
+    #[test]
+    fn test_odds_freshness() {
+        let policy = CachePolicy::default();
+        let race_date = parse_cache_date("20251228").unwrap();
+        let closes_at = Some(at("2025-12-28 15:23:00"));
+        let fresh = |fetched: &str, now: &str| {
+            policy.is_fresh(CacheKind::Odds, race_date, Some(at(fetched)), closes_at, at(now))
+        };
+
+        assert!(fresh("2025-12-28 15:00:00", "2025-12-28 15:00:30"));
+        assert!(!fresh("2025-12-28 15:00:00", "2025-12-28 15:02:00"));
+        // 締切前の取得は締切後に一度再取得し、締切後の取得（確定オッズ）は期限なし
+        assert!(!fresh("2025-12-28 15:22:50", "2025-12-28 15:23:10"));
+        assert!(fresh("2025-12-28 15:30:00", "2025-12-30 12:00:00"));
+        // 締切時刻が不明ならレース日の終わりまで短い期限
+        assert!(!policy.is_fresh(
+            CacheKind::Odds,
+            race_date,
+            Some(at("2025-12-28 20:00:00")),
+            None,
+            at("2025-12-29 00:00:10"),
+        ));
+    }
+
+    #[test]
+    fn test_fetched_at_jst() {
+        let fetched = fetched_at_jst("2025-12-28T06:00:00+00:00").unwrap();
+        assert_eq!(fetched, at("2025-12-28 15:00:00"));
+        assert!(fetched_at_jst("not a time").is_none());
+    }
+}
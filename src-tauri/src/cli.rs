@@ -0,0 +1,467 @@
+//! GUI を使わずにデータ収集・検索を行うためのコマンドラインインターフェース（bort-cli）
+//!
+//! Tauri コマンドと同じサービス層を利用する。結果は JSON で標準出力に書き出し、
+//! サービス層の進捗ログは標準エラーに出す（標準出力は結果の JSON だけになる）。
+//! エラー時は `{code, message, details}` 形式の JSON を標準エラーに出して終了コード 1 で終わる。
+
+use crate::error::AppError;
+use crate::models::migration::MigrationOptions;
+use crate::models::open_api::{ApiDataType, SearchParams};
+use crate::repositories::local_db;
+use crate::services::job_service::JobService;
+use crate::services::open_api_service::{self, OpenApiService};
+use crate::services::schedule_service::ScheduleService;
+use crate::services::scraping_service::ScrapingService;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+
+#[derive(Debug, Parser)]
+#[command(name = "bort-cli", about = "競艇データ収集・検索 CLI", version)]
+pub struct Cli {
+    /// Open API データベースのパス（省略時は data/open_api.db）
+    #[arg(long, global = true)]
+    db: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Open API から期間一括取得して DB に保存
+    FetchOpenApi {
+        #[arg(long = "type", value_enum)]
+        data_type: FetchType,
+        /// 開始日（YYYYMMDD）
+        #[arg(long)]
+        from: String,
+        /// 終了日（YYYYMMDD）
+        #[arg(long)]
+        to: String,
+        /// 同時取得数（--type all のみ）
+        #[arg(long, default_value_t = open_api_service::DEFAULT_BULK_CONCURRENCY)]
+        concurrency: usize,
+    },
+    /// kyoteibiyori から1レース分のデータを取得
+    ScrapeRace {
+        /// 日付（YYYY-MM-DD）
+        #[arg(long)]
+        date: String,
+        /// 競艇場番号（1〜24）
+        #[arg(long)]
+        venue: u32,
+        /// レース番号（1〜12）
+        #[arg(long)]
+        race: u32,
+        /// キャッシュの鮮度にかかわらず取得し直す
+        #[arg(long)]
+        force_refresh: bool,
+    },
+    /// 公式サイトの出走表を取得して表示（Open API の出走表がまだないときの予備）
+    RaceCard {
+        /// 日付（YYYYMMDD / YYYY-MM-DD）
+        #[arg(long)]
+        date: String,
+        /// 競艇場（01〜24 / 1〜24）
+        #[arg(long)]
+        venue: String,
+        /// レース番号（1〜12）
+        #[arg(long)]
+        race: u32,
+    },
+    /// 1レースの出走表・直前情報・結果と kyoteibiyori のレースデータを表示
+    RaceView {
+        /// 日付（YYYYMMDD / YYYY-MM-DD）
+        #[arg(long)]
+        date: String,
+        /// 競艇場（01〜24 / 1〜24）
+        #[arg(long)]
+        venue: String,
+        /// レース番号（1〜12）
+        #[arg(long)]
+        race: u32,
+    },
+    /// kyoteibiyori の逃げ率と実際の逃げを比較
+    EscapeCheck {
+        /// 開始日（YYYYMMDD）
+        #[arg(long)]
+        from: Option<String>,
+        /// 終了日（YYYYMMDD）
+        #[arg(long)]
+        to: Option<String>,
+        /// 競艇場コード（01〜24）
+        #[arg(long)]
+        venue_code: Option<String>,
+        /// 比較する逃げ率（last_half_year / last_year）
+        #[arg(long)]
+        rate_period: Option<String>,
+    },
+    /// 選手プロフィール（コース別・場別成績など）を表示
+    Racer {
+        /// 登録番号
+        #[arg(long)]
+        number: i32,
+    },
+    /// 競艇場のモーター上位・下位（2連率順）を表示
+    Motors {
+        /// 競艇場コード（01〜24）
+        #[arg(long)]
+        venue_code: String,
+        /// シーズン（年、省略時は最新）。年の途中でモーターを入れ替えた場では新旧が混ざる
+        #[arg(long)]
+        season: Option<String>,
+        /// 集計期間の開始日（YYYYMMDD、--season の代わりに入れ替え日以降などを指定）
+        #[arg(long)]
+        from: Option<String>,
+        /// 集計期間の終了日（YYYYMMDD）
+        #[arg(long)]
+        to: Option<String>,
+        /// 集計対象の最低出走数
+        #[arg(long)]
+        min_starts: Option<i32>,
+        /// 上位・下位それぞれの件数
+        #[arg(long)]
+        limit: Option<i32>,
+    },
+    /// 複合条件でレースを検索
+    Search(Box<SearchArgs>),
+    /// races.csv / race_participants.csv を出力
+    ExportCsvV3 {
+        /// 出力先ディレクトリ
+        #[arg(long, default_value = "data/exports")]
+        output_dir: String,
+    },
+    /// 日付ごとのデータ取得状況を表示
+    Summary,
+    /// DBスキーマのマイグレーションを実行
+    Migrate {
+        /// 未適用のマイグレーションを検証のみ行い、変更はロールバックする
+        #[arg(long)]
+        dry_run: bool,
+        /// 適用前のバックアップを作成しない
+        #[arg(long)]
+        no_backup: bool,
+    },
+    /// 旧 JSON ファイルのスクレイピングキャッシュを DB に取り込む（DB にあるレースは上書きしない）
+    ImportScrapeCache {
+        /// 取り込む JSON ファイル
+        #[arg(long, default_value = local_db::LEGACY_JSON_PATH)]
+        json: String,
+    },
+    /// REST/JSON の HTTP サーバーとして常駐する
+    #[cfg(feature = "server")]
+    Serve {
+        /// 待ち受けアドレス
+        #[arg(long, default_value = "127.0.0.1:8787")]
+        addr: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum FetchType {
+    Previews,
+    Results,
+    Programs,
+    /// previews / results / programs をまとめて並行取得
+    All,
+}
+
+/// `SearchParams` のフラグ版
+#[derive(Debug, Args)]
+struct SearchArgs {
+    #[arg(long)]
+    racer_number: Option<i32>,
+    #[arg(long)]
+    racer_name: Option<String>,
+    /// 1=A1, 2=A2, 3=B1, 4=B2
+    #[arg(long)]
+    racer_class: Option<i32>,
+    /// YYYYMMDD
+    #[arg(long)]
+    date_from: Option<String>,
+    /// YYYYMMDD
+    #[arg(long)]
+    date_to: Option<String>,
+    #[arg(long)]
+    venue_code: Option<String>,
+    /// 1=SG, 2=G1, 3=G2, 4=G3, 5=一般
+    #[arg(long)]
+    race_grade: Option<i32>,
+    #[arg(long)]
+    race_number: Option<i32>,
+    #[arg(long)]
+    min_trifecta_payout: Option<i32>,
+    #[arg(long)]
+    max_trifecta_payout: Option<i32>,
+    #[arg(long)]
+    min_win_payout: Option<i32>,
+    #[arg(long)]
+    min_wind: Option<f64>,
+    #[arg(long)]
+    max_wind: Option<f64>,
+    #[arg(long)]
+    min_wave: Option<f64>,
+    #[arg(long)]
+    max_wave: Option<f64>,
+    #[arg(long)]
+    min_temperature: Option<f64>,
+    #[arg(long)]
+    max_temperature: Option<f64>,
+    #[arg(long)]
+    winner_boat_number: Option<i32>,
+    #[arg(long)]
+    place_number: Option<i32>,
+    /// 払戻の賭式（win / place / exacta / quinella / quinella_place / trifecta / trio）
+    #[arg(long)]
+    bet_type: Option<String>,
+    /// 払戻の組番（例: 1-2-3, 2=6）
+    #[arg(long)]
+    combination: Option<String>,
+    #[arg(long)]
+    min_payout: Option<i32>,
+    #[arg(long)]
+    max_payout: Option<i32>,
+    /// 展示タイムの上限（秒）
+    #[arg(long)]
+    max_exhibition_time: Option<f64>,
+    #[arg(long)]
+    exhibition_course_number: Option<i32>,
+    #[arg(long)]
+    max_exhibition_start_timing: Option<f64>,
+    #[arg(long)]
+    min_tilt_adjustment: Option<f64>,
+    #[arg(long)]
+    limit: Option<i32>,
+}
+
+impl From<SearchArgs> for SearchParams {
+    fn from(args: SearchArgs) -> Self {
+        SearchParams {
+            racer_number: args.racer_number,
+            racer_name: args.racer_name,
+            racer_class: args.racer_class,
+            date_from: args.date_from,
+            date_to: args.date_to,
+            venue_code: args.venue_code,
+            race_grade: args.race_grade,
+            race_number: args.race_number,
+            min_trifecta_payout: args.min_trifecta_payout,
+            max_trifecta_payout: args.max_trifecta_payout,
+            min_win_payout: args.min_win_payout,
+            min_wind: args.min_wind,
+            max_wind: args.max_wind,
+            min_wave: args.min_wave,
+            max_wave: args.max_wave,
+            min_temperature: args.min_temperature,
+            max_temperature: args.max_temperature,
+            winner_boat_number: args.winner_boat_number,
+            place_number: args.place_number,
+            bet_type: args.bet_type,
+            combination: args.combination,
+            min_payout: args.min_payout,
+            max_payout: args.max_payout,
+            max_exhibition_time: args.max_exhibition_time,
+            exhibition_course_number: args.exhibition_course_number,
+            max_exhibition_start_timing: args.max_exhibition_start_timing,
+            min_tilt_adjustment: args.min_tilt_adjustment,
+            limit: args.limit,
+        }
+    }
+}
+
+/// bort-cli のエントリポイント
+pub fn run() {
+    let cli = Cli::parse();
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => exit_with_error(AppError::internal(format!("Failed to start runtime: {}", e))),
+    };
+
+    if let Err(e) = runtime.block_on(execute(cli)) {
+        exit_with_error(e);
+    }
+}
+
+async fn execute(cli: Cli) -> Result<(), AppError> {
+    let db = cli.db.as_deref();
+
+    match cli.command {
+        Command::FetchOpenApi {
+            data_type,
+            from,
+            to,
+            concurrency,
+        } => {
+            if concurrency == 0 {
+                return Err(AppError::invalid_input("concurrency", "concurrency must be at least 1"));
+            }
+            let service = OpenApiService::new(db).await?;
+            let jobs = JobService::new(service.job_repository()).await?;
+            let summary = match data_type {
+                FetchType::Previews => {
+                    service.fetch_data_bulk(None, &jobs, ApiDataType::Previews, &from, &to).await?
+                }
+                FetchType::Results => {
+                    service.fetch_data_bulk(None, &jobs, ApiDataType::Results, &from, &to).await?
+                }
+                FetchType::Programs => {
+                    service.fetch_data_bulk(None, &jobs, ApiDataType::Programs, &from, &to).await?
+                }
+                FetchType::All => {
+                    service
+                        .fetch_all_data_bulk(None, &jobs, &from, &to, concurrency)
+                        .await?
+                }
+            };
+            print_json(&summary)
+        }
+        Command::ScrapeRace { date, venue, race, force_refresh } => {
+            let service = OpenApiService::new(db).await?;
+            let race_data = ScrapingService::new()
+                .with_cache(service.scrape_cache())
+                .get_race_info(&date, race, venue, force_refresh)
+                .await?;
+            if let Err(e) = service.update_racers_from_race_data(&date, &race_data).await {
+                eprintln!("⚠️ 選手マスタの更新に失敗: {}", e);
+            }
+            print_json(&race_data)
+        }
+        Command::RaceCard { date, venue, race } => {
+            print_json(&ScheduleService::new().get_race_card(&date, &venue, race).await?)
+        }
+        Command::RaceView { date, venue, race } => {
+            let service = OpenApiService::new(db).await?;
+            print_json(&service.get_race_view(&date, &venue, race).await?)
+        }
+        Command::EscapeCheck {
+            from,
+            to,
+            venue_code,
+            rate_period,
+        } => {
+            let service = OpenApiService::new(db).await?;
+            let report = service
+                .get_escape_calibration(
+                    from.as_deref(),
+                    to.as_deref(),
+                    venue_code.as_deref(),
+                    rate_period.as_deref(),
+                )
+                .await?;
+            print_json(&report)
+        }
+        Command::Racer { number } => {
+            let service = OpenApiService::new(db).await?;
+            print_json(&service.get_racer_profile(number).await?)
+        }
+        Command::Motors {
+            venue_code,
+            season,
+            from,
+            to,
+            min_starts,
+            limit,
+        } => {
+            let service = OpenApiService::new(db).await?;
+            let ranking = service
+                .get_motor_ranking(
+                    &venue_code,
+                    season.as_deref(),
+                    from.as_deref(),
+                    to.as_deref(),
+                    min_starts,
+                    limit,
+                )
+                .await?;
+            print_json(&ranking)
+        }
+        Command::Search(args) => {
+            let service = OpenApiService::new(db).await?;
+            let races = service.search_races_advanced((*args).into()).await?;
+            print_json(&races)
+        }
+        Command::ExportCsvV3 { output_dir } => {
+            std::fs::create_dir_all(&output_dir)
+                .map_err(|e| AppError::io(format!("Failed to create output directory: {}", e)))?;
+            let service = OpenApiService::new(db).await?;
+            let (race_count, participant_count) = service.export_to_csv_v3(&output_dir).await?;
+            print_json(&serde_json::json!({
+                "output_dir": output_dir,
+                "race_count": race_count,
+                "participant_count": participant_count,
+            }))
+        }
+        Command::Summary => {
+            let service = OpenApiService::new(db).await?;
+            print_json(&service.get_data_summary().await?)
+        }
+        Command::Migrate { dry_run, no_backup } => {
+            let options = MigrationOptions {
+                dry_run,
+                backup: !no_backup,
+            };
+            print_json(&OpenApiService::migrate(db, options).await?)
+        }
+        Command::ImportScrapeCache { json } => {
+            if !std::path::Path::new(&json).exists() {
+                return Err(AppError::invalid_input("json", format!("File not found: {}", json)));
+            }
+            let service = OpenApiService::new(db).await?;
+            print_json(&service.scrape_cache().import_legacy_json(&json).await?)
+        }
+        #[cfg(feature = "server")]
+        Command::Serve { addr } => crate::server::serve(&addr, db).await,
+    }
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<(), AppError> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+fn exit_with_error(error: AppError) -> ! {
+    let json = serde_json::to_string(&error).unwrap_or_else(|_| error.to_string());
+    eprintln!("{}", json);
+    std::process::exit(1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fetch_open_api() {
+        let cli = Cli::try_parse_from([
+            "bort-cli", "fetch-open-api", "--type", "results", "--from", "20250101", "--to", "20250131",
+        ])
+        .unwrap();
+        match cli.command {
+            Command::FetchOpenApi { data_type, from, to, concurrency } => {
+                assert!(matches!(data_type, FetchType::Results));
+                assert_eq!(from, "20250101");
+                assert_eq!(to, "20250131");
+                assert_eq!(concurrency, open_api_service::DEFAULT_BULK_CONCURRENCY);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_search_flags_into_params() {
+        let cli = Cli::try_parse_from([
+            "bort-cli", "--db", "/tmp/x.db", "search", "--venue-code", "04", "--min-trifecta-payout", "10000",
+            "--limit", "20",
+        ])
+        .unwrap();
+        assert_eq!(cli.db.as_deref(), Some("/tmp/x.db"));
+        let Command::Search(args) = cli.command else {
+            panic!("search expected");
+        };
+        let params = SearchParams::from(*args);
+        assert_eq!(params.venue_code.as_deref(), Some("04"));
+        assert_eq!(params.min_trifecta_payout, Some(10000));
+        assert_eq!(params.limit, Some(20));
+        assert!(params.racer_number.is_none());
+    }
+}
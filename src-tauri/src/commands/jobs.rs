@@ -0,0 +1,79 @@
+use crate::commands::open_api::{scrape_cache, service_not_initialized, OpenApiServiceState};
+use crate::error::AppError;
+use crate::models::job::{BulkJob, JobKind};
+use crate::services::job_service::JobService;
+use crate::services::scraping_service::ScrapingService;
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+// ジョブ管理サービスのグローバルステート（init_open_api_service で Open API と同じ接続プールから初期化）
+//
+// 実行中のジョブを一時停止・中止できるよう、ロックは取り出すときだけ取る。
+pub type JobServiceState = Arc<Mutex<Option<Arc<JobService>>>>;
+
+/// 初期化済みのジョブ管理サービスを取得
+pub async fn job_service(state: &JobServiceState) -> Result<Arc<JobService>, AppError> {
+    state.lock().await.clone().ok_or_else(|| {
+        AppError::NotInitialized(
+            "Job service not initialized. Call init_open_api_service first.".to_string(),
+        )
+    })
+}
+
+/// 一括取得ジョブの一覧（新しい順）
+#[tauri::command]
+pub async fn list_bulk_jobs(jobs: State<'_, JobServiceState>) -> Result<Vec<BulkJob>, AppError> {
+    job_service(&jobs).await?.list_jobs().await
+}
+
+/// 一括取得ジョブを一時停止（実行中なら処理中のレース・日付の完了後に止まる）
+#[tauri::command]
+pub async fn pause_bulk_job(
+    jobs: State<'_, JobServiceState>,
+    job_id: i64,
+) -> Result<BulkJob, AppError> {
+    job_service(&jobs).await?.pause(job_id).await
+}
+
+/// 一括取得ジョブを中止
+#[tauri::command]
+pub async fn cancel_bulk_job(
+    jobs: State<'_, JobServiceState>,
+    job_id: i64,
+) -> Result<BulkJob, AppError> {
+    job_service(&jobs).await?.cancel(job_id).await
+}
+
+/// 一時停止・中断した一括取得ジョブを未処理分から再開
+///
+/// 進捗は元の実行と同じイベント（`bulk-progress` / `open-api-bulk-progress`）で通知され、
+/// ジョブが完了・一時停止・中止した時点の状態を返す。
+#[tauri::command]
+pub async fn resume_bulk_job(
+    window: tauri::Window,
+    jobs: State<'_, JobServiceState>,
+    open_api: State<'_, OpenApiServiceState>,
+    job_id: i64,
+) -> Result<BulkJob, AppError> {
+    let jobs = job_service(&jobs).await?;
+    let job = jobs.get_job(job_id).await?;
+
+    match job.kind {
+        JobKind::ScrapingBulk => {
+            ScrapingService::new()
+                .with_cache(scrape_cache(&open_api).await?)
+                .run_bulk_job(Some(window), &jobs, job_id)
+                .await?;
+        }
+        JobKind::OpenApiBulk => {
+            let service_state = open_api.lock().await;
+            let service = service_state
+                .as_ref()
+                .ok_or_else(service_not_initialized)?;
+            service.run_bulk_job(Some(window), &jobs, job_id).await?;
+        }
+    }
+
+    jobs.get_job(job_id).await
+}
@@ -1,3 +1,5 @@
+pub mod jobs;
+pub mod odds;
 pub mod open_api;
 pub mod schedule;
 pub mod scraping;
@@ -5,6 +7,8 @@ pub mod storage;
 pub mod utils;
 
 // Re-export all commands for easy registration
+pub use jobs::*;
+pub use odds::*;
 pub use open_api::*;
 pub use schedule::*;
 pub use scraping::*;
@@ -0,0 +1,76 @@
+use crate::commands::open_api::{service_not_initialized, OpenApiServiceState};
+use crate::error::AppError;
+use crate::models::odds_history::{OddsPollTarget, OddsPollerStatus};
+use crate::services::odds_poller_service::OddsPollerService;
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+// オッズ監視サービスのグローバルステート
+pub type OddsPollerState = Arc<Mutex<Option<OddsPollerService>>>;
+
+fn poller_not_initialized() -> AppError {
+    AppError::NotInitialized(
+        "Odds poller not initialized. Call init_odds_poller first.".to_string(),
+    )
+}
+
+/// オッズ監視サービスを初期化（初期化済みの Open API サービスと同じDBに保存する）
+///
+/// 実行中の監視があれば止めてから置き換える。
+#[tauri::command]
+pub async fn init_odds_poller(
+    state: State<'_, OddsPollerState>,
+    open_api: State<'_, OpenApiServiceState>,
+) -> Result<String, AppError> {
+    let service = open_api
+        .lock()
+        .await
+        .as_ref()
+        .cloned()
+        .ok_or_else(service_not_initialized)?;
+
+    let mut poller_state = state.lock().await;
+    if let Some(previous) = poller_state.as_ref() {
+        previous.stop();
+    }
+    *poller_state = Some(OddsPollerService::new(service));
+
+    Ok("Odds poller initialized successfully".to_string())
+}
+
+/// 指定レースの単勝・複勝オッズの定期取得を開始（締切時刻まで、interval_seconds 省略時は60秒）
+///
+/// 取得のたびに `odds-snapshot` イベントでレースの状態を通知する。実行中の監視は置き換える。
+#[tauri::command]
+pub async fn start_odds_polling(
+    window: tauri::Window,
+    state: State<'_, OddsPollerState>,
+    races: Vec<OddsPollTarget>,
+    interval_seconds: Option<u64>,
+) -> Result<OddsPollerStatus, AppError> {
+    let poller_state = state.lock().await;
+    let poller = poller_state.as_ref().ok_or_else(poller_not_initialized)?;
+
+    poller.start(Some(window), &races, interval_seconds).await
+}
+
+/// オッズの定期取得を停止
+#[tauri::command]
+pub async fn stop_odds_polling(state: State<'_, OddsPollerState>) -> Result<OddsPollerStatus, AppError> {
+    let poller_state = state.lock().await;
+    let poller = poller_state.as_ref().ok_or_else(poller_not_initialized)?;
+
+    Ok(poller.stop())
+}
+
+/// オッズの定期取得の状態
+#[tauri::command]
+pub async fn get_odds_polling_status(
+    state: State<'_, OddsPollerState>,
+) -> Result<OddsPollerStatus, AppError> {
+    let poller_state = state.lock().await;
+    let poller = poller_state.as_ref().ok_or_else(poller_not_initialized)?;
+
+    Ok(poller.status())
+}
@@ -1,8 +1,19 @@
 use crate::models::open_api::{
-    ApiDataType, PayoutStats, RaceResult, SearchParams, RaceRecord, RaceParticipantRecord, DataSummaryRow,
+    ApiDataType, CombinationPayoutStats, PayoutStats, RaceResult, SearchParams, RaceRecord, RaceParticipantRecord, DataSummaryRow,
     BulkFetchSummary,
 };
-use crate::services::open_api_service::OpenApiService;
+use crate::models::backtest::{BacktestReport, BacktestStrategy};
+use crate::models::expected_value::ExpectedValueReport;
+use crate::models::race::OddsData;
+use crate::models::motor::MotorRanking;
+use crate::models::odds_history::OddsMovementReport;
+use crate::models::race_view::{EscapeCalibrationReport, RaceView};
+use crate::models::racer::RacerProfile;
+use crate::commands::jobs::{job_service, JobServiceState};
+use crate::error::AppError;
+use crate::repositories::local_db::{self, LocalDbRepository};
+use crate::services::job_service::JobService;
+use crate::services::open_api_service::{self, OpenApiService};
 use std::sync::Arc;
 use tauri::State;
 use tokio::sync::Mutex;
@@ -10,14 +21,44 @@ use tokio::sync::Mutex;
 // サービスのグローバルステート
 pub type OpenApiServiceState = Arc<Mutex<Option<OpenApiService>>>;
 
+pub(crate) fn service_not_initialized() -> AppError {
+    AppError::NotInitialized(
+        "Service not initialized. Call init_open_api_service first.".to_string(),
+    )
+}
+
+/// 初期化済みの Open API サービスと同じDBのスクレイピングキャッシュ
+///
+/// 接続プールを共有するため、コマンドごとに新しい接続を開かない。
+pub(crate) async fn scrape_cache(state: &OpenApiServiceState) -> Result<LocalDbRepository, AppError> {
+    let service_state = state.lock().await;
+    service_state
+        .as_ref()
+        .map(OpenApiService::scrape_cache)
+        .ok_or_else(service_not_initialized)
+}
+
 /// Open API サービスを初期化（デフォルトパスを使用）
 #[tauri::command]
 pub async fn init_open_api_service(
     state: State<'_, OpenApiServiceState>,
-) -> Result<String, String> {
-    println!("🚀 Initializing Open API service with default DB path");
+    jobs: State<'_, JobServiceState>,
+) -> Result<String, AppError> {
+    eprintln!("🚀 Initializing Open API service with default DB path");
 
     let service = OpenApiService::new(None).await?;
+    // 旧 JSON キャッシュが残っていれば一度だけ取り込む
+    service
+        .scrape_cache()
+        .import_legacy_json_once(local_db::LEGACY_JSON_PATH)
+        .await;
+    // ジョブ管理は同じ接続プールを使う（再初期化時は実行中のジョブを一時停止扱いにしないよう既存を残す）
+    {
+        let mut jobs_state = jobs.lock().await;
+        if jobs_state.is_none() {
+            *jobs_state = Some(Arc::new(JobService::new(service.job_repository()).await?));
+        }
+    }
     let mut service_state = state.lock().await;
     *service_state = Some(service);
 
@@ -29,16 +70,16 @@ pub async fn init_open_api_service(
 pub async fn fetch_previews_data(
     state: State<'_, OpenApiServiceState>,
     date: String,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
     // 日付フォーマット検証
     if date.len() != 8 || !date.chars().all(|c| c.is_numeric()) {
-        return Err("Invalid date format. Expected YYYYMMDD".to_string());
+        return Err(AppError::invalid_input("date", "Invalid date format. Expected YYYYMMDD"));
     }
 
     let service_state = state.lock().await;
     let service = service_state
         .as_ref()
-        .ok_or("Service not initialized. Call init_open_api_service first.")?;
+        .ok_or_else(service_not_initialized)?;
 
     service.fetch_data(ApiDataType::Previews, &date).await
 }
@@ -48,16 +89,16 @@ pub async fn fetch_previews_data(
 pub async fn fetch_results_data(
     state: State<'_, OpenApiServiceState>,
     date: String,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
     // 日付フォーマット検証
     if date.len() != 8 || !date.chars().all(|c| c.is_numeric()) {
-        return Err("Invalid date format. Expected YYYYMMDD".to_string());
+        return Err(AppError::invalid_input("date", "Invalid date format. Expected YYYYMMDD"));
     }
 
     let service_state = state.lock().await;
     let service = service_state
         .as_ref()
-        .ok_or("Service not initialized. Call init_open_api_service first.")?;
+        .ok_or_else(service_not_initialized)?;
 
     service.fetch_data(ApiDataType::Results, &date).await
 }
@@ -67,16 +108,16 @@ pub async fn fetch_results_data(
 pub async fn fetch_programs_data(
     state: State<'_, OpenApiServiceState>,
     date: String,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
     // 日付フォーマット検証
     if date.len() != 8 || !date.chars().all(|c| c.is_numeric()) {
-        return Err("Invalid date format. Expected YYYYMMDD".to_string());
+        return Err(AppError::invalid_input("date", "Invalid date format. Expected YYYYMMDD"));
     }
 
     let service_state = state.lock().await;
     let service = service_state
         .as_ref()
-        .ok_or("Service not initialized. Call init_open_api_service first.")?;
+        .ok_or_else(service_not_initialized)?;
 
     service.fetch_data(ApiDataType::Programs, &date).await
 }
@@ -87,11 +128,11 @@ pub async fn save_previews_to_db(
     state: State<'_, OpenApiServiceState>,
     date: String,
     json_data: String,
-) -> Result<usize, String> {
+) -> Result<usize, AppError> {
     let service_state = state.lock().await;
     let service = service_state
         .as_ref()
-        .ok_or("Service not initialized. Call init_open_api_service first.")?;
+        .ok_or_else(service_not_initialized)?;
 
     service.save_previews_data(&date, &json_data).await
 }
@@ -102,11 +143,11 @@ pub async fn save_results_to_db(
     state: State<'_, OpenApiServiceState>,
     date: String,
     json_data: String,
-) -> Result<usize, String> {
+) -> Result<usize, AppError> {
     let service_state = state.lock().await;
     let service = service_state
         .as_ref()
-        .ok_or("Service not initialized. Call init_open_api_service first.")?;
+        .ok_or_else(service_not_initialized)?;
 
     service.save_results_data(&date, &json_data).await
 }
@@ -117,11 +158,11 @@ pub async fn save_programs_to_db(
     state: State<'_, OpenApiServiceState>,
     date: String,
     json_data: String,
-) -> Result<usize, String> {
+) -> Result<usize, AppError> {
     let service_state = state.lock().await;
     let service = service_state
         .as_ref()
-        .ok_or("Service not initialized. Call init_open_api_service first.")?;
+        .ok_or_else(service_not_initialized)?;
 
     service.save_programs_data(&date, &json_data).await
 }
@@ -132,11 +173,11 @@ pub async fn export_open_api_to_csv(
     state: State<'_, OpenApiServiceState>,
     output_path: String,
     data_type: Option<String>,
-) -> Result<usize, String> {
+) -> Result<usize, AppError> {
     let service_state = state.lock().await;
     let service = service_state
         .as_ref()
-        .ok_or("Service not initialized. Call init_open_api_service first.")?;
+        .ok_or_else(service_not_initialized)?;
 
     // data_type 文字列を ApiDataType に変換
     let api_data_type = match data_type.as_deref() {
@@ -145,9 +186,12 @@ pub async fn export_open_api_to_csv(
         Some("programs") => Some(ApiDataType::Programs),
         None => None,
         Some(other) => {
-            return Err(format!(
+            return Err(AppError::invalid_input(
+                "data_type",
+                format!(
                 "Invalid data_type: '{}'. Expected 'previews', 'results', 'programs', or null",
                 other
+            ),
             ))
         }
     };
@@ -169,21 +213,24 @@ pub async fn export_open_api_to_csv(
 pub async fn export_open_api_to_csv_v3(
     state: State<'_, OpenApiServiceState>,
     output_dir: String,
-) -> Result<(usize, usize), String> {
+) -> Result<(usize, usize), AppError> {
     // 出力ディレクトリ検証
     let path = std::path::Path::new(&output_dir);
     if !path.exists() {
         std::fs::create_dir_all(path)
-            .map_err(|e| format!("Failed to create output directory: {}", e))?;
+            .map_err(|e| AppError::io(format!("Failed to create output directory: {}", e)))?;
     }
     if !path.is_dir() {
-        return Err(format!("Output path is not a directory: {}", output_dir));
+        return Err(AppError::invalid_input(
+            "output_dir",
+            format!("Output path is not a directory: {}", output_dir),
+        ));
     }
 
     let service_state = state.lock().await;
     let service = service_state
         .as_ref()
-        .ok_or("Service not initialized. Call init_open_api_service first.")?;
+        .ok_or_else(service_not_initialized)?;
 
     service.export_to_csv_v3(&output_dir).await
 }
@@ -197,24 +244,27 @@ pub async fn search_high_payout_races(
     min_payout: i32,
     payout_type: String,
     limit: Option<i32>,
-) -> Result<Vec<RaceResult>, String> {
+) -> Result<Vec<RaceResult>, AppError> {
     // payout_type バリデーション
     if !["win", "trifecta", "exacta", "place"].contains(&payout_type.as_str()) {
-        return Err(format!(
+        return Err(AppError::invalid_input(
+            "payout_type",
+            format!(
             "Invalid payout_type: '{}'. Expected 'win', 'trifecta', 'exacta', or 'place'",
             payout_type
+        ),
         ));
     }
 
     // min_payout バリデーション
     if min_payout < 0 {
-        return Err("min_payout must be non-negative".to_string());
+        return Err(AppError::invalid_input("min_payout", "min_payout must be non-negative"));
     }
 
     let service_state = state.lock().await;
     let service = service_state
         .as_ref()
-        .ok_or("Service not initialized. Call init_open_api_service first.")?;
+        .ok_or_else(service_not_initialized)?;
 
     service.search_high_payout_races(min_payout, payout_type, limit).await
 }
@@ -223,15 +273,174 @@ pub async fn search_high_payout_races(
 #[tauri::command]
 pub async fn get_payout_statistics(
     state: State<'_, OpenApiServiceState>,
-) -> Result<PayoutStats, String> {
+) -> Result<PayoutStats, AppError> {
     let service_state = state.lock().await;
     let service = service_state
         .as_ref()
-        .ok_or("Service not initialized. Call init_open_api_service first.")?;
+        .ok_or_else(service_not_initialized)?;
 
     service.get_payout_statistics().await
 }
 
+/// 組番ごとの配当統計取得（全賭式・全組番）
+#[tauri::command]
+pub async fn get_combination_payout_stats(
+    state: State<'_, OpenApiServiceState>,
+    bet_type: String,
+    venue_code: Option<String>,
+    limit: Option<i32>,
+) -> Result<Vec<CombinationPayoutStats>, AppError> {
+    let service_state = state.lock().await;
+    let service = service_state
+        .as_ref()
+        .ok_or_else(service_not_initialized)?;
+
+    service
+        .get_combination_payout_stats(&bet_type, venue_code.as_deref(), limit)
+        .await
+}
+
+/// 単勝・複勝オッズの期待値・ケリー基準・割安フラグを計算（odds は get_win_place_odds_info の結果）
+#[tauri::command]
+pub async fn calculate_expected_values(
+    state: State<'_, OpenApiServiceState>,
+    date: String,
+    venue_code: String,
+    race_number: i32,
+    odds: OddsData,
+) -> Result<ExpectedValueReport, AppError> {
+    let service_state = state.lock().await;
+    let service = service_state
+        .as_ref()
+        .ok_or_else(service_not_initialized)?;
+
+    service
+        .calculate_expected_values(&date, &venue_code, race_number, &odds)
+        .await
+}
+
+/// 保存済みのオッズスナップショットから推移と下落率の大きい買い目を取得（date は YYYYMMDD / YYYY-MM-DD）
+#[tauri::command]
+pub async fn get_odds_movement(
+    state: State<'_, OpenApiServiceState>,
+    date: String,
+    venue_code: String,
+    race_number: i32,
+    drop_limit: Option<usize>,
+) -> Result<OddsMovementReport, AppError> {
+    let service_state = state.lock().await;
+    let service = service_state
+        .as_ref()
+        .ok_or_else(service_not_initialized)?;
+
+    service
+        .get_odds_movement(&date, &venue_code, race_number, drop_limit.unwrap_or(5))
+        .await
+}
+
+/// 1レースの出走表・直前情報・結果と kyoteibiyori のレースデータ（date は YYYYMMDD / YYYY-MM-DD）
+#[tauri::command]
+pub async fn get_race_view(
+    state: State<'_, OpenApiServiceState>,
+    date: String,
+    venue_code: String,
+    race_number: u32,
+) -> Result<RaceView, AppError> {
+    let service_state = state.lock().await;
+    let service = service_state
+        .as_ref()
+        .ok_or_else(service_not_initialized)?;
+
+    service.get_race_view(&date, &venue_code, race_number).await
+}
+
+/// kyoteibiyori の逃げ率と実際の逃げの比較（日付は YYYYMMDD）
+#[tauri::command]
+pub async fn get_escape_rate_calibration(
+    state: State<'_, OpenApiServiceState>,
+    date_from: Option<String>,
+    date_to: Option<String>,
+    venue_code: Option<String>,
+    rate_period: Option<String>,
+) -> Result<EscapeCalibrationReport, AppError> {
+    let service_state = state.lock().await;
+    let service = service_state
+        .as_ref()
+        .ok_or_else(service_not_initialized)?;
+
+    service
+        .get_escape_calibration(
+            date_from.as_deref(),
+            date_to.as_deref(),
+            venue_code.as_deref(),
+            rate_period.as_deref(),
+        )
+        .await
+}
+
+/// 買い方戦略のバックテスト（日付は YYYYMMDD）
+#[tauri::command]
+pub async fn run_backtest(
+    state: State<'_, OpenApiServiceState>,
+    strategy: BacktestStrategy,
+    date_from: Option<String>,
+    date_to: Option<String>,
+) -> Result<BacktestReport, AppError> {
+    let service_state = state.lock().await;
+    let service = service_state
+        .as_ref()
+        .ok_or_else(service_not_initialized)?;
+
+    service
+        .run_backtest(&strategy, date_from.as_deref(), date_to.as_deref())
+        .await
+}
+
+/// 競艇場のモーター上位・下位取得（シーズン内の2連率順）
+///
+/// シーズンは年で近似しているため、年の途中でモーターを入れ替えた場では
+/// date_from / date_to で入れ替え後の期間を指定する。
+#[tauri::command]
+pub async fn get_motor_ranking(
+    state: State<'_, OpenApiServiceState>,
+    venue_code: String,
+    season: Option<String>,
+    date_from: Option<String>,
+    date_to: Option<String>,
+    min_starts: Option<i32>,
+    limit: Option<i32>,
+) -> Result<MotorRanking, AppError> {
+    let service_state = state.lock().await;
+    let service = service_state
+        .as_ref()
+        .ok_or_else(service_not_initialized)?;
+
+    service
+        .get_motor_ranking(
+            &venue_code,
+            season.as_deref(),
+            date_from.as_deref(),
+            date_to.as_deref(),
+            min_starts,
+            limit,
+        )
+        .await
+}
+
+/// 選手プロフィール取得（選手マスタ・級別履歴・コース別/場別成績）
+#[tauri::command]
+pub async fn get_racer_profile(
+    state: State<'_, OpenApiServiceState>,
+    racer_number: i32,
+) -> Result<RacerProfile, AppError> {
+    let service_state = state.lock().await;
+    let service = service_state
+        .as_ref()
+        .ok_or_else(service_not_initialized)?;
+
+    service.get_racer_profile(racer_number).await
+}
+
 // ===== V3検索API: 複合条件検索 =====
 
 /// 複合条件検索
@@ -239,11 +448,11 @@ pub async fn get_payout_statistics(
 pub async fn search_races_advanced(
     state: State<'_, OpenApiServiceState>,
     params: SearchParams,
-) -> Result<Vec<(RaceRecord, Vec<RaceParticipantRecord>)>, String> {
+) -> Result<Vec<(RaceRecord, Vec<RaceParticipantRecord>)>, AppError> {
     let service_state = state.lock().await;
     let service = service_state
         .as_ref()
-        .ok_or("Service not initialized. Call init_open_api_service first.")?;
+        .ok_or_else(service_not_initialized)?;
 
     service.search_races_advanced(params).await
 }
@@ -254,15 +463,15 @@ pub async fn search_races_by_racer(
     state: State<'_, OpenApiServiceState>,
     racer_number: i32,
     limit: Option<i32>,
-) -> Result<Vec<(RaceRecord, Vec<RaceParticipantRecord>)>, String> {
+) -> Result<Vec<(RaceRecord, Vec<RaceParticipantRecord>)>, AppError> {
     if racer_number < 0 {
-        return Err("racer_number must be non-negative".to_string());
+        return Err(AppError::invalid_input("racer_number", "racer_number must be non-negative"));
     }
 
     let service_state = state.lock().await;
     let service = service_state
         .as_ref()
-        .ok_or("Service not initialized. Call init_open_api_service first.")?;
+        .ok_or_else(service_not_initialized)?;
 
     service.search_races_by_racer(racer_number, limit).await
 }
@@ -273,15 +482,15 @@ pub async fn search_races_by_racer_name(
     state: State<'_, OpenApiServiceState>,
     racer_name: String,
     limit: Option<i32>,
-) -> Result<Vec<(RaceRecord, Vec<RaceParticipantRecord>)>, String> {
+) -> Result<Vec<(RaceRecord, Vec<RaceParticipantRecord>)>, AppError> {
     if racer_name.is_empty() {
-        return Err("racer_name cannot be empty".to_string());
+        return Err(AppError::invalid_input("racer_name", "racer_name cannot be empty"));
     }
 
     let service_state = state.lock().await;
     let service = service_state
         .as_ref()
-        .ok_or("Service not initialized. Call init_open_api_service first.")?;
+        .ok_or_else(service_not_initialized)?;
 
     service.search_races_by_racer_name(racer_name, limit).await
 }
@@ -292,16 +501,19 @@ pub async fn search_races_by_class(
     state: State<'_, OpenApiServiceState>,
     racer_class: i32,
     limit: Option<i32>,
-) -> Result<Vec<(RaceRecord, Vec<RaceParticipantRecord>)>, String> {
+) -> Result<Vec<(RaceRecord, Vec<RaceParticipantRecord>)>, AppError> {
     // 級別バリデーション: 1=A1, 2=A2, 3=B1, 4=B2
     if !(1..=4).contains(&racer_class) {
-        return Err("racer_class must be between 1 and 4 (1=A1, 2=A2, 3=B1, 4=B2)".to_string());
+        return Err(AppError::invalid_input(
+            "racer_class",
+            "racer_class must be between 1 and 4 (1=A1, 2=A2, 3=B1, 4=B2)",
+        ));
     }
 
     let service_state = state.lock().await;
     let service = service_state
         .as_ref()
-        .ok_or("Service not initialized. Call init_open_api_service first.")?;
+        .ok_or_else(service_not_initialized)?;
 
     service.search_races_by_class(racer_class, limit).await
 }
@@ -313,22 +525,28 @@ pub async fn search_races_by_date_range(
     date_from: String,
     date_to: String,
     limit: Option<i32>,
-) -> Result<Vec<(RaceRecord, Vec<RaceParticipantRecord>)>, String> {
+) -> Result<Vec<(RaceRecord, Vec<RaceParticipantRecord>)>, AppError> {
     // 日付フォーマット検証
     if date_from.len() != 8 || !date_from.chars().all(|c| c.is_numeric()) {
-        return Err("Invalid date_from format. Expected YYYYMMDD".to_string());
+        return Err(AppError::invalid_input(
+            "date_from",
+            "Invalid date_from format. Expected YYYYMMDD",
+        ));
     }
     if date_to.len() != 8 || !date_to.chars().all(|c| c.is_numeric()) {
-        return Err("Invalid date_to format. Expected YYYYMMDD".to_string());
+        return Err(AppError::invalid_input("date_to", "Invalid date_to format. Expected YYYYMMDD"));
     }
     if date_from > date_to {
-        return Err("date_from must be less than or equal to date_to".to_string());
+        return Err(AppError::invalid_input(
+            "date_from",
+            "date_from must be less than or equal to date_to",
+        ));
     }
 
     let service_state = state.lock().await;
     let service = service_state
         .as_ref()
-        .ok_or("Service not initialized. Call init_open_api_service first.")?;
+        .ok_or_else(service_not_initialized)?;
 
     service.search_races_by_date_range(date_from, date_to, limit).await
 }
@@ -339,20 +557,23 @@ pub async fn search_races_by_venue(
     state: State<'_, OpenApiServiceState>,
     venue_code: String,
     limit: Option<i32>,
-) -> Result<Vec<(RaceRecord, Vec<RaceParticipantRecord>)>, String> {
+) -> Result<Vec<(RaceRecord, Vec<RaceParticipantRecord>)>, AppError> {
     // 会場コードバリデーション（01-24の2桁形式）
     if venue_code.len() != 2 || !venue_code.chars().all(|c| c.is_numeric()) {
-        return Err("Invalid venue_code format. Expected 2-digit code (01-24)".to_string());
+        return Err(AppError::invalid_input(
+            "venue_code",
+            "Invalid venue_code format. Expected 2-digit code (01-24)",
+        ));
     }
     let venue_num: i32 = venue_code.parse().unwrap_or(0);
     if !(1..=24).contains(&venue_num) {
-        return Err("venue_code must be between 01 and 24".to_string());
+        return Err(AppError::invalid_input("venue_code", "venue_code must be between 01 and 24"));
     }
 
     let service_state = state.lock().await;
     let service = service_state
         .as_ref()
-        .ok_or("Service not initialized. Call init_open_api_service first.")?;
+        .ok_or_else(service_not_initialized)?;
 
     service.search_races_by_venue(venue_code, limit).await
 }
@@ -361,11 +582,11 @@ pub async fn search_races_by_venue(
 #[tauri::command]
 pub async fn get_open_api_data_summary(
     state: State<'_, OpenApiServiceState>,
-) -> Result<Vec<DataSummaryRow>, String> {
+) -> Result<Vec<DataSummaryRow>, AppError> {
     let service_state = state.lock().await;
     let service = service_state
         .as_ref()
-        .ok_or("Service not initialized. Call init_open_api_service first.")?;
+        .ok_or_else(service_not_initialized)?;
 
     service.get_data_summary().await
 }
@@ -377,28 +598,40 @@ pub async fn get_open_api_data_summary(
 pub async fn fetch_previews_data_bulk(
     window: tauri::Window,
     state: State<'_, OpenApiServiceState>,
+    jobs: State<'_, JobServiceState>,
     start_date: String,  // YYYYMMDD形式
     end_date: String,    // YYYYMMDD形式
-) -> Result<BulkFetchSummary, String> {
+) -> Result<BulkFetchSummary, AppError> {
     // パラメータ検証
     if start_date.len() != 8 || !start_date.chars().all(|c| c.is_numeric()) {
-        return Err("Invalid start_date format. Expected YYYYMMDD".to_string());
+        return Err(AppError::invalid_input(
+            "start_date",
+            "Invalid start_date format. Expected YYYYMMDD",
+        ));
     }
     if end_date.len() != 8 || !end_date.chars().all(|c| c.is_numeric()) {
-        return Err("Invalid end_date format. Expected YYYYMMDD".to_string());
+        return Err(AppError::invalid_input(
+            "end_date",
+            "Invalid end_date format. Expected YYYYMMDD",
+        ));
     }
     if start_date > end_date {
-        return Err("start_date must be less than or equal to end_date".to_string());
+        return Err(AppError::invalid_input(
+            "start_date",
+            "start_date must be less than or equal to end_date",
+        ));
     }
 
+    let jobs = job_service(&jobs).await?;
     let service_state = state.lock().await;
     let service = service_state
         .as_ref()
-        .ok_or("Service not initialized. Call init_open_api_service first.")?;
+        .ok_or_else(service_not_initialized)?;
 
     service
         .fetch_data_bulk(
             Some(window),
+            &jobs,
             ApiDataType::Previews,
             &start_date,
             &end_date,
@@ -411,28 +644,40 @@ pub async fn fetch_previews_data_bulk(
 pub async fn fetch_results_data_bulk(
     window: tauri::Window,
     state: State<'_, OpenApiServiceState>,
+    jobs: State<'_, JobServiceState>,
     start_date: String,  // YYYYMMDD形式
     end_date: String,    // YYYYMMDD形式
-) -> Result<BulkFetchSummary, String> {
+) -> Result<BulkFetchSummary, AppError> {
     // パラメータ検証
     if start_date.len() != 8 || !start_date.chars().all(|c| c.is_numeric()) {
-        return Err("Invalid start_date format. Expected YYYYMMDD".to_string());
+        return Err(AppError::invalid_input(
+            "start_date",
+            "Invalid start_date format. Expected YYYYMMDD",
+        ));
     }
     if end_date.len() != 8 || !end_date.chars().all(|c| c.is_numeric()) {
-        return Err("Invalid end_date format. Expected YYYYMMDD".to_string());
+        return Err(AppError::invalid_input(
+            "end_date",
+            "Invalid end_date format. Expected YYYYMMDD",
+        ));
     }
     if start_date > end_date {
-        return Err("start_date must be less than or equal to end_date".to_string());
+        return Err(AppError::invalid_input(
+            "start_date",
+            "start_date must be less than or equal to end_date",
+        ));
     }
 
+    let jobs = job_service(&jobs).await?;
     let service_state = state.lock().await;
     let service = service_state
         .as_ref()
-        .ok_or("Service not initialized. Call init_open_api_service first.")?;
+        .ok_or_else(service_not_initialized)?;
 
     service
         .fetch_data_bulk(
             Some(window),
+            &jobs,
             ApiDataType::Results,
             &start_date,
             &end_date,
@@ -445,31 +690,98 @@ pub async fn fetch_results_data_bulk(
 pub async fn fetch_programs_data_bulk(
     window: tauri::Window,
     state: State<'_, OpenApiServiceState>,
+    jobs: State<'_, JobServiceState>,
     start_date: String,  // YYYYMMDD形式
     end_date: String,    // YYYYMMDD形式
-) -> Result<BulkFetchSummary, String> {
+) -> Result<BulkFetchSummary, AppError> {
     // パラメータ検証
     if start_date.len() != 8 || !start_date.chars().all(|c| c.is_numeric()) {
-        return Err("Invalid start_date format. Expected YYYYMMDD".to_string());
+        return Err(AppError::invalid_input(
+            "start_date",
+            "Invalid start_date format. Expected YYYYMMDD",
+        ));
     }
     if end_date.len() != 8 || !end_date.chars().all(|c| c.is_numeric()) {
-        return Err("Invalid end_date format. Expected YYYYMMDD".to_string());
+        return Err(AppError::invalid_input(
+            "end_date",
+            "Invalid end_date format. Expected YYYYMMDD",
+        ));
     }
     if start_date > end_date {
-        return Err("start_date must be less than or equal to end_date".to_string());
+        return Err(AppError::invalid_input(
+            "start_date",
+            "start_date must be less than or equal to end_date",
+        ));
     }
 
+    let jobs = job_service(&jobs).await?;
     let service_state = state.lock().await;
     let service = service_state
         .as_ref()
-        .ok_or("Service not initialized. Call init_open_api_service first.")?;
+        .ok_or_else(service_not_initialized)?;
 
     service
         .fetch_data_bulk(
             Some(window),
+            &jobs,
             ApiDataType::Programs,
             &start_date,
             &end_date,
         )
         .await
 }
+
+/// Previews / Results / Programs をまとめて期間一括取得（並行取得）
+///
+/// `concurrency` は同時に取得する件数（省略時 4、上限 16）。
+#[tauri::command]
+pub async fn fetch_all_data_bulk(
+    window: tauri::Window,
+    state: State<'_, OpenApiServiceState>,
+    jobs: State<'_, JobServiceState>,
+    start_date: String,  // YYYYMMDD形式
+    end_date: String,    // YYYYMMDD形式
+    concurrency: Option<usize>,
+) -> Result<BulkFetchSummary, AppError> {
+    // パラメータ検証
+    if start_date.len() != 8 || !start_date.chars().all(|c| c.is_numeric()) {
+        return Err(AppError::invalid_input(
+            "start_date",
+            "Invalid start_date format. Expected YYYYMMDD",
+        ));
+    }
+    if end_date.len() != 8 || !end_date.chars().all(|c| c.is_numeric()) {
+        return Err(AppError::invalid_input(
+            "end_date",
+            "Invalid end_date format. Expected YYYYMMDD",
+        ));
+    }
+    if start_date > end_date {
+        return Err(AppError::invalid_input(
+            "start_date",
+            "start_date must be less than or equal to end_date",
+        ));
+    }
+    if concurrency == Some(0) {
+        return Err(AppError::invalid_input(
+            "concurrency",
+            "concurrency must be at least 1",
+        ));
+    }
+
+    let jobs = job_service(&jobs).await?;
+    let service_state = state.lock().await;
+    let service = service_state
+        .as_ref()
+        .ok_or_else(service_not_initialized)?;
+
+    service
+        .fetch_all_data_bulk(
+            Some(window),
+            &jobs,
+            &start_date,
+            &end_date,
+            concurrency.unwrap_or(open_api_service::DEFAULT_BULK_CONCURRENCY),
+        )
+        .await
+}
@@ -1,18 +1,31 @@
+use crate::error::AppError;
 use crate::services::schedule_service::ScheduleService;
-use crate::parse::official::MonthlySchedule;
+use crate::parse::official::{MonthlySchedule, RaceCard};
 use crate::models::venue::{ActiveRace, AllVenuesResponse};
 
 #[tauri::command]
-pub async fn get_monthly_schedule() -> Result<MonthlySchedule, String> {
-    ScheduleService::get_monthly_schedule().await
+pub async fn get_monthly_schedule() -> Result<MonthlySchedule, AppError> {
+    ScheduleService::new().get_monthly_schedule().await
 }
 
 #[tauri::command]
-pub async fn get_active_races() -> Result<ActiveRace, String> {
-    ScheduleService::get_active_races().await
+pub async fn get_active_races() -> Result<ActiveRace, AppError> {
+    ScheduleService::new().get_active_races().await
 }
 
 #[tauri::command]
-pub async fn get_all_venues_with_status() -> Result<AllVenuesResponse, String> {
-    ScheduleService::get_all_venues_with_status().await
+pub async fn get_all_venues_with_status() -> Result<AllVenuesResponse, AppError> {
+    ScheduleService::new().get_all_venues_with_status().await
+}
+
+/// 公式サイトの出走表（Open API の出走表がまだないときの予備）
+#[tauri::command]
+pub async fn get_official_race_card(
+    date: String,
+    venue_code: String,
+    race_number: u32,
+) -> Result<RaceCard, AppError> {
+    ScheduleService::new()
+        .get_race_card(&date, &venue_code, race_number)
+        .await
 }
@@ -1,85 +1,167 @@
+use crate::commands::jobs::{job_service, JobServiceState};
+use crate::commands::open_api::{scrape_cache, OpenApiServiceState};
+use crate::error::AppError;
 use crate::services::scraping_service::ScrapingService;
+use crate::models::archive::ReparseSummary;
+use crate::models::job::ScrapingJobParams;
 use crate::models::race::{RaceData, OddsData, BulkRaceData};
+use tauri::State;
 
+/// スクレイピングした選手情報を選手マスタに反映（Open API サービス未初期化・失敗時はログのみ）
+async fn record_racers(open_api: &OpenApiServiceState, date: &str, race_data: &RaceData) {
+    let service_state = open_api.lock().await;
+    let Some(service) = service_state.as_ref() else {
+        return;
+    };
+    if let Err(e) = service.update_racers_from_race_data(date, race_data).await {
+        eprintln!("⚠️ 選手マスタの更新に失敗: {}", e);
+    }
+}
+
+/// 保存済みの出走表から締切時刻を取得（Open API サービス未初期化・失敗時は None）
+async fn race_closed_at(
+    open_api: &OpenApiServiceState,
+    date: &str,
+    place_number: &str,
+    race_number: &str,
+) -> Option<String> {
+    let service_state = open_api.lock().await;
+    let service = service_state.as_ref()?;
+    let venue_code = format!("{:02}", place_number.parse::<u32>().ok()?);
+    service
+        .get_race_closed_at(date, &venue_code, race_number.parse().ok()?)
+        .await
+        .ok()
+        .flatten()
+}
+
+/// 出走表・選手成績を取得（期限内のキャッシュがあればそれを返す、force_refresh で取得し直す）
 #[tauri::command]
 pub async fn get_biyori_info(
+    open_api: State<'_, OpenApiServiceState>,
     date: &str,
     race_number: &str,
     place_number: &str,
-) -> Result<RaceData, String> {
-    let date = date.to_string();
-    let race_number = race_number.to_string();
-    let place_number = place_number.to_string();
+    force_refresh: Option<bool>,
+) -> Result<RaceData, AppError> {
+    let race_no = race_number.parse::<u32>()
+        .map_err(|_| {
+            AppError::invalid_input("race_number", format!("Invalid race number: {}", race_number))
+        })?;
+    let place_no = place_number.parse::<u32>()
+        .map_err(|_| {
+            AppError::invalid_input("place_number", format!("Invalid place number: {}", place_number))
+        })?;
 
-    // 重い処理を別スレッドで実行
-    tokio::task::spawn_blocking(move || {
-        let race_no = race_number.parse::<u32>()
-            .map_err(|_| format!("Invalid race number: {}", race_number))?;
-        let place_no = place_number.parse::<u32>()
-            .map_err(|_| format!("Invalid place number: {}", place_number))?;
+    let race_data = ScrapingService::new()
+        .with_cache(scrape_cache(&open_api).await?)
+        .get_race_info(date, race_no, place_no, force_refresh.unwrap_or(false))
+        .await?;
 
-        ScrapingService::get_race_info(&date, race_no, place_no)
-    })
-    .await
-    .map_err(|e| format!("Task execution error: {}", e))?
+    record_racers(&open_api, date, &race_data).await;
+    Ok(race_data)
 }
 
 #[tauri::command]
-pub fn get_odds_info(date: &str, race_number: &str, place_number: &str) -> Result<String, String> {
+pub fn get_odds_info(
+    date: &str,
+    race_number: &str,
+    place_number: &str,
+) -> Result<String, AppError> {
     let race_no = race_number.parse::<u32>()
-        .map_err(|_| format!("Invalid race number: {}", race_number))?;
+        .map_err(|_| {
+            AppError::invalid_input("race_number", format!("Invalid race number: {}", race_number))
+        })?;
     let place_no = place_number.parse::<u32>()
-        .map_err(|_| format!("Invalid place number: {}", place_number))?;
+        .map_err(|_| {
+            AppError::invalid_input("place_number", format!("Invalid place number: {}", place_number))
+        })?;
 
-    ScrapingService::get_odds_html(date, race_no, place_no)
+    ScrapingService::new().get_odds_html(date, race_no, place_no)
 }
 
+/// 単勝・複勝オッズを取得（締切前のキャッシュは短い期限で取得し直す、force_refresh で常に取得）
+///
+/// 締切時刻は保存済みの出走表から取得し、なければレース日の終わりを締切とみなす。
 #[tauri::command]
 pub async fn get_win_place_odds_info(
+    open_api: State<'_, OpenApiServiceState>,
     date: &str,
     race_number: &str,
     place_number: &str,
-) -> Result<OddsData, String> {
-    let date = date.to_string();
-    let race_number = race_number.to_string();
-    let place_number = place_number.to_string();
-
-    // 重い処理を別スレッドで実行
-    tokio::task::spawn_blocking(move || {
-        let race_no = race_number.parse::<u32>()
-            .map_err(|_| format!("Invalid race number: {}", race_number))?;
-        let place_no = place_number.parse::<u32>()
-            .map_err(|_| format!("Invalid place number: {}", place_number))?;
+    force_refresh: Option<bool>,
+) -> Result<OddsData, AppError> {
+    let closed_at = race_closed_at(&open_api, date, place_number, race_number).await;
+    let race_no = race_number.parse::<u32>()
+        .map_err(|_| {
+            AppError::invalid_input("race_number", format!("Invalid race number: {}", race_number))
+        })?;
+    let place_no = place_number.parse::<u32>()
+        .map_err(|_| {
+            AppError::invalid_input("place_number", format!("Invalid place number: {}", place_number))
+        })?;
 
-        ScrapingService::get_win_place_odds(&date, race_no, place_no)
-    })
-    .await
-    .map_err(|e| format!("Task execution error: {}", e))?
+    ScrapingService::new()
+        .with_cache(scrape_cache(&open_api).await?)
+        .get_win_place_odds(
+            date,
+            race_no,
+            place_no,
+            force_refresh.unwrap_or(false),
+            closed_at.as_deref(),
+        )
+        .await
 }
 
+/// 日付・会場・レースを指定して一括取得（期限切れのキャッシュは取得し直す、force_refresh で全件取得）
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn get_bulk_race_data(
     window: tauri::Window,
+    jobs: State<'_, JobServiceState>,
+    open_api: State<'_, OpenApiServiceState>,
     start_date: &str,
     end_date: &str,
     place_numbers: Vec<u32>,
     race_numbers: Vec<u32>,
-) -> Result<Vec<BulkRaceData>, String> {
-    ScrapingService::get_bulk_race_data(
-        Some(window),
-        start_date,
-        end_date,
+    force_refresh: Option<bool>,
+) -> Result<Vec<BulkRaceData>, AppError> {
+    let params = ScrapingJobParams {
+        start_date: start_date.to_string(),
+        end_date: end_date.to_string(),
         place_numbers,
         race_numbers,
-    ).await
+        force_refresh: force_refresh.unwrap_or(false),
+    };
+    let jobs = job_service(&jobs).await?;
+    let results = ScrapingService::new()
+        .with_cache(scrape_cache(&open_api).await?)
+        .get_bulk_race_data(Some(window), &jobs, params)
+        .await?;
+
+    for result in &results {
+        if let Some(race_data) = &result.race_data {
+            record_racers(&open_api, &result.date, race_data).await;
+        }
+    }
+    Ok(results)
 }
 
 #[tauri::command]
-pub async fn scrape_html_from_url(url: String) -> Result<String, String> {
+pub async fn scrape_html_from_url(url: String) -> Result<String, AppError> {
     // 重い処理を別スレッドで実行
     tokio::task::spawn_blocking(move || {
-        ScrapingService::scrape_html(&url)
+        ScrapingService::new().scrape_html(&url)
     })
-    .await
-    .map_err(|e| format!("Task execution error: {}", e))?
+    .await?
+}
+
+#[tauri::command]
+pub async fn reparse_archived_html(
+    open_api: State<'_, OpenApiServiceState>,
+) -> Result<ReparseSummary, AppError> {
+    ScrapingService::new()
+        .with_cache(scrape_cache(&open_api).await?)
+        .reparse_archive()
+        .await
 }
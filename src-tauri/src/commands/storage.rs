@@ -1,62 +1,93 @@
+use crate::commands::open_api::{scrape_cache, OpenApiServiceState};
+use crate::error::AppError;
 use crate::services::storage_service::StorageService;
+use crate::models::archive::ArchiveEntry;
 use crate::models::race::{RaceData, OddsData};
+use tauri::State;
+
+/// Open API サービスと同じ接続プールでストレージサービスを作る
+async fn storage_service(open_api: &OpenApiServiceState) -> Result<StorageService, AppError> {
+    Ok(StorageService::new(scrape_cache(open_api).await?))
+}
 
 #[tauri::command]
-pub fn save_race_data_to_db(
+pub async fn save_race_data_to_db(
+    open_api: State<'_, OpenApiServiceState>,
     date: &str,
     place_number: u32,
     race_number: u32,
     race_data: RaceData,
-) -> Result<(), String> {
-    let service = StorageService::new()?;
-    service.save_race(date, place_number, race_number, &race_data)
+) -> Result<(), AppError> {
+    let service = storage_service(&open_api).await?;
+    service.save_race(date, place_number, race_number, &race_data).await
 }
 
 #[tauri::command]
-pub fn get_race_data_from_db(
+pub async fn get_race_data_from_db(
+    open_api: State<'_, OpenApiServiceState>,
     date: &str,
     place_number: u32,
     race_number: u32,
-) -> Result<Option<RaceData>, String> {
-    let service = StorageService::new()?;
-    service.get_race(date, place_number, race_number)
+) -> Result<Option<RaceData>, AppError> {
+    let service = storage_service(&open_api).await?;
+    service.get_race(date, place_number, race_number).await
 }
 
 #[tauri::command]
-pub fn save_odds_data_to_db(
+pub async fn save_odds_data_to_db(
+    open_api: State<'_, OpenApiServiceState>,
     date: &str,
     place_number: u32,
     race_number: u32,
     odds_data: OddsData,
-) -> Result<(), String> {
-    let service = StorageService::new()?;
-    service.save_odds(date, place_number, race_number, &odds_data)
+) -> Result<(), AppError> {
+    let service = storage_service(&open_api).await?;
+    service.save_odds(date, place_number, race_number, &odds_data).await
 }
 
 #[tauri::command]
-pub fn get_odds_data_from_db(
+pub async fn get_odds_data_from_db(
+    open_api: State<'_, OpenApiServiceState>,
     date: &str,
     place_number: u32,
     race_number: u32,
-) -> Result<Option<OddsData>, String> {
-    let service = StorageService::new()?;
-    service.get_odds(date, place_number, race_number)
+) -> Result<Option<OddsData>, AppError> {
+    let service = storage_service(&open_api).await?;
+    service.get_odds(date, place_number, race_number).await
+}
+
+#[tauri::command]
+pub async fn get_race_source_page(
+    open_api: State<'_, OpenApiServiceState>,
+    date: &str,
+    place_number: u32,
+    race_number: u32,
+    kind: &str,
+) -> Result<Option<ArchiveEntry>, AppError> {
+    let service = storage_service(&open_api).await?;
+    service.get_source_page(date, place_number, race_number, kind).await
+}
+
+#[tauri::command]
+pub async fn get_archived_html(open_api: State<'_, OpenApiServiceState>, content_hash: &str) -> Result<String, AppError> {
+    let service = storage_service(&open_api).await?;
+    service.get_archived_html(content_hash)
 }
 
 #[tauri::command]
-pub fn get_all_stored_race_keys() -> Result<Vec<String>, String> {
-    let service = StorageService::new()?;
-    service.get_all_race_keys()
+pub async fn get_all_stored_race_keys(open_api: State<'_, OpenApiServiceState>) -> Result<Vec<String>, AppError> {
+    let service = storage_service(&open_api).await?;
+    service.get_all_race_keys().await
 }
 
 #[tauri::command]
-pub fn delete_race_data_from_db(date: &str, place_number: u32, race_number: u32) -> Result<(), String> {
-    let service = StorageService::new()?;
-    service.delete_race(date, place_number, race_number)
+pub async fn delete_race_data_from_db(open_api: State<'_, OpenApiServiceState>, date: &str, place_number: u32, race_number: u32) -> Result<(), AppError> {
+    let service = storage_service(&open_api).await?;
+    service.delete_race(date, place_number, race_number).await
 }
 
 #[tauri::command]
-pub fn clear_all_stored_data() -> Result<(), String> {
-    let service = StorageService::new()?;
-    service.clear_all()
+pub async fn clear_all_stored_data(open_api: State<'_, OpenApiServiceState>) -> Result<(), AppError> {
+    let service = storage_service(&open_api).await?;
+    service.clear_all().await
 }
@@ -0,0 +1,316 @@
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use std::fmt;
+
+/// アプリ全体で使うエラー型
+///
+/// フロントエンドには `{ code, message, details }` の形でシリアライズされる。
+/// `code` でエラーの種類を判別でき、`is_transient()` でリトライ可能かを判断できる。
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppError {
+    /// HTTP・ブラウザ通信の失敗
+    NetworkError {
+        message: String,
+        url: Option<String>,
+        status: Option<u16>,
+        /// サーバーが Retry-After で指定した待機秒数
+        retry_after: Option<u64>,
+    },
+    /// ページ内に期待した要素が存在しない
+    ElementNotFound { selector: String },
+    /// 値のパース失敗
+    ParseError {
+        field: String,
+        selector: Option<String>,
+        message: String,
+    },
+    /// SQLite・ローカルDBのエラー
+    DbError(String),
+    /// ファイル入出力のエラー
+    IoError {
+        message: String,
+        /// 対象のファイル・ディレクトリ
+        path: Option<String>,
+    },
+    /// 引数の検証エラー
+    InvalidInput { field: String, message: String },
+    /// サービスが未初期化
+    NotInitialized(String),
+    /// その他の内部エラー（タスク実行失敗など）
+    Internal(String),
+}
+
+impl AppError {
+    /// 成功以外のHTTPステータスを受け取った
+    pub fn http_status(url: &str, status: u16, retry_after: Option<u64>) -> Self {
+        AppError::NetworkError {
+            message: format!("HTTPステータスエラー: {}", status),
+            url: Some(url.to_string()),
+            status: Some(status),
+            retry_after,
+        }
+    }
+
+    pub fn element_not_found(selector: &str) -> Self {
+        AppError::ElementNotFound {
+            selector: selector.to_string(),
+        }
+    }
+
+    pub fn parse(field: &str, message: impl fmt::Display) -> Self {
+        AppError::ParseError {
+            field: field.to_string(),
+            selector: None,
+            message: message.to_string(),
+        }
+    }
+
+    pub fn db(message: impl fmt::Display) -> Self {
+        AppError::DbError(message.to_string())
+    }
+
+    pub fn io(message: impl fmt::Display) -> Self {
+        AppError::IoError {
+            message: message.to_string(),
+            path: None,
+        }
+    }
+
+    /// 対象パス付きの入出力エラー（パスは details に入る）
+    pub fn io_at(path: &std::path::Path, message: impl fmt::Display) -> Self {
+        AppError::IoError {
+            message: message.to_string(),
+            path: Some(path.display().to_string()),
+        }
+    }
+
+    pub fn invalid_input(field: &str, message: impl fmt::Display) -> Self {
+        AppError::InvalidInput {
+            field: field.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    pub fn internal(message: impl fmt::Display) -> Self {
+        AppError::Internal(message.to_string())
+    }
+
+    /// パーサー（`Box<dyn Error>` を返す関数）のエラーを変換する
+    ///
+    /// パーサー内部で `AppError` が使われていればそのまま取り出し、
+    /// それ以外は `field` を付けた `ParseError` にする。
+    pub fn from_parser(field: &str, error: Box<dyn std::error::Error>) -> Self {
+        match error.downcast::<AppError>() {
+            Ok(app_error) => *app_error,
+            Err(other) => AppError::parse(field, other),
+        }
+    }
+
+    /// フロントエンドで判別に使うエラーコード
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::NetworkError { .. } => "NETWORK_ERROR",
+            AppError::ElementNotFound { .. } => "ELEMENT_NOT_FOUND",
+            AppError::ParseError { .. } => "PARSE_ERROR",
+            AppError::DbError(_) => "DB_ERROR",
+            AppError::IoError { .. } => "IO_ERROR",
+            AppError::InvalidInput { .. } => "INVALID_INPUT",
+            AppError::NotInitialized(_) => "NOT_INITIALIZED",
+            AppError::Internal(_) => "INTERNAL_ERROR",
+        }
+    }
+
+    /// 時間をおいて再試行すれば成功する可能性があるか
+    ///
+    /// 通信エラーのうち、ステータスなし（タイムアウト・接続失敗）、429、5xx を一時的とみなす。
+    /// Retry-After が付いていればステータスに関わらず再試行可能とする。
+    pub fn is_transient(&self) -> bool {
+        match self {
+            AppError::NetworkError {
+                retry_after: Some(_),
+                ..
+            } => true,
+            AppError::NetworkError { status: None, .. } => true,
+            AppError::NetworkError {
+                status: Some(status),
+                ..
+            } => *status == 429 || *status >= 500,
+            _ => false,
+        }
+    }
+
+    /// サーバーが指定した再試行までの待機秒数
+    pub fn retry_after(&self) -> Option<u64> {
+        match self {
+            AppError::NetworkError { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+
+    fn details(&self) -> serde_json::Value {
+        use serde_json::json;
+
+        match self {
+            AppError::NetworkError {
+                url,
+                status,
+                retry_after,
+                ..
+            } => json!({
+                "url": url,
+                "status": status,
+                "retry_after": retry_after,
+                "transient": self.is_transient(),
+            }),
+            AppError::ElementNotFound { selector } => json!({ "selector": selector }),
+            AppError::ParseError {
+                field, selector, ..
+            } => json!({ "field": field, "selector": selector }),
+            AppError::InvalidInput { field, .. } => json!({ "field": field }),
+            AppError::IoError { path: Some(path), .. } => json!({ "path": path }),
+            AppError::DbError(_)
+            | AppError::IoError { path: None, .. }
+            | AppError::NotInitialized(_)
+            | AppError::Internal(_) => serde_json::Value::Null,
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::NetworkError {
+                message,
+                url: Some(url),
+                ..
+            } => write!(f, "通信エラー: {} ({})", message, url),
+            AppError::NetworkError { message, .. } => write!(f, "通信エラー: {}", message),
+            AppError::ElementNotFound { selector } => {
+                write!(f, "要素が見つかりません: {}", selector)
+            }
+            AppError::ParseError {
+                field,
+                selector: Some(selector),
+                message,
+            } => write!(f, "パースエラー [{}] ({}): {}", field, selector, message),
+            AppError::ParseError { field, message, .. } => {
+                write!(f, "パースエラー [{}]: {}", field, message)
+            }
+            AppError::DbError(message) => write!(f, "データベースエラー: {}", message),
+            AppError::IoError {
+                message,
+                path: Some(path),
+            } => write!(f, "ファイル入出力エラー: {} ({})", message, path),
+            AppError::IoError { message, .. } => write!(f, "ファイル入出力エラー: {}", message),
+            AppError::InvalidInput { field, message } => {
+                write!(f, "入力値エラー [{}]: {}", field, message)
+            }
+            AppError::NotInitialized(message) => write!(f, "未初期化: {}", message),
+            AppError::Internal(message) => write!(f, "内部エラー: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("AppError", 3)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("details", &self.details())?;
+        state.end()
+    }
+}
+
+impl From<sqlx::Error> for AppError {
+    fn from(error: sqlx::Error) -> Self {
+        AppError::db(error)
+    }
+}
+
+impl From<reqwest::Error> for AppError {
+    fn from(error: reqwest::Error) -> Self {
+        AppError::NetworkError {
+            url: error.url().map(|url| url.to_string()),
+            status: error.status().map(|status| status.as_u16()),
+            message: error.to_string(),
+            retry_after: None,
+        }
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    fn from(error: std::io::Error) -> Self {
+        AppError::io(error)
+    }
+}
+
+impl From<csv::Error> for AppError {
+    fn from(error: csv::Error) -> Self {
+        AppError::io(error)
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(error: serde_json::Error) -> Self {
+        AppError::parse("json", error)
+    }
+}
+
+impl From<tokio::task::JoinError> for AppError {
+    fn from(error: tokio::task::JoinError) -> Self {
+        AppError::internal(format!("Task execution error: {}", error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize_shape() {
+        let error = AppError::ParseError {
+            field: "escape_last_year".to_string(),
+            selector: Some("#raceBasic table".to_string()),
+            message: "invalid float".to_string(),
+        };
+        let value = serde_json::to_value(&error).unwrap();
+
+        assert_eq!(value["code"], "PARSE_ERROR");
+        assert_eq!(value["details"]["field"], "escape_last_year");
+        assert_eq!(value["details"]["selector"], "#raceBasic table");
+        assert!(value["message"].as_str().unwrap().contains("invalid float"));
+    }
+
+    #[test]
+    fn test_transient_classification() {
+        let timeout = AppError::NetworkError {
+            message: "timeout".to_string(),
+            url: None,
+            status: None,
+            retry_after: None,
+        };
+        let server_error = AppError::http_status("https://example.com", 502, None);
+        let not_found = AppError::http_status("https://example.com", 404, None);
+
+        assert!(timeout.is_transient());
+        assert!(server_error.is_transient());
+        assert!(!not_found.is_transient());
+        assert!(!AppError::element_not_found("#raceBasic").is_transient());
+    }
+
+    #[test]
+    fn test_from_parser_keeps_typed_error() {
+        let boxed: Box<dyn std::error::Error> = Box::new(AppError::element_not_found("#raceBasic"));
+        assert_eq!(
+            AppError::from_parser("race_data", boxed),
+            AppError::element_not_found("#raceBasic")
+        );
+
+        let boxed: Box<dyn std::error::Error> = "unexpected".into();
+        assert_eq!(
+            AppError::from_parser("race_data", boxed).code(),
+            "PARSE_ERROR"
+        );
+    }
+}
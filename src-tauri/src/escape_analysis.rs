@@ -0,0 +1,177 @@
+//! kyoteibiyori の逃げ率と実際の結果の比較
+//!
+//! 逃げは1号艇がインから先頭で1周1マークを回って1着になる決まり手。逃げ率を「1号艇が逃げで
+//! 1着になる確率」とみなし、結果（races の1着艇・決まり手）と突き合わせて予想の精度を集計する。
+
+use crate::models::code::{decode_real, CodeEnum, Technique};
+use crate::models::open_api::RaceRecord;
+use crate::models::race::RaceData;
+use crate::models::race_view::{
+    EscapeCalibrationBucket, EscapeCalibrationReport, EscapeCheck, EscapeOutcomeRow,
+};
+
+/// この逃げ率以上なら逃げると予想する
+pub const PREDICTION_THRESHOLD: f64 = 0.5;
+/// 集計区間の数（逃げ率 0〜1 を 0.1 刻み）
+const BUCKET_COUNT: usize = 10;
+
+/// 比較に使う逃げ率の期間
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RatePeriod {
+    LastHalfYear,
+    LastYear,
+}
+
+impl RatePeriod {
+    pub fn parse(value: Option<&str>) -> Option<Self> {
+        match value.unwrap_or("last_half_year") {
+            "last_half_year" => Some(RatePeriod::LastHalfYear),
+            "last_year" => Some(RatePeriod::LastYear),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RatePeriod::LastHalfYear => "last_half_year",
+            RatePeriod::LastYear => "last_year",
+        }
+    }
+}
+
+/// 1号艇が逃げで1着になったか
+pub fn is_escape(winner_boat_number: Option<i32>, technique_number: Option<f64>) -> bool {
+    winner_boat_number == Some(1)
+        && technique_number.and_then(Technique::from_real_code) == Some(Technique::Nige)
+}
+
+/// 逃げ率とレース結果を比較（結果が未保存なら None）
+pub fn escape_check(biyori: &RaceData, race: &RaceRecord) -> Option<EscapeCheck> {
+    race.result_data_json.as_ref()?;
+    let predicted_escape = biyori.escape_last_half_year >= PREDICTION_THRESHOLD;
+    let actual_escape = is_escape(race.winner_boat_number, race.race_technique_number);
+    Some(EscapeCheck {
+        escape_last_year: biyori.escape_last_year,
+        escape_last_half_year: biyori.escape_last_half_year,
+        predicted_escape,
+        winner_boat_number: race.winner_boat_number,
+        technique: decode_real::<Technique>(race.race_technique_number),
+        actual_escape,
+        prediction_hit: predicted_escape == actual_escape,
+    })
+}
+
+/// 逃げ率の区間ごとに予想と実際の逃げの割合を集計
+pub fn build_calibration(
+    rows: &[EscapeOutcomeRow],
+    period: RatePeriod,
+    date_from: Option<&str>,
+    date_to: Option<&str>,
+    venue_code: Option<&str>,
+) -> EscapeCalibrationReport {
+    // (レース数, 逃げた数, 逃げ率の合計)
+    let mut buckets = vec![(0i64, 0i64, 0.0f64); BUCKET_COUNT];
+    let (mut races, mut escapes, mut hits) = (0i64, 0i64, 0i64);
+    let (mut rate_sum, mut squared_error) = (0.0, 0.0);
+
+    for row in rows {
+        let rate = match period {
+            RatePeriod::LastHalfYear => row.escape_last_half_year,
+            RatePeriod::LastYear => row.escape_last_year,
+        };
+        let Some(rate) = rate.map(|rate| rate.clamp(0.0, 1.0)) else {
+            continue;
+        };
+        let escaped = is_escape(row.winner_boat_number, row.race_technique_number);
+        let outcome = if escaped { 1.0 } else { 0.0 };
+
+        races += 1;
+        escapes += escaped as i64;
+        hits += ((rate >= PREDICTION_THRESHOLD) == escaped) as i64;
+        rate_sum += rate;
+        squared_error += (rate - outcome).powi(2);
+
+        let index = ((rate * BUCKET_COUNT as f64) as usize).min(BUCKET_COUNT - 1);
+        let bucket = &mut buckets[index];
+        bucket.0 += 1;
+        bucket.1 += escaped as i64;
+        bucket.2 += rate;
+    }
+
+    let ratio = |value: f64, count: i64| if count > 0 { value / count as f64 } else { 0.0 };
+    EscapeCalibrationReport {
+        date_from: date_from.map(str::to_string),
+        date_to: date_to.map(str::to_string),
+        venue_code: venue_code.map(str::to_string),
+        rate_period: period.as_str().to_string(),
+        races,
+        escapes,
+        average_escape_rate: ratio(rate_sum, races),
+        actual_escape_rate: ratio(escapes as f64, races),
+        prediction_accuracy: ratio(hits as f64, races),
+        brier_score: ratio(squared_error, races),
+        buckets: buckets
+            .into_iter()
+            .enumerate()
+            .filter(|(_, (count, _, _))| *count > 0)
+            .map(|(index, (count, escaped, rate_total))| EscapeCalibrationBucket {
+                rate_from: index as f64 / BUCKET_COUNT as f64,
+                rate_to: (index + 1) as f64 / BUCKET_COUNT as f64,
+                races: count,
+                escapes: escaped,
+                average_escape_rate: ratio(rate_total, count),
+                actual_escape_rate: ratio(escaped as f64, count),
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(rate: f64, winner: i32, technique: f64) -> EscapeOutcomeRow {
+        EscapeOutcomeRow {
+            race_date: "20251228".to_string(),
+            venue_code: "01".to_string(),
+            race_number: 1,
+            escape_last_year: Some(rate),
+            escape_last_half_year: Some(rate),
+            winner_boat_number: Some(winner),
+            race_technique_number: Some(technique),
+        }
+    }
+
+    #[test]
+    fn test_is_escape() {
+        assert!(is_escape(Some(1), Some(1.0)));
+        // 1号艇の恵まれ、2号艇の差しは逃げではない
+        assert!(!is_escape(Some(1), Some(6.0)));
+        assert!(!is_escape(Some(2), Some(2.0)));
+        assert!(!is_escape(None, None));
+    }
+
+    #[test]
+    fn test_build_calibration() {
+        let rows = vec![
+            row(0.72, 1, 1.0),
+            row(0.65, 1, 1.0),
+            row(0.61, 3, 3.0),
+            row(0.30, 2, 2.0),
+            row(1.0, 1, 1.0),
+        ];
+        let report = build_calibration(&rows, RatePeriod::LastHalfYear, Some("20251201"), None, None);
+
+        assert_eq!((report.races, report.escapes), (5, 3));
+        assert!((report.actual_escape_rate - 0.6).abs() < 1e-9);
+        // 0.61 の予想だけが外れ
+        assert!((report.prediction_accuracy - 0.8).abs() < 1e-9);
+        let brier = (0.28f64.powi(2) + 0.35f64.powi(2) + 0.61f64.powi(2) + 0.30f64.powi(2)) / 5.0;
+        assert!((report.brier_score - brier).abs() < 1e-9);
+
+        // 0.3 / 0.6 / 0.7 / 1.0（最後の区間に含める）の4区間
+        let ranges: Vec<(i64, i64)> = report.buckets.iter().map(|b| (b.races, b.escapes)).collect();
+        assert_eq!(ranges, vec![(1, 0), (2, 1), (1, 1), (1, 1)]);
+        assert!((report.buckets[1].average_escape_rate - 0.63).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,253 @@
+//! オッズと推定確率による期待値・ケリー基準の計算
+//!
+//! 各艇の1着確率は、保存済みのレース結果から集計した要素ごとの1着率（進入コース・級別・
+//! レース内の当地勝率順位・モーター2連率順位）を全体の1着率で割った倍率を掛け合わせ、
+//! レース内で合計1になるよう正規化して推定する。2着以内の確率は1着確率から
+//! Harville 式で求める。
+
+use crate::models::expected_value::{BoatProbability, ExpectedValueBet, FactorBucket, WinFactor};
+use crate::models::open_api::RaceParticipantRecord;
+use crate::models::race::{BettingType, OddsData};
+use std::collections::HashMap;
+
+/// 推定に使う要素
+pub const WIN_FACTORS: [WinFactor; 4] = [
+    WinFactor::Course,
+    WinFactor::RacerClass,
+    WinFactor::LocalWinRateRank,
+    WinFactor::MotorTop2Rank,
+];
+
+/// 出走数の少ない値を全体の1着率に寄せるための仮想出走数
+const PRIOR_STARTS: f64 = 20.0;
+
+/// 要素の値ごとの1着率の倍率（全体の1着率 = 1.0）
+pub struct FactorTable {
+    lifts: HashMap<i64, f64>,
+    total_starts: i64,
+}
+
+impl FactorTable {
+    pub fn new(buckets: &[FactorBucket]) -> Self {
+        let total_starts: i64 = buckets.iter().map(|b| b.starts).sum();
+        let total_wins: i64 = buckets.iter().map(|b| b.wins).sum();
+        let base = if total_starts > 0 && total_wins > 0 {
+            total_wins as f64 / total_starts as f64
+        } else {
+            1.0 / 6.0
+        };
+        let lifts = buckets
+            .iter()
+            .map(|b| {
+                let smoothed = (b.wins as f64 + base * PRIOR_STARTS) / (b.starts as f64 + PRIOR_STARTS);
+                (b.bucket, smoothed / base)
+            })
+            .collect();
+        Self { lifts, total_starts }
+    }
+
+    /// 値の倍率（値がない・過去に出現しない場合は 1.0）
+    pub fn lift(&self, bucket: Option<i64>) -> f64 {
+        bucket.and_then(|b| self.lifts.get(&b).copied()).unwrap_or(1.0)
+    }
+
+    pub fn total_starts(&self) -> i64 {
+        self.total_starts
+    }
+}
+
+/// 出走艇の各要素の値
+fn factor_values(entries: &[RaceParticipantRecord], entry: &RaceParticipantRecord) -> [Option<i64>; 4] {
+    // レース内の順位（同値は同順位、RANK() と同じ）
+    let rank = |value: fn(&RaceParticipantRecord) -> Option<f64>| {
+        let own = value(entry)?;
+        Some(1 + entries.iter().filter_map(value).filter(|other| *other > own).count() as i64)
+    };
+    [
+        Some(course_number(entry) as i64),
+        entry.racer_class_number.map(i64::from),
+        rank(|e| e.local_top_1_percent),
+        rank(|e| e.assigned_motor_top_2_percent),
+    ]
+}
+
+/// 推定に使う進入コース（展示進入、なければ枠番）
+fn course_number(entry: &RaceParticipantRecord) -> i32 {
+    entry.exhibition_course_number.unwrap_or(entry.boat_number)
+}
+
+/// 出走艇ごとの1着・2着以内の確率を推定（tables は WIN_FACTORS の順）
+pub fn estimate_probabilities(
+    entries: &[RaceParticipantRecord],
+    tables: &[FactorTable],
+) -> Vec<BoatProbability> {
+    let scores: Vec<f64> = entries
+        .iter()
+        .map(|entry| {
+            factor_values(entries, entry)
+                .iter()
+                .zip(tables)
+                .map(|(value, table)| table.lift(*value))
+                .product()
+        })
+        .collect();
+    let total: f64 = scores.iter().sum();
+    let win: Vec<f64> = scores.iter().map(|score| score / total).collect();
+
+    entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| BoatProbability {
+            boat_number: entry.boat_number,
+            racer_number: entry.racer_number,
+            racer_name: entry.racer_name.clone(),
+            course_number: course_number(entry),
+            win_probability: win[i],
+            place_probability: harville_top2(&win, i),
+        })
+        .collect()
+}
+
+/// Harville 式による2着以内の確率
+fn harville_top2(win: &[f64], i: usize) -> f64 {
+    let second: f64 = win
+        .iter()
+        .enumerate()
+        .filter(|(j, p)| *j != i && **p < 1.0)
+        .map(|(_, p)| p * win[i] / (1.0 - p))
+        .sum();
+    (win[i] + second).min(1.0)
+}
+
+/// ケリー基準の賭け金割合（オッズは払戻倍率）
+pub fn kelly_fraction(probability: f64, odds: f64) -> f64 {
+    if odds <= 1.0 {
+        return 0.0;
+    }
+    ((probability * odds - 1.0) / (odds - 1.0)).max(0.0)
+}
+
+/// 単勝・複勝オッズと推定確率から買い目ごとの期待値を計算（期待値の高い順）
+///
+/// 複勝オッズは範囲の下限で計算する。
+pub fn evaluate_bets(boats: &[BoatProbability], odds: &OddsData) -> Vec<ExpectedValueBet> {
+    let mut bets: Vec<ExpectedValueBet> = odds
+        .combinations
+        .iter()
+        .filter(|_| odds.betting_type == BettingType::WinPlace)
+        .filter_map(|combination| {
+            let boat = boats.iter().find(|b| b.boat_number == combination.first as i32)?;
+            let (bet_type, probability, odds) = match combination.second {
+                0 => ("win", boat.win_probability, combination.odds),
+                _ => {
+                    let lower = combination
+                        .range_text
+                        .as_deref()
+                        .and_then(|range| range.split('-').next())
+                        .and_then(|lower| lower.trim().parse().ok())
+                        .unwrap_or(combination.odds);
+                    ("place", boat.place_probability, lower)
+                }
+            };
+            let expected_value = probability * odds;
+            Some(ExpectedValueBet {
+                bet_type: bet_type.to_string(),
+                boat_number: boat.boat_number,
+                odds,
+                odds_range: combination.range_text.clone(),
+                probability,
+                expected_value,
+                kelly_fraction: kelly_fraction(probability, odds),
+                is_overlay: expected_value > 1.0,
+            })
+        })
+        .collect();
+    bets.sort_by(|a, b| b.expected_value.total_cmp(&a.expected_value));
+    bets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::race::OddsCombination;
+
+    fn entry(boat_number: i32, local_rate: f64) -> RaceParticipantRecord {
+        let mut entry: RaceParticipantRecord = serde_json::from_value(serde_json::json!({
+            "id": boat_number,
+            "race_id": 1,
+            "boat_number": boat_number,
+            "created_at": "",
+            "updated_at": "",
+        }))
+        .unwrap();
+        entry.local_top_1_percent = Some(local_rate);
+        entry
+    }
+
+    fn bucket(bucket: i64, starts: i64, wins: i64) -> FactorBucket {
+        FactorBucket { bucket, starts, wins }
+    }
+
+    #[test]
+    fn test_estimate_probabilities() {
+        let entries: Vec<_> = (1..=6).map(|boat| entry(boat, 7.0 - boat as f64)).collect();
+        // 1コースが強く、他の要素は過去データなし（倍率1.0）
+        let course = FactorTable::new(&[
+            bucket(1, 100, 50),
+            bucket(2, 100, 10),
+            bucket(3, 100, 10),
+            bucket(4, 100, 10),
+            bucket(5, 100, 10),
+            bucket(6, 100, 10),
+        ]);
+        let empty = || FactorTable::new(&[]);
+        let boats = estimate_probabilities(&entries, &[course, empty(), empty(), empty()]);
+
+        let total: f64 = boats.iter().map(|b| b.win_probability).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        let place_total: f64 = boats.iter().map(|b| b.place_probability).sum();
+        assert!((place_total - 2.0).abs() < 1e-9);
+        assert!(boats[0].win_probability > 0.3);
+        assert!((boats[1].win_probability - boats[5].win_probability).abs() < 1e-9);
+        assert_eq!(boats[2].course_number, 3);
+    }
+
+    #[test]
+    fn test_kelly_and_overlay() {
+        assert!((kelly_fraction(0.5, 3.0) - 0.25).abs() < 1e-9);
+        assert_eq!(kelly_fraction(0.2, 3.0), 0.0);
+        assert_eq!(kelly_fraction(0.9, 1.0), 0.0);
+
+        let boats = vec![BoatProbability {
+            boat_number: 1,
+            racer_number: None,
+            racer_name: None,
+            course_number: 1,
+            win_probability: 0.5,
+            place_probability: 0.7,
+        }];
+        let odds = OddsData {
+            betting_type: BettingType::WinPlace,
+            combinations: vec![
+                OddsCombination { first: 1, second: 0, third: None, odds: 2.4, is_combined: false, range_text: None },
+                OddsCombination {
+                    first: 1,
+                    second: 1,
+                    third: None,
+                    odds: 1.6,
+                    is_combined: true,
+                    range_text: Some("1.2-2.0".to_string()),
+                },
+            ],
+        };
+        let bets = evaluate_bets(&boats, &odds);
+        assert_eq!(bets.len(), 2);
+        assert_eq!(bets[0].bet_type, "win");
+        assert!(bets[0].is_overlay);
+        assert!((bets[0].kelly_fraction - 0.2 / 1.4).abs() < 1e-9);
+        // 複勝は範囲の下限で計算する
+        assert_eq!(bets[1].odds, 1.2);
+        assert!((bets[1].expected_value - 0.84).abs() < 1e-9);
+        assert!(!bets[1].is_overlay && bets[1].kelly_fraction == 0.0);
+    }
+}
@@ -1,58 +1,130 @@
-/// 月間スケジュールのHTTPフェッチとファイル保存を行うモジュール
+use crate::error::AppError;
+use crate::models::race_key::RaceKey;
+use crate::page_fetcher::{PageFetcher, PageRequest};
+use crate::parse::official::{self, RaceCard};
+use std::path::Path;
+use std::sync::Arc;
+
+/// 月間スケジュールのフェッチとファイル保存を行うモジュール
+pub const MONTHLY_SCHEDULE_URL: &str = "https://www.boatrace.jp/owpc/pc/race/monthlyschedule";
+
 /// 月間スケジュールをフェッチしてファイルに保存
-pub async fn fetch_and_cache_monthly_schedule() -> Result<(), String> {
-    let url = "https://www.boatrace.jp/owpc/pc/race/monthlyschedule";
-
-    println!("月間スケジュールページをフェッチ中: {}", url);
-
-    match reqwest::get(url).await {
-        Ok(response) => {
-            println!("HTTPレスポンス受信完了: {}", response.status());
-
-            match response.text().await {
-                Ok(html) => {
-                    println!("HTMLコンテンツサイズ: {} バイト", html.len());
-
-                    // HTMLファイルとして保存（月単位、bort-htmlディレクトリ内）
-                    let dir_path = "bort-html";
-                    let file_path = format!(
-                        "{}/monthly_schedule_{}.html",
-                        dir_path,
-                        chrono::Local::now().format("%Y%m")
-                    );
-
-                    // ディレクトリを作成（存在しない場合）
-                    if let Err(e) = std::fs::create_dir_all(dir_path) {
-                        return Err(format!("ディレクトリ作成エラー: {}", e));
-                    }
-
-                    if let Err(e) = std::fs::write(&file_path, &html) {
-                        return Err(format!("HTMLファイル保存エラー: {}", e));
-                    }
-
-                    println!("HTMLファイルを保存しました: {}", file_path);
-
-                    // HTMLの最初の200文字を表示（デバッグ用、文字境界を考慮）
-                    let preview = html.chars().take(200).collect::<String>();
-                    println!("HTML プレビュー: {}", preview);
-
-                    Ok(())
-                }
-                Err(e) => Err(format!("レスポンステキスト取得エラー: {}", e)),
-            }
-        }
-        Err(e) => Err(format!("HTTP リクエストエラー: {}", e)),
+pub async fn fetch_and_cache_monthly_schedule(fetcher: Arc<dyn PageFetcher>) -> Result<(), AppError> {
+    fetch_monthly_schedule_into(fetcher, Path::new("bort-html")).await
+}
+
+/// 月間スケジュールをフェッチして指定ディレクトリに保存
+async fn fetch_monthly_schedule_into(fetcher: Arc<dyn PageFetcher>, dir_path: &Path) -> Result<(), AppError> {
+    let url = MONTHLY_SCHEDULE_URL;
+
+    eprintln!("月間スケジュールページをフェッチ中: {}", url);
+
+    // ページ取得は同期処理なので別スレッドで実行
+    let html =
+        tokio::task::spawn_blocking(move || fetcher.fetch(&PageRequest::Url(url.to_string())))
+            .await??;
+
+    eprintln!("HTMLコンテンツサイズ: {} バイト", html.len());
+
+    // HTMLファイルとして保存（月単位、bort-htmlディレクトリ内）
+    let file_path = dir_path.join(monthly_schedule_file_name());
+
+    // ディレクトリを作成（存在しない場合）
+    if let Err(e) = std::fs::create_dir_all(dir_path) {
+        return Err(AppError::io(format!("ディレクトリ作成エラー: {}", e)));
+    }
+
+    if let Err(e) = std::fs::write(&file_path, &html) {
+        return Err(AppError::io(format!("HTMLファイル保存エラー: {}", e)));
     }
+
+    eprintln!("HTMLファイルを保存しました: {}", file_path.display());
+
+    // HTMLの最初の200文字を表示（デバッグ用、文字境界を考慮）
+    let preview = html.chars().take(200).collect::<String>();
+    eprintln!("HTML プレビュー: {}", preview);
+
+    Ok(())
+}
+
+/// 当月の月間スケジュールの保存ファイル名
+pub fn monthly_schedule_file_name() -> String {
+    format!("monthly_schedule_{}.html", chrono::Local::now().format("%Y%m"))
+}
+
+/// 公式サイトの出走表ページ
+pub const RACELIST_URL: &str = "https://www.boatrace.jp/owpc/pc/race/racelist";
+
+pub fn racelist_url(key: &RaceKey) -> String {
+    format!(
+        "{}?rno={}&jcd={}&hd={}",
+        RACELIST_URL,
+        key.race_number,
+        key.venue_code(),
+        key.race_date()
+    )
+}
+
+/// 公式サイトの出走表を取得して解析（静的HTMLなので Chrome は不要）
+///
+/// Open API の出走表の公開が遅れているときの予備の取得元。
+pub async fn fetch_racelist(fetcher: Arc<dyn PageFetcher>, key: RaceKey) -> Result<RaceCard, AppError> {
+    let url = racelist_url(&key);
+    eprintln!("出走表ページをフェッチ中: {}", url);
+
+    let request = PageRequest::Url(url.clone());
+    let html = tokio::task::spawn_blocking(move || fetcher.fetch(&request)).await??;
+    let entries = official::parse_racelist(&html).map_err(|e| AppError::from_parser("racelist", e))?;
+
+    Ok(RaceCard {
+        race_date: key.race_date(),
+        venue_code: key.venue_code(),
+        race_number: key.race_number,
+        source_url: url,
+        entries,
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::page_fetcher::ReplayPageFetcher;
+    use crate::test_support::replay_fetcher;
 
     #[tokio::test]
     async fn test_fetch_and_cache_monthly_schedule() {
-        let result = fetch_and_cache_monthly_schedule().await;
-        println!("{:?}", result);
-        assert!(result.is_ok())
+        let dir = std::env::temp_dir().join(format!("bort_schedule_{}", std::process::id()));
+        let result = fetch_monthly_schedule_into(Arc::new(replay_fetcher()), &dir).await;
+        assert!(result.is_ok(), "{:?}", result);
+
+        // 保存したHTMLから大会を読み取れる
+        let html = std::fs::read_to_string(dir.join(monthly_schedule_file_name())).unwrap();
+        let schedule = official::parse_monthly_schedule(&html).unwrap();
+        assert_eq!(schedule.events.len(), 2);
+        assert_eq!(schedule.events[1].venue_id, 4);
+        assert_eq!(schedule.events[1].grade, "G1");
+        assert_eq!(schedule.events[1].start_date, "2025-12-27");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_racelist_from_replay() {
+        let dir = std::env::temp_dir().join(format!("bort_racelist_{}", std::process::id()));
+        let key = RaceKey::parse("20251228", "01", 1).unwrap();
+        let html = std::fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/official/racelist_20251228_01_1R.html"
+        ))
+        .unwrap();
+        let replay = ReplayPageFetcher::new(&dir);
+        replay.save_page(&racelist_url(&key), &html).unwrap();
+
+        let card = fetch_racelist(Arc::new(replay), key).await.unwrap();
+        assert_eq!((card.race_date.as_str(), card.venue_code.as_str(), card.race_number), ("20251228", "01", 1));
+        assert_eq!(card.source_url, "https://www.boatrace.jp/owpc/pc/race/racelist?rno=1&jcd=01&hd=20251228");
+        assert_eq!(card.entries.len(), 6);
+
+        let _ = std::fs::remove_dir_all(&dir);
     }
 }
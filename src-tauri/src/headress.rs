@@ -1,32 +1,61 @@
-use headless_chrome::{Browser, LaunchOptions};
-use std::fs;
-use std::fs::File;
-use std::io::Write;
-use std::path::Path;
+use crate::browser_pool::BrowserPool;
+use crate::error::AppError;
+use headless_chrome::Tab;
 
-/// 指定されたURLからHTMLコンテンツをスクレイピングする汎用関数
-pub fn scrape_html_from_url(url: &str) -> Result<String, Box<dyn std::error::Error>> {
-    println!("=== URLからHTMLスクレイピング開始 ===");
-    println!("URL: {}", url);
+/// 単勝・複勝オッズが載っているkyoteibiyoriのタブ番号
+pub const WIN_PLACE_ODDS_SLIDER: u32 = 6;
 
-    // ブラウザを起動
-    let browser = Browser::new(LaunchOptions::default())?;
-    let tab = browser.new_tab()?;
+/// kyoteibiyori 出走表ページのURLを組み立てる
+pub fn kyoteibiyori_race_url(race_no: u32, place_no: u32, today: &str, slider: u32) -> String {
+    let url_base = "https://kyoteibiyori.com/race_shusso.php";
+    format!(
+        "{}?place_no={}&race_no={}&hiduke={}&slider={}",
+        url_base, place_no, race_no, today, slider
+    )
+}
 
-    // URLに移動
-    tab.navigate_to(url)?.wait_until_navigated()?;
+/// URLに移動して読み込み完了を待つ
+///
+/// 移動の失敗・タイムアウトは通信エラー（再試行対象）として返す。
+fn navigate(tab: &Tab, url: &str) -> Result<(), Box<dyn std::error::Error>> {
+    tab.navigate_to(url)
+        .and_then(|tab| tab.wait_until_navigated())
+        .map_err(|e| AppError::NetworkError {
+            message: format!("ページ移動エラー: {}", e),
+            url: Some(url.to_string()),
+            status: None,
+            retry_after: None,
+        })?;
+    Ok(())
+}
 
-    // ページの読み込みを待つ（最大5秒）
-    std::thread::sleep(std::time::Duration::from_secs(2));
+/// 要素が表示されるまで待つ
+///
+/// 見つからない場合はページ構造の変化とみなし、再試行しない `ElementNotFound` を返す。
+fn wait_for(tab: &Tab, selector: &str) -> Result<(), Box<dyn std::error::Error>> {
+    tab.wait_for_element(selector)
+        .map_err(|_| AppError::element_not_found(selector))?;
+    Ok(())
+}
 
-    // ページのHTMLコンテンツを取得
-    let content = tab.get_content()?;
+/// 指定されたURLからHTMLコンテンツをスクレイピングする汎用関数
+pub fn scrape_html_from_url(url: &str) -> Result<String, Box<dyn std::error::Error>> {
+    eprintln!("=== URLからHTMLスクレイピング開始 ===");
+    eprintln!("URL: {}", url);
+
+    // プールのブラウザからタブを借りる
+    let content = BrowserPool::global().with_tab(|tab| {
+        // URLに移動
+        navigate(tab, url)?;
+
+        // ページの読み込みを待つ（最大5秒）
+        std::thread::sleep(std::time::Duration::from_secs(2));
 
-    println!("HTML取得成功: {} bytes", content.len());
+        // ページのHTMLコンテンツを取得
+        Ok(tab.get_content()?)
+    })?;
 
-    // ブラウザを閉じる
-    drop(tab);
-    drop(browser);
+    eprintln!("HTML取得成功: {} bytes", content.len());
 
     Ok(content)
 }
@@ -38,47 +67,35 @@ pub fn fetch_shusso_info_from_kyoteibiyori(
     slider: u32,
 ) -> Result<String, Box<dyn std::error::Error>> {
     // ベースURLとパラメータを組み立てる
-    let url_base = "https://kyoteibiyori.com/race_shusso.php";
-    let url = format!(
-        "{}?place_no={}&race_no={}&hiduke={}&slider={}",
-        url_base, place_no, race_no, today, slider
-    );
-
-    // ブラウザを起動
-    let browser = Browser::new(LaunchOptions::default())?;
-    let tab = browser.new_tab()?;
+    let url = kyoteibiyori_race_url(race_no, place_no, today, slider);
 
-    // URLに移動
-    tab.navigate_to(&url)?.wait_until_navigated()?;
+    // プールのブラウザからタブを借りる
+    let content = BrowserPool::global().with_tab(|tab| {
+        // URLに移動
+        navigate(tab, &url)?;
 
-    // スクリーンショットを取得（必要なら）
-    // let screenshot_data = tab.capture_screenshot(
-    //     headless_chrome::protocol::cdp::Page::CaptureScreenshotFormatOption::Png,
-    //     None, // 画質の指定（Noneでデフォルト）
-    //     None, // クリッピングの指定（Noneで全画面）
-    //     true, // from_surface（trueでスクリーン全体をキャプチャ）
-    // )?;
+        // スクリーンショットを取得（必要なら）
+        // let screenshot_data = tab.capture_screenshot(
+        //     headless_chrome::protocol::cdp::Page::CaptureScreenshotFormatOption::Png,
+        //     None, // 画質の指定（Noneでデフォルト）
+        //     None, // クリッピングの指定（Noneで全画面）
+        //     true, // from_surface（trueでスクリーン全体をキャプチャ）
+        // )?;
 
-    // let mut file = File::create("screenshot.png")?;
-    // file.write_all(&screenshot_data)?;
+        // let mut file = File::create("screenshot.png")?;
+        // file.write_all(&screenshot_data)?;
 
-    // 必要な要素がロードされるまで待機
-    tab.wait_for_element("#raceBasic")?;
+        // 必要な要素がロードされるまで待機
+        wait_for(tab, "#raceBasic")?;
 
-    // ページのHTMLコンテンツを取得
-    let content = tab.get_content()?;
+        // ページのHTMLコンテンツを取得
+        Ok(tab.get_content()?)
+    })?;
 
     // 必要ならデータをパース
+    // （生HTMLの保存は呼び出し側でアーカイブに行う）
     let data = get_escaped_flame_info(&content)?;
 
-    let file_dir = format!("./bort-html/{}", today);
-    fs::create_dir_all(Path::new(&file_dir))?;
-    let file_path = format!("./bort-html/{}/biyori.html", today);
-    let mut file = File::create(&file_path)?;
-    file.write_all(content.as_bytes())?;
-
-    drop(browser);
-    drop(tab);
     Ok(data)
 }
 
@@ -88,77 +105,67 @@ pub fn fetch_odds_info_from_kyoteibiyori(
     today: &str,
 ) -> Result<String, Box<dyn std::error::Error>> {
     // オッズ一覧ページ（slider=6）にアクセスし、単勝・複勝タブをクリック
-    let slider = 6;
+    let slider = WIN_PLACE_ODDS_SLIDER;
 
-    println!("=== 単勝・複勝オッズデータ取得開始 ===");
-    println!(
+    eprintln!("=== 単勝・複勝オッズデータ取得開始 ===");
+    eprintln!(
         "競艇場: {}, レース: {}, 日付: {}, slider: {}",
         place_no, race_no, today, slider
     );
 
     // ベースURLとパラメータを組み立てる
-    let url_base = "https://kyoteibiyori.com/race_shusso.php";
-    let url = format!(
-        "{}?place_no={}&race_no={}&hiduke={}&slider={}",
-        url_base, place_no, race_no, today, slider
-    );
-
-    println!("アクセスURL: {}", url);
+    let url = kyoteibiyori_race_url(race_no, place_no, today, slider);
 
-    // ブラウザを起動
-    let browser = Browser::new(LaunchOptions::default())?;
-    let tab = browser.new_tab()?;
+    eprintln!("アクセスURL: {}", url);
 
-    // URLに移動
-    tab.navigate_to(&url)?.wait_until_navigated()?;
+    // プールのブラウザからタブを借りる
+    let content = BrowserPool::global().with_tab(|tab| {
+        // URLに移動
+        navigate(tab, &url)?;
 
-    // オッズページの読み込みを待つ
-    tab.wait_for_element("li.btnOdds")?;
+        // オッズページの読み込みを待つ
+        wait_for(tab, "li.btnOdds")?;
 
-    // 単勝・複勝タブを探してクリック
-    println!("単勝・複勝タブを探しています...");
-    let win_place_tab_result = tab.find_element(r#"li.line-left.btnOdds[id="tf"]"#);
+        // 単勝・複勝タブを探してクリック
+        eprintln!("単勝・複勝タブを探しています...");
+        let win_place_tab_result = tab.find_element(r#"li.line-left.btnOdds[id="tf"]"#);
 
-    match win_place_tab_result {
-        Ok(win_place_tab) => {
-            println!("単勝・複勝タブを発見、クリックします");
-            win_place_tab.click()?;
+        match win_place_tab_result {
+            Ok(win_place_tab) => {
+                eprintln!("単勝・複勝タブを発見、クリックします");
+                win_place_tab.click()?;
 
-            // クリック後の遷移を待つ
-            std::thread::sleep(std::time::Duration::from_secs(2));
-        }
-        Err(_) => {
-            println!("単勝・複勝タブが見つかりません。別のセレクタを試します...");
-            // より汎用的なセレクタで再試行
-            let tabs = tab.find_elements("li.btnOdds")?;
-            println!("見つかったタブ数: {}", tabs.len());
-
-            for (i, tab_element) in tabs.iter().enumerate() {
-                if let Ok(text) = tab_element.get_inner_text() {
-                    println!("タブ{}: {}", i, text);
-                    if text.contains("単勝") || text.contains("複勝") {
-                        println!("単勝・複勝タブを発見、クリックします");
-                        tab_element.click()?;
-                        std::thread::sleep(std::time::Duration::from_secs(2));
-                        break;
+                // クリック後の遷移を待つ
+                std::thread::sleep(std::time::Duration::from_secs(2));
+            }
+            Err(_) => {
+                eprintln!("単勝・複勝タブが見つかりません。別のセレクタを試します...");
+                // より汎用的なセレクタで再試行
+                let tabs = tab
+                    .find_elements("li.btnOdds")
+                    .map_err(|_| AppError::element_not_found("li.btnOdds"))?;
+                eprintln!("見つかったタブ数: {}", tabs.len());
+
+                for (i, tab_element) in tabs.iter().enumerate() {
+                    if let Ok(text) = tab_element.get_inner_text() {
+                        eprintln!("タブ{}: {}", i, text);
+                        if text.contains("単勝") || text.contains("複勝") {
+                            eprintln!("単勝・複勝タブを発見、クリックします");
+                            tab_element.click()?;
+                            std::thread::sleep(std::time::Duration::from_secs(2));
+                            break;
+                        }
                     }
                 }
             }
         }
-    }
 
-    // ページのHTMLコンテンツを取得
-    let content = tab.get_content()?;
+        // ページのHTMLコンテンツを取得
+        Ok(tab.get_content()?)
+    })?;
 
-    // 単勝・複勝オッズHTML用のファイル保存
-    let file_dir = format!("./bort-html/{}", today);
-    fs::create_dir_all(Path::new(&file_dir))?;
-    let file_path = format!("./bort-html/{}/win_place_odds.html", today);
-    let mut file = File::create(&file_path)?;
-    file.write_all(content.as_bytes())?;
-
-    println!("単勝・複勝オッズHTMLを保存: {}", file_path);
-    println!("HTMLサイズ: {} bytes", content.len());
+    // 生HTMLの保存は呼び出し側でアーカイブに行う
+    eprintln!("HTMLサイズ: {} bytes", content.len());
 
     Ok(content)
 }
@@ -213,10 +220,6 @@ mod tests {
                     "単勝・複勝オッズHTMLを取得しました！HTMLサイズ: {} bytes",
                     html_content.len()
                 );
-                println!(
-                    "HTMLファイルは ./bort-html/{}/win_place_odds.html に保存されました",
-                    today
-                );
 
                 // HTMLコンテンツの先頭部分を表示（文字境界を考慮）
                 let preview = html_content.chars().take(500).collect::<String>();
@@ -1,8 +1,17 @@
 // Module declarations
+pub mod backtest;
+mod browser_pool;
+mod cache_policy;
+pub mod cli;
 mod commands;
+mod error;
+pub mod escape_analysis;
+pub mod expected_value;
 mod fetcher;
 mod headress;
 mod models;
+pub mod odds_history;
+mod page_fetcher;
 mod parse {
     pub mod biyori {
         pub mod flame;
@@ -10,7 +19,12 @@ mod parse {
     pub mod official;
 }
 mod repositories;
+mod request_policy;
+#[cfg(feature = "server")]
+pub mod server;
 mod services;
+#[cfg(test)]
+mod test_support;
 
 // Re-export model types for backward compatibility
 pub use models::race::*;
@@ -23,12 +37,15 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .manage(commands::OpenApiServiceState::default())
+        .manage(commands::JobServiceState::default())
+        .manage(commands::OddsPollerState::default())
         .invoke_handler(tauri::generate_handler![
             // Utils
             commands::greet,
             // Schedule
             commands::get_active_races,
             commands::get_all_venues_with_status,
+            commands::get_official_race_card,
             commands::get_monthly_schedule,
             // Scraping
             commands::get_biyori_info,
@@ -36,11 +53,14 @@ pub fn run() {
             commands::get_win_place_odds_info,
             commands::get_bulk_race_data,
             commands::scrape_html_from_url,
+            commands::reparse_archived_html,
             // Storage
             commands::save_race_data_to_db,
             commands::get_race_data_from_db,
             commands::save_odds_data_to_db,
             commands::get_odds_data_from_db,
+            commands::get_race_source_page,
+            commands::get_archived_html,
             commands::get_all_stored_race_keys,
             commands::delete_race_data_from_db,
             commands::clear_all_stored_data,
@@ -57,6 +77,14 @@ pub fn run() {
             // Open API - 高配当検索
             commands::search_high_payout_races,
             commands::get_payout_statistics,
+            commands::get_combination_payout_stats,
+            commands::get_racer_profile,
+            commands::get_motor_ranking,
+            commands::run_backtest,
+            commands::calculate_expected_values,
+            commands::get_odds_movement,
+            commands::get_race_view,
+            commands::get_escape_rate_calibration,
             // Open API - V3検索（複合条件）
             commands::search_races_advanced,
             commands::search_races_by_racer,
@@ -69,7 +97,18 @@ pub fn run() {
             // Open API - Bulk Fetch
             commands::fetch_previews_data_bulk,
             commands::fetch_results_data_bulk,
-            commands::fetch_programs_data_bulk
+            commands::fetch_programs_data_bulk,
+            commands::fetch_all_data_bulk,
+            // Bulk Jobs
+            commands::list_bulk_jobs,
+            commands::pause_bulk_job,
+            commands::cancel_bulk_job,
+            commands::resume_bulk_job,
+            // Odds Polling
+            commands::init_odds_poller,
+            commands::start_odds_polling,
+            commands::stop_odds_polling,
+            commands::get_odds_polling_status
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -78,7 +117,11 @@ pub fn run() {
 // Tests
 #[cfg(test)]
 mod tests {
+    use crate::error::AppError;
+    use crate::models::open_api::ApiDataType;
+    use crate::services::job_service::JobService;
     use crate::services::open_api_service::OpenApiService;
+    use crate::test_support::{seeded_service, temp_db_path, MockOpenApiServer, FIXTURE_DATE, FIXTURE_RACE_COUNT};
 
     // ===== V2マイグレーションテスト =====
 
@@ -87,12 +130,65 @@ mod tests {
         println!("🔄 Testing V2 migration...");
 
         // OpenApiServiceを初期化するとマイグレーションが実行される
-        let service = OpenApiService::new(Some("data/open_api.db")).await;
+        let path = temp_db_path("v2_migration");
+        let service = OpenApiService::new(Some(path.to_str().unwrap())).await;
 
         assert!(service.is_ok(), "Service initialization should succeed");
+        let _ = std::fs::remove_file(&path);
         println!("✅ V2 migration test passed");
     }
 
+    // ===== Open API 取得テスト（モックサーバー） =====
+
+    #[tokio::test]
+    async fn test_fetch_data_from_mock_server() {
+        let server = MockOpenApiServer::start().await;
+        let path = temp_db_path("fetch_data");
+        let service = OpenApiService::with_base_url(Some(path.to_str().unwrap()), server.base_url())
+            .await
+            .expect("Failed to initialize service");
+
+        let json = service
+            .fetch_data(ApiDataType::Results, FIXTURE_DATE)
+            .await
+            .expect("Failed to fetch results");
+        assert_eq!(
+            service.save_results_data(FIXTURE_DATE, &json).await.unwrap(),
+            FIXTURE_RACE_COUNT
+        );
+
+        // fixture のない日付は 404（リトライ対象外）
+        let error = service
+            .fetch_data(ApiDataType::Results, "20000101")
+            .await
+            .expect_err("Missing date should fail");
+        assert!(matches!(error, AppError::NetworkError { status: Some(404), .. }));
+        assert_eq!(server.request_count(), 2);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_data_bulk_skips_cached_dates() {
+        let (service, server, path) = seeded_service("bulk_cache").await;
+        let jobs = JobService::new(service.job_repository()).await.unwrap();
+        let requests_after_seed = server.request_count();
+        assert_eq!(requests_after_seed, 3);
+
+        // 保存済みの日付は取得せずスキップ、fixture のない日付はエラーとして記録
+        let summary = service
+            .fetch_data_bulk(None, &jobs, ApiDataType::Previews, FIXTURE_DATE, "20251229")
+            .await
+            .expect("Bulk fetch should complete");
+        assert_eq!(summary.total_days, 2);
+        assert_eq!(summary.skipped_count, 1);
+        assert_eq!(summary.error_count, 1);
+        assert_eq!(summary.errors[0].date, "20251229");
+        assert_eq!(server.request_count(), requests_after_seed + 1);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
     // ===== パラメータ検証テスト =====
 
     #[tokio::test]
@@ -134,18 +230,16 @@ mod tests {
 
     #[tokio::test]
     async fn test_search_high_payout_races() {
-        use crate::services::open_api_service::OpenApiService;
-
         println!("🔍 Testing high payout search...");
 
-        let service = OpenApiService::new(Some("data/open_api.db")).await
-            .expect("Failed to initialize service");
+        let (service, _server, path) = seeded_service("high_payout").await;
 
         // 3連単配当100,000円以上のレースを検索
         let results = service.search_high_payout_races(100000, "trifecta".to_string(), Some(5)).await
             .expect("Failed to search high payout races");
 
-        assert!(results.len() > 0, "Should find at least one high payout race");
+        // fixture では 07場12R（390,200円）と 23場3R（185,660円）
+        assert_eq!(results.len(), 2, "Should find the two high payout races in fixtures");
         println!("✅ Found {} high payout races (trifecta >= 100,000)", results.len());
 
         for (i, result) in results.iter().enumerate() {
@@ -158,17 +252,15 @@ mod tests {
                 i + 1, result.race_date, result.race_stadium_number, result.race_number, trifecta_payout);
         }
 
+        let _ = std::fs::remove_file(&path);
         println!("✅ High payout search test passed");
     }
 
     #[tokio::test]
     async fn test_get_payout_statistics() {
-        use crate::services::open_api_service::OpenApiService;
-
         println!("📊 Testing payout statistics...");
 
-        let service = OpenApiService::new(Some("data/open_api.db")).await
-            .expect("Failed to initialize service");
+        let (service, _server, path) = seeded_service("payout_stats").await;
 
         let stats = service.get_payout_statistics().await
             .expect("Failed to get payout statistics");
@@ -180,7 +272,9 @@ mod tests {
 
         assert!(stats.max_trifecta.is_some(), "Should have max trifecta payout");
         assert!(stats.avg_trifecta.is_some(), "Should have average trifecta payout");
+        assert_eq!(stats.max_trifecta, Some(390200));
 
+        let _ = std::fs::remove_file(&path);
         println!("✅ Payout statistics test passed");
     }
 
@@ -188,15 +282,12 @@ mod tests {
 
     #[tokio::test]
     async fn test_export_csv_v3_structure() {
-        use crate::services::open_api_service::OpenApiService;
-
         println!("📁 Testing V3 CSV export...");
 
-        let service = OpenApiService::new(Some("data/open_api.db")).await
-            .expect("Failed to initialize service");
+        let (service, _server, path) = seeded_service("csv_export").await;
 
         // 一時ディレクトリに出力
-        let temp_dir = std::env::temp_dir().join("bort_csv_export_test");
+        let temp_dir = std::env::temp_dir().join(format!("bort_csv_export_test_{}", std::process::id()));
         std::fs::create_dir_all(&temp_dir).expect("Failed to create temp directory");
         let temp_dir_str = temp_dir.to_str().unwrap();
 
@@ -223,6 +314,7 @@ mod tests {
         assert!(races_headers.iter().any(|h| h == "race_date"), "Should have race_date column");
         assert!(races_headers.iter().any(|h| h == "trifecta_payout"), "Should have trifecta_payout column");
         assert!(races_headers.iter().any(|h| h == "race_title"), "Should have race_title column");
+        assert!(races_headers.iter().any(|h| h == "race_technique_ja"), "Should have decoded technique column");
 
         // JSONカラムが除外されていることを確認
         assert!(!races_headers.iter().any(|h| h == "result_data_json"), "Should NOT have result_data_json");
@@ -243,16 +335,24 @@ mod tests {
         assert!(participants_headers.iter().any(|h| h == "racer_exhibition_time"), "Should have racer_exhibition_time column");
         assert!(participants_headers.iter().any(|h| h == "racer_tilt_adjustment"), "Should have racer_tilt_adjustment column");
 
+        // 展示タイムはすべての艇で出力される
+        let exhibition_index = participants_headers.iter().position(|h| h == "racer_exhibition_time").unwrap();
+        for record in participants_reader.records() {
+            let record = record.expect("Failed to read record");
+            assert!(!record[exhibition_index].is_empty(), "racer_exhibition_time should be filled");
+        }
+
         println!("  ✅ race_participants.csv has correct structure (including previews data)");
 
         // レコード数の検証
-        assert_eq!(race_count, 471, "Should export 471 races");
-        assert_eq!(participant_count, 2826, "Should export 2826 participants");
+        assert_eq!(race_count, FIXTURE_RACE_COUNT, "Should export every fixture race");
+        assert_eq!(participant_count, FIXTURE_RACE_COUNT * 6, "Should export 6 participants per race");
 
         println!("  ✅ Correct number of records exported");
 
         // クリーンアップ
         std::fs::remove_dir_all(&temp_dir).expect("Failed to cleanup temp directory");
+        let _ = std::fs::remove_file(&path);
 
         println!("✅ V3 CSV export test passed");
     }
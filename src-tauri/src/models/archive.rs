@@ -0,0 +1,25 @@
+// ===== Raw HTML Archive =====
+
+/// アーカイブ済みHTML 1ページ分のメタデータ（インデックスの1行）
+#[derive(Debug, Clone, PartialEq, norimaki_db::Serialize, norimaki_db::Deserialize)]
+pub struct ArchiveEntry {
+    pub url: String,
+    pub date: Option<String>, // YYYYMMDD
+    pub place_number: Option<u32>,
+    pub race_number: Option<u32>,
+    pub slider: Option<u32>,
+    pub content_hash: String, // SHA-256（非圧縮HTML）
+    pub fetched_at: String,   // RFC3339
+    pub path: String,         // アーカイブルートからの相対パス（.html.gz）
+    pub size_bytes: usize,    // 非圧縮サイズ
+}
+
+/// アーカイブからの再パース結果
+#[derive(Debug, Clone, Default, norimaki_db::Serialize, norimaki_db::Deserialize)]
+pub struct ReparseSummary {
+    pub pages_processed: usize,
+    pub race_data_updated: usize, // 値が変わったレースデータ件数
+    pub odds_updated: usize,      // 値が変わったオッズデータ件数
+    pub values_changed: usize,    // 変化した値の総数
+    pub errors: Vec<String>,
+}
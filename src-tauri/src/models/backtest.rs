@@ -0,0 +1,104 @@
+//! 買い方戦略のバックテスト（保存済みのレース結果・払戻で検証）
+
+use crate::models::open_api::SearchParams;
+use serde::{Deserialize, Serialize};
+
+/// 買い目の指定（艇番は1〜6）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CombinationRule {
+    /// 組番をそのまま指定（例: ["1-2-3", "1-3-2"]、"2=6"）
+    Fixed { combinations: Vec<String> },
+    /// ボックス（指定した艇のすべての組み合わせ）
+    Box { boats: Vec<u8> },
+    /// フォーメーション（着順ごとの候補艇、賭式で使わない着順は空でよい）
+    Formation {
+        first: Vec<u8>,
+        #[serde(default)]
+        second: Vec<u8>,
+        #[serde(default)]
+        third: Vec<u8>,
+    },
+}
+
+/// 1点あたりの賭け金の決め方
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum StakeRule {
+    /// 1点あたり定額（100円単位）
+    Flat { amount: i64 },
+    /// レースごとに資金の一定割合を買い目に均等配分（1点100円単位に切り捨て、initial_bankroll が必要）
+    Proportional { fraction: f64 },
+}
+
+/// 宣言的な買い方戦略
+///
+/// filters は複合条件検索と同じ条件で対象レースを絞り込む。着順・払戻など結果に依存する条件を
+/// 指定すると先読みになるため、バックテストでは展示・気象・選手などの事前情報のみを使う。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacktestStrategy {
+    #[serde(default)]
+    pub filters: SearchParams,
+    /// 賭式（win / place / exacta / quinella / quinella_place / trifecta / trio）
+    pub bet_type: String,
+    pub combinations: CombinationRule,
+    pub stake: StakeRule,
+    /// 初期資金（円）。指定するとドローダウン率と最終資金も算出する
+    #[serde(default)]
+    pub initial_bankroll: Option<i64>,
+}
+
+/// バックテスト対象のレース（指定賭式の払戻付き）
+#[derive(Debug, Clone)]
+pub struct BacktestRace {
+    pub race_id: i64,
+    pub race_date: String,
+    pub venue_code: String,
+    pub race_number: i32,
+    /// (組番, 100円あたりの払戻金)
+    pub payouts: Vec<(String, i32)>,
+}
+
+/// 競艇場別の成績
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VenueBacktestStats {
+    pub venue_code: String,
+    pub race_count: i64,
+    pub hit_count: i64,
+    pub hit_rate: f64,
+    pub total_stake: i64,
+    pub total_return: i64,
+    /// 回収率（払戻 / 賭け金）
+    pub roi: Option<f64>,
+}
+
+/// バックテスト結果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BacktestReport {
+    pub bet_type: String,
+    pub date_from: Option<String>,
+    pub date_to: Option<String>,
+    /// 買い目（1レースあたり）
+    pub combinations: Vec<String>,
+    /// 購入したレース数
+    pub race_count: i64,
+    /// 対象だが購入しなかったレース数（指定賭式の払戻なし・資金不足）
+    pub skipped_race_count: i64,
+    pub bet_count: i64,
+    /// 的中したレース数
+    pub hit_count: i64,
+    pub hit_rate: f64,
+    pub total_stake: i64,
+    pub total_return: i64,
+    pub profit: i64,
+    /// 回収率（払戻 / 賭け金）
+    pub roi: Option<f64>,
+    /// 収支（初期資金を含む）の最大下落幅（円）
+    pub max_drawdown: i64,
+    /// 最大下落幅の直前の最高値に対する割合（initial_bankroll 指定時）
+    pub max_drawdown_rate: Option<f64>,
+    /// 最長連敗（不的中が続いたレース数）
+    pub longest_losing_streak: i64,
+    pub final_bankroll: Option<i64>,
+    pub venues: Vec<VenueBacktestStats>,
+}
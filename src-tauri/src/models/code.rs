@@ -0,0 +1,244 @@
+//! Open API のコード値（天候・風向・決まり手・グレード・級別・都道府県）と日本語/英語ラベル
+//!
+//! 各コードは SQLite の `master_*` テーブルにも同じ内容で登録される（V6 マイグレーション）。
+
+use serde::{Deserialize, Serialize};
+
+/// コード値とラベル（検索結果の表示用）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CodeLabel {
+    pub code: i32,
+    pub ja: String,
+    pub en: String,
+}
+
+/// コード値を持つ列挙型
+pub trait CodeEnum: Copy + Sized + 'static {
+    /// 対応するマスタテーブル名
+    const MASTER_TABLE: &'static str;
+    /// すべての値（コード順）
+    const ALL: &'static [Self];
+
+    fn code(self) -> i32;
+    fn label_ja(self) -> &'static str;
+    fn label_en(self) -> &'static str;
+
+    fn from_code(code: i32) -> Option<Self> {
+        Self::ALL.iter().copied().find(|value| value.code() == code)
+    }
+
+    /// REAL カラムに保存されているコード値（天候・風向・決まり手）から変換
+    fn from_real_code(code: f64) -> Option<Self> {
+        Self::from_code(code as i32)
+    }
+
+    fn label(self) -> CodeLabel {
+        CodeLabel {
+            code: self.code(),
+            ja: self.label_ja().to_string(),
+            en: self.label_en().to_string(),
+        }
+    }
+}
+
+/// コード値をラベルに変換（未知のコードは None）
+pub fn decode<E: CodeEnum>(code: Option<i32>) -> Option<CodeLabel> {
+    code.and_then(E::from_code).map(E::label)
+}
+
+/// REAL カラムに保存されているコード値（天候・風向・決まり手）をラベルに変換
+pub fn decode_real<E: CodeEnum>(code: Option<f64>) -> Option<CodeLabel> {
+    code.and_then(E::from_real_code).map(E::label)
+}
+
+macro_rules! code_enum {
+    (
+        $(#[$meta:meta])*
+        $name:ident => $table:literal {
+            $($variant:ident = $code:literal => ($ja:literal, $en:literal),)+
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+        pub enum $name {
+            $($variant,)+
+        }
+
+        impl CodeEnum for $name {
+            const MASTER_TABLE: &'static str = $table;
+            const ALL: &'static [Self] = &[$(Self::$variant,)+];
+
+            fn code(self) -> i32 {
+                match self {
+                    $(Self::$variant => $code,)+
+                }
+            }
+
+            fn label_ja(self) -> &'static str {
+                match self {
+                    $(Self::$variant => $ja,)+
+                }
+            }
+
+            fn label_en(self) -> &'static str {
+                match self {
+                    $(Self::$variant => $en,)+
+                }
+            }
+        }
+    };
+}
+
+code_enum! {
+    /// 天候（race_weather_number）
+    Weather => "master_weathers" {
+        Sunny = 1 => ("晴", "Sunny"),
+        Cloudy = 2 => ("曇り", "Cloudy"),
+        Rain = 3 => ("雨", "Rain"),
+        Snow = 4 => ("雪", "Snow"),
+        Typhoon = 5 => ("台風", "Typhoon"),
+        Fog = 6 => ("霧", "Fog"),
+    }
+}
+
+code_enum! {
+    /// 風向（race_wind_direction_number）: 1=北から時計回りに16方位、17=無風
+    WindDirection => "master_wind_directions" {
+        North = 1 => ("北", "North"),
+        NorthNortheast = 2 => ("北北東", "North-northeast"),
+        Northeast = 3 => ("北東", "Northeast"),
+        EastNortheast = 4 => ("東北東", "East-northeast"),
+        East = 5 => ("東", "East"),
+        EastSoutheast = 6 => ("東南東", "East-southeast"),
+        Southeast = 7 => ("南東", "Southeast"),
+        SouthSoutheast = 8 => ("南南東", "South-southeast"),
+        South = 9 => ("南", "South"),
+        SouthSouthwest = 10 => ("南南西", "South-southwest"),
+        Southwest = 11 => ("南西", "Southwest"),
+        WestSouthwest = 12 => ("西南西", "West-southwest"),
+        West = 13 => ("西", "West"),
+        WestNorthwest = 14 => ("西北西", "West-northwest"),
+        Northwest = 15 => ("北西", "Northwest"),
+        NorthNorthwest = 16 => ("北北西", "North-northwest"),
+        Calm = 17 => ("無風", "Calm"),
+    }
+}
+
+code_enum! {
+    /// 決まり手（race_technique_number）
+    Technique => "master_techniques" {
+        Nige = 1 => ("逃げ", "Nige (escape)"),
+        Sashi = 2 => ("差し", "Sashi (inside cut)"),
+        Makuri = 3 => ("まくり", "Makuri (outside overtake)"),
+        MakuriSashi = 4 => ("まくり差し", "Makuri-sashi"),
+        Nuki = 5 => ("抜き", "Nuki (overtake after turn)"),
+        Megumare = 6 => ("恵まれ", "Megumare (by default)"),
+    }
+}
+
+code_enum! {
+    /// レースグレード（race_grade_number）
+    RaceGrade => "master_race_grades" {
+        Sg = 1 => ("SG", "SG"),
+        G1 = 2 => ("G1", "G1"),
+        G2 = 3 => ("G2", "G2"),
+        G3 = 4 => ("G3", "G3"),
+        General = 5 => ("一般", "General"),
+    }
+}
+
+code_enum! {
+    /// 級別（racer_class_number）
+    RacerClass => "master_racer_classes" {
+        A1 = 1 => ("A1", "A1"),
+        A2 = 2 => ("A2", "A2"),
+        B1 = 3 => ("B1", "B1"),
+        B2 = 4 => ("B2", "B2"),
+    }
+}
+
+code_enum! {
+    /// 都道府県（racer_branch_number / racer_birthplace_number、JIS X 0401）
+    Prefecture => "master_prefectures" {
+        Hokkaido = 1 => ("北海道", "Hokkaido"),
+        Aomori = 2 => ("青森", "Aomori"),
+        Iwate = 3 => ("岩手", "Iwate"),
+        Miyagi = 4 => ("宮城", "Miyagi"),
+        Akita = 5 => ("秋田", "Akita"),
+        Yamagata = 6 => ("山形", "Yamagata"),
+        Fukushima = 7 => ("福島", "Fukushima"),
+        Ibaraki = 8 => ("茨城", "Ibaraki"),
+        Tochigi = 9 => ("栃木", "Tochigi"),
+        Gunma = 10 => ("群馬", "Gunma"),
+        Saitama = 11 => ("埼玉", "Saitama"),
+        Chiba = 12 => ("千葉", "Chiba"),
+        Tokyo = 13 => ("東京", "Tokyo"),
+        Kanagawa = 14 => ("神奈川", "Kanagawa"),
+        Niigata = 15 => ("新潟", "Niigata"),
+        Toyama = 16 => ("富山", "Toyama"),
+        Ishikawa = 17 => ("石川", "Ishikawa"),
+        Fukui = 18 => ("福井", "Fukui"),
+        Yamanashi = 19 => ("山梨", "Yamanashi"),
+        Nagano = 20 => ("長野", "Nagano"),
+        Gifu = 21 => ("岐阜", "Gifu"),
+        Shizuoka = 22 => ("静岡", "Shizuoka"),
+        Aichi = 23 => ("愛知", "Aichi"),
+        Mie = 24 => ("三重", "Mie"),
+        Shiga = 25 => ("滋賀", "Shiga"),
+        Kyoto = 26 => ("京都", "Kyoto"),
+        Osaka = 27 => ("大阪", "Osaka"),
+        Hyogo = 28 => ("兵庫", "Hyogo"),
+        Nara = 29 => ("奈良", "Nara"),
+        Wakayama = 30 => ("和歌山", "Wakayama"),
+        Tottori = 31 => ("鳥取", "Tottori"),
+        Shimane = 32 => ("島根", "Shimane"),
+        Okayama = 33 => ("岡山", "Okayama"),
+        Hiroshima = 34 => ("広島", "Hiroshima"),
+        Yamaguchi = 35 => ("山口", "Yamaguchi"),
+        Tokushima = 36 => ("徳島", "Tokushima"),
+        Kagawa = 37 => ("香川", "Kagawa"),
+        Ehime = 38 => ("愛媛", "Ehime"),
+        Kochi = 39 => ("高知", "Kochi"),
+        Fukuoka = 40 => ("福岡", "Fukuoka"),
+        Saga = 41 => ("佐賀", "Saga"),
+        Nagasaki = 42 => ("長崎", "Nagasaki"),
+        Kumamoto = 43 => ("熊本", "Kumamoto"),
+        Oita = 44 => ("大分", "Oita"),
+        Miyazaki = 45 => ("宮崎", "Miyazaki"),
+        Kagoshima = 46 => ("鹿児島", "Kagoshima"),
+        Okinawa = 47 => ("沖縄", "Okinawa"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codes_round_trip_and_are_unique() {
+        fn check<E: CodeEnum>() {
+            for (i, value) in E::ALL.iter().enumerate() {
+                assert_eq!(value.code(), i as i32 + 1, "{} はコード順に1から連番", E::MASTER_TABLE);
+                assert_eq!(E::from_code(value.code()).map(E::code), Some(value.code()));
+            }
+            assert!(E::from_code(0).is_none());
+        }
+        check::<Weather>();
+        check::<WindDirection>();
+        check::<Technique>();
+        check::<RaceGrade>();
+        check::<RacerClass>();
+        check::<Prefecture>();
+        assert_eq!(Prefecture::ALL.len(), 47);
+    }
+
+    #[test]
+    fn test_decode_labels() {
+        let label = decode_real::<Technique>(Some(3.0)).unwrap();
+        assert_eq!((label.ja.as_str(), label.en.as_str()), ("まくり", "Makuri (outside overtake)"));
+        assert_eq!(decode::<RacerClass>(Some(1)).unwrap().ja, "A1");
+        assert_eq!(decode::<Prefecture>(Some(13)).unwrap().en, "Tokyo");
+        assert!(decode::<RaceGrade>(Some(99)).is_none());
+        assert!(decode::<Weather>(None).is_none());
+    }
+}
@@ -0,0 +1,69 @@
+//! オッズと推定確率による期待値計算
+
+use serde::{Deserialize, Serialize};
+
+/// 勝率の推定に使う要素（過去の結果から1着率を集計する単位）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WinFactor {
+    /// 進入コース
+    Course,
+    /// 級別
+    RacerClass,
+    /// レース内での当地勝率の順位
+    LocalWinRateRank,
+    /// レース内でのモーター2連率の順位
+    MotorTop2Rank,
+}
+
+/// 要素の値ごとの出走数・1着数
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct FactorBucket {
+    pub bucket: i64,
+    pub starts: i64,
+    pub wins: i64,
+}
+
+/// 艇ごとの推定確率
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoatProbability {
+    pub boat_number: i32,
+    pub racer_number: Option<i32>,
+    pub racer_name: Option<String>,
+    /// 推定に使った進入コース（展示進入、なければ枠番）
+    pub course_number: i32,
+    pub win_probability: f64,
+    /// 2着以内（複勝的中）の確率
+    pub place_probability: f64,
+}
+
+/// 買い目ごとの期待値
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExpectedValueBet {
+    /// "win" | "place"
+    pub bet_type: String,
+    pub boat_number: i32,
+    /// 期待値の計算に使ったオッズ（複勝は下限）
+    pub odds: f64,
+    /// 複勝オッズの元の範囲（例: "1.5-3.3"）
+    pub odds_range: Option<String>,
+    pub probability: f64,
+    /// 1円あたりの払戻の期待値（確率 × オッズ）
+    pub expected_value: f64,
+    /// ケリー基準の賭け金割合（期待値が1以下なら0）
+    pub kelly_fraction: f64,
+    /// 期待値が1を超える（オッズが推定確率に対して割安）
+    pub is_overlay: bool,
+}
+
+/// 期待値計算の結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExpectedValueReport {
+    pub race_date: String,
+    pub venue_code: String,
+    pub race_number: i32,
+    /// 推定に使った過去の出走数（コース別集計の母数）
+    pub sample_starts: i64,
+    pub boats: Vec<BoatProbability>,
+    /// 期待値の高い順
+    pub bets: Vec<ExpectedValueBet>,
+}
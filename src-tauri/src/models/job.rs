@@ -0,0 +1,129 @@
+use serde::{Deserialize, Serialize};
+
+/// 一括取得ジョブの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+pub enum JobKind {
+    /// kyoteibiyori の一括スクレイピング（get_bulk_race_data）
+    ScrapingBulk,
+    /// Open API の期間一括取得（fetch_*_data_bulk）
+    OpenApiBulk,
+}
+
+/// ジョブの状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+pub enum JobStatus {
+    Pending,
+    Running,
+    Paused,
+    Cancelled,
+    Completed,
+}
+
+impl JobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobStatus::Pending => "pending",
+            JobStatus::Running => "running",
+            JobStatus::Paused => "paused",
+            JobStatus::Cancelled => "cancelled",
+            JobStatus::Completed => "completed",
+        }
+    }
+
+    /// 再開できる状態か（中断された実行中ジョブは起動時に paused へ戻される）
+    pub fn is_resumable(&self) -> bool {
+        matches!(self, JobStatus::Pending | JobStatus::Paused)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        matches!(self, JobStatus::Cancelled | JobStatus::Completed)
+    }
+}
+
+/// 作業単位の状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+pub enum UnitStatus {
+    Pending,
+    /// 取得・保存に成功
+    Done,
+    /// キャッシュ済みのため取得しなかった
+    Skipped,
+    Failed,
+}
+
+/// 一括取得ジョブ（bulk_jobs テーブル）
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct BulkJob {
+    pub id: i64,
+    pub kind: JobKind,
+    pub status: JobStatus,
+    pub params_json: String,
+    pub total_units: i64,
+    pub done_units: i64,
+    pub skipped_units: i64,
+    pub failed_units: i64,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl BulkJob {
+    /// 処理済み（成功・スキップ・失敗）の作業単位数
+    pub fn processed_units(&self) -> i64 {
+        self.done_units + self.skipped_units + self.failed_units
+    }
+}
+
+/// ジョブの作業単位（bulk_job_units テーブル）
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct JobUnit {
+    pub job_id: i64,
+    pub seq: i64,
+    pub payload_json: String,
+    pub status: UnitStatus,
+    pub error: Option<String>,
+}
+
+/// スクレイピング一括取得のパラメータ（日付は YYYY-MM-DD）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScrapingJobParams {
+    pub start_date: String,
+    pub end_date: String,
+    pub place_numbers: Vec<u32>,
+    pub race_numbers: Vec<u32>,
+    /// キャッシュの鮮度にかかわらず取得し直す（旧ジョブには存在しない）
+    #[serde(default)]
+    pub force_refresh: bool,
+}
+
+/// スクレイピング一括取得の作業単位（1レース）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScrapingJobUnit {
+    pub date: String,
+    pub place_number: u32,
+    pub race_number: u32,
+}
+
+/// Open API 一括取得のパラメータ（日付は YYYYMMDD）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenApiJobParams {
+    pub data_type: String, // "previews" | "results" | "programs" | "all"
+    pub start_date: String,
+    pub end_date: String,
+    /// 並行取得の同時取得数（data_type が "all" の場合のみ）
+    #[serde(default)]
+    pub concurrency: Option<usize>,
+}
+
+/// Open API 一括取得の作業単位（1日分。全種別取得では1日・1種別）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenApiJobUnit {
+    pub date: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_type: Option<String>,
+}
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+/// マイグレーション実行時のオプション
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct MigrationOptions {
+    /// 未適用のマイグレーションをトランザクション内で実行し、最後にロールバックする
+    pub dry_run: bool,
+    /// 適用前にデータベースのバックアップを作成する（既存データがある場合のみ）
+    pub backup: bool,
+}
+
+impl Default for MigrationOptions {
+    fn default() -> Self {
+        Self {
+            dry_run: false,
+            backup: true,
+        }
+    }
+}
+
+/// マイグレーション1件分の情報
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationStep {
+    pub version: i64,
+    pub description: String,
+}
+
+/// マイグレーションの実行結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationReport {
+    /// 実行前のスキーマバージョン
+    pub from_version: i64,
+    /// 実行後のスキーマバージョン（dry-run では実行前と同じ）
+    pub to_version: i64,
+    /// 適用した（dry-run では適用可能だった）マイグレーション
+    pub applied: Vec<MigrationStep>,
+    pub dry_run: bool,
+    pub backup_path: Option<String>,
+}
@@ -1,5 +1,16 @@
+pub mod archive;
+pub mod backtest;
+pub mod code;
+pub mod expected_value;
+pub mod job;
+pub mod migration;
+pub mod motor;
+pub mod odds_history;
 pub mod open_api;
 pub mod race;
+pub mod race_key;
+pub mod race_view;
+pub mod racer;
 pub mod venue;
 
 // Re-export all types for convenience
@@ -0,0 +1,52 @@
+//! モーター成績（motors ビュー）
+//!
+//! ボート（assigned_boat_number）の成績は集計の対象外。出走表のボート2連率・3連率は
+//! race_participants に保存している。
+
+use serde::{Deserialize, Serialize};
+
+/// motorsビューの行（競艇場・モーター番号・シーズンごとの集計）
+///
+/// シーズンはレース日の年（"2025" など）。モーターの入れ替え日はデータに含まれないため、
+/// 年の途中で入れ替える場では入れ替え前後のモーターが同じシーズンに集計される。
+/// 期間を指定して集計した行は season が None になる。
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct MotorStats {
+    pub venue_code: String,
+    pub motor_number: i32,
+    pub season: Option<String>,
+    /// 結果のあるレースへの出走数
+    pub starts: i64,
+    pub wins: i64,
+    pub top2_count: i64,
+    pub top3_count: i64,
+    pub win_rate: Option<f64>,
+    pub top2_rate: Option<f64>,
+    pub top3_rate: Option<f64>,
+    /// 使用した選手の人数
+    pub racer_count: i64,
+    /// 直前情報のある出走数と展示タイムの平均・最速
+    pub exhibition_count: i64,
+    pub average_exhibition_time: Option<f64>,
+    pub best_exhibition_time: Option<f64>,
+    pub average_start_timing: Option<f64>,
+    /// 最新の出走表に記載されたモーター2連率・3連率（%）
+    pub program_top_2_percent: Option<f64>,
+    pub program_top_3_percent: Option<f64>,
+    pub first_race_date: String,
+    pub last_race_date: String,
+}
+
+/// 競艇場のモーター上位・下位（2連率順、下位には上位のモーターを含めない）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MotorRanking {
+    pub venue_code: String,
+    pub season: Option<String>,
+    /// 期間を指定した場合の集計期間（YYYYMMDD）
+    pub date_from: Option<String>,
+    pub date_to: Option<String>,
+    /// 集計対象の最低出走数
+    pub min_starts: i32,
+    pub top: Vec<MotorStats>,
+    pub bottom: Vec<MotorStats>,
+}
@@ -0,0 +1,89 @@
+//! オッズの時系列（取得時刻ごとのスナップショット）
+
+use serde::{Deserialize, Serialize};
+
+/// odds_snapshots テーブルの行（1回の取得・1艇・1賭式）
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct OddsSnapshotRecord {
+    pub race_date: String,
+    pub venue_code: String,
+    pub race_number: i32,
+    /// 取得時刻（RFC3339）
+    pub captured_at: String,
+    /// "win" | "place"
+    pub bet_type: String,
+    pub boat_number: i32,
+    /// 単勝オッズ、複勝は範囲の下限
+    pub odds: f64,
+    /// 複勝オッズの範囲の上限
+    pub odds_max: Option<f64>,
+}
+
+/// オッズ監視の対象レース
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OddsPollTarget {
+    /// YYYY-MM-DD / YYYYMMDD
+    pub date: String,
+    pub place_number: u32,
+    pub race_number: u32,
+    /// 締切時刻（"YYYY-MM-DD HH:MM:SS"、日本時間）。省略時は保存済みの出走表から取得する
+    #[serde(default)]
+    pub race_closed_at: Option<String>,
+}
+
+/// 監視中レースの状態
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OddsPollRaceStatus {
+    pub race_date: String,
+    pub venue_code: String,
+    pub race_number: i32,
+    pub race_closed_at: String,
+    pub snapshot_count: i64,
+    pub last_captured_at: Option<String>,
+    pub last_error: Option<String>,
+    /// 締切を過ぎて監視を終えた
+    pub closed: bool,
+}
+
+/// オッズ監視の状態
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OddsPollerStatus {
+    pub running: bool,
+    pub interval_seconds: u64,
+    pub started_at: Option<String>,
+    pub races: Vec<OddsPollRaceStatus>,
+}
+
+/// 1艇・1賭式のオッズの推移
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OddsSeries {
+    pub bet_type: String,
+    pub boat_number: i32,
+    /// (取得時刻, オッズ) の時刻順
+    pub points: Vec<OddsPoint>,
+    pub first_odds: f64,
+    pub last_odds: f64,
+    /// 最初の取得からの変化率（(最新 - 最初) / 最初、下落は負）
+    pub change_rate: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OddsPoint {
+    pub captured_at: String,
+    pub odds: f64,
+    pub odds_max: Option<f64>,
+}
+
+/// レースのオッズの推移と下落の大きい買い目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OddsMovementReport {
+    pub race_date: String,
+    pub venue_code: String,
+    pub race_number: i32,
+    pub snapshot_count: i64,
+    pub first_captured_at: Option<String>,
+    pub last_captured_at: Option<String>,
+    pub series: Vec<OddsSeries>,
+    /// 下落率の大きい順（最初の取得より下がった買い目のみ）
+    pub biggest_drops: Vec<OddsSeries>,
+}
@@ -1,3 +1,8 @@
+use crate::models::code::{
+    decode, decode_real, CodeEnum, CodeLabel, Prefecture, RaceGrade, RacerClass, Technique, Weather,
+    WindDirection,
+};
+use crate::models::job::JobStatus;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -81,6 +86,31 @@ pub struct PayoutInfo {
 pub struct PayoutEntry {
     pub combination: Option<String>,
     pub payout: Option<i32>,
+    pub popularity: Option<i32>,
+}
+
+/// 賭式（race_payouts.bet_type の値）
+pub const BET_TYPES: [&str; 7] = [
+    "win", "place", "exacta", "quinella", "quinella_place", "trifecta", "trio",
+];
+
+impl PayoutInfo {
+    /// 全賭式の払戻エントリ（同着・複数組番もすべて含む）
+    pub fn entries(&self) -> impl Iterator<Item = (&'static str, &PayoutEntry)> {
+        [
+            ("win", &self.win),
+            ("place", &self.place),
+            ("exacta", &self.exacta),
+            ("quinella", &self.quinella),
+            ("quinella_place", &self.quinella_place),
+            ("trifecta", &self.trifecta),
+            ("trio", &self.trio),
+        ]
+        .into_iter()
+        .flat_map(|(bet_type, entries)| {
+            entries.iter().flatten().map(move |entry| (bet_type, entry))
+        })
+    }
 }
 
 // 3. Programs（出走表）
@@ -221,11 +251,27 @@ pub struct RaceCsvRow {
     // 勝者情報
     pub winner_boat_number: Option<i32>,
     pub winner_racer_number: Option<i32>,
+    // 直前気象条件
+    pub preview_wind: Option<f64>,
+    pub preview_wind_direction_number: Option<f64>,
+    pub preview_wave: Option<f64>,
+    pub preview_weather_number: Option<f64>,
+    pub preview_temperature: Option<f64>,
+    pub preview_water_temperature: Option<f64>,
     // レース詳細
     pub race_grade_number: Option<i32>,
     pub race_title: Option<String>,
     pub race_subtitle: Option<String>,
     pub race_distance: Option<i32>,
+    // コード値のラベル
+    pub race_weather_ja: Option<&'static str>,
+    pub race_weather_en: Option<&'static str>,
+    pub race_wind_direction_ja: Option<&'static str>,
+    pub race_wind_direction_en: Option<&'static str>,
+    pub race_technique_ja: Option<&'static str>,
+    pub race_technique_en: Option<&'static str>,
+    pub race_grade_ja: Option<&'static str>,
+    pub race_grade_en: Option<&'static str>,
     // JSONカラムは除外（ユーザー要求により）
 }
 
@@ -270,15 +316,29 @@ pub struct RaceParticipantCsvRow {
     pub assigned_boat_number: Option<i32>,
     pub assigned_boat_top_2_percent: Option<f64>,
     pub assigned_boat_top_3_percent: Option<f64>,
-    // プレビュー情報（previewsテーブルから）
+    // 展示情報（列名は従来のCSVと互換）
     pub racer_weight_adjustment: Option<f64>,
     pub racer_exhibition_time: Option<f64>,
     pub racer_tilt_adjustment: Option<f64>,
+    pub exhibition_course_number: Option<i32>,
+    pub exhibition_start_timing: Option<f64>,
+    // コード値のラベル
+    pub racer_class_ja: Option<&'static str>,
+    pub racer_class_en: Option<&'static str>,
+    pub racer_branch_ja: Option<&'static str>,
+    pub racer_branch_en: Option<&'static str>,
+    pub racer_birthplace_ja: Option<&'static str>,
+    pub racer_birthplace_en: Option<&'static str>,
 }
 
 // RaceRecord から RaceCsvRow への変換実装
 impl From<&RaceRecord> for RaceCsvRow {
     fn from(record: &RaceRecord) -> Self {
+        let weather = record.race_weather_number.and_then(Weather::from_real_code);
+        let wind_direction = record.race_wind_direction_number.and_then(WindDirection::from_real_code);
+        let technique = record.race_technique_number.and_then(Technique::from_real_code);
+        let grade = record.race_grade_number.and_then(RaceGrade::from_code);
+
         RaceCsvRow {
             race_date: record.race_date.clone(),
             venue_code: record.venue_code.clone(),
@@ -298,22 +358,34 @@ impl From<&RaceRecord> for RaceCsvRow {
             trio_payout: record.trio_payout,
             winner_boat_number: record.winner_boat_number,
             winner_racer_number: record.winner_racer_number,
+            preview_wind: record.preview_wind,
+            preview_wind_direction_number: record.preview_wind_direction_number,
+            preview_wave: record.preview_wave,
+            preview_weather_number: record.preview_weather_number,
+            preview_temperature: record.preview_temperature,
+            preview_water_temperature: record.preview_water_temperature,
             race_grade_number: record.race_grade_number,
             race_title: record.race_title.clone(),
             race_subtitle: record.race_subtitle.clone(),
             race_distance: record.race_distance,
+            race_weather_ja: weather.map(Weather::label_ja),
+            race_weather_en: weather.map(Weather::label_en),
+            race_wind_direction_ja: wind_direction.map(WindDirection::label_ja),
+            race_wind_direction_en: wind_direction.map(WindDirection::label_en),
+            race_technique_ja: technique.map(Technique::label_ja),
+            race_technique_en: technique.map(Technique::label_en),
+            race_grade_ja: grade.map(RaceGrade::label_ja),
+            race_grade_en: grade.map(RaceGrade::label_en),
         }
     }
 }
 
 // RaceParticipantRecord から RaceParticipantCsvRow への変換実装
 impl RaceParticipantCsvRow {
-    pub fn from_record(
-        participant: &RaceParticipantRecord,
-        race: &RaceRecord,
-        preview_data: Option<(Option<f64>, Option<f64>, Option<f64>)>, // (weight_adj, exhibition_time, tilt_adj)
-    ) -> Self {
-        let (weight_adjustment, exhibition_time, tilt_adjustment) = preview_data.unwrap_or((None, None, None));
+    pub fn from_record(participant: &RaceParticipantRecord, race: &RaceRecord) -> Self {
+        let class = participant.racer_class_number.and_then(RacerClass::from_code);
+        let branch = participant.racer_branch_number.and_then(Prefecture::from_code);
+        let birthplace = participant.racer_birthplace_number.and_then(Prefecture::from_code);
 
         RaceParticipantCsvRow {
             race_date: race.race_date.clone(),
@@ -347,9 +419,17 @@ impl RaceParticipantCsvRow {
             assigned_boat_number: participant.assigned_boat_number,
             assigned_boat_top_2_percent: participant.assigned_boat_top_2_percent,
             assigned_boat_top_3_percent: participant.assigned_boat_top_3_percent,
-            racer_weight_adjustment: weight_adjustment,
-            racer_exhibition_time: exhibition_time,
-            racer_tilt_adjustment: tilt_adjustment,
+            racer_weight_adjustment: participant.weight_adjustment,
+            racer_exhibition_time: participant.exhibition_time,
+            racer_tilt_adjustment: participant.tilt_adjustment,
+            exhibition_course_number: participant.exhibition_course_number,
+            exhibition_start_timing: participant.exhibition_start_timing,
+            racer_class_ja: class.map(RacerClass::label_ja),
+            racer_class_en: class.map(RacerClass::label_en),
+            racer_branch_ja: branch.map(Prefecture::label_ja),
+            racer_branch_en: branch.map(Prefecture::label_en),
+            racer_birthplace_ja: birthplace.map(Prefecture::label_ja),
+            racer_birthplace_en: birthplace.map(Prefecture::label_en),
         }
     }
 }
@@ -371,6 +451,15 @@ impl ApiDataType {
             ApiDataType::Programs => "programs",
         }
     }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "previews" => Some(ApiDataType::Previews),
+            "results" => Some(ApiDataType::Results),
+            "programs" => Some(ApiDataType::Programs),
+            _ => None,
+        }
+    }
 }
 
 // ===== 配当統計情報用構造体 =====
@@ -383,6 +472,20 @@ pub struct PayoutStats {
     pub max_win: Option<i32>,
 }
 
+/// 組番ごとの配当統計（race_payouts から集計）
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct CombinationPayoutStats {
+    pub bet_type: String,
+    pub combination: String,
+    /// 的中したレース数
+    pub hit_count: i64,
+    /// 結果のあるレースに対する的中率（0〜1）
+    pub hit_rate: f64,
+    pub avg_payout: Option<f64>,
+    pub min_payout: Option<i32>,
+    pub max_payout: Option<i32>,
+}
+
 // ===== 検索パラメータ構造体 =====
 
 /// 複合条件検索のパラメータ
@@ -421,6 +524,18 @@ pub struct SearchParams {
     // 着順条件（選手検索時）
     pub place_number: Option<i32>,
 
+    // 払戻条件（race_payouts の任意の賭式・組番）
+    pub bet_type: Option<String>,     // "win" | "place" | "exacta" | "quinella" | "quinella_place" | "trifecta" | "trio"
+    pub combination: Option<String>,  // 例: "1-2-3", "2=6"
+    pub min_payout: Option<i32>,
+    pub max_payout: Option<i32>,
+
+    // 展示条件（選手条件と同じ艇に適用）
+    pub max_exhibition_time: Option<f64>,
+    pub exhibition_course_number: Option<i32>,
+    pub max_exhibition_start_timing: Option<f64>,
+    pub min_tilt_adjustment: Option<f64>,
+
     // 結果数制限
     pub limit: Option<i32>,
 }
@@ -452,6 +567,13 @@ pub struct RaceRecord {
     // 勝者情報
     pub winner_boat_number: Option<i32>,
     pub winner_racer_number: Option<i32>,
+    // 直前気象条件（Previews APIから）
+    pub preview_wind: Option<f64>,
+    pub preview_wind_direction_number: Option<f64>,
+    pub preview_wave: Option<f64>,
+    pub preview_weather_number: Option<f64>,
+    pub preview_temperature: Option<f64>,
+    pub preview_water_temperature: Option<f64>,
     // レース詳細（Programs APIから）
     pub race_grade_number: Option<i32>,
     pub race_title: Option<String>,
@@ -463,6 +585,29 @@ pub struct RaceRecord {
     // メタデータ
     pub created_at: String,
     pub updated_at: String,
+    // コード値のラベル（テーブルには保存せず、取得後に decode_labels で付与）
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub race_weather_label: Option<CodeLabel>,
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub race_wind_direction_label: Option<CodeLabel>,
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub race_technique_label: Option<CodeLabel>,
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub race_grade_label: Option<CodeLabel>,
+}
+
+impl RaceRecord {
+    /// コード値（天候・風向・決まり手・グレード）のラベルを付与
+    pub fn decode_labels(&mut self) {
+        self.race_weather_label = decode_real::<Weather>(self.race_weather_number);
+        self.race_wind_direction_label = decode_real::<WindDirection>(self.race_wind_direction_number);
+        self.race_technique_label = decode_real::<Technique>(self.race_technique_number);
+        self.race_grade_label = decode::<RaceGrade>(self.race_grade_number);
+    }
 }
 
 /// race_participantsテーブルのレコード構造体
@@ -504,9 +649,34 @@ pub struct RaceParticipantRecord {
     pub assigned_boat_number: Option<i32>,
     pub assigned_boat_top_2_percent: Option<f64>,
     pub assigned_boat_top_3_percent: Option<f64>,
+    // 展示情報（Previews APIから）
+    pub exhibition_time: Option<f64>,
+    pub exhibition_course_number: Option<i32>,
+    pub exhibition_start_timing: Option<f64>,
+    pub tilt_adjustment: Option<f64>,
+    pub weight_adjustment: Option<f64>,
     // メタデータ
     pub created_at: String,
     pub updated_at: String,
+    // コード値のラベル（テーブルには保存せず、取得後に decode_labels で付与）
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub racer_class_label: Option<CodeLabel>,
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub racer_branch_label: Option<CodeLabel>,
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub racer_birthplace_label: Option<CodeLabel>,
+}
+
+impl RaceParticipantRecord {
+    /// コード値（級別・支部・出身地）のラベルを付与
+    pub fn decode_labels(&mut self) {
+        self.racer_class_label = decode::<RacerClass>(self.racer_class_number);
+        self.racer_branch_label = decode::<Prefecture>(self.racer_branch_number);
+        self.racer_birthplace_label = decode::<Prefecture>(self.racer_birthplace_number);
+    }
 }
 
 // ===== Bulk Fetch用構造体 =====
@@ -514,6 +684,8 @@ pub struct RaceParticipantRecord {
 /// 一括取得のサマリー
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BulkFetchSummary {
+    pub job_id: i64,
+    pub status: JobStatus,
     pub total_days: usize,
     pub success_count: usize,
     pub error_count: usize,
@@ -525,16 +697,19 @@ pub struct BulkFetchSummary {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BulkFetchError {
     pub date: String,
+    /// 全種別の並行取得時のみ
+    pub data_type: Option<String>,
     pub error_message: String,
 }
 
 /// 進捗通知ペイロード
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OpenApiBulkProgressPayload {
+    pub job_id: i64,
     pub message: String,
     pub current: usize,
     pub total: usize,
     pub date: String,
     pub data_type: String,  // "previews" | "results" | "programs"
-    pub status: String,     // "fetching" | "cached" | "saved" | "error" | "completed"
+    pub status: String,     // "fetching" | "cached" | "saved" | "error" | "completed" | "paused" | "cancelled"
 }
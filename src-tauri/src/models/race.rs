@@ -1,3 +1,4 @@
+use crate::error::AppError;
 use std::fmt;
 
 // ===== Player Information =====
@@ -222,6 +223,27 @@ impl WinningHandData {
     }
 }
 
+// ===== Boat Data (各艇個別) =====
+
+#[derive(Debug, Clone, norimaki_db::Serialize, norimaki_db::Deserialize)]
+pub struct BoatRaceData {
+    pub boat_number: u8,
+    pub player_basic_info: PlayerBasicInfo,
+    pub detailed_performance: DetailedPerformanceData,
+    pub st_data: STRelatedData,
+}
+
+impl BoatRaceData {
+    pub fn new(boat_number: u8) -> Self {
+        BoatRaceData {
+            boat_number,
+            player_basic_info: PlayerBasicInfo::new(),
+            detailed_performance: DetailedPerformanceData::new(),
+            st_data: STRelatedData::new(),
+        }
+    }
+}
+
 // ===== Race Data =====
 
 #[derive(Debug, Clone, norimaki_db::Serialize, norimaki_db::Deserialize)]
@@ -238,6 +260,9 @@ pub struct RaceData {
     pub detailed_performance: DetailedPerformanceData,
     pub st_data: STRelatedData,
     pub winning_hand: WinningHandData,
+    // 1〜6号艇の個別データ（旧キャッシュには存在しないため空で読み込む）
+    #[serde(default)]
+    pub boats: Vec<BoatRaceData>,
 }
 
 impl Default for RaceData {
@@ -261,8 +286,14 @@ impl RaceData {
             detailed_performance: DetailedPerformanceData::new(),
             st_data: STRelatedData::new(),
             winning_hand: WinningHandData::new(),
+            boats: Vec::new(),
         }
     }
+
+    /// 指定号艇の個別データを取得
+    pub fn boat(&self, boat_number: u8) -> Option<&BoatRaceData> {
+        self.boats.iter().find(|b| b.boat_number == boat_number)
+    }
 }
 
 impl fmt::Display for RaceData {
@@ -312,12 +343,13 @@ pub struct OddsData {
 
 // ===== Bulk Race Data =====
 
-#[derive(Debug, Clone, norimaki_db::Serialize, norimaki_db::Deserialize)]
+#[derive(Debug, Clone, norimaki_db::Serialize)]
 pub struct BulkRaceData {
     pub date: String,
     pub place_number: u32,
     pub race_number: u32,
     pub race_data: Option<RaceData>,
     pub win_place_odds_data: Option<OddsData>,
-    pub error: Option<String>,
+    /// 取得・解析に失敗した場合のエラー（コマンドのエラーと同じ `{code, message, details}` 形式）
+    pub error: Option<AppError>,
 }
@@ -0,0 +1,89 @@
+//! レースのキー（日付・競艇場・レース番号）
+//!
+//! Open API（races など）は日付 YYYYMMDD・競艇場コード "01"、kyoteibiyori のスクレイピングは
+//! 日付 YYYY-MM-DD・場番号 1 を使う。どちらの形式からも作れ、どちらの形式でも取り出せる。
+
+use crate::error::AppError;
+use chrono::NaiveDate;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RaceKey {
+    pub date: NaiveDate,
+    /// 競艇場番号（1〜24）
+    pub venue: u32,
+    /// レース番号（1〜12）
+    pub race_number: u32,
+}
+
+impl RaceKey {
+    pub fn new(date: NaiveDate, venue: u32, race_number: u32) -> Result<Self, AppError> {
+        if !(1..=24).contains(&venue) {
+            return Err(AppError::invalid_input("venue_code", format!("Invalid venue code: {}", venue)));
+        }
+        if !(1..=12).contains(&race_number) {
+            return Err(AppError::invalid_input(
+                "race_number",
+                format!("Invalid race number: {}", race_number),
+            ));
+        }
+        Ok(Self { date, venue, race_number })
+    }
+
+    /// 日付（YYYYMMDD / YYYY-MM-DD）と競艇場（"01" / "1"）から作る
+    pub fn parse(date: &str, venue: &str, race_number: u32) -> Result<Self, AppError> {
+        let date = NaiveDate::parse_from_str(&date.trim().replace('-', ""), "%Y%m%d")
+            .map_err(|_| AppError::invalid_input("date", format!("Invalid date: {}", date)))?;
+        Self::new(date, parse_venue(venue)?, race_number)
+    }
+
+    /// Open API 形式の日付（YYYYMMDD）
+    pub fn race_date(&self) -> String {
+        self.date.format("%Y%m%d").to_string()
+    }
+
+    /// Open API 形式の競艇場コード（"01"）
+    pub fn venue_code(&self) -> String {
+        format!("{:02}", self.venue)
+    }
+
+    /// スクレイピング・キャッシュ形式の日付（YYYY-MM-DD）
+    pub fn scrape_date(&self) -> String {
+        self.date.format("%Y-%m-%d").to_string()
+    }
+}
+
+/// 競艇場（"01" / "1"）を場番号（1〜24）に変換
+pub fn parse_venue(venue: &str) -> Result<u32, AppError> {
+    match venue.trim().parse::<u32>() {
+        Ok(venue @ 1..=24) => Ok(venue),
+        _ => Err(AppError::invalid_input("venue_code", format!("Invalid venue code: {}", venue))),
+    }
+}
+
+impl fmt::Display for RaceKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}-{}R", self.race_date(), self.venue_code(), self.race_number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_both_formats() {
+        let open_api = RaceKey::parse("20251228", "01", 12).unwrap();
+        let scraping = RaceKey::parse("2025-12-28", "1", 12).unwrap();
+        assert_eq!(open_api, scraping);
+        assert_eq!(open_api.race_date(), "20251228");
+        assert_eq!(open_api.scrape_date(), "2025-12-28");
+        assert_eq!(open_api.venue_code(), "01");
+        assert_eq!(open_api.to_string(), "20251228-01-12R");
+
+        assert!(RaceKey::parse("2025-13-01", "01", 1).is_err());
+        assert!(RaceKey::parse("20251228", "25", 1).is_err());
+        assert!(RaceKey::parse("20251228", "", 1).is_err());
+        assert!(RaceKey::parse("20251228", "01", 13).is_err());
+    }
+}
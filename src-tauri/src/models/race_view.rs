@@ -0,0 +1,90 @@
+//! 1レースの Open API データと kyoteibiyori の統計をまとめたビュー
+
+use crate::models::code::CodeLabel;
+use crate::models::open_api::{RaceParticipantRecord, RaceRecord};
+use crate::models::race::RaceData;
+use serde::{Deserialize, Serialize};
+
+/// 出走表・直前情報・結果（races / race_participants）と kyoteibiyori のレースデータ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RaceView {
+    /// YYYYMMDD
+    pub race_date: String,
+    /// "01"〜"24"
+    pub venue_code: String,
+    pub race_number: i32,
+    pub has_program: bool,
+    pub has_preview: bool,
+    pub has_result: bool,
+    /// races の行（Open API のデータがなければ None）
+    pub race: Option<RaceRecord>,
+    /// 出走表・展示・着順（艇番順）
+    pub participants: Vec<RaceParticipantRecord>,
+    /// kyoteibiyori のレースデータ（スクレイピング済みでなければ None）
+    pub biyori: Option<RaceData>,
+    /// kyoteibiyori の逃げ率と結果の比較（どちらかがなければ None）
+    pub escape_check: Option<EscapeCheck>,
+}
+
+/// 逃げ率（1号艇の逃げ）と実際の結果の比較
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EscapeCheck {
+    /// kyoteibiyori の逃げ率（0〜1）
+    pub escape_last_year: f64,
+    pub escape_last_half_year: f64,
+    /// 直近半年の逃げ率が 0.5 以上なら逃げると予想
+    pub predicted_escape: bool,
+    pub winner_boat_number: Option<i32>,
+    /// 決まり手
+    pub technique: Option<CodeLabel>,
+    /// 1号艇が逃げで1着
+    pub actual_escape: bool,
+    pub prediction_hit: bool,
+}
+
+/// 逃げ率と結果を比較する行（biyori_race_data と races の結合）
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct EscapeOutcomeRow {
+    pub race_date: String,
+    pub venue_code: String,
+    pub race_number: i32,
+    pub escape_last_year: Option<f64>,
+    pub escape_last_half_year: Option<f64>,
+    pub winner_boat_number: Option<i32>,
+    pub race_technique_number: Option<f64>,
+}
+
+/// 逃げ率の区間ごとの予想と実際
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EscapeCalibrationBucket {
+    /// 区間の下限・上限（0〜1、上限は含まない。最後の区間のみ 1.0 を含む）
+    pub rate_from: f64,
+    pub rate_to: f64,
+    pub races: i64,
+    pub escapes: i64,
+    /// 区間内の逃げ率の平均（予想）
+    pub average_escape_rate: f64,
+    /// 実際に逃げた割合
+    pub actual_escape_rate: f64,
+}
+
+/// 逃げ率が結果をどれだけ予想できたか
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EscapeCalibrationReport {
+    pub date_from: Option<String>,
+    pub date_to: Option<String>,
+    pub venue_code: Option<String>,
+    /// 比較に使った逃げ率（"last_half_year" | "last_year"）
+    pub rate_period: String,
+    /// 逃げ率と結果の両方があるレース数
+    pub races: i64,
+    pub escapes: i64,
+    /// 逃げ率の平均と実際に逃げた割合
+    pub average_escape_rate: f64,
+    pub actual_escape_rate: f64,
+    /// 逃げ率 0.5 以上で逃げると予想したときの的中率
+    pub prediction_accuracy: f64,
+    /// ブライアスコア（逃げ率を確率とみなした二乗誤差の平均、小さいほど良い）
+    pub brier_score: f64,
+    pub buckets: Vec<EscapeCalibrationBucket>,
+}
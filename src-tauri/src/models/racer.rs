@@ -0,0 +1,213 @@
+//! 選手マスタ（racers）と選手プロフィール
+
+use crate::models::code::{decode, CodeEnum, CodeLabel, Prefecture, RacerClass};
+use crate::models::open_api::ProgramRacerInfo;
+use crate::models::race::PlayerBasicInfo;
+use serde::{Deserialize, Serialize};
+
+/// racersテーブルのレコード構造体（登録番号ごとに1行）
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RacerRecord {
+    pub racer_number: i32,
+    pub racer_name: Option<String>,
+    pub branch_number: Option<i32>,
+    pub birthplace_number: Option<i32>,
+    /// 最新の級別
+    pub class_number: Option<i32>,
+    /// 期別（例: 100期 → 100）
+    pub period: Option<i32>,
+    pub gender: Option<String>,
+    /// 最後に出走表・スクレイピングで確認した日付（YYYYMMDD）
+    pub last_seen_date: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    // コード値のラベル（テーブルには保存せず、取得後に decode_labels で付与）
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub class_label: Option<CodeLabel>,
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub branch_label: Option<CodeLabel>,
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub birthplace_label: Option<CodeLabel>,
+}
+
+impl RacerRecord {
+    /// コード値（級別・支部・出身地）のラベルを付与
+    pub fn decode_labels(&mut self) {
+        self.class_label = decode::<RacerClass>(self.class_number);
+        self.branch_label = decode::<Prefecture>(self.branch_number);
+        self.birthplace_label = decode::<Prefecture>(self.birthplace_number);
+    }
+}
+
+/// racer_class_historyテーブルのレコード構造体（級別の適用期ごとに1行）
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RacerClassHistoryRecord {
+    pub racer_number: i32,
+    /// 適用期（例: "2025H2" = 2025年後期）
+    pub term: String,
+    pub class_number: i32,
+    pub first_seen_date: String,
+    pub last_seen_date: String,
+}
+
+/// 出走表・スクレイピング結果から得た選手情報（racers への反映用）
+#[derive(Debug, Clone, Default)]
+pub struct RacerUpdate {
+    pub racer_number: i32,
+    pub racer_name: Option<String>,
+    pub branch_number: Option<i32>,
+    pub birthplace_number: Option<i32>,
+    pub class_number: Option<i32>,
+    pub period: Option<i32>,
+    pub gender: Option<String>,
+    /// 情報の日付（YYYYMMDD）。古い情報で最新の級別・支部を上書きしないために使う
+    pub seen_date: String,
+}
+
+impl RacerUpdate {
+    /// Open API の出走表から作成（登録番号がない場合は None）
+    pub fn from_program(boat: &ProgramRacerInfo, race_date: &str) -> Option<Self> {
+        Some(Self {
+            racer_number: boat.racer_number?,
+            racer_name: boat.racer_name.clone(),
+            branch_number: boat.racer_branch_number,
+            birthplace_number: boat.racer_birthplace_number,
+            class_number: boat.racer_class_number,
+            period: None,
+            gender: None,
+            seen_date: race_date.replace('-', ""),
+        })
+    }
+
+    /// kyoteibiyori の選手基本情報から作成（登録番号が読めない場合は None）
+    ///
+    /// 級別（"A1"）・支部（"東京"）・期別（"100期"）は文字列からコード値に変換する。
+    pub fn from_player_info(info: &PlayerBasicInfo, race_date: &str) -> Option<Self> {
+        let racer_number = info.registration_number.trim().parse().ok()?;
+        let non_empty = |value: &str| {
+            let value = value.trim();
+            (!value.is_empty()).then(|| value.to_string())
+        };
+        let class_number = RacerClass::ALL
+            .iter()
+            .find(|class| class.label_ja() == info.class_level.trim())
+            .map(|class| class.code());
+        let branch_number = Prefecture::ALL
+            .iter()
+            .find(|prefecture| prefecture.label_ja() == info.support_group.trim())
+            .map(|prefecture| prefecture.code());
+        let period = info
+            .period
+            .chars()
+            .filter(|c| c.is_ascii_digit())
+            .collect::<String>()
+            .parse()
+            .ok();
+
+        Some(Self {
+            racer_number,
+            racer_name: non_empty(&info.name),
+            branch_number,
+            birthplace_number: None,
+            class_number,
+            period,
+            gender: non_empty(&info.gender),
+            seen_date: race_date.replace('-', ""),
+        })
+    }
+}
+
+/// 級別の適用期（1〜6月は前期 "H1"、7〜12月は後期 "H2"）
+pub fn class_term(date: &str) -> Option<String> {
+    let date = date.replace('-', "");
+    let year = date.get(0..4)?;
+    let month: u32 = date.get(4..6)?.parse().ok()?;
+    Some(format!("{}H{}", year, if month <= 6 { 1 } else { 2 }))
+}
+
+/// 着順ごとの回数
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PlaceCount {
+    pub place_number: i32,
+    pub count: i64,
+}
+
+/// 進入コース別成績
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RacerCourseStats {
+    pub course_number: i32,
+    pub starts: i64,
+    pub wins: i64,
+    pub win_rate: f64,
+    pub top2_rate: f64,
+    pub top3_rate: f64,
+    pub average_start_timing: Option<f64>,
+}
+
+/// 競艇場別成績
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RacerVenueStats {
+    pub venue_code: String,
+    pub starts: i64,
+    pub wins: i64,
+    pub win_rate: f64,
+    pub top3_rate: f64,
+}
+
+/// 選手プロフィール（保存済みのレース結果から集計）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RacerProfile {
+    pub racer_number: i32,
+    pub racer: Option<RacerRecord>,
+    pub class_history: Vec<RacerClassHistoryRecord>,
+    /// 結果のあるレースへの出走数（フライング・欠場を含む）
+    pub starts: i64,
+    pub place_distribution: Vec<PlaceCount>,
+    pub win_rate: f64,
+    pub top2_rate: f64,
+    pub top3_rate: f64,
+    /// レース結果のスタートタイミングの平均
+    pub average_start_timing: Option<f64>,
+    /// 最新の出走表時点のフライング・出遅れ回数
+    pub flying_count: Option<i32>,
+    pub late_count: Option<i32>,
+    pub course_stats: Vec<RacerCourseStats>,
+    pub venue_stats: Vec<RacerVenueStats>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_racer_update_from_player_info() {
+        let info = PlayerBasicInfo {
+            registration_number: " 4444 ".to_string(),
+            name: "テスト選手".to_string(),
+            class_level: "A1".to_string(),
+            period: "100期".to_string(),
+            support_group: "東京".to_string(),
+            gender: String::new(),
+        };
+        let update = RacerUpdate::from_player_info(&info, "2025-07-05").unwrap();
+        assert_eq!(update.racer_number, 4444);
+        assert_eq!(update.class_number, Some(1));
+        assert_eq!(update.branch_number, Some(13));
+        assert_eq!(update.period, Some(100));
+        assert_eq!(update.gender, None);
+        assert_eq!(update.seen_date, "20250705");
+
+        let unknown = PlayerBasicInfo::new();
+        assert!(RacerUpdate::from_player_info(&unknown, "2025-07-05").is_none());
+    }
+
+    #[test]
+    fn test_class_term() {
+        assert_eq!(class_term("20250630").as_deref(), Some("2025H1"));
+        assert_eq!(class_term("2025-07-01").as_deref(), Some("2025H2"));
+        assert_eq!(class_term("2025"), None);
+    }
+}
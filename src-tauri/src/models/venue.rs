@@ -27,11 +27,12 @@ pub struct AllVenuesResponse {
 
 #[derive(serde::Serialize, Clone)]
 pub struct BulkProgressPayload {
+    pub job_id: i64,
     pub message: String,
     pub current: usize,
     pub total: usize,
     pub date: String,
     pub place_number: u32,
     pub race_number: u32,
-    pub status: String, // "cache_hit" | "scraping" | "saved" | "error"
+    pub status: String, // "cache_hit" | "scraping" | "saved" | "error" | "completed" | "paused" | "cancelled"
 }
@@ -0,0 +1,177 @@
+//! オッズの時系列：スナップショットへの変換と推移・下落の集計
+//!
+//! 単勝・複勝オッズは締切まで変動するため、取得するたびに取得時刻付きで odds_snapshots に
+//! 追記する。複勝は範囲の下限を odds、上限を odds_max として保存し、推移も下限で比較する。
+
+use crate::models::odds_history::{OddsMovementReport, OddsPoint, OddsSeries, OddsSnapshotRecord};
+use crate::models::race::{BettingType, OddsData};
+use chrono::{FixedOffset, NaiveDateTime, Utc};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// 出走表の締切時刻の形式（日本時間）
+const CLOSED_AT_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// 現在の日本時間
+pub fn now_jst() -> NaiveDateTime {
+    let jst = FixedOffset::east_opt(9 * 3600).expect("valid offset");
+    Utc::now().with_timezone(&jst).naive_local()
+}
+
+/// 締切時刻（"YYYY-MM-DD HH:MM:SS"）を解釈
+pub fn parse_closed_at(race_closed_at: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(race_closed_at.trim(), CLOSED_AT_FORMAT).ok()
+}
+
+/// 1回分の単勝・複勝オッズを保存用の行に変換（単勝・複勝以外の賭式は空）
+pub fn snapshot_records(
+    race_date: &str,
+    venue_code: &str,
+    race_number: i32,
+    captured_at: &str,
+    odds: &OddsData,
+) -> Vec<OddsSnapshotRecord> {
+    if odds.betting_type != BettingType::WinPlace {
+        return Vec::new();
+    }
+    odds.combinations
+        .iter()
+        .map(|combination| {
+            let (bet_type, odds_value, odds_max) = match combination.second {
+                0 => ("win", combination.odds, None),
+                _ => {
+                    let mut bounds = combination
+                        .range_text
+                        .as_deref()
+                        .unwrap_or_default()
+                        .split('-')
+                        .map(|value| value.trim().parse::<f64>().ok());
+                    let lower = bounds.next().flatten().unwrap_or(combination.odds);
+                    ("place", lower, bounds.next().flatten())
+                }
+            };
+            OddsSnapshotRecord {
+                race_date: race_date.to_string(),
+                venue_code: venue_code.to_string(),
+                race_number,
+                captured_at: captured_at.to_string(),
+                bet_type: bet_type.to_string(),
+                boat_number: combination.first as i32,
+                odds: odds_value,
+                odds_max,
+            }
+        })
+        .collect()
+}
+
+/// スナップショット（取得時刻順）から買い目ごとの推移と下落率の大きい買い目を集計
+pub fn build_movement(
+    race_date: &str,
+    venue_code: &str,
+    race_number: i32,
+    snapshots: &[OddsSnapshotRecord],
+    drop_limit: usize,
+) -> OddsMovementReport {
+    let captures: BTreeSet<&str> = snapshots.iter().map(|s| s.captured_at.as_str()).collect();
+
+    let mut points: BTreeMap<(&str, i32), Vec<OddsPoint>> = BTreeMap::new();
+    for snapshot in snapshots {
+        points
+            .entry((snapshot.bet_type.as_str(), snapshot.boat_number))
+            .or_default()
+            .push(OddsPoint {
+                captured_at: snapshot.captured_at.clone(),
+                odds: snapshot.odds,
+                odds_max: snapshot.odds_max,
+            });
+    }
+
+    // 単勝を先に、艇番順
+    let mut series: Vec<OddsSeries> = points
+        .into_iter()
+        .filter_map(|((bet_type, boat_number), points)| {
+            let first_odds = points.first()?.odds;
+            let last_odds = points.last()?.odds;
+            let change_rate = if first_odds > 0.0 { (last_odds - first_odds) / first_odds } else { 0.0 };
+            Some(OddsSeries {
+                bet_type: bet_type.to_string(),
+                boat_number,
+                points,
+                first_odds,
+                last_odds,
+                change_rate,
+            })
+        })
+        .collect();
+    series.sort_by_key(|s| (s.bet_type != "win", s.boat_number));
+
+    let mut biggest_drops: Vec<OddsSeries> = series.iter().filter(|s| s.change_rate < 0.0).cloned().collect();
+    biggest_drops.sort_by(|a, b| a.change_rate.total_cmp(&b.change_rate));
+    biggest_drops.truncate(drop_limit);
+
+    OddsMovementReport {
+        race_date: race_date.to_string(),
+        venue_code: venue_code.to_string(),
+        race_number,
+        snapshot_count: captures.len() as i64,
+        first_captured_at: captures.first().map(|c| c.to_string()),
+        last_captured_at: captures.last().map(|c| c.to_string()),
+        series,
+        biggest_drops,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::race::OddsCombination;
+
+    fn win_place(win: f64, place: &str) -> OddsData {
+        OddsData {
+            betting_type: BettingType::WinPlace,
+            combinations: vec![
+                OddsCombination { first: 1, second: 0, third: None, odds: win, is_combined: false, range_text: None },
+                OddsCombination {
+                    first: 1,
+                    second: 1,
+                    third: None,
+                    odds: 0.0,
+                    is_combined: true,
+                    range_text: Some(place.to_string()),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_snapshot_records() {
+        let rows = snapshot_records("20251228", "01", 1, "t1", &win_place(2.4, "1.2-2.0"));
+        assert_eq!(rows.len(), 2);
+        assert_eq!((rows[0].bet_type.as_str(), rows[0].odds, rows[0].odds_max), ("win", 2.4, None));
+        assert_eq!((rows[1].bet_type.as_str(), rows[1].odds, rows[1].odds_max), ("place", 1.2, Some(2.0)));
+    }
+
+    #[test]
+    fn test_build_movement() {
+        let mut rows = snapshot_records("20251228", "01", 1, "t1", &win_place(4.0, "1.5-2.0"));
+        rows.extend(snapshot_records("20251228", "01", 1, "t2", &win_place(2.0, "1.2-1.8")));
+        let report = build_movement("20251228", "01", 1, &rows, 1);
+
+        assert_eq!(report.snapshot_count, 2);
+        assert_eq!(report.last_captured_at.as_deref(), Some("t2"));
+        assert_eq!(report.series.len(), 2);
+        assert_eq!(report.series[0].bet_type, "win");
+        assert_eq!(report.series[0].points.len(), 2);
+        assert!((report.series[1].change_rate + 0.2).abs() < 1e-9);
+        // 単勝 4.0 → 2.0（-50%）が最大の下落
+        assert_eq!(report.biggest_drops.len(), 1);
+        assert_eq!(report.biggest_drops[0].bet_type, "win");
+        assert!((report.biggest_drops[0].change_rate + 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_parse_closed_at() {
+        let closed_at = parse_closed_at("2025-12-28 15:23:00").unwrap();
+        assert_eq!(closed_at.format("%H:%M").to_string(), "15:23");
+        assert!(parse_closed_at("15:23").is_none());
+    }
+}
@@ -0,0 +1,407 @@
+use crate::error::AppError;
+use crate::headress;
+use crate::request_policy::{self, RequestPolicy};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// リプレイ用HTMLディレクトリを指定する環境変数
+pub const REPLAY_DIR_ENV: &str = "BORT_REPLAY_DIR";
+
+/// ライブ取得したHTMLをリプレイ形式で保存するディレクトリを指定する環境変数
+pub const RECORD_DIR_ENV: &str = "BORT_RECORD_DIR";
+
+/// 取得対象のページ
+#[derive(Debug, Clone, PartialEq)]
+pub enum PageRequest {
+    /// 任意のURL（公式サイトなど）
+    Url(String),
+    /// kyoteibiyori 出走表ページ（date は YYYYMMDD）
+    BiyoriShusso {
+        race_no: u32,
+        place_no: u32,
+        date: String,
+        slider: u32,
+    },
+    /// kyoteibiyori 単勝・複勝オッズページ（date は YYYYMMDD）
+    BiyoriWinPlaceOdds {
+        race_no: u32,
+        place_no: u32,
+        date: String,
+    },
+}
+
+impl PageRequest {
+    /// リクエストに対応するURL（リプレイのキーにも使う）
+    pub fn url(&self) -> String {
+        match self {
+            PageRequest::Url(url) => url.clone(),
+            PageRequest::BiyoriShusso {
+                race_no,
+                place_no,
+                date,
+                slider,
+            } => headress::kyoteibiyori_race_url(*race_no, *place_no, date, *slider),
+            PageRequest::BiyoriWinPlaceOdds {
+                race_no,
+                place_no,
+                date,
+            } => headress::kyoteibiyori_race_url(
+                *race_no,
+                *place_no,
+                date,
+                headress::WIN_PLACE_ODDS_SLIDER,
+            ),
+        }
+    }
+}
+
+/// ページのHTMLを取得する手段を抽象化したトレイト
+///
+/// サービス層はこのトレイトにのみ依存し、ライブ取得（Chrome / HTTP）と
+/// 保存済みHTMLのリプレイを差し替えられるようにする。
+pub trait PageFetcher: Send + Sync {
+    fn fetch(&self, request: &PageRequest) -> Result<String, AppError>;
+}
+
+// ===== Headless Chrome =====
+
+/// ヘッドレスChromeで取得する（JavaScriptで描画されるkyoteibiyori向け）
+pub struct ChromePageFetcher {
+    policy: Arc<RequestPolicy>,
+}
+
+impl Default for ChromePageFetcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChromePageFetcher {
+    pub fn new() -> Self {
+        Self::with_policy(RequestPolicy::global())
+    }
+
+    pub fn with_policy(policy: Arc<RequestPolicy>) -> Self {
+        ChromePageFetcher { policy }
+    }
+
+    fn fetch_once(request: &PageRequest) -> Result<String, AppError> {
+        let result = match request {
+            PageRequest::Url(url) => headress::scrape_html_from_url(url),
+            PageRequest::BiyoriShusso {
+                race_no,
+                place_no,
+                date,
+                slider,
+            } => headress::fetch_shusso_info_from_kyoteibiyori(*race_no, *place_no, date, *slider),
+            PageRequest::BiyoriWinPlaceOdds {
+                race_no,
+                place_no,
+                date,
+            } => headress::fetch_odds_info_from_kyoteibiyori(*race_no, *place_no, date),
+        };
+        result.map_err(|e| chrome_error(request, e))
+    }
+}
+
+/// ヘッドレスChromeの失敗を `AppError` に変換する
+///
+/// 移動・待機の段階で分類済みの `AppError` はそのまま返す。
+/// ブラウザとの接続が切れた場合だけ通信エラー（再試行対象）とし、
+/// それ以外（ブラウザの起動失敗・DOM操作の失敗など）は再試行しても直らないため内部エラーにする。
+fn chrome_error(request: &PageRequest, error: Box<dyn std::error::Error>) -> AppError {
+    let error = match error.downcast::<AppError>() {
+        Ok(app_error) => return *app_error,
+        Err(other) => other,
+    };
+    if error.is::<headless_chrome::browser::ConnectionClosed>() {
+        return AppError::NetworkError {
+            message: error.to_string(),
+            url: Some(request.url()),
+            status: None,
+            retry_after: None,
+        };
+    }
+    AppError::internal(format!("ブラウザ操作エラー: {}", error))
+}
+
+impl PageFetcher for ChromePageFetcher {
+    fn fetch(&self, request: &PageRequest) -> Result<String, AppError> {
+        self.policy
+            .execute_blocking(&request.url(), || Self::fetch_once(request))
+    }
+}
+
+// ===== reqwest =====
+
+/// 素のHTTP GETで取得する（静的HTMLを返す公式サイト向け）
+///
+/// タブのクリックなどは行えないため、kyoteibiyoriのオッズページは
+/// 初期表示のHTMLがそのまま返る。
+pub struct HttpPageFetcher {
+    timeout: Duration,
+    policy: Arc<RequestPolicy>,
+}
+
+impl Default for HttpPageFetcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HttpPageFetcher {
+    pub fn new() -> Self {
+        Self::with_policy(RequestPolicy::global())
+    }
+
+    pub fn with_policy(policy: Arc<RequestPolicy>) -> Self {
+        HttpPageFetcher {
+            timeout: Duration::from_secs(30),
+            policy,
+        }
+    }
+
+    fn fetch_once(&self, url: &str) -> Result<String, AppError> {
+        // blocking::Client は非同期ランタイム上で生成・破棄できないため、呼び出しごとに作る
+        let client = reqwest::blocking::Client::builder()
+            .timeout(self.timeout)
+            .build()
+            .map_err(|e| AppError::internal(format!("HTTPクライアント作成エラー: {}", e)))?;
+
+        let response = client.get(url).send()?;
+        eprintln!("HTTPレスポンス受信完了: {}", response.status());
+
+        if !response.status().is_success() {
+            return Err(AppError::http_status(
+                url,
+                response.status().as_u16(),
+                request_policy::retry_after_secs(response.headers()),
+            ));
+        }
+
+        Ok(response.text()?)
+    }
+}
+
+impl PageFetcher for HttpPageFetcher {
+    fn fetch(&self, request: &PageRequest) -> Result<String, AppError> {
+        let url = request.url();
+        self.policy.execute_blocking(&url, || self.fetch_once(&url))
+    }
+}
+
+// ===== Replay =====
+
+/// 保存済みHTMLをディレクトリから返す（オフライン実行・テスト用）
+///
+/// ファイル名はURLから [`replay_file_name`] で決まる。
+pub struct ReplayPageFetcher {
+    dir: PathBuf,
+}
+
+impl ReplayPageFetcher {
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        ReplayPageFetcher {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    /// URLに対応する保存先パス
+    pub fn page_path(&self, url: &str) -> PathBuf {
+        self.dir.join(replay_file_name(url))
+    }
+
+    /// 取得済みHTMLをリプレイ用に保存
+    pub fn save_page(&self, url: &str, html: &str) -> Result<PathBuf, String> {
+        fs::create_dir_all(&self.dir).map_err(|e| format!("ディレクトリ作成エラー: {}", e))?;
+        let path = self.page_path(url);
+        fs::write(&path, html).map_err(|e| format!("HTMLファイル保存エラー: {}", e))?;
+        Ok(path)
+    }
+}
+
+impl PageFetcher for ReplayPageFetcher {
+    fn fetch(&self, request: &PageRequest) -> Result<String, AppError> {
+        let url = request.url();
+        let path = self.page_path(&url);
+        eprintln!("📼 リプレイHTMLを読み込み: {}", path.display());
+        fs::read_to_string(&path).map_err(|e| {
+            AppError::io(format!(
+                "リプレイ用HTMLが見つかりません: {} ({}): {}",
+                url,
+                path.display(),
+                e
+            ))
+        })
+    }
+}
+
+/// 別のフェッチャーで取得したHTMLをリプレイ用ディレクトリに保存しながら返す
+pub struct RecordingPageFetcher {
+    inner: Arc<dyn PageFetcher>,
+    recorder: ReplayPageFetcher,
+}
+
+impl RecordingPageFetcher {
+    pub fn new<P: AsRef<Path>>(inner: Arc<dyn PageFetcher>, dir: P) -> Self {
+        RecordingPageFetcher {
+            inner,
+            recorder: ReplayPageFetcher::new(dir),
+        }
+    }
+}
+
+impl PageFetcher for RecordingPageFetcher {
+    fn fetch(&self, request: &PageRequest) -> Result<String, AppError> {
+        let html = self.inner.fetch(request)?;
+        match self.recorder.save_page(&request.url(), &html) {
+            Ok(path) => eprintln!("📼 リプレイ用HTMLを保存: {}", path.display()),
+            Err(e) => eprintln!("⚠️ リプレイ用HTML保存エラー: {}", e),
+        }
+        Ok(html)
+    }
+}
+
+/// URLをリプレイ用のファイル名に変換
+///
+/// 例: `https://kyoteibiyori.com/race_shusso.php?place_no=1&race_no=1&hiduke=20250726&slider=1`
+/// → `kyoteibiyori.com_race_shusso.php_place_no_1_race_no_1_hiduke_20250726_slider_1.html`
+pub fn replay_file_name(url: &str) -> String {
+    let trimmed = url
+        .trim_start_matches("https://")
+        .trim_start_matches("http://");
+    let name: String = trimmed
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{}.html", name)
+}
+
+fn env_dir(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|dir| !dir.is_empty())
+}
+
+/// 環境変数に応じてライブ取得をリプレイ／記録付きに差し替える
+fn from_env(live: Arc<dyn PageFetcher>) -> Arc<dyn PageFetcher> {
+    if let Some(dir) = env_dir(REPLAY_DIR_ENV) {
+        eprintln!("📼 リプレイモード: {}", dir);
+        return Arc::new(ReplayPageFetcher::new(dir));
+    }
+    if let Some(dir) = env_dir(RECORD_DIR_ENV) {
+        return Arc::new(RecordingPageFetcher::new(live, dir));
+    }
+    live
+}
+
+/// スクレイピング用のデフォルト（Chrome。`BORT_REPLAY_DIR` / `BORT_RECORD_DIR` で差し替え）
+pub fn default_browser_fetcher() -> Arc<dyn PageFetcher> {
+    from_env(Arc::new(ChromePageFetcher::new()))
+}
+
+/// 公式サイト用のデフォルト（HTTP。`BORT_REPLAY_DIR` / `BORT_RECORD_DIR` で差し替え）
+pub fn default_http_fetcher() -> Arc<dyn PageFetcher> {
+    from_env(Arc::new(HttpPageFetcher::new()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_replay_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("bort_replay_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_chrome_error_classification() {
+        let request = PageRequest::Url("https://example.com/".to_string());
+
+        // 要素が見つからない場合は再試行しない
+        let missing = chrome_error(&request, Box::new(AppError::element_not_found("#raceBasic")));
+        assert_eq!(missing, AppError::element_not_found("#raceBasic"));
+        assert!(!missing.is_transient());
+
+        // ブラウザとの接続切れは通信エラーとして再試行する
+        let closed = chrome_error(&request, Box::new(headless_chrome::browser::ConnectionClosed {}));
+        assert_eq!(closed.code(), "NETWORK_ERROR");
+        assert!(closed.is_transient());
+
+        // それ以外のブラウザ操作の失敗は内部エラー
+        let other = chrome_error(&request, "click failed".into());
+        assert_eq!(other.code(), "INTERNAL_ERROR");
+        assert!(!other.is_transient());
+    }
+
+    #[test]
+    fn test_replay_file_name() {
+        let request = PageRequest::BiyoriShusso {
+            race_no: 1,
+            place_no: 1,
+            date: "20250726".to_string(),
+            slider: 1,
+        };
+        assert_eq!(
+            replay_file_name(&request.url()),
+            "kyoteibiyori.com_race_shusso.php_place_no_1_race_no_1_hiduke_20250726_slider_1.html"
+        );
+    }
+
+    #[test]
+    fn test_replay_round_trip() {
+        let dir = temp_replay_dir("round_trip");
+        let fetcher = ReplayPageFetcher::new(&dir);
+        let request = PageRequest::BiyoriWinPlaceOdds {
+            race_no: 3,
+            place_no: 12,
+            date: "20250726".to_string(),
+        };
+
+        fetcher
+            .save_page(&request.url(), "<html><body>単勝</body></html>")
+            .unwrap();
+        let html = fetcher.fetch(&request).unwrap();
+        assert!(html.contains("単勝"));
+
+        // 別のレースは保存されていないのでエラー
+        let missing = PageRequest::BiyoriWinPlaceOdds {
+            race_no: 4,
+            place_no: 12,
+            date: "20250726".to_string(),
+        };
+        assert!(fetcher.fetch(&missing).is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_recording_saves_replayable_page() {
+        let source_dir = temp_replay_dir("record_source");
+        let record_dir = temp_replay_dir("record_target");
+        let request =
+            PageRequest::Url("https://www.boatrace.jp/owpc/pc/race/monthlyschedule".to_string());
+
+        let source = ReplayPageFetcher::new(&source_dir);
+        source
+            .save_page(&request.url(), "<html>月間スケジュール</html>")
+            .unwrap();
+
+        let recording = RecordingPageFetcher::new(Arc::new(source), &record_dir);
+        let live_html = recording.fetch(&request).unwrap();
+
+        // 記録したHTMLがそのままリプレイできる
+        let replayed = ReplayPageFetcher::new(&record_dir).fetch(&request).unwrap();
+        assert_eq!(live_html, replayed);
+
+        let _ = fs::remove_dir_all(&source_dir);
+        let _ = fs::remove_dir_all(&record_dir);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::error::AppError;
 use scraper::{Html, Selector};
 
 // Import data models from centralized models module
@@ -13,11 +14,11 @@ pub fn get_escaped_flame_info(content: &str) -> Result<RaceData, Box<dyn std::er
     let race_basic = document
         .select(&race_basic_selector)
         .next()
-        .ok_or("raceBasic not found")?;
+        .ok_or_else(|| AppError::element_not_found("#raceBasic"))?;
     let table = race_basic
         .select(&table_selector)
         .next()
-        .ok_or("Table not found")?;
+        .ok_or_else(|| AppError::element_not_found("#raceBasic table.table_fixed"))?;
 
     let mut race_data = RaceData::new();
 
@@ -52,10 +53,10 @@ pub fn get_escaped_flame_info(content: &str) -> Result<RaceData, Box<dyn std::er
     // 正しいデータマッピング - 実際のテーブル順序に合わせて修正
     // extracted_values[0] = 逃げ率のデータ (1年間, 半年間)
     // extracted_values[1] = 逃がし率のデータ (1年間, 半年間)
-    race_data.escape_last_year = from_percent_string_to_float(&extracted_values[1][0])?;      // 1年間逃げ率
-    race_data.escape_last_half_year = from_percent_string_to_float(&extracted_values[0][0])?; // 半年間逃げ率
-    race_data.allow_escape_last_year = from_percent_string_to_float(&extracted_values[1][1])?; // 1年間逃がし率
-    race_data.allow_escape_last_half_year = from_percent_string_to_float(&extracted_values[0][1])?; // 半年間逃がし率
+    race_data.escape_last_year = percent_value(&extracted_values, 1, 0, "escape_last_year", "逃げ")?;      // 1年間逃げ率
+    race_data.escape_last_half_year = percent_value(&extracted_values, 0, 0, "escape_last_half_year", "逃げ")?; // 半年間逃げ率
+    race_data.allow_escape_last_year = percent_value(&extracted_values, 1, 1, "allow_escape_last_year", "逃げ")?; // 1年間逃がし率
+    race_data.allow_escape_last_half_year = percent_value(&extracted_values, 0, 1, "allow_escape_last_half_year", "逃げ")?; // 半年間逃がし率
 
     // 刺され率（1号艇のみ）
     let search_text = "差され";
@@ -87,8 +88,8 @@ pub fn get_escaped_flame_info(content: &str) -> Result<RaceData, Box<dyn std::er
 
     // データ行0は半年間、データ行1は1年間のデータ
     // 1号艇のデータは各行の最初の列（インデックス0）
-    race_data.pierce_last_half_year = from_percent_string_to_float(&extracted_values[0][0])?; // 半年間差され率
-    race_data.pierce_last_year = from_percent_string_to_float(&extracted_values[1][0])?; // 1年間差され率
+    race_data.pierce_last_half_year = percent_value(&extracted_values, 0, 0, "pierce_last_half_year", "差され")?; // 半年間差され率
+    race_data.pierce_last_year = percent_value(&extracted_values, 1, 0, "pierce_last_year", "差され")?; // 1年間差され率
 
     // 捲られ率（1号艇のみ）- 差され率と同じシンプルなアプローチ + 空行スキップ
     let search_text = "捲られ";
@@ -130,18 +131,21 @@ pub fn get_escaped_flame_info(content: &str) -> Result<RaceData, Box<dyn std::er
     // 捲られ率のデータ構造: データ行0は半年間、データ行2は1年間（行1,3は別項目）
     // 1号艇のデータは各行の最初の列（インデックス0）
     if extracted_values.len() >= 3 {
-        race_data.overtake_last_half_year = from_percent_string_to_float(&extracted_values[0][0])?; // 半年間捲られ率
-        race_data.overtake_last_year = from_percent_string_to_float(&extracted_values[2][0])?; // 1年間捲られ率
+        race_data.overtake_last_half_year = percent_value(&extracted_values, 0, 0, "overtake_last_half_year", "捲られ")?; // 半年間捲られ率
+        race_data.overtake_last_year = percent_value(&extracted_values, 2, 0, "overtake_last_year", "捲られ")?; // 1年間捲られ率
     }
 
-    // 選手基本情報を抽出
-    race_data.player_basic_info = extract_player_basic_info(&document)?;
-
-    // 詳細成績データを抽出
-    race_data.detailed_performance = extract_detailed_performance_data(&document)?;
+    // 1〜6号艇の個別データを抽出（選手基本情報・詳細成績・ST関連）
+    for boat_number in 1..=BOAT_COUNT {
+        race_data.boats.push(extract_boat_race_data(&document, boat_number)?);
+    }
 
-    // ST関連データを抽出
-    race_data.st_data = extract_st_related_data(&document)?;
+    // 1号艇のデータは従来どおりトップレベルにも格納
+    if let Some(boat1) = race_data.boats.first() {
+        race_data.player_basic_info = boat1.player_basic_info.clone();
+        race_data.detailed_performance = boat1.detailed_performance.clone();
+        race_data.st_data = boat1.st_data.clone();
+    }
 
     // 決まり手データを抽出
     race_data.winning_hand = extract_winning_hand_data(&document)?;
@@ -149,16 +153,32 @@ pub fn get_escaped_flame_info(content: &str) -> Result<RaceData, Box<dyn std::er
     Ok(race_data)
 }
 
-fn extract_player_basic_info(document: &Html) -> Result<PlayerBasicInfo, Box<dyn std::error::Error>> {
+const BOAT_COUNT: u8 = 6;
+
+fn extract_boat_race_data(document: &Html, boat_number: u8) -> Result<BoatRaceData, Box<dyn std::error::Error>> {
+    Ok(BoatRaceData {
+        boat_number,
+        player_basic_info: extract_player_basic_info(document, boat_number)?,
+        detailed_performance: extract_detailed_performance_data(document, boat_number)?,
+        st_data: extract_st_related_data(document, boat_number)?,
+    })
+}
+
+/// 号艇に対応するセルのセレクタ（td.course1〜td.course6）
+fn course_cell_selector(boat_number: u8) -> Selector {
+    Selector::parse(&format!("td.course{}", boat_number)).unwrap()
+}
+
+fn extract_player_basic_info(document: &Html, boat_number: u8) -> Result<PlayerBasicInfo, Box<dyn std::error::Error>> {
     let race_member_names_selector = Selector::parse("#raceMemerNames").unwrap();
     let race_member_names2_selector = Selector::parse("#raceMemerNames2").unwrap();
     let table_selector = Selector::parse("table").unwrap();
     let row_selector = Selector::parse("tr").unwrap();
-    let course1_selector = Selector::parse("td.course1").unwrap();
+    let course_selector = course_cell_selector(boat_number);
 
     let mut player_info = PlayerBasicInfo::new();
 
-    // 1号艇の基本情報を抽出
+    // 指定号艇の基本情報を抽出
     // #raceMemerNames から 登録番号、名前、級別を取得
     if let Some(race_member_names) = document.select(&race_member_names_selector).next() {
         if let Some(table) = race_member_names.select(&table_selector).next() {
@@ -166,14 +186,14 @@ fn extract_player_basic_info(document: &Html) -> Result<PlayerBasicInfo, Box<dyn
             
             // 2行目：登録番号
             if let Some(row) = rows.get(1) {
-                if let Some(cell) = row.select(&course1_selector).next() {
+                if let Some(cell) = row.select(&course_selector).next() {
                     player_info.registration_number = cell.text().collect::<String>().trim().to_string();
                 }
             }
             
             // 3行目：選手名
             if let Some(row) = rows.get(2) {
-                if let Some(cell) = row.select(&course1_selector).next() {
+                if let Some(cell) = row.select(&course_selector).next() {
                     // 性別の判定（女性選手の場合はjoshi.pngがある）
                     let has_female_image = cell.select(&Selector::parse("img").unwrap())
                         .any(|img| img.value().attr("src").is_some_and(|src| src.contains("joshi.png")));
@@ -192,7 +212,7 @@ fn extract_player_basic_info(document: &Html) -> Result<PlayerBasicInfo, Box<dyn
             
             // 4行目：級別
             if let Some(row) = rows.get(3) {
-                if let Some(cell) = row.select(&course1_selector).next() {
+                if let Some(cell) = row.select(&course_selector).next() {
                     player_info.class_level = cell.text().collect::<String>().trim().to_string();
                 }
             }
@@ -206,7 +226,7 @@ fn extract_player_basic_info(document: &Html) -> Result<PlayerBasicInfo, Box<dyn
             
             // 1行目：期別と支部
             if let Some(row) = rows.first() {
-                if let Some(cell) = row.select(&course1_selector).next() {
+                if let Some(cell) = row.select(&course_selector).next() {
                     // HTMLの構造: "65期<br><span>群馬</span>" または "65期<br>群馬"
                     let cell_html = cell.html();
                     
@@ -239,7 +259,7 @@ fn extract_player_basic_info(document: &Html) -> Result<PlayerBasicInfo, Box<dyn
     Ok(player_info)
 }
 
-fn extract_detailed_performance_data(document: &Html) -> Result<DetailedPerformanceData, Box<dyn std::error::Error>> {
+fn extract_detailed_performance_data(document: &Html, boat_number: u8) -> Result<DetailedPerformanceData, Box<dyn std::error::Error>> {
     let race_basic_selector = Selector::parse("#raceBasic").unwrap();
     let table_selector = Selector::parse("table.table_fixed").unwrap();
     let row_selector = Selector::parse("tr").unwrap();
@@ -247,11 +267,11 @@ fn extract_detailed_performance_data(document: &Html) -> Result<DetailedPerforma
     let race_basic = document
         .select(&race_basic_selector)
         .next()
-        .ok_or("raceBasic not found")?;
+        .ok_or_else(|| AppError::element_not_found("#raceBasic"))?;
     let table = race_basic
         .select(&table_selector)
         .next()
-        .ok_or("Table not found")?;
+        .ok_or_else(|| AppError::element_not_found("#raceBasic table.table_fixed"))?;
 
     let mut detailed_performance = DetailedPerformanceData::new();
 
@@ -264,11 +284,11 @@ fn extract_detailed_performance_data(document: &Html) -> Result<DetailedPerforma
     ];
 
     for (metric_name, performance_data) in performance_metrics {
-        extract_performance_metric_data(&rows, metric_name, performance_data)?;
+        extract_performance_metric_data(&rows, metric_name, boat_number, performance_data)?;
     }
 
     // 枠別勝率データを個別に処理
-    extract_lane_win_rate_data(&rows, &mut detailed_performance.lane_win_rate)?;
+    extract_lane_win_rate_data(&rows, boat_number, &mut detailed_performance.lane_win_rate)?;
 
     Ok(detailed_performance)
 }
@@ -276,6 +296,7 @@ fn extract_detailed_performance_data(document: &Html) -> Result<DetailedPerforma
 fn extract_performance_metric_data(
     rows: &[scraper::ElementRef],
     metric_name: &str,
+    boat_number: u8,
     performance_data: &mut PerformanceData,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let cell_selector = Selector::parse("td").unwrap();
@@ -309,7 +330,7 @@ fn extract_performance_metric_data(
         ];
 
         for (period_name, data_ref) in time_periods {
-            if let Some(value) = find_period_data_for_boat1(rows, start_index, period_name)? {
+            if let Some(value) = find_period_data_for_boat(rows, start_index, period_name, boat_number)? {
                 *data_ref = Some(value);
             }
         }
@@ -320,6 +341,7 @@ fn extract_performance_metric_data(
 
 fn extract_lane_win_rate_data(
     rows: &[scraper::ElementRef],
+    boat_number: u8,
     lane_win_rate: &mut LaneWinRateData,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let cell_selector = Selector::parse("td").unwrap();
@@ -348,7 +370,7 @@ fn extract_lane_win_rate_data(
         ];
 
         for (period_name, data_ref) in time_periods {
-            if let Some(value) = find_period_data_for_boat1(rows, start_index, period_name)? {
+            if let Some(value) = find_period_data_for_boat(rows, start_index, period_name, boat_number)? {
                 *data_ref = Some(value);
             }
         }
@@ -357,28 +379,17 @@ fn extract_lane_win_rate_data(
     Ok(())
 }
 
-fn find_period_data_for_boat1(
+fn find_period_data_for_boat(
     rows: &[scraper::ElementRef],
     start_index: usize,
     period_name: &str,
+    boat_number: u8,
 ) -> Result<Option<f64>, Box<dyn std::error::Error>> {
-    let cell_selector = Selector::parse("td").unwrap();
-
     // 指定された時期のデータ行を探す
     for i in start_index..rows.len().min(start_index + 10) {
         if let Some(row) = rows.get(i) {
-            let cells: Vec<_> = row.select(&cell_selector).collect();
-            
-            // 最初のセルが時期名かチェック
-            if let Some(first_cell) = cells.first() {
-                let first_cell_text = first_cell.text().collect::<String>();
-                if first_cell_text.trim() == period_name {
-                    // 1号艇のデータは2番目のセル（インデックス1）
-                    if let Some(boat1_cell) = cells.get(1) {
-                        let boat1_text = boat1_cell.text().collect::<String>();
-                        return parse_performance_value(&boat1_text);
-                    }
-                }
+            if let Some(boat_text) = find_boat_cell_text(row, period_name, boat_number) {
+                return parse_performance_value(&boat_text);
             }
         }
     }
@@ -386,6 +397,26 @@ fn find_period_data_for_boat1(
     Ok(None)
 }
 
+/// 最初のセルが項目名と一致する行から、指定号艇のセルのテキストを取得
+fn find_boat_cell_text(row: &scraper::ElementRef, item_name: &str, boat_number: u8) -> Option<String> {
+    let cell_selector = Selector::parse("td").unwrap();
+    let cells: Vec<_> = row.select(&cell_selector).collect();
+
+    // 最初のセルが項目名かチェック
+    let first_cell_text = cells.first()?.text().collect::<String>();
+    if first_cell_text.trim() != item_name {
+        return None;
+    }
+
+    // td.courseN のセルを優先し、クラスがない場合は列位置（N号艇はインデックスN）で取得
+    let boat_cell = row
+        .select(&course_cell_selector(boat_number))
+        .next()
+        .or_else(|| cells.get(boat_number as usize).copied())?;
+
+    Some(boat_cell.text().collect::<String>())
+}
+
 fn parse_performance_value(text: &str) -> Result<Option<f64>, Box<dyn std::error::Error>> {
     let cleaned_text = text.trim();
     
@@ -406,7 +437,7 @@ fn parse_performance_value(text: &str) -> Result<Option<f64>, Box<dyn std::error
     }
 }
 
-fn extract_st_related_data(document: &Html) -> Result<STRelatedData, Box<dyn std::error::Error>> {
+fn extract_st_related_data(document: &Html, boat_number: u8) -> Result<STRelatedData, Box<dyn std::error::Error>> {
     let race_basic_selector = Selector::parse("#raceBasic").unwrap();
     let table_selector = Selector::parse("table.table_fixed").unwrap();
     let row_selector = Selector::parse("tr").unwrap();
@@ -414,11 +445,11 @@ fn extract_st_related_data(document: &Html) -> Result<STRelatedData, Box<dyn std
     let race_basic = document
         .select(&race_basic_selector)
         .next()
-        .ok_or("raceBasic not found")?;
+        .ok_or_else(|| AppError::element_not_found("#raceBasic"))?;
     let table = race_basic
         .select(&table_selector)
         .next()
-        .ok_or("Table not found")?;
+        .ok_or_else(|| AppError::element_not_found("#raceBasic table.table_fixed"))?;
 
     let mut st_data = STRelatedData::new();
 
@@ -432,11 +463,11 @@ fn extract_st_related_data(document: &Html) -> Result<STRelatedData, Box<dyn std
     ];
 
     for (metric_name, st_metric_data) in st_metrics {
-        extract_st_metric_data(&rows, metric_name, st_metric_data)?;
+        extract_st_metric_data(&rows, metric_name, boat_number, st_metric_data)?;
     }
 
     // ST考察データを個別に処理
-    extract_st_analysis_data(&rows, &mut st_data.st_analysis)?;
+    extract_st_analysis_data(&rows, boat_number, &mut st_data.st_analysis)?;
 
     Ok(st_data)
 }
@@ -444,6 +475,7 @@ fn extract_st_related_data(document: &Html) -> Result<STRelatedData, Box<dyn std
 fn extract_st_metric_data(
     rows: &[scraper::ElementRef],
     metric_name: &str,
+    boat_number: u8,
     st_data: &mut STData,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let cell_selector = Selector::parse("td").unwrap();
@@ -481,7 +513,7 @@ fn extract_st_metric_data(
         ];
 
         for (period_name, data_ref) in time_periods {
-            if let Some(value) = find_st_period_data_for_boat1(rows, start_index, period_name)? {
+            if let Some(value) = find_st_period_data_for_boat(rows, start_index, period_name, boat_number)? {
                 *data_ref = Some(value);
             }
         }
@@ -492,6 +524,7 @@ fn extract_st_metric_data(
 
 fn extract_st_analysis_data(
     rows: &[scraper::ElementRef],
+    boat_number: u8,
     st_analysis: &mut STAnalysisData,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let cell_selector = Selector::parse("td").unwrap();
@@ -521,7 +554,7 @@ fn extract_st_analysis_data(
         ];
 
         for (item_name, data_ref) in analysis_items {
-            if let Some(value) = find_st_period_data_for_boat1(rows, start_index, item_name)? {
+            if let Some(value) = find_st_period_data_for_boat(rows, start_index, item_name, boat_number)? {
                 *data_ref = Some(value);
             }
         }
@@ -530,28 +563,17 @@ fn extract_st_analysis_data(
     Ok(())
 }
 
-fn find_st_period_data_for_boat1(
+fn find_st_period_data_for_boat(
     rows: &[scraper::ElementRef],
     start_index: usize,
     period_name: &str,
+    boat_number: u8,
 ) -> Result<Option<f64>, Box<dyn std::error::Error>> {
-    let cell_selector = Selector::parse("td").unwrap();
-
     // 指定された時期のデータ行を探す
     for i in start_index..rows.len().min(start_index + 15) {
         if let Some(row) = rows.get(i) {
-            let cells: Vec<_> = row.select(&cell_selector).collect();
-            
-            // 最初のセルが時期名かチェック
-            if let Some(first_cell) = cells.first() {
-                let first_cell_text = first_cell.text().collect::<String>();
-                if first_cell_text.trim() == period_name {
-                    // 1号艇のデータは2番目のセル（インデックス1）
-                    if let Some(boat1_cell) = cells.get(1) {
-                        let boat1_text = boat1_cell.text().collect::<String>();
-                        return parse_st_value(&boat1_text);
-                    }
-                }
+            if let Some(boat_text) = find_boat_cell_text(row, period_name, boat_number) {
+                return parse_st_value(&boat_text);
             }
         }
     }
@@ -592,11 +614,11 @@ fn extract_winning_hand_data(document: &Html) -> Result<WinningHandData, Box<dyn
     let race_basic = document
         .select(&race_basic_selector)
         .next()
-        .ok_or("raceBasic not found")?;
+        .ok_or_else(|| AppError::element_not_found("#raceBasic"))?;
     let table = race_basic
         .select(&table_selector)
         .next()
-        .ok_or("Table not found")?;
+        .ok_or_else(|| AppError::element_not_found("#raceBasic table.table_fixed"))?;
 
     let mut winning_hand = WinningHandData::new();
 
@@ -733,6 +755,29 @@ fn from_percent_string_to_float(percent: &str) -> Result<f64, Box<dyn std::error
     Ok(value / 100.0)
 }
 
+/// 見出し行（`heading` を含む行）の後に続く行データから割合を取り出す
+///
+/// セルの欠損や形式不正は、項目名とセレクタ付きの ParseError にする。
+fn percent_value(
+    rows: &[Vec<String>],
+    row: usize,
+    col: usize,
+    field: &str,
+    heading: &str,
+) -> Result<f64, AppError> {
+    let selector = format!("#raceBasic table.table_fixed tr（「{}」の後続行{}・列{}）", heading, row, col);
+    let text = rows.get(row).and_then(|cells| cells.get(col)).ok_or_else(|| AppError::ParseError {
+        field: field.to_string(),
+        selector: Some(selector.clone()),
+        message: "セルが見つかりません".to_string(),
+    })?;
+    from_percent_string_to_float(text).map_err(|e| AppError::ParseError {
+        field: field.to_string(),
+        selector: Some(selector),
+        message: format!("{} (\"{}\")", e, text),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -991,6 +1036,67 @@ mod tests {
             println!("捲られ率データ行 {}: {:?}", idx, row_values);
         }
     }
+
+    #[test]
+    fn test_extract_boat_race_data_all_boats() {
+        // course1〜course6 のセルを持つ最小構成のHTMLで各艇の抽出を検証
+        let courses = |values: [&str; 6]| -> String {
+            values
+                .iter()
+                .enumerate()
+                .map(|(i, v)| format!(r#"<td class="course{}">{}</td>"#, i + 1, v))
+                .collect()
+        };
+        let html = format!(
+            r#"<html><body>
+            <div id="raceMemerNames"><table>
+                <tr><td>枠</td></tr>
+                <tr><td>登録番号</td>{}</tr>
+                <tr><td>選手名</td>{}</tr>
+                <tr><td>級別</td>{}</tr>
+            </table></div>
+            <div id="raceMemerNames2"><table>
+                <tr><td>期別</td>{}</tr>
+            </table></div>
+            <div id="raceBasic"><table class="table_fixed">
+                <tr><td>1着率</td></tr>
+                <tr><td>今期</td>{}</tr>
+                <tr><td>枠別勝率</td></tr>
+                <tr><td>直近1年</td>{}</tr>
+                <tr><td>平均ST</td></tr>
+                <tr><td>今期</td>{}</tr>
+            </table></div>
+            </body></html>"#,
+            courses(["3448", "4001", "4002", "4003", "4004", "4005"]),
+            courses(["高山秀雄", "選手B", "選手C", "選手D", "選手E", "選手F"]),
+            courses(["B1", "A1", "A2", "B1", "B2", "A1"]),
+            courses(["65期<br><span>群馬</span>", "90期<br><span>埼玉</span>", "100期<br><span>東京</span>",
+                     "110期<br><span>大阪</span>", "120期<br><span>福岡</span>", "125期<br><span>愛知</span>"]),
+            courses(["50.0%", "20.0%", "10.0%", "5.0%", "3.0%", "1.0%"]),
+            courses(["40.0%", "15.0%", "12.0%", "8.0%", "4.0%", "2.0%"]),
+            courses(["0.15", "0.16", "0.17", "0.18", "0.19", "0.20"]),
+        );
+        let document = Html::parse_document(&html);
+
+        let boat1 = extract_boat_race_data(&document, 1).unwrap();
+        assert_eq!(boat1.player_basic_info.registration_number, "3448");
+        assert_eq!(boat1.player_basic_info.period, "65期");
+        assert_eq!(boat1.player_basic_info.support_group, "群馬");
+
+        let boat4 = extract_boat_race_data(&document, 4).unwrap();
+        assert_eq!(boat4.boat_number, 4);
+        assert_eq!(boat4.player_basic_info.registration_number, "4003");
+        assert_eq!(boat4.player_basic_info.class_level, "B1");
+        assert_eq!(boat4.player_basic_info.period, "110期");
+        assert_eq!(boat4.player_basic_info.support_group, "大阪");
+        assert_eq!(boat4.detailed_performance.first_place_rate.this_period, Some(0.05));
+        assert_eq!(boat4.detailed_performance.lane_win_rate.last_1_year, Some(0.08));
+        assert_eq!(boat4.st_data.average_st.this_period, Some(0.18));
+
+        let boat6 = extract_boat_race_data(&document, 6).unwrap();
+        assert_eq!(boat6.player_basic_info.name, "選手F");
+        assert_eq!(boat6.st_data.average_st.this_period, Some(0.20));
+    }
 }
 
 // オッズデータ解析関数
@@ -1005,7 +1111,7 @@ pub fn parse_win_place_odds_from_html(html_content: &str) -> Result<OddsData, Bo
     
     // オッズテーブルを探す
     if let Some(odds_table) = document.select(&table_selector).next() {
-        println!("単勝・複勝オッズテーブルが見つかりました");
+        eprintln!("単勝・複勝オッズテーブルが見つかりました");
         
         let rows: Vec<_> = odds_table.select(&row_selector).collect();
         
@@ -1061,10 +1167,11 @@ pub fn parse_win_place_odds_from_html(html_content: &str) -> Result<OddsData, Bo
             }
         }
     } else {
-        return Err("単勝・複勝オッズテーブルが見つかりませんでした".into());
+        eprintln!("単勝・複勝オッズテーブルが見つかりませんでした");
+        return Err(AppError::element_not_found("table.odds_table").into());
     }
     
-    println!("単勝・複勝解析完了: {}個のオッズを取得", combinations.len());
+    eprintln!("単勝・複勝解析完了: {}個のオッズを取得", combinations.len());
     
     Ok(OddsData {
         betting_type: BettingType::WinPlace,
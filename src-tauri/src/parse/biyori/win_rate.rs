@@ -74,7 +74,7 @@
 
 //         let win_rates = result.unwrap();
 
-//         println!("Win rates: {:?}", win_rates);
+//         eprintln!("Win rates: {:?}", win_rates);
 
 //         // assert_eq!(
 //         //     win_rates[0], 6.5,
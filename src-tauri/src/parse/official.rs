@@ -1,5 +1,7 @@
+use crate::error::AppError;
+use crate::models::code::{CodeEnum, Prefecture, RacerClass};
 use chrono::Local;
-use scraper::{Html, Selector};
+use scraper::{ElementRef, Html, Selector};
 use serde::Serialize;
 use std::collections::HashMap;
 
@@ -19,6 +21,49 @@ pub struct MonthlySchedule {
     pub events: Vec<RaceEvent>,
 }
 
+/// 出走表（racelist ページ）の1艇分
+///
+/// 項目名は Open API の出走表（programs）に合わせている。
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct RaceCardEntry {
+    pub boat_number: i32,
+    pub racer_number: i32,
+    pub racer_name: String, // 姓と名は半角スペース区切り
+    pub racer_class: String, // "A1" 〜 "B2"
+    pub racer_class_number: Option<i32>,
+    pub racer_branch: String, // 支部
+    pub racer_branch_number: Option<i32>,
+    pub racer_birthplace: String,
+    pub racer_birthplace_number: Option<i32>,
+    pub racer_age: Option<i32>,
+    pub racer_weight: Option<f64>,
+    pub flying_count: i32,
+    pub late_count: i32,
+    pub average_start_timing: Option<f64>,
+    pub national_top_1_percent: Option<f64>, // 全国勝率
+    pub national_top_2_percent: Option<f64>,
+    pub national_top_3_percent: Option<f64>,
+    pub local_top_1_percent: Option<f64>, // 当地勝率
+    pub local_top_2_percent: Option<f64>,
+    pub local_top_3_percent: Option<f64>,
+    pub assigned_motor_number: Option<i32>,
+    pub assigned_motor_top_2_percent: Option<f64>,
+    pub assigned_motor_top_3_percent: Option<f64>,
+    pub assigned_boat_number: Option<i32>,
+    pub assigned_boat_top_2_percent: Option<f64>,
+    pub assigned_boat_top_3_percent: Option<f64>,
+}
+
+/// 公式サイトの出走表
+#[derive(Debug, Serialize, Clone)]
+pub struct RaceCard {
+    pub race_date: String, // YYYYMMDD
+    pub venue_code: String, // "01"
+    pub race_number: u32,
+    pub source_url: String,
+    pub entries: Vec<RaceCardEntry>,
+}
+
 /// 競艇場コードから名称への変換マップ
 fn get_venue_name_map() -> HashMap<u32, String> {
     let mut map = HashMap::new();
@@ -56,13 +101,13 @@ pub fn parse_monthly_schedule(
     let document = Html::parse_document(html_content);
     let venue_name_map = get_venue_name_map();
 
-    println!("月間スケジュール解析開始");
+    eprintln!("月間スケジュール解析開始");
 
     // 現在の年月を取得
     let now = Local::now();
     let year_month = now.format("%Y-%m").to_string();
 
-    println!("対象年月: {}", year_month);
+    eprintln!("対象年月: {}", year_month);
 
     // テーブルセレクタ
     let table_selector = Selector::parse("div.table1 table.is-spritedNone1").unwrap();
@@ -75,7 +120,7 @@ pub fn parse_monthly_schedule(
 
     // 各地区のテーブルを処理
     for (table_index, table) in document.select(&table_selector).enumerate() {
-        println!("テーブル {} を処理中", table_index + 1);
+        eprintln!("テーブル {} を処理中", table_index + 1);
 
         // 各競艇場の行を処理
         for row in table.select(&row_selector) {
@@ -108,7 +153,7 @@ pub fn parse_monthly_schedule(
 
                                     // 開始日を抽出（hd=YYYYMMDD から）
                                     if let Some(start_date) = extract_start_date(race_href) {
-                                        println!(
+                                        eprintln!(
                                             "大会発見: {} - {} ({}) {}日間 グレード: {}",
                                             venue_name,
                                             event_name,
@@ -135,7 +180,7 @@ pub fn parse_monthly_schedule(
         }
     }
 
-    println!("抽出した大会数: {}", all_events.len());
+    eprintln!("抽出した大会数: {}", all_events.len());
 
     Ok(MonthlySchedule {
         year_month,
@@ -143,6 +188,162 @@ pub fn parse_monthly_schedule(
     })
 }
 
+/// 出走表（racelist）HTMLを解析して6艇分の選手・モーター・ボート情報を取得
+///
+/// 各艇は `tbody.is-fs12` の1行目で、列は 枠 / 写真 / 登録番号・級別・氏名・支部・年齢体重 /
+/// F・L・平均ST / 全国 / 当地 / モーター / ボート の順（各列は `<br>` 区切りの3行）。
+pub fn parse_racelist(html_content: &str) -> Result<Vec<RaceCardEntry>, Box<dyn std::error::Error>> {
+    let document = Html::parse_document(html_content);
+
+    let tbody_selector = Selector::parse("div.table1 tbody.is-fs12").unwrap();
+    let row_selector = Selector::parse("tr").unwrap();
+    let cell_selector = Selector::parse("td").unwrap();
+    let info_selector = Selector::parse("div.is-fs11").unwrap();
+    let class_selector = Selector::parse("span").unwrap();
+    let name_selector = Selector::parse("div.is-fs18").unwrap();
+
+    let mut entries = Vec::new();
+
+    for tbody in document.select(&tbody_selector) {
+        let Some(row) = tbody.select(&row_selector).next() else {
+            continue;
+        };
+        let cells: Vec<_> = row.select(&cell_selector).collect();
+        // 枠の列（is-boatColorN）がない tbody は出走表ではない
+        if cells.len() < 8 || !cells[0].value().classes().any(|c| c.starts_with("is-boatColor")) {
+            continue;
+        }
+
+        let boat_number = parse_value::<i32>(&to_half_width(&cell_lines(cells[0]).concat()))
+            .ok_or_else(|| AppError::parse("boat_number", "枠番を読み取れません"))?;
+
+        // 登録番号/級別、支部/出身地、年齢/体重
+        let infos: Vec<_> = cells[2].select(&info_selector).collect();
+        if infos.len() < 2 {
+            return Err(AppError::element_not_found("div.is-fs11").into());
+        }
+        let racer_number = cell_lines(infos[0])
+            .first()
+            .and_then(|line| line.split('/').next().and_then(parse_value::<i32>))
+            .ok_or_else(|| AppError::parse("racer_number", format!("{}号艇の登録番号を読み取れません", boat_number)))?;
+        let racer_class = infos[0]
+            .select(&class_selector)
+            .next()
+            .map(|span| span.text().collect::<String>().trim().to_string())
+            .unwrap_or_default();
+        let racer_name = cells[2]
+            .select(&name_selector)
+            .next()
+            .map(|div| div.text().collect::<String>().split_whitespace().collect::<Vec<_>>().join(" "))
+            .unwrap_or_default();
+
+        let profile = cell_lines(infos[1]);
+        let (racer_branch, racer_birthplace) = split_pair(profile.first());
+        let (age, weight) = split_pair(profile.get(1));
+
+        let start = cell_lines(cells[3]);
+        let national = cell_values(cells[4]);
+        let local = cell_values(cells[5]);
+        let motor = cell_values(cells[6]);
+        let boat = cell_values(cells[7]);
+
+        entries.push(RaceCardEntry {
+            boat_number,
+            racer_number,
+            racer_name,
+            racer_class_number: RacerClass::ALL
+                .iter()
+                .find(|class| class.label_ja() == racer_class)
+                .map(|class| class.code()),
+            racer_class,
+            racer_branch_number: prefecture_code(&racer_branch),
+            racer_branch,
+            racer_birthplace_number: prefecture_code(&racer_birthplace),
+            racer_birthplace,
+            racer_age: parse_value(age.trim_end_matches('歳')),
+            racer_weight: parse_value(weight.trim_end_matches("kg")),
+            flying_count: start
+                .first()
+                .and_then(|f| parse_value(f.trim_start_matches('F')))
+                .unwrap_or(0),
+            late_count: start
+                .get(1)
+                .and_then(|l| parse_value(l.trim_start_matches('L')))
+                .unwrap_or(0),
+            average_start_timing: start.get(2).and_then(|st| parse_value(st)),
+            national_top_1_percent: national[0],
+            national_top_2_percent: national[1],
+            national_top_3_percent: national[2],
+            local_top_1_percent: local[0],
+            local_top_2_percent: local[1],
+            local_top_3_percent: local[2],
+            assigned_motor_number: motor[0].map(|number| number as i32),
+            assigned_motor_top_2_percent: motor[1],
+            assigned_motor_top_3_percent: motor[2],
+            assigned_boat_number: boat[0].map(|number| number as i32),
+            assigned_boat_top_2_percent: boat[1],
+            assigned_boat_top_3_percent: boat[2],
+        });
+    }
+
+    if entries.is_empty() {
+        return Err(AppError::element_not_found("div.table1 tbody.is-fs12").into());
+    }
+    if entries.len() != 6 {
+        return Err(AppError::parse("racelist", format!("出走表が6艇分ありません（{}艇）", entries.len())).into());
+    }
+
+    eprintln!("出走表解析完了: {}艇", entries.len());
+    Ok(entries)
+}
+
+/// セル内のテキストを `<br>` などで区切られた行ごとに取得（空行は除く）
+fn cell_lines(element: ElementRef) -> Vec<String> {
+    element
+        .text()
+        .map(|text| text.trim())
+        .filter(|text| !text.is_empty())
+        .map(|text| text.to_string())
+        .collect()
+}
+
+/// 3行の数値セル（勝率・2連率・3連率、No・2連率・3連率）
+fn cell_values(element: ElementRef) -> [Option<f64>; 3] {
+    let lines = cell_lines(element);
+    [0, 1, 2].map(|i| lines.get(i).and_then(|line| parse_value(line)))
+}
+
+/// "群馬/東京" や "29歳/55.6kg" を2つに分ける
+fn split_pair(line: Option<&String>) -> (String, String) {
+    let mut parts = line.map(|line| line.as_str()).unwrap_or_default().splitn(2, '/');
+    let first = parts.next().unwrap_or_default().trim().to_string();
+    let second = parts.next().unwrap_or_default().trim().to_string();
+    (first, second)
+}
+
+/// 数値に変換（"-" などは None）
+fn parse_value<T: std::str::FromStr>(text: &str) -> Option<T> {
+    text.trim().parse().ok()
+}
+
+/// 全角数字を半角に変換（枠番は "１" のように全角で表示される）
+fn to_half_width(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '０'..='９' => char::from_u32(c as u32 - '０' as u32 + '0' as u32).unwrap_or(c),
+            _ => c,
+        })
+        .collect()
+}
+
+/// 都道府県名を Open API の都道府県コードに変換
+fn prefecture_code(name: &str) -> Option<i32> {
+    Prefecture::ALL
+        .iter()
+        .find(|prefecture| prefecture.label_ja() == name)
+        .map(|prefecture| prefecture.code())
+}
+
 /// href から競艇場コード(jcd)を抽出
 fn extract_venue_code(href: &str) -> Option<u32> {
     for part in href.split(&['?', '&'][..]) {
@@ -342,4 +543,32 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_parse_racelist() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/official/racelist_20251228_01_1R.html");
+        let html = std::fs::read_to_string(path).unwrap();
+        let entries = parse_racelist(&html).unwrap();
+
+        assert_eq!(entries.len(), 6);
+        assert_eq!(entries.iter().map(|e| e.boat_number).collect::<Vec<_>>(), vec![1, 2, 3, 4, 5, 6]);
+
+        let first = &entries[0];
+        assert_eq!(first.racer_number, 5100);
+        assert_eq!(first.racer_name, "栗原 一馬");
+        assert_eq!((first.racer_class.as_str(), first.racer_class_number), ("B2", Some(4)));
+        assert_eq!((first.racer_branch.as_str(), first.racer_branch_number), ("群馬", Some(10)));
+        assert_eq!((first.racer_age, first.racer_weight), (Some(29), Some(55.6)));
+        assert_eq!((first.flying_count, first.late_count, first.average_start_timing), (0, 0, Some(0.2)));
+        assert_eq!(first.national_top_1_percent, Some(4.25));
+        assert_eq!(first.local_top_3_percent, Some(26.58));
+        assert_eq!((first.assigned_motor_number, first.assigned_motor_top_2_percent), (Some(50), Some(31.58)));
+        assert_eq!((first.assigned_boat_number, first.assigned_boat_top_3_percent), (Some(71), Some(43.48)));
+
+        assert_eq!(entries[3].racer_class_number, Some(2));
+        assert_eq!(entries[4].racer_birthplace, "千葉");
+
+        // 出走表のないページはエラー
+        assert!(parse_racelist("<html><body></body></html>").is_err());
+    }
 }
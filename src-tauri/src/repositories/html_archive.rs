@@ -0,0 +1,254 @@
+use crate::error::AppError;
+use crate::models::archive::ArchiveEntry;
+use crate::page_fetcher::PageRequest;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use sha2::{Digest, Sha256};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const DEFAULT_ARCHIVE_ROOT: &str = "bort-html/archive";
+const INDEX_FILE_NAME: &str = "index.jsonl";
+
+// インデックスへの追記を直列化する
+static INDEX_LOCK: Mutex<()> = Mutex::new(());
+
+/// 取得した生HTMLを保存するコンテンツアドレス型アーカイブ
+///
+/// レイアウト: `{root}/{date}/{place:02}/{race:02}/slider{n}/{sha256}.html.gz`
+/// （レースに紐付かないページは `{root}/misc/{sha256}.html.gz`）。
+/// 同一内容のページは1ファイルだけ保存し、取得のたびに `index.jsonl` へ1行追記する。
+pub struct HtmlArchive {
+    root: PathBuf,
+}
+
+impl Default for HtmlArchive {
+    fn default() -> Self {
+        Self::new(DEFAULT_ARCHIVE_ROOT)
+    }
+}
+
+impl HtmlArchive {
+    pub fn new<P: AsRef<Path>>(root: P) -> Self {
+        HtmlArchive {
+            root: root.as_ref().to_path_buf(),
+        }
+    }
+
+    /// HTMLを保存してインデックスに記録する
+    pub fn store(&self, request: &PageRequest, html: &str) -> Result<ArchiveEntry, AppError> {
+        let content_hash = content_hash(html);
+        let (date, place_number, race_number, slider) = race_location(request);
+
+        let relative_dir = match (&date, place_number, race_number, slider) {
+            (Some(date), Some(place), Some(race), Some(slider)) => {
+                format!("{}/{:02}/{:02}/slider{}", date, place, race, slider)
+            }
+            _ => "misc".to_string(),
+        };
+        let relative_path = format!("{}/{}.html.gz", relative_dir, content_hash);
+        let full_path = self.root.join(&relative_path);
+
+        // 同じ内容が保存済みなら書き込みを省略
+        if !full_path.exists() {
+            let dir = self.root.join(&relative_dir);
+            fs::create_dir_all(&dir)
+                .map_err(|e| AppError::io_at(&dir, format!("ディレクトリ作成エラー: {}", e)))?;
+            write_gzip(&full_path, html)?;
+        }
+
+        let entry = ArchiveEntry {
+            url: request.url(),
+            date,
+            place_number,
+            race_number,
+            slider,
+            content_hash,
+            fetched_at: chrono::Local::now().to_rfc3339(),
+            path: relative_path,
+            size_bytes: html.len(),
+        };
+        self.append_index(&entry)?;
+
+        Ok(entry)
+    }
+
+    /// アーカイブからHTMLを復元する
+    pub fn read(&self, entry: &ArchiveEntry) -> Result<String, AppError> {
+        let path = self.root.join(&entry.path);
+        let file = File::open(&path)
+            .map_err(|e| AppError::io_at(&path, format!("アーカイブ読み込みエラー: {}", e)))?;
+        let mut html = String::new();
+        GzDecoder::new(file)
+            .read_to_string(&mut html)
+            .map_err(|e| AppError::io_at(&path, format!("gzip展開エラー: {}", e)))?;
+        Ok(html)
+    }
+
+    /// インデックスの全エントリ（取得順）
+    pub fn entries(&self) -> Result<Vec<ArchiveEntry>, AppError> {
+        let index_path = self.root.join(INDEX_FILE_NAME);
+        if !index_path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let read_error =
+            |e: std::io::Error| AppError::io_at(&index_path, format!("インデックス読み込みエラー: {}", e));
+        let file = File::open(&index_path).map_err(read_error)?;
+        let mut entries = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line.map_err(read_error)?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<ArchiveEntry>(&line) {
+                Ok(entry) => entries.push(entry),
+                Err(e) => eprintln!("⚠️ インデックス行のパースに失敗: {}", e),
+            }
+        }
+        Ok(entries)
+    }
+
+    /// コンテンツハッシュからエントリを探す（最新の取得記録）
+    pub fn find_by_hash(&self, content_hash: &str) -> Result<Option<ArchiveEntry>, AppError> {
+        Ok(self
+            .entries()?
+            .into_iter()
+            .rev()
+            .find(|entry| entry.content_hash == content_hash))
+    }
+
+    fn append_index(&self, entry: &ArchiveEntry) -> Result<(), AppError> {
+        let line = serde_json::to_string(entry)
+            .map_err(|e| AppError::parse("archive_index", format!("インデックス変換エラー: {}", e)))?;
+
+        let _guard = INDEX_LOCK
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        fs::create_dir_all(&self.root)
+            .map_err(|e| AppError::io_at(&self.root, format!("ディレクトリ作成エラー: {}", e)))?;
+        let index_path = self.root.join(INDEX_FILE_NAME);
+        let write_error =
+            |e: std::io::Error| AppError::io_at(&index_path, format!("インデックス書き込みエラー: {}", e));
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&index_path)
+            .map_err(write_error)?;
+        writeln!(file, "{}", line).map_err(write_error)
+    }
+}
+
+/// HTMLのSHA-256（16進）
+pub fn content_hash(html: &str) -> String {
+    format!("{:x}", Sha256::digest(html.as_bytes()))
+}
+
+fn race_location(request: &PageRequest) -> (Option<String>, Option<u32>, Option<u32>, Option<u32>) {
+    match request {
+        PageRequest::Url(_) => (None, None, None, None),
+        PageRequest::BiyoriShusso {
+            race_no,
+            place_no,
+            date,
+            slider,
+        } => (
+            Some(date.clone()),
+            Some(*place_no),
+            Some(*race_no),
+            Some(*slider),
+        ),
+        PageRequest::BiyoriWinPlaceOdds {
+            race_no,
+            place_no,
+            date,
+        } => (
+            Some(date.clone()),
+            Some(*place_no),
+            Some(*race_no),
+            Some(crate::headress::WIN_PLACE_ODDS_SLIDER),
+        ),
+    }
+}
+
+fn write_gzip(path: &Path, html: &str) -> Result<(), AppError> {
+    let file = File::create(path)
+        .map_err(|e| AppError::io_at(path, format!("アーカイブ書き込みエラー: {}", e)))?;
+    let compress_error = |e: std::io::Error| AppError::io_at(path, format!("gzip圧縮エラー: {}", e));
+    let mut encoder = GzEncoder::new(file, Compression::default());
+    encoder.write_all(html.as_bytes()).map_err(compress_error)?;
+    encoder.finish().map_err(compress_error)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_archive(name: &str) -> (HtmlArchive, PathBuf) {
+        let dir =
+            std::env::temp_dir().join(format!("bort_archive_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        (HtmlArchive::new(&dir), dir)
+    }
+
+    #[test]
+    fn test_store_and_read_race_page() {
+        let (archive, dir) = temp_archive("store");
+        let request = PageRequest::BiyoriShusso {
+            race_no: 5,
+            place_no: 1,
+            date: "20250726".to_string(),
+            slider: 1,
+        };
+        let html = "<html><body><div id=\"raceBasic\">桐生 5R</div></body></html>";
+
+        let entry = archive.store(&request, html).unwrap();
+        assert_eq!(entry.content_hash, content_hash(html));
+        assert_eq!(
+            entry.path,
+            format!("20250726/01/05/slider1/{}.html.gz", entry.content_hash)
+        );
+        assert_eq!(archive.read(&entry).unwrap(), html);
+
+        // 失われたファイルは対象パス付きの IoError になる
+        fs::remove_file(dir.join(&entry.path)).unwrap();
+        let error = archive.read(&entry).unwrap_err();
+        assert_eq!(error.code(), "IO_ERROR");
+        let path = dir.join(&entry.path).display().to_string();
+        assert_eq!(serde_json::to_value(&error).unwrap()["details"]["path"], path.as_str());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_same_content_is_stored_once_but_indexed_per_fetch() {
+        let (archive, dir) = temp_archive("dedupe");
+        let request = PageRequest::BiyoriWinPlaceOdds {
+            race_no: 1,
+            place_no: 24,
+            date: "20250726".to_string(),
+        };
+
+        let first = archive.store(&request, "<html>単勝</html>").unwrap();
+        let second = archive.store(&request, "<html>単勝</html>").unwrap();
+        let other_race = PageRequest::BiyoriWinPlaceOdds {
+            race_no: 2,
+            place_no: 24,
+            date: "20250726".to_string(),
+        };
+        let third = archive.store(&other_race, "<html>複勝</html>").unwrap();
+
+        assert_eq!(first.path, second.path);
+        assert_ne!(first.path, third.path);
+        assert_eq!(archive.entries().unwrap().len(), 3);
+
+        let found = archive.find_by_hash(&third.content_hash).unwrap().unwrap();
+        assert_eq!(found.race_number, Some(2));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,162 @@
+use crate::models::job::{BulkJob, JobKind, JobStatus, JobUnit, UnitStatus};
+use chrono::Utc;
+use sqlx::SqlitePool;
+
+/// 一括取得ジョブと作業単位を SQLite に永続化するリポジトリ
+pub struct JobRepository {
+    pool: SqlitePool,
+}
+
+impl JobRepository {
+    /// マイグレーション済みの接続プールから生成（bulk_jobs / bulk_job_units は V11 マイグレーションで作成）
+    pub(crate) fn with_pool(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// ジョブと作業単位をまとめて登録し、ジョブIDを返す
+    pub async fn create_job(
+        &self,
+        kind: JobKind,
+        params_json: &str,
+        unit_payloads: &[String],
+    ) -> Result<i64, sqlx::Error> {
+        let now = Utc::now().to_rfc3339();
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO bulk_jobs (kind, status, params_json, total_units, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(kind)
+        .bind(JobStatus::Pending)
+        .bind(params_json)
+        .bind(unit_payloads.len() as i64)
+        .bind(&now)
+        .bind(&now)
+        .execute(&mut *tx)
+        .await?;
+        let job_id = result.last_insert_rowid();
+
+        for (seq, payload) in unit_payloads.iter().enumerate() {
+            sqlx::query(
+                r#"
+                INSERT INTO bulk_job_units (job_id, seq, payload_json, status, error, updated_at)
+                VALUES (?, ?, ?, ?, NULL, ?)
+                "#,
+            )
+            .bind(job_id)
+            .bind(seq as i64)
+            .bind(payload)
+            .bind(UnitStatus::Pending)
+            .bind(&now)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(job_id)
+    }
+
+    /// 作業単位の集計つきでジョブを取得する際の SELECT 句
+    const JOB_SELECT: &'static str = r#"
+        SELECT
+            j.id, j.kind, j.status, j.params_json, j.total_units, j.created_at, j.updated_at,
+            COALESCE(SUM(CASE WHEN u.status = 'done' THEN 1 ELSE 0 END), 0) AS done_units,
+            COALESCE(SUM(CASE WHEN u.status = 'skipped' THEN 1 ELSE 0 END), 0) AS skipped_units,
+            COALESCE(SUM(CASE WHEN u.status = 'failed' THEN 1 ELSE 0 END), 0) AS failed_units
+        FROM bulk_jobs j
+        LEFT JOIN bulk_job_units u ON u.job_id = j.id
+    "#;
+
+    pub async fn get_job(&self, job_id: i64) -> Result<Option<BulkJob>, sqlx::Error> {
+        let sql = format!("{} WHERE j.id = ? GROUP BY j.id", Self::JOB_SELECT);
+        sqlx::query_as::<_, BulkJob>(&sql)
+            .bind(job_id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// 新しい順にジョブ一覧を取得
+    pub async fn list_jobs(&self) -> Result<Vec<BulkJob>, sqlx::Error> {
+        let sql = format!("{} GROUP BY j.id ORDER BY j.id DESC", Self::JOB_SELECT);
+        sqlx::query_as::<_, BulkJob>(&sql).fetch_all(&self.pool).await
+    }
+
+    pub async fn set_status(&self, job_id: i64, status: JobStatus) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE bulk_jobs SET status = ?, updated_at = ? WHERE id = ?")
+            .bind(status)
+            .bind(Utc::now().to_rfc3339())
+            .bind(job_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// 前回の実行中にアプリが終了したジョブを一時停止扱いに戻す
+    pub async fn pause_interrupted_jobs(&self) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query("UPDATE bulk_jobs SET status = ?, updated_at = ? WHERE status = ?")
+            .bind(JobStatus::Paused)
+            .bind(Utc::now().to_rfc3339())
+            .bind(JobStatus::Running)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// 未処理の作業単位（seq 順）
+    pub async fn pending_units(&self, job_id: i64) -> Result<Vec<JobUnit>, sqlx::Error> {
+        sqlx::query_as::<_, JobUnit>(
+            "SELECT job_id, seq, payload_json, status, error
+             FROM bulk_job_units
+             WHERE job_id = ? AND status = ?
+             ORDER BY seq",
+        )
+        .bind(job_id)
+        .bind(UnitStatus::Pending)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// 失敗した作業単位（seq 順）
+    pub async fn failed_units(&self, job_id: i64) -> Result<Vec<JobUnit>, sqlx::Error> {
+        sqlx::query_as::<_, JobUnit>(
+            "SELECT job_id, seq, payload_json, status, error
+             FROM bulk_job_units
+             WHERE job_id = ? AND status = ?
+             ORDER BY seq",
+        )
+        .bind(job_id)
+        .bind(UnitStatus::Failed)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    pub async fn update_unit(
+        &self,
+        job_id: i64,
+        seq: i64,
+        status: UnitStatus,
+        error: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        let now = Utc::now().to_rfc3339();
+        sqlx::query(
+            "UPDATE bulk_job_units SET status = ?, error = ?, updated_at = ? WHERE job_id = ? AND seq = ?",
+        )
+        .bind(status)
+        .bind(error)
+        .bind(&now)
+        .bind(job_id)
+        .bind(seq)
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query("UPDATE bulk_jobs SET updated_at = ? WHERE id = ?")
+            .bind(&now)
+            .bind(job_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
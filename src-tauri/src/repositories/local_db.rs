@@ -1,146 +1,432 @@
-use norimaki_db::{FileStore, KeyValueStore, serialize_to_string, deserialize_from_string, Result};
-use crate::models::race::{RaceData, OddsData};
-use std::sync::Mutex;
+use crate::models::archive::ArchiveEntry;
+use crate::models::race::{OddsData, RaceData};
+use chrono::Utc;
+use norimaki_db::{deserialize_from_string, FileStore, KeyValueStore};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::path::Path;
+use tokio::sync::Mutex;
+
+/// 旧キャッシュ（norimaki-db の JSON ファイル）
+pub const LEGACY_JSON_PATH: &str = "bort_race_data.json";
+
+/// 旧キャッシュの取り込みを同時に実行しないためのロック
+static LEGACY_IMPORT: Mutex<()> = Mutex::const_new(());
+
+/// JSON ファイルからの取り込み結果
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LegacyImportSummary {
+    pub race_data_imported: usize,
+    pub odds_imported: usize,
+    /// すでに SQLite にあったため取り込まなかった件数
+    pub skipped: usize,
+    pub errors: Vec<String>,
+}
 
-static DB: Mutex<Option<FileStore>> = Mutex::new(None);
+/// kyoteibiyori のスクレイピング結果（レースデータ・単勝複勝オッズ）のキャッシュ
+///
+/// Open API と同じ SQLite データベースの biyori_race_data / biyori_odds テーブルに保存する。
+/// 日付は YYYY-MM-DD / YYYYMMDD のどちらでも受け付け、races と同じ YYYYMMDD・競艇場コード "01" 形式で保存する。
+pub struct LocalDbRepository {
+    pool: SqlitePool,
+}
 
-const DB_FILE_PATH: &str = "bort_race_data.json";
+/// 保存用の日付（YYYYMMDD）と競艇場コード
+fn race_key(date: &str, place_number: u32) -> (String, String) {
+    (date.replace('-', ""), format!("{:02}", place_number))
+}
 
-fn get_db() -> Result<&'static Mutex<Option<FileStore>>> {
-    let mut db = DB.lock().unwrap();
-    if db.is_none() {
-        *db = Some(FileStore::new(DB_FILE_PATH)?);
+fn table(kind: &str) -> Result<&'static str, sqlx::Error> {
+    match kind {
+        "data" => Ok("biyori_race_data"),
+        "odds" => Ok("biyori_odds"),
+        _ => Err(sqlx::Error::Protocol(format!("Unknown cache kind: {}", kind))),
     }
-    Ok(&DB)
 }
 
-/// Local database repository using norimaki-db
-pub struct LocalDbRepository;
+fn decode<T: serde::de::DeserializeOwned>(json: &str) -> Result<T, sqlx::Error> {
+    serde_json::from_str(json).map_err(|e| sqlx::Error::Decode(Box::new(e)))
+}
+
+/// 旧キャッシュ（norimaki-db）で保存した値を読む
+fn decode_legacy<T: serde::de::DeserializeOwned>(value: &str) -> Result<T, sqlx::Error> {
+    deserialize_from_string(value).map_err(|e| sqlx::Error::Decode(e.to_string().into()))
+}
+
+fn encode<T: Serialize>(value: &T) -> Result<String, sqlx::Error> {
+    serde_json::to_string(value).map_err(|e| sqlx::Error::Protocol(format!("Failed to encode cache data: {}", e)))
+}
 
 impl LocalDbRepository {
-    pub fn new() -> Result<Self> {
-        // Initialize DB
-        get_db()?;
-        Ok(LocalDbRepository)
+    /// 開いている Open API データベースの接続を使う
+    pub(crate) fn with_pool(pool: SqlitePool) -> Self {
+        Self { pool }
     }
 
-    pub fn save_race_data(
+    /// データベースを指定して開く（未適用のマイグレーションも実行）
+    #[cfg(test)]
+    pub(crate) async fn open(db_path: &str) -> Result<Self, sqlx::Error> {
+        let repo = crate::repositories::sqlite_db::SqliteRepository::new(db_path).await?;
+        Ok(Self { pool: repo.pool().clone() })
+    }
+
+    pub async fn save_race_data(
         &self,
         date: &str,
         place_number: u32,
         race_number: u32,
         race_data: &RaceData,
-    ) -> Result<()> {
-        let db_lock = get_db()?;
-        let mut db_guard = db_lock.lock().unwrap();
-        let db = db_guard.as_mut().unwrap();
-
-        let key = format!("race_{}_{}_{}_{}", date, place_number, race_number, "data");
-        let value = serialize_to_string(race_data)?;
-
-        db.put(key, value)
+    ) -> Result<(), sqlx::Error> {
+        let (race_date, venue_code) = race_key(date, place_number);
+        let now = Utc::now().to_rfc3339();
+        sqlx::query(
+            r#"
+            INSERT INTO biyori_race_data (
+                race_date, venue_code, race_number, data_json,
+                escape_last_year, escape_last_half_year,
+                allow_escape_last_year, allow_escape_last_half_year,
+                pierce_last_year, pierce_last_half_year,
+                overtake_last_year, overtake_last_half_year,
+                fetched_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(race_date, venue_code, race_number) DO UPDATE SET
+                data_json = excluded.data_json,
+                escape_last_year = excluded.escape_last_year,
+                escape_last_half_year = excluded.escape_last_half_year,
+                allow_escape_last_year = excluded.allow_escape_last_year,
+                allow_escape_last_half_year = excluded.allow_escape_last_half_year,
+                pierce_last_year = excluded.pierce_last_year,
+                pierce_last_half_year = excluded.pierce_last_half_year,
+                overtake_last_year = excluded.overtake_last_year,
+                overtake_last_half_year = excluded.overtake_last_half_year,
+                fetched_at = excluded.fetched_at,
+                updated_at = excluded.updated_at
+            "#
+        )
+        .bind(&race_date)
+        .bind(&venue_code)
+        .bind(race_number)
+        .bind(encode(race_data)?)
+        .bind(race_data.escape_last_year)
+        .bind(race_data.escape_last_half_year)
+        .bind(race_data.allow_escape_last_year)
+        .bind(race_data.allow_escape_last_half_year)
+        .bind(race_data.pierce_last_year)
+        .bind(race_data.pierce_last_half_year)
+        .bind(race_data.overtake_last_year)
+        .bind(race_data.overtake_last_half_year)
+        .bind(&now)
+        .bind(&now)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
     }
 
-    pub fn get_race_data(
+    pub async fn get_race_data(
         &self,
         date: &str,
         place_number: u32,
         race_number: u32,
-    ) -> Result<Option<RaceData>> {
-        let db_lock = get_db()?;
-        let db_guard = db_lock.lock().unwrap();
-        let db = db_guard.as_ref().unwrap();
-
-        let key = format!("race_{}_{}_{}_{}", date, place_number, race_number, "data");
-
-        match db.get(&key)? {
-            Some(value) => {
-                let race_data: RaceData = deserialize_from_string(&value)?;
-                Ok(Some(race_data))
-            }
-            None => Ok(None)
+    ) -> Result<Option<RaceData>, sqlx::Error> {
+        match self.get_json("data", date, place_number, race_number, "data_json").await? {
+            Some(json) => Ok(Some(decode(&json)?)),
+            None => Ok(None),
         }
     }
 
-    pub fn save_odds_data(
+    pub async fn save_odds_data(
         &self,
         date: &str,
         place_number: u32,
         race_number: u32,
         odds_data: &OddsData,
-    ) -> Result<()> {
-        let db_lock = get_db()?;
-        let mut db_guard = db_lock.lock().unwrap();
-        let db = db_guard.as_mut().unwrap();
+    ) -> Result<(), sqlx::Error> {
+        let (race_date, venue_code) = race_key(date, place_number);
+        let now = Utc::now().to_rfc3339();
+        sqlx::query(
+            r#"
+            INSERT INTO biyori_odds (
+                race_date, venue_code, race_number, betting_type, data_json, fetched_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(race_date, venue_code, race_number) DO UPDATE SET
+                betting_type = excluded.betting_type,
+                data_json = excluded.data_json,
+                fetched_at = excluded.fetched_at,
+                updated_at = excluded.updated_at
+            "#
+        )
+        .bind(&race_date)
+        .bind(&venue_code)
+        .bind(race_number)
+        .bind(format!("{:?}", odds_data.betting_type))
+        .bind(encode(odds_data)?)
+        .bind(&now)
+        .bind(&now)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
 
-        let key = format!("race_{}_{}_{}_{}", date, place_number, race_number, "odds");
-        let value = serialize_to_string(odds_data)?;
+    pub async fn get_odds_data(
+        &self,
+        date: &str,
+        place_number: u32,
+        race_number: u32,
+    ) -> Result<Option<OddsData>, sqlx::Error> {
+        match self.get_json("odds", date, place_number, race_number, "data_json").await? {
+            Some(json) => Ok(Some(decode(&json)?)),
+            None => Ok(None),
+        }
+    }
 
-        db.put(key, value)
+    /// 保存済みデータ（kind: "data" / "odds"）の列を取得（行・値がなければ None）
+    async fn get_json(
+        &self,
+        kind: &str,
+        date: &str,
+        place_number: u32,
+        race_number: u32,
+        column: &str,
+    ) -> Result<Option<String>, sqlx::Error> {
+        let (race_date, venue_code) = race_key(date, place_number);
+        let value: Option<(Option<String>,)> = sqlx::query_as(&format!(
+            "SELECT {} FROM {} WHERE race_date = ? AND venue_code = ? AND race_number = ?",
+            column,
+            table(kind)?
+        ))
+        .bind(&race_date)
+        .bind(&venue_code)
+        .bind(race_number)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(value.and_then(|(value,)| value))
     }
 
-    pub fn get_odds_data(
+    /// 保存済みデータの列を更新（行がなければ何もしない）
+    async fn set_column(
         &self,
+        kind: &str,
         date: &str,
         place_number: u32,
         race_number: u32,
-    ) -> Result<Option<OddsData>> {
-        let db_lock = get_db()?;
-        let db_guard = db_lock.lock().unwrap();
-        let db = db_guard.as_ref().unwrap();
+        column: &str,
+        value: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        let (race_date, venue_code) = race_key(date, place_number);
+        sqlx::query(&format!(
+            "UPDATE {} SET {} = ? WHERE race_date = ? AND venue_code = ? AND race_number = ?",
+            table(kind)?,
+            column
+        ))
+        .bind(value)
+        .bind(&race_date)
+        .bind(&venue_code)
+        .bind(race_number)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
 
-        let key = format!("race_{}_{}_{}_{}", date, place_number, race_number, "odds");
+    /// 保存済みデータ（kind: "data" / "odds"）の取得時刻（RFC3339）を上書き
+    ///
+    /// 保存時に現在時刻が記録されるため、アーカイブからの再パースなど取得時刻が異なる場合に使う。
+    pub async fn save_fetched_at(
+        &self,
+        date: &str,
+        place_number: u32,
+        race_number: u32,
+        kind: &str,
+        fetched_at: &str,
+    ) -> Result<(), sqlx::Error> {
+        self.set_column(kind, date, place_number, race_number, "fetched_at", Some(fetched_at))
+            .await
+    }
 
-        match db.get(&key)? {
-            Some(value) => {
-                let odds_data: OddsData = deserialize_from_string(&value)?;
-                Ok(Some(odds_data))
-            }
-            None => Ok(None)
-        }
+    /// 保存済みデータの取得時刻（取得時刻の記録がない旧データは None）
+    pub async fn get_fetched_at(
+        &self,
+        date: &str,
+        place_number: u32,
+        race_number: u32,
+        kind: &str,
+    ) -> Result<Option<String>, sqlx::Error> {
+        self.get_json(kind, date, place_number, race_number, "fetched_at").await
     }
 
+    /// 保存済みデータ（kind: "data" / "odds"）の取得元HTMLを記録
+    pub async fn save_source_page(
+        &self,
+        date: &str,
+        place_number: u32,
+        race_number: u32,
+        kind: &str,
+        entry: &ArchiveEntry,
+    ) -> Result<(), sqlx::Error> {
+        self.set_column(kind, date, place_number, race_number, "source_json", Some(&encode(entry)?))
+            .await
+    }
 
-    pub fn get_all_race_keys(&self) -> Result<Vec<String>> {
-        let db_lock = get_db()?;
-        let db_guard = db_lock.lock().unwrap();
-        let db = db_guard.as_ref().unwrap();
+    pub async fn get_source_page(
+        &self,
+        date: &str,
+        place_number: u32,
+        race_number: u32,
+        kind: &str,
+    ) -> Result<Option<ArchiveEntry>, sqlx::Error> {
+        match self.get_json(kind, date, place_number, race_number, "source_json").await? {
+            Some(json) => Ok(Some(decode(&json)?)),
+            None => Ok(None),
+        }
+    }
 
-        let all_keys = db.keys()?;
-        let race_keys: Vec<String> = all_keys
+    /// 保存済みデータのキー一覧（旧キャッシュと同じ `race_{YYYY-MM-DD}_{場}_{R}_{data|odds}` 形式）
+    pub async fn get_all_race_keys(&self) -> Result<Vec<String>, sqlx::Error> {
+        let rows: Vec<(String, String, i64, String)> = sqlx::query_as(
+            r#"
+            SELECT race_date, venue_code, race_number, 'data' FROM biyori_race_data
+            UNION ALL
+            SELECT race_date, venue_code, race_number, 'odds' FROM biyori_odds
+            ORDER BY 1, 2, 3, 4
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
             .into_iter()
-            .filter(|key| key.contains("race_") && (key.ends_with("_data") || key.ends_with("_odds")))
-            .collect();
-
-        Ok(race_keys)
+            .map(|(race_date, venue_code, race_number, kind)| {
+                let date = match (race_date.get(0..4), race_date.get(4..6), race_date.get(6..8)) {
+                    (Some(y), Some(m), Some(d)) => format!("{}-{}-{}", y, m, d),
+                    _ => race_date.clone(),
+                };
+                let place_number = venue_code.parse::<u32>().unwrap_or_default();
+                format!("race_{}_{}_{}_{}", date, place_number, race_number, kind)
+            })
+            .collect())
     }
 
-    pub fn delete_race_data(
+    pub async fn delete_race_data(
         &self,
         date: &str,
         place_number: u32,
         race_number: u32,
-    ) -> Result<()> {
-        let db_lock = get_db()?;
-        let mut db_guard = db_lock.lock().unwrap();
-        let db = db_guard.as_mut().unwrap();
-
-        let data_key = format!("race_{}_{}_{}_{}", date, place_number, race_number, "data");
-        let odds_key = format!("race_{}_{}_{}_{}", date, place_number, race_number, "odds");
-
+    ) -> Result<(), sqlx::Error> {
+        let (race_date, venue_code) = race_key(date, place_number);
         // データが存在しなくてもエラーにしない
-        let _ = db.delete(&data_key);
-        let _ = db.delete(&odds_key);
+        for table in ["biyori_race_data", "biyori_odds"] {
+            sqlx::query(&format!(
+                "DELETE FROM {} WHERE race_date = ? AND venue_code = ? AND race_number = ?",
+                table
+            ))
+            .bind(&race_date)
+            .bind(&venue_code)
+            .bind(race_number)
+            .execute(&self.pool)
+            .await?;
+        }
+        Ok(())
+    }
 
+    pub async fn clear_all_data(&self) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM biyori_race_data").execute(&self.pool).await?;
+        sqlx::query("DELETE FROM biyori_odds").execute(&self.pool).await?;
         Ok(())
     }
 
-    pub fn clear_all_data(&self) -> Result<()> {
-        let db_lock = get_db()?;
-        let mut db_guard = db_lock.lock().unwrap();
-        let db = db_guard.as_mut().unwrap();
+    /// 旧 JSON キャッシュがあれば取り込み、取り込み済みのファイルは `.imported` を付けて退避する
+    pub async fn import_legacy_json_once(&self, json_path: &str) {
+        let _guard = LEGACY_IMPORT.lock().await;
+        if !Path::new(json_path).exists() {
+            return;
+        }
 
-        db.clear()
+        match self.import_legacy_json(json_path).await {
+            Ok(summary) => {
+                eprintln!(
+                    "📥 旧キャッシュを取り込み: レースデータ {} 件, オッズ {} 件（既存 {} 件, エラー {} 件）",
+                    summary.race_data_imported,
+                    summary.odds_imported,
+                    summary.skipped,
+                    summary.errors.len()
+                );
+                let imported_path = format!("{}.imported", json_path);
+                if let Err(e) = std::fs::rename(json_path, &imported_path) {
+                    eprintln!("⚠️ 旧キャッシュの退避に失敗: {}", e);
+                }
+            }
+            Err(e) => eprintln!("⚠️ 旧キャッシュの取り込みに失敗: {}", e),
+        }
+    }
+
+    /// 旧 JSON キャッシュ（norimaki-db）の内容を取り込む
+    ///
+    /// SQLite にすでにあるレース・種別は上書きしない。取得時刻・取得元HTMLの記録も引き継ぐ。
+    pub async fn import_legacy_json(&self, json_path: &str) -> Result<LegacyImportSummary, sqlx::Error> {
+        let store = FileStore::new(json_path)
+            .map_err(|e| sqlx::Error::Configuration(e.to_string().into()))?;
+        let keys = store
+            .keys()
+            .map_err(|e| sqlx::Error::Configuration(e.to_string().into()))?;
+
+        let mut summary = LegacyImportSummary::default();
+        for key in &keys {
+            // race_{date}_{place}_{race}_{kind}（取得時刻・取得元は後から対応する行に反映）
+            let parts: Vec<&str> = key.split('_').collect();
+            let (date, place_number, race_number, kind) = match parts.as_slice() {
+                ["race", date, place, race, kind] => match (place.parse::<u32>(), race.parse::<u32>()) {
+                    (Ok(place), Ok(race)) => (*date, place, race, *kind),
+                    _ => continue,
+                },
+                _ => continue,
+            };
+            let Ok(Some(value)) = store.get(key) else {
+                continue;
+            };
+
+            if self.get_json(kind, date, place_number, race_number, "data_json").await.ok().flatten().is_some() {
+                summary.skipped += 1;
+                continue;
+            }
+
+            let result = match kind {
+                "data" => match decode_legacy::<RaceData>(&value) {
+                    Ok(data) => self.save_race_data(date, place_number, race_number, &data).await.map(|_| {
+                        summary.race_data_imported += 1;
+                    }),
+                    Err(e) => Err(e),
+                },
+                "odds" => match decode_legacy::<OddsData>(&value) {
+                    Ok(data) => self.save_odds_data(date, place_number, race_number, &data).await.map(|_| {
+                        summary.odds_imported += 1;
+                    }),
+                    Err(e) => Err(e),
+                },
+                _ => continue,
+            };
+            if let Err(e) = result {
+                summary.errors.push(format!("{}: {}", key, e));
+                continue;
+            }
+
+            // 旧キャッシュに取得時刻の記録がなければ不明のまま（レース日を過ぎたものは新しい扱い）
+            let fetched_at = store
+                .get(&format!("{}_fetched_at", key))
+                .ok()
+                .flatten()
+                .and_then(|value| decode_legacy::<String>(&value).ok());
+            self.set_column(kind, date, place_number, race_number, "fetched_at", fetched_at.as_deref())
+                .await?;
+            let source = store
+                .get(&format!("{}_source", key))
+                .ok()
+                .flatten()
+                .and_then(|value| decode_legacy::<ArchiveEntry>(&value).ok());
+            if let Some(entry) = source {
+                self.save_source_page(date, place_number, race_number, kind, &entry).await?;
+            }
+        }
+
+        Ok(summary)
     }
 }
 
@@ -148,6 +434,12 @@ impl LocalDbRepository {
 mod tests {
     use super::*;
     use crate::models::race::*;
+    use crate::test_support::temp_db_path;
+
+    async fn open_repo(name: &str) -> LocalDbRepository {
+        let path = temp_db_path(name);
+        LocalDbRepository::open(path.to_str().unwrap()).await.unwrap()
+    }
 
     fn create_sample_race_data() -> RaceData {
         RaceData {
@@ -222,6 +514,7 @@ mod tests {
                 pierce_rate_6months: Some(0.20),
                 overtake_rate_6months: Some(0.18),
             },
+            boats: Vec::new(),
         }
     }
 
@@ -249,15 +542,15 @@ mod tests {
         }
     }
 
-    #[test]
-    fn test_save_and_get_race_data() {
-        let repo = LocalDbRepository::new().unwrap();
+    #[tokio::test]
+    async fn test_save_and_get_race_data() {
+        let repo = open_repo("save_and_get_race_data").await;
         let race_data = create_sample_race_data();
 
-        let save_result = repo.save_race_data("2025-09-15", 1, 1, &race_data);
+        let save_result = repo.save_race_data("2025-09-15", 1, 1, &race_data).await;
         assert!(save_result.is_ok(), "Failed to save race data: {:?}", save_result.err());
 
-        let get_result = repo.get_race_data("2025-09-15", 1, 1);
+        let get_result = repo.get_race_data("2025-09-15", 1, 1).await;
         assert!(get_result.is_ok(), "Failed to get race data: {:?}", get_result.err());
 
         let retrieved_data = get_result.unwrap();
@@ -270,15 +563,15 @@ mod tests {
         println!("✅ Race data save/get test passed");
     }
 
-    #[test]
-    fn test_save_and_get_odds_data() {
-        let repo = LocalDbRepository::new().unwrap();
+    #[tokio::test]
+    async fn test_save_and_get_odds_data() {
+        let repo = open_repo("save_and_get_odds_data").await;
         let odds_data = create_sample_odds_data();
 
-        let save_result = repo.save_odds_data("2025-09-15", 1, 1, &odds_data);
+        let save_result = repo.save_odds_data("2025-09-15", 1, 1, &odds_data).await;
         assert!(save_result.is_ok(), "Failed to save odds data: {:?}", save_result.err());
 
-        let get_result = repo.get_odds_data("2025-09-15", 1, 1);
+        let get_result = repo.get_odds_data("2025-09-15", 1, 1).await;
         assert!(get_result.is_ok(), "Failed to get odds data: {:?}", get_result.err());
 
         let retrieved_data = get_result.unwrap();
@@ -292,17 +585,17 @@ mod tests {
         println!("✅ Odds data save/get test passed");
     }
 
-    #[test]
-    fn test_get_all_race_keys() {
-        let repo = LocalDbRepository::new().unwrap();
+    #[tokio::test]
+    async fn test_get_all_race_keys() {
+        let repo = open_repo("get_all_race_keys").await;
         let race_data = create_sample_race_data();
         let odds_data = create_sample_odds_data();
 
-        let _ = repo.save_race_data("2025-09-15", 1, 1, &race_data);
-        let _ = repo.save_race_data("2025-09-15", 1, 2, &race_data);
-        let _ = repo.save_odds_data("2025-09-15", 1, 1, &odds_data);
+        let _ = repo.save_race_data("2025-09-15", 1, 1, &race_data).await;
+        let _ = repo.save_race_data("2025-09-15", 1, 2, &race_data).await;
+        let _ = repo.save_odds_data("2025-09-15", 1, 1, &odds_data).await;
 
-        let keys_result = repo.get_all_race_keys();
+        let keys_result = repo.get_all_race_keys().await;
         assert!(keys_result.is_ok(), "Failed to get race keys: {:?}", keys_result.err());
 
         let keys = keys_result.unwrap();
@@ -317,27 +610,124 @@ mod tests {
         println!("✅ Get all race keys test passed. Found {} keys", keys.len());
     }
 
-    #[test]
-    fn test_delete_race_data() {
-        let repo = LocalDbRepository::new().unwrap();
+    #[tokio::test]
+    async fn test_delete_race_data() {
+        let repo = open_repo("delete_race_data").await;
         let race_data = create_sample_race_data();
         let odds_data = create_sample_odds_data();
 
-        let _ = repo.save_race_data("2025-09-15", 2, 1, &race_data);
-        let _ = repo.save_odds_data("2025-09-15", 2, 1, &odds_data);
+        let _ = repo.save_race_data("2025-09-15", 2, 1, &race_data).await;
+        let _ = repo.save_odds_data("2025-09-15", 2, 1, &odds_data).await;
 
-        let get_result = repo.get_race_data("2025-09-15", 2, 1);
+        let get_result = repo.get_race_data("2025-09-15", 2, 1).await;
         assert!(get_result.unwrap().is_some(), "Data should exist before deletion");
 
-        let delete_result = repo.delete_race_data("2025-09-15", 2, 1);
+        let delete_result = repo.delete_race_data("2025-09-15", 2, 1).await;
         assert!(delete_result.is_ok(), "Failed to delete race data: {:?}", delete_result.err());
 
-        let get_after_delete = repo.get_race_data("2025-09-15", 2, 1);
+        let get_after_delete = repo.get_race_data("2025-09-15", 2, 1).await;
         assert!(get_after_delete.unwrap().is_none(), "Data should be deleted");
 
-        let get_odds_after_delete = repo.get_odds_data("2025-09-15", 2, 1);
+        let get_odds_after_delete = repo.get_odds_data("2025-09-15", 2, 1).await;
         assert!(get_odds_after_delete.unwrap().is_none(), "Odds data should be deleted");
 
         println!("✅ Delete race data test passed");
     }
+
+    #[tokio::test]
+    async fn test_save_and_get_source_page() {
+        let repo = open_repo("save_and_get_source_page").await;
+        let entry = ArchiveEntry {
+            url: "https://kyoteibiyori.com/race_shusso.php?place_no=3&race_no=1&hiduke=20250915&slider=1".to_string(),
+            date: Some("20250915".to_string()),
+            place_number: Some(3),
+            race_number: Some(1),
+            slider: Some(1),
+            content_hash: "abc123".to_string(),
+            fetched_at: "2025-09-15T10:00:00+09:00".to_string(),
+            path: "20250915/03/01/slider1/abc123.html.gz".to_string(),
+            size_bytes: 1024,
+        };
+
+        // 取得元は保存済みデータに紐付ける
+        repo.save_race_data("2025-09-15", 3, 1, &create_sample_race_data()).await.unwrap();
+        let save_result = repo.save_source_page("2025-09-15", 3, 1, "data", &entry).await;
+        assert!(save_result.is_ok(), "Failed to save source page: {:?}", save_result.err());
+
+        let retrieved = repo.get_source_page("2025-09-15", 3, 1, "data").await.unwrap();
+        assert_eq!(retrieved, Some(entry));
+
+        // ソース記録はレースキー一覧に含めない
+        let keys = repo.get_all_race_keys().await.unwrap();
+        assert!(!keys.iter().any(|k| k.ends_with("_source")));
+
+        println!("✅ Source page save/get test passed");
+    }
+
+    #[tokio::test]
+    async fn test_fetched_at_is_recorded_on_save() {
+        let repo = open_repo("fetched_at_is_recorded_on_save").await;
+        let _ = repo.save_odds_data("2025-09-16", 4, 1, &create_sample_odds_data()).await;
+
+        let fetched_at = repo.get_fetched_at("2025-09-16", 4, 1, "odds").await.unwrap();
+        assert!(fetched_at.is_some(), "保存時に取得時刻が記録される");
+        assert!(repo.get_fetched_at("2025-09-16", 4, 1, "data").await.unwrap().is_none());
+
+        repo.save_fetched_at("2025-09-16", 4, 1, "odds", "2025-09-16T10:00:00+09:00").await.unwrap();
+        assert_eq!(
+            repo.get_fetched_at("2025-09-16", 4, 1, "odds").await.unwrap().as_deref(),
+            Some("2025-09-16T10:00:00+09:00")
+        );
+
+        // 取得時刻はレースキー一覧に含めず、削除時に消える
+        assert!(!repo.get_all_race_keys().await.unwrap().iter().any(|k| k.ends_with("_fetched_at")));
+        repo.delete_race_data("2025-09-16", 4, 1).await.unwrap();
+        assert!(repo.get_fetched_at("2025-09-16", 4, 1, "odds").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_import_legacy_json() {
+        let repo = open_repo("import_legacy_json").await;
+        let json_path = std::env::temp_dir().join(format!("bort_legacy_cache_{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&json_path);
+        {
+            let mut store = FileStore::new(json_path.to_str().unwrap()).unwrap();
+            let race_data = create_sample_race_data();
+            store.put("race_2025-09-15_1_1_data".to_string(), norimaki_db::serialize_to_string(&race_data).unwrap()).unwrap();
+            store
+                .put(
+                    "race_2025-09-15_1_1_data_fetched_at".to_string(),
+                    norimaki_db::serialize_to_string(&"2025-09-16T10:00:00+09:00".to_string()).unwrap(),
+                )
+                .unwrap();
+            store.put("race_2025-09-15_1_1_odds".to_string(), norimaki_db::serialize_to_string(&create_sample_odds_data()).unwrap()).unwrap();
+            store.put("race_2025-09-15_1_2_data".to_string(), norimaki_db::serialize_to_string(&race_data).unwrap()).unwrap();
+            store.put("race_2025-09-15_1_3_odds".to_string(), "not json".to_string()).unwrap();
+        }
+
+        // SQLite にすでにあるレースは上書きしない
+        let mut existing = create_sample_race_data();
+        existing.escape_last_year = 99.0;
+        repo.save_race_data("2025-09-15", 1, 2, &existing).await.unwrap();
+
+        let summary = repo.import_legacy_json(json_path.to_str().unwrap()).await.unwrap();
+        assert_eq!(summary.race_data_imported, 1);
+        assert_eq!(summary.odds_imported, 1);
+        assert_eq!(summary.skipped, 1);
+        assert_eq!(summary.errors.len(), 1);
+
+        assert_eq!(
+            repo.get_fetched_at("20250915", 1, 1, "data").await.unwrap().as_deref(),
+            Some("2025-09-16T10:00:00+09:00")
+        );
+        // 取得時刻の記録がない旧データは不明のまま
+        assert!(repo.get_fetched_at("2025-09-15", 1, 1, "odds").await.unwrap().is_none());
+        assert!(repo.get_odds_data("2025-09-15", 1, 1).await.unwrap().is_some());
+        assert_eq!(repo.get_race_data("2025-09-15", 1, 2).await.unwrap().unwrap().escape_last_year, 99.0);
+
+        // 2回目は取り込み済みのため何も追加しない
+        let again = repo.import_legacy_json(json_path.to_str().unwrap()).await.unwrap();
+        assert_eq!(again.race_data_imported + again.odds_imported, 0);
+        let _ = std::fs::remove_file(&json_path);
+    }
 }
@@ -0,0 +1,251 @@
+use crate::models::migration::{MigrationOptions, MigrationReport, MigrationStep};
+use chrono::{Local, Utc};
+use sqlx::{Sqlite, SqlitePool, Transaction};
+use std::future::Future;
+use std::path::Path;
+use std::pin::Pin;
+
+pub type MigrationTx = Transaction<'static, Sqlite>;
+pub type MigrationFuture<'a> = Pin<Box<dyn Future<Output = Result<(), sqlx::Error>> + Send + 'a>>;
+
+/// バージョン付きマイグレーション
+///
+/// `up` は1件ごとに開始されたトランザクション内で実行され、成功すると
+/// `schema_version` に記録されてコミットされる。途中で失敗した場合はそのバージョンの変更だけが戻る。
+/// dry-run では未適用分をまとめて1つのトランザクションで実行し、最後にロールバックする。
+pub struct Migration<R: 'static> {
+    pub version: i64,
+    pub description: &'static str,
+    pub up: for<'a> fn(&'a R, &'a mut MigrationTx) -> MigrationFuture<'a>,
+}
+
+/// `schema_version` がないデータベースの現在バージョンを推定する関数
+pub type DetectLegacyVersion =
+    for<'a> fn(&'a SqlitePool) -> Pin<Box<dyn Future<Output = Result<i64, sqlx::Error>> + Send + 'a>>;
+
+/// 未適用のマイグレーションを順に適用する
+pub async fn run<R>(
+    repo: &R,
+    pool: &SqlitePool,
+    db_path: &str,
+    migrations: &[Migration<R>],
+    detect_legacy_version: DetectLegacyVersion,
+    options: MigrationOptions,
+) -> Result<MigrationReport, sqlx::Error> {
+    let from_version = current_version(pool, detect_legacy_version, options.dry_run).await?;
+    let pending: Vec<&Migration<R>> = migrations
+        .iter()
+        .filter(|m| m.version > from_version)
+        .collect();
+
+    let mut report = MigrationReport {
+        from_version,
+        to_version: from_version,
+        applied: Vec::new(),
+        dry_run: options.dry_run,
+        backup_path: None,
+    };
+    if pending.is_empty() {
+        return Ok(report);
+    }
+
+    if options.backup && !options.dry_run && from_version > 0 {
+        report.backup_path = Some(backup(pool, db_path, from_version).await?);
+    }
+
+    // dry-run は後続のマイグレーションが前の変更を前提にできるよう、全件を1つのトランザクションで検証する
+    let mut dry_run_tx = if options.dry_run { Some(pool.begin().await?) } else { None };
+
+    for migration in pending {
+        eprintln!(
+            "🔄 Running V{} migration{}: {}",
+            migration.version,
+            if options.dry_run { " (dry-run)" } else { "" },
+            migration.description
+        );
+
+        if let Some(tx) = dry_run_tx.as_mut() {
+            (migration.up)(repo, tx).await?;
+        } else {
+            let mut tx = pool.begin().await?;
+            (migration.up)(repo, &mut tx).await?;
+            sqlx::query("INSERT INTO schema_version (version, description, applied_at) VALUES (?, ?, ?)")
+                .bind(migration.version)
+                .bind(migration.description)
+                .bind(Utc::now().to_rfc3339())
+                .execute(&mut *tx)
+                .await?;
+            tx.commit().await?;
+            report.to_version = migration.version;
+        }
+
+        eprintln!("✅ V{} migration completed", migration.version);
+        report.applied.push(MigrationStep {
+            version: migration.version,
+            description: migration.description.to_string(),
+        });
+    }
+
+    if let Some(tx) = dry_run_tx {
+        tx.rollback().await?;
+    }
+
+    Ok(report)
+}
+
+/// 適用済みの最新バージョン（`schema_version` 導入前のDBは推定して記録する）
+async fn current_version(
+    pool: &SqlitePool,
+    detect_legacy_version: DetectLegacyVersion,
+    dry_run: bool,
+) -> Result<i64, sqlx::Error> {
+    let (has_table,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='schema_version'",
+    )
+    .fetch_one(pool)
+    .await?;
+
+    if has_table > 0 {
+        let (version,): (Option<i64>,) = sqlx::query_as("SELECT MAX(version) FROM schema_version")
+            .fetch_one(pool)
+            .await?;
+        return Ok(version.unwrap_or(0));
+    }
+
+    let legacy_version = detect_legacy_version(pool).await?;
+    if dry_run {
+        return Ok(legacy_version);
+    }
+
+    sqlx::query(
+        r#"
+        CREATE TABLE schema_version (
+            version INTEGER PRIMARY KEY,
+            description TEXT NOT NULL,
+            applied_at TEXT NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    if legacy_version > 0 {
+        eprintln!("📌 Existing database detected at schema V{}", legacy_version);
+        sqlx::query("INSERT INTO schema_version (version, description, applied_at) VALUES (?, ?, ?)")
+            .bind(legacy_version)
+            .bind("baseline (detected from existing schema)")
+            .bind(Utc::now().to_rfc3339())
+            .execute(pool)
+            .await?;
+    }
+    Ok(legacy_version)
+}
+
+/// `VACUUM INTO` で整合性の取れたコピーを作成し、そのパスを返す
+///
+/// 例: `data/open_api.db` → `data/open_api_backup_v3_20250101_120000.db`
+async fn backup(pool: &SqlitePool, db_path: &str, version: i64) -> Result<String, sqlx::Error> {
+    let path = Path::new(db_path);
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("database");
+    let file_name = format!(
+        "{}_backup_v{}_{}.db",
+        stem,
+        version,
+        Local::now().format("%Y%m%d_%H%M%S")
+    );
+    let backup_path = path.with_file_name(file_name);
+    let backup_path = backup_path.to_string_lossy().to_string();
+
+    sqlx::query("VACUUM INTO ?")
+        .bind(&backup_path)
+        .execute(pool)
+        .await?;
+
+    eprintln!("💾 Database backup created: {}", backup_path);
+    Ok(backup_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::migration::MigrationOptions;
+    use crate::repositories::sqlite_db::SqliteRepository;
+    use crate::test_support::temp_db_path;
+
+    /// 最新のスキーマバージョン
+    fn latest_version() -> i64 {
+        SqliteRepository::MIGRATIONS.last().unwrap().version
+    }
+
+    async fn versions(db_path: &str) -> Vec<i64> {
+        let pool = sqlx::SqlitePool::connect(&format!("sqlite://{}", db_path)).await.unwrap();
+        let rows: Vec<(i64,)> = sqlx::query_as("SELECT version FROM schema_version ORDER BY version")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        pool.close().await;
+        rows.into_iter().map(|(v,)| v).collect()
+    }
+
+    #[tokio::test]
+    async fn test_fresh_database_applies_all_versions_once() {
+        let path = temp_db_path("migrate_fresh");
+        let db = path.to_str().unwrap();
+
+        let report = SqliteRepository::migrate(db, MigrationOptions::default()).await.unwrap();
+        assert_eq!(report.from_version, 0);
+        assert_eq!(report.to_version, latest_version());
+        assert_eq!(report.applied.len(), SqliteRepository::MIGRATIONS.len());
+        assert!(report.backup_path.is_none(), "空のDBはバックアップしない");
+
+        // 2回目は何も適用しない
+        let report = SqliteRepository::migrate(db, MigrationOptions::default()).await.unwrap();
+        assert!(report.applied.is_empty());
+        assert_eq!(versions(db).await, (1..=latest_version()).collect::<Vec<_>>());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_legacy_database_dry_run_then_upgrade_with_backup() {
+        let path = temp_db_path("migrate_legacy");
+        let db = path.to_str().unwrap();
+
+        // schema_version 導入前の V1 相当のDBを用意
+        let pool = sqlx::SqlitePool::connect(&format!("sqlite://{}?mode=rwc", db)).await.unwrap();
+        for table in ["previews", "results", "programs"] {
+            sqlx::query(&format!(
+                "CREATE TABLE {} (id INTEGER PRIMARY KEY AUTOINCREMENT, date TEXT NOT NULL,
+                 venue_code TEXT NOT NULL, race_number INTEGER NOT NULL, data_json TEXT NOT NULL,
+                 created_at TEXT NOT NULL, updated_at TEXT NOT NULL, UNIQUE(date, venue_code, race_number))",
+                table
+            ))
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        pool.close().await;
+
+        // dry-run は検証のみでスキーマを変更しない
+        let dry_run = MigrationOptions { dry_run: true, backup: true };
+        let report = SqliteRepository::migrate(db, dry_run).await.unwrap();
+        assert_eq!(report.from_version, 1);
+        assert_eq!(report.to_version, 1);
+        assert_eq!(
+            report.applied.iter().map(|m| m.version).collect::<Vec<_>>(),
+            (2..=latest_version()).collect::<Vec<_>>()
+        );
+        assert!(report.backup_path.is_none());
+        let report = SqliteRepository::migrate(db, dry_run).await.unwrap();
+        assert_eq!(report.applied.len() as i64, latest_version() - 1, "dry-run 後も未適用のまま");
+
+        // 本実行では適用前にバックアップを作成
+        let report = SqliteRepository::migrate(db, MigrationOptions::default()).await.unwrap();
+        assert_eq!(report.to_version, latest_version());
+        let backup_path = report.backup_path.expect("backup should be created");
+        assert!(std::path::Path::new(&backup_path).exists());
+        assert_eq!(versions(db).await, (1..=latest_version()).collect::<Vec<_>>());
+
+        let _ = std::fs::remove_file(&backup_path);
+        let _ = std::fs::remove_file(&path);
+    }
+}
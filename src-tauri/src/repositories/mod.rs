@@ -1,4 +1,7 @@
+pub mod html_archive;
+pub mod job_repository;
 pub mod local_db;
+pub mod migrations;
 pub mod sqlite_db;
 
 // Re-export for convenience
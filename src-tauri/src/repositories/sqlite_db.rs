@@ -1,10 +1,26 @@
 use crate::models::open_api::{
-    PayoutStats, PreviewRecord, ProgramRecord, ResultRecord, RaceResult,
+    CombinationPayoutStats, PayoutInfo, PayoutStats, PreviewRecord, ProgramRecord, ResultRecord, RaceResult, RacePreview,
     RaceRecord, RaceParticipantRecord, RaceProgram, SearchParams, DataSummaryRow
 };
-use sqlx::{SqlitePool, QueryBuilder};
+use crate::models::code::{
+    CodeEnum, Prefecture, RaceGrade, RacerClass, Technique, Weather, WindDirection,
+};
+use crate::models::backtest::BacktestRace;
+use crate::models::expected_value::{FactorBucket, WinFactor};
+use crate::models::migration::{MigrationOptions, MigrationReport};
+use crate::models::motor::MotorStats;
+use crate::models::odds_history::OddsSnapshotRecord;
+use crate::models::race_view::EscapeOutcomeRow;
+use crate::models::racer::{
+    class_term, PlaceCount, RacerClassHistoryRecord, RacerCourseStats, RacerRecord, RacerUpdate,
+    RacerVenueStats,
+};
+use crate::repositories::migrations::{self, Migration, MigrationTx};
+use sqlx::{SqliteConnection, SqlitePool, QueryBuilder};
 use std::collections::HashMap;
 
+/// クローンは同じ接続プールを共有する
+#[derive(Clone)]
 pub struct SqliteRepository {
     pool: SqlitePool,
 }
@@ -12,15 +28,141 @@ pub struct SqliteRepository {
 impl SqliteRepository {
     /// データベース接続とマイグレーションを実行
     pub async fn new(db_path: &str) -> Result<Self, sqlx::Error> {
+        let repo = Self::connect(db_path).await?;
+        repo.run_migrations(db_path, MigrationOptions::default()).await?;
+        Ok(repo)
+    }
+
+    /// マイグレーションのみ実行（dry-run では未適用分を検証してロールバックする）
+    pub async fn migrate(db_path: &str, options: MigrationOptions) -> Result<MigrationReport, sqlx::Error> {
+        let repo = Self::connect(db_path).await?;
+        let report = repo.run_migrations(db_path, options).await;
+        repo.pool.close().await;
+        report
+    }
+
+    /// 接続プール（同じDBに別テーブルを持つリポジトリと共有する）
+    pub(crate) fn pool(&self) -> &SqlitePool {
+        &self.pool
+    }
+
+    async fn connect(db_path: &str) -> Result<Self, sqlx::Error> {
         let connection_string = format!("sqlite://{}?mode=rwc", db_path);
         let pool = SqlitePool::connect(&connection_string).await?;
-        let repo = Self { pool };
-        repo.run_migrations().await?;
-        Ok(repo)
+        Ok(Self { pool })
+    }
+
+    /// スキーマのマイグレーション一覧（バージョン順）
+    ///
+    /// スキーマを変更する場合は末尾に新しいバージョンを追加する。適用済みのものは変更しない。
+    pub(crate) const MIGRATIONS: &'static [Migration<Self>] = &[
+        Migration {
+            version: 1,
+            description: "Create previews / results / programs tables",
+            up: |repo, tx| Box::pin(repo.migrate_to_v1(tx)),
+        },
+        Migration {
+            version: 2,
+            description: "Add search columns to results table",
+            up: |repo, tx| Box::pin(repo.migrate_to_v2(tx)),
+        },
+        Migration {
+            version: 3,
+            description: "Normalize racer information into races / race_participants",
+            up: |repo, tx| Box::pin(repo.migrate_to_v3(tx)),
+        },
+        Migration {
+            version: 4,
+            description: "Normalize preview data into races / race_participants",
+            up: |repo, tx| Box::pin(repo.migrate_to_v4(tx)),
+        },
+        Migration {
+            version: 5,
+            description: "Create race_payouts table for every bet type and combination",
+            up: |repo, tx| Box::pin(repo.migrate_to_v5(tx)),
+        },
+        Migration {
+            version: 6,
+            description: "Seed code master tables and fill decision_hand",
+            up: |repo, tx| Box::pin(repo.migrate_to_v6(tx)),
+        },
+        Migration {
+            version: 7,
+            description: "Create racers master and class history",
+            up: |repo, tx| Box::pin(repo.migrate_to_v7(tx)),
+        },
+        Migration {
+            version: 8,
+            description: "Create motors view aggregated per venue, motor and season",
+            up: |repo, tx| Box::pin(repo.migrate_to_v8(tx)),
+        },
+        Migration {
+            version: 9,
+            description: "Create odds_snapshots table for odds time series",
+            up: |repo, tx| Box::pin(repo.migrate_to_v9(tx)),
+        },
+        Migration {
+            version: 10,
+            description: "Create biyori_race_data / biyori_odds tables for the scrape cache",
+            up: |repo, tx| Box::pin(repo.migrate_to_v10(tx)),
+        },
+        Migration {
+            version: 11,
+            description: "Create bulk_jobs / bulk_job_units tables for resumable bulk jobs",
+            up: |repo, tx| Box::pin(repo.migrate_to_v11(tx)),
+        },
+        Migration {
+            version: 12,
+            description: "Drop unused racer_name_kana column from racers",
+            up: |repo, tx| Box::pin(repo.migrate_to_v12(tx)),
+        },
+    ];
+
+    /// 未適用のマイグレーションを実行
+    async fn run_migrations(
+        &self,
+        db_path: &str,
+        options: MigrationOptions,
+    ) -> Result<MigrationReport, sqlx::Error> {
+        migrations::run(
+            self,
+            &self.pool,
+            db_path,
+            Self::MIGRATIONS,
+            |pool| Box::pin(Self::detect_legacy_version(pool)),
+            options,
+        )
+        .await
     }
 
-    /// マイグレーション実行（テーブル作成）
-    async fn run_migrations(&self) -> Result<(), sqlx::Error> {
+    /// schema_version 導入前のDBのバージョンを既存テーブルから推定
+    async fn detect_legacy_version(pool: &SqlitePool) -> Result<i64, sqlx::Error> {
+        let table_exists = |name: &'static str| async move {
+            let (count,): (i64,) = sqlx::query_as(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name=?"
+            )
+            .bind(name)
+            .fetch_one(pool)
+            .await?;
+            Ok::<bool, sqlx::Error>(count > 0)
+        };
+
+        if table_exists("races").await? {
+            return Ok(3);
+        }
+        if !table_exists("previews").await? {
+            return Ok(0);
+        }
+        let (v2_columns,): (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM pragma_table_info('results') WHERE name='trifecta_payout'"
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(if v2_columns > 0 { 2 } else { 1 })
+    }
+
+    /// V1マイグレーション: 取得データ保存用テーブル作成
+    async fn migrate_to_v1(&self, tx: &mut MigrationTx) -> Result<(), sqlx::Error> {
         // Previews テーブル作成
         sqlx::query(
             r#"
@@ -36,7 +178,7 @@ impl SqliteRepository {
             )
             "#,
         )
-        .execute(&self.pool)
+        .execute(&mut **tx)
         .await?;
 
         // Results テーブル作成
@@ -54,7 +196,7 @@ impl SqliteRepository {
             )
             "#,
         )
-        .execute(&self.pool)
+        .execute(&mut **tx)
         .await?;
 
         // Programs テーブル作成
@@ -72,35 +214,37 @@ impl SqliteRepository {
             )
             "#,
         )
-        .execute(&self.pool)
+        .execute(&mut **tx)
         .await?;
 
         // インデックス作成
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_previews_date ON previews(date)")
-            .execute(&self.pool)
+            .execute(&mut **tx)
             .await?;
 
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_results_date ON results(date)")
-            .execute(&self.pool)
+            .execute(&mut **tx)
             .await?;
 
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_programs_date ON programs(date)")
-            .execute(&self.pool)
+            .execute(&mut **tx)
             .await?;
 
-        // V2マイグレーション: 高配当検索用カラム追加
-        self.migrate_to_v2().await?;
-
-        // V3マイグレーション: 選手情報正規化
-        self.migrate_to_v3().await?;
-
         Ok(())
     }
 
     // ===== Previews CRUD =====
 
     /// Preview データを保存（UPSERT）
+    ///
+    /// 生JSONを previews に保存し、直前情報（展示タイム・チルト等）を
+    /// races / race_participants のカラムにも反映する。
     pub async fn save_preview(&self, record: &PreviewRecord) -> Result<(), sqlx::Error> {
+        let data: RacePreview = serde_json::from_str(&record.data_json)
+            .map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
+
+        let mut tx = self.pool.begin().await?;
+
         sqlx::query(
             r#"
             INSERT INTO previews (date, venue_code, race_number, data_json, created_at, updated_at)
@@ -115,11 +259,98 @@ impl SqliteRepository {
         .bind(&record.data_json)
         .bind(&record.created_at)
         .bind(&record.updated_at)
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await?;
+
+        Self::upsert_preview_columns(&mut tx, record, &data).await?;
+
+        tx.commit().await?;
         Ok(())
     }
 
+    /// 直前情報を races / race_participants にUPSERT
+    ///
+    /// 結果・出走表由来のカラムは変更しない（レース行がなければ直前情報のみで作成）。
+    async fn upsert_preview_columns(
+        conn: &mut SqliteConnection,
+        record: &PreviewRecord,
+        data: &RacePreview,
+    ) -> Result<(), sqlx::Error> {
+        let race_id: i64 = sqlx::query_scalar(
+            r#"
+            INSERT INTO races (
+                race_date, venue_code, race_number,
+                preview_wind, preview_wind_direction_number, preview_wave,
+                preview_weather_number, preview_temperature, preview_water_temperature,
+                created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(race_date, venue_code, race_number)
+            DO UPDATE SET
+                preview_wind = excluded.preview_wind,
+                preview_wind_direction_number = excluded.preview_wind_direction_number,
+                preview_wave = excluded.preview_wave,
+                preview_weather_number = excluded.preview_weather_number,
+                preview_temperature = excluded.preview_temperature,
+                preview_water_temperature = excluded.preview_water_temperature,
+                updated_at = excluded.updated_at
+            RETURNING id
+            "#,
+        )
+        .bind(&record.date)
+        .bind(&record.venue_code)
+        .bind(record.race_number)
+        .bind(data.race_wind)
+        .bind(data.race_wind_direction_number)
+        .bind(data.race_wave)
+        .bind(data.race_weather_number)
+        .bind(data.race_temperature)
+        .bind(data.race_water_temperature)
+        .bind(&record.created_at)
+        .bind(&record.updated_at)
+        .fetch_one(&mut *conn)
+        .await?;
+
+        for (key, boat) in &data.boats {
+            // boats のキーは艇番（"1"〜"6"）
+            let Some(boat_number) = boat.racer_boat_number.or_else(|| key.parse().ok()) else {
+                continue;
+            };
+
+            sqlx::query(
+                r#"
+                INSERT INTO race_participants (
+                    race_id, boat_number,
+                    exhibition_time, exhibition_course_number, exhibition_start_timing,
+                    tilt_adjustment, weight_adjustment,
+                    created_at, updated_at
+                )
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+                ON CONFLICT(race_id, boat_number)
+                DO UPDATE SET
+                    exhibition_time = excluded.exhibition_time,
+                    exhibition_course_number = excluded.exhibition_course_number,
+                    exhibition_start_timing = excluded.exhibition_start_timing,
+                    tilt_adjustment = excluded.tilt_adjustment,
+                    weight_adjustment = excluded.weight_adjustment,
+                    updated_at = excluded.updated_at
+                "#,
+            )
+            .bind(race_id)
+            .bind(boat_number)
+            .bind(boat.racer_exhibition_time)
+            .bind(boat.racer_course_number)
+            .bind(boat.racer_start_timing)
+            .bind(boat.racer_tilt_adjustment)
+            .bind(boat.racer_weight_adjustment)
+            .bind(&record.created_at)
+            .bind(&record.updated_at)
+            .execute(&mut *conn)
+            .await?;
+        }
+
+        Ok(())
+    }
 
     /// すべての Previews を取得（CSV エクスポート用）
     pub async fn get_all_previews(&self) -> Result<Vec<PreviewRecord>, sqlx::Error> {
@@ -180,6 +411,8 @@ impl SqliteRepository {
         let winner_boat_number = winner.and_then(|w| Some(w.racer_boat_number));
         let winner_racer_number = winner.and_then(|w| w.racer_number);
 
+        let mut tx = self.pool.begin().await?;
+
         // races テーブルにUPSERT
         let race_id: i64 = sqlx::query_scalar(
             r#"
@@ -238,21 +471,30 @@ impl SqliteRepository {
         .bind(&record.data_json)
         .bind(&record.created_at)
         .bind(&record.updated_at)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *tx)
         .await?;
 
+        // 決まり手は1着艇に記録
+        let technique = data.race_technique_number.and_then(Technique::from_real_code);
+
         // race_participants テーブルにUPSERT（6艇分）
         for boat in &data.boats {
+            let decision_hand = if boat.racer_place_number == Some(1) {
+                technique.map(Technique::label_ja)
+            } else {
+                None
+            };
+
             sqlx::query(
                 r#"
                 INSERT INTO race_participants (
                     race_id, boat_number,
                     racer_number, racer_name,
                     course_number, start_timing,
-                    place_number,
+                    place_number, decision_hand,
                     created_at, updated_at
                 )
-                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
                 ON CONFLICT(race_id, boat_number)
                 DO UPDATE SET
                     racer_number = excluded.racer_number,
@@ -260,6 +502,7 @@ impl SqliteRepository {
                     course_number = excluded.course_number,
                     start_timing = excluded.start_timing,
                     place_number = excluded.place_number,
+                    decision_hand = excluded.decision_hand,
                     updated_at = excluded.updated_at
                 "#,
             )
@@ -270,9 +513,51 @@ impl SqliteRepository {
             .bind(boat.racer_course_number)
             .bind(boat.racer_start_timing)
             .bind(boat.racer_place_number)
+            .bind(decision_hand)
             .bind(&record.created_at)
             .bind(&record.updated_at)
-            .execute(&self.pool)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        // race_payouts テーブルを置き換え（全賭式・全組番）
+        Self::replace_race_payouts(&mut tx, race_id, &data.payouts).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// レースの払戻をすべて削除してから登録し直す
+    async fn replace_race_payouts(
+        conn: &mut SqliteConnection,
+        race_id: i64,
+        payouts: &PayoutInfo,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM race_payouts WHERE race_id = ?")
+            .bind(race_id)
+            .execute(&mut *conn)
+            .await?;
+
+        for (bet_type, entry) in payouts.entries() {
+            // 組番のないエントリ（不成立など）は保存しない
+            let Some(combination) = &entry.combination else {
+                continue;
+            };
+
+            sqlx::query(
+                r#"
+                INSERT INTO race_payouts (race_id, bet_type, combination, payout, popularity)
+                VALUES (?, ?, ?, ?, ?)
+                ON CONFLICT(race_id, bet_type, combination)
+                DO UPDATE SET payout = excluded.payout, popularity = excluded.popularity
+                "#,
+            )
+            .bind(race_id)
+            .bind(bet_type)
+            .bind(combination)
+            .bind(entry.payout)
+            .bind(entry.popularity)
+            .execute(&mut *conn)
             .await?;
         }
 
@@ -328,6 +613,8 @@ impl SqliteRepository {
         let data: RaceProgram = serde_json::from_str(&record.data_json)
             .map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
 
+        let mut tx = self.pool.begin().await?;
+
         // races テーブルにUPSERT（program関連データのみ）
         let race_id: i64 = sqlx::query_scalar(
             r#"
@@ -359,7 +646,7 @@ impl SqliteRepository {
         .bind(&record.data_json)
         .bind(&record.created_at)
         .bind(&record.updated_at)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *tx)
         .await?;
 
         // race_participants テーブルにUPSERT（選手詳細情報）
@@ -437,10 +724,16 @@ impl SqliteRepository {
             .bind(boat.racer_assigned_boat_top_3_percent)
             .bind(&record.created_at)
             .bind(&record.updated_at)
-            .execute(&self.pool)
+            .execute(&mut *tx)
             .await?;
+
+            // 選手マスタを更新
+            if let Some(update) = RacerUpdate::from_program(boat, &record.date) {
+                Self::upsert_racer(&mut tx, &update, &record.updated_at).await?;
+            }
         }
 
+        tx.commit().await?;
         Ok(())
     }
 
@@ -483,13 +776,13 @@ impl SqliteRepository {
         Ok(records)
     }
 
-    /// V3: すべてのレースと選手情報、プレビュー情報を取得（CSVエクスポート用）
+    /// V3: すべてのレースと選手情報を取得（CSVエクスポート用）
     ///
-    /// 正規化されたracesとrace_participantsテーブル、およびpreviewsテーブルから全データを取得。
-    /// previewsデータは展示タイム、体重調整、チルト調整などの予測情報を含む。
+    /// 正規化されたracesとrace_participantsテーブルから全データを取得。
+    /// 展示タイム、体重調整、チルト調整などの直前情報もカラムとして含まれる。
     pub async fn get_all_races_with_participants(
         &self,
-    ) -> Result<Vec<(RaceRecord, Vec<RaceParticipantRecord>, Option<PreviewRecord>)>, sqlx::Error> {
+    ) -> Result<Vec<(RaceRecord, Vec<RaceParticipantRecord>)>, sqlx::Error> {
         // 1. すべてのレースを取得（日付順）
         let races = sqlx::query_as::<_, RaceRecord>(
             "SELECT * FROM races ORDER BY race_date, venue_code, race_number"
@@ -497,28 +790,19 @@ impl SqliteRepository {
         .fetch_all(&self.pool)
         .await?;
 
-        // 2. 各レースの選手情報とプレビュー情報を取得
+        // 2. 各レースの選手情報を取得
         let mut results = Vec::new();
-        for race in races {
-            // 選手情報を取得
-            let participants = sqlx::query_as::<_, RaceParticipantRecord>(
+        for mut race in races {
+            let mut participants = sqlx::query_as::<_, RaceParticipantRecord>(
                 "SELECT * FROM race_participants WHERE race_id = ? ORDER BY boat_number"
             )
             .bind(race.id)
             .fetch_all(&self.pool)
             .await?;
 
-            // プレビュー情報を取得（存在しない場合はNone）
-            let preview = sqlx::query_as::<_, PreviewRecord>(
-                "SELECT * FROM previews WHERE date = ? AND venue_code = ? AND race_number = ?"
-            )
-            .bind(&race.race_date)
-            .bind(&race.venue_code)
-            .bind(race.race_number)
-            .fetch_optional(&self.pool)
-            .await?;
-
-            results.push((race, participants, preview));
+            race.decode_labels();
+            participants.iter_mut().for_each(RaceParticipantRecord::decode_labels);
+            results.push((race, participants));
         }
 
         Ok(results)
@@ -527,134 +811,112 @@ impl SqliteRepository {
     // ===== V2マイグレーション: 高配当検索用カラム追加 =====
 
     /// V2マイグレーション: Resultsテーブルに検索用カラムを追加
-    async fn migrate_to_v2(&self) -> Result<(), sqlx::Error> {
-        println!("🔄 Running V2 migration: Adding search columns to results table");
-
-        // カラムが既に存在するかチェック（冪等性確保）
-        let column_check: (i64,) = sqlx::query_as(
-            "SELECT COUNT(*) FROM pragma_table_info('results') WHERE name='trifecta_payout'"
-        )
-        .fetch_one(&self.pool)
-        .await?;
-
-        if column_check.0 > 0 {
-            println!("✅ V2 migration already applied, skipping");
-            return Ok(());
-        }
-
-        // トランザクション開始
-        let mut tx = self.pool.begin().await?;
-
+    async fn migrate_to_v2(&self, tx: &mut MigrationTx) -> Result<(), sqlx::Error> {
         // Resultsテーブルにカラム追加
-        println!("  📝 Adding columns to results table...");
+        eprintln!("  📝 Adding columns to results table...");
         sqlx::query(
             r#"
             ALTER TABLE results ADD COLUMN race_wind REAL;
             "#
-        ).execute(&mut *tx).await?;
+        ).execute(&mut **tx).await?;
 
         sqlx::query(
             r#"
             ALTER TABLE results ADD COLUMN race_wind_direction_number REAL;
             "#
-        ).execute(&mut *tx).await?;
+        ).execute(&mut **tx).await?;
 
         sqlx::query(
             r#"
             ALTER TABLE results ADD COLUMN race_wave REAL;
             "#
-        ).execute(&mut *tx).await?;
+        ).execute(&mut **tx).await?;
 
         sqlx::query(
             r#"
             ALTER TABLE results ADD COLUMN race_weather_number REAL;
             "#
-        ).execute(&mut *tx).await?;
+        ).execute(&mut **tx).await?;
 
         sqlx::query(
             r#"
             ALTER TABLE results ADD COLUMN race_temperature REAL;
             "#
-        ).execute(&mut *tx).await?;
+        ).execute(&mut **tx).await?;
 
         sqlx::query(
             r#"
             ALTER TABLE results ADD COLUMN race_water_temperature REAL;
             "#
-        ).execute(&mut *tx).await?;
+        ).execute(&mut **tx).await?;
 
         sqlx::query(
             r#"
             ALTER TABLE results ADD COLUMN race_technique_number REAL;
             "#
-        ).execute(&mut *tx).await?;
+        ).execute(&mut **tx).await?;
 
         sqlx::query(
             r#"
             ALTER TABLE results ADD COLUMN win_payout INTEGER;
             "#
-        ).execute(&mut *tx).await?;
+        ).execute(&mut **tx).await?;
 
         sqlx::query(
             r#"
             ALTER TABLE results ADD COLUMN place_payout_max INTEGER;
             "#
-        ).execute(&mut *tx).await?;
+        ).execute(&mut **tx).await?;
 
         sqlx::query(
             r#"
             ALTER TABLE results ADD COLUMN exacta_payout INTEGER;
             "#
-        ).execute(&mut *tx).await?;
+        ).execute(&mut **tx).await?;
 
         sqlx::query(
             r#"
             ALTER TABLE results ADD COLUMN trifecta_payout INTEGER;
             "#
-        ).execute(&mut *tx).await?;
+        ).execute(&mut **tx).await?;
 
         sqlx::query(
             r#"
             ALTER TABLE results ADD COLUMN winner_boat_number INTEGER;
             "#
-        ).execute(&mut *tx).await?;
+        ).execute(&mut **tx).await?;
 
         sqlx::query(
             r#"
             ALTER TABLE results ADD COLUMN winner_racer_number INTEGER;
             "#
-        ).execute(&mut *tx).await?;
+        ).execute(&mut **tx).await?;
 
-        println!("  ✅ Columns added successfully");
+        eprintln!("  ✅ Columns added successfully");
 
         // 既存データの移行
-        println!("  🔄 Migrating existing data...");
-        self.migrate_existing_results_data(&mut tx).await?;
-        println!("  ✅ Data migration completed");
+        eprintln!("  🔄 Migrating existing data...");
+        self.migrate_existing_results_data(tx).await?;
+        eprintln!("  ✅ Data migration completed");
 
         // インデックス作成
-        println!("  📊 Creating indexes...");
+        eprintln!("  📊 Creating indexes...");
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_results_trifecta_payout ON results(trifecta_payout)")
-            .execute(&mut *tx).await?;
+            .execute(&mut **tx).await?;
 
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_results_win_payout ON results(win_payout)")
-            .execute(&mut *tx).await?;
+            .execute(&mut **tx).await?;
 
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_results_exacta_payout ON results(exacta_payout)")
-            .execute(&mut *tx).await?;
+            .execute(&mut **tx).await?;
 
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_results_venue ON results(venue_code)")
-            .execute(&mut *tx).await?;
+            .execute(&mut **tx).await?;
 
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_results_date_venue ON results(date, venue_code)")
-            .execute(&mut *tx).await?;
-
-        println!("  ✅ Indexes created successfully");
-
-        // コミット
-        tx.commit().await?;
+            .execute(&mut **tx).await?;
 
-        println!("✅ V2 migration completed successfully");
+        eprintln!("  ✅ Indexes created successfully");
         Ok(())
     }
 
@@ -670,7 +932,7 @@ impl SqliteRepository {
         .fetch_all(&mut **tx)
         .await?;
 
-        println!("    📦 Found {} records to migrate", results.len());
+        eprintln!("    📦 Found {} records to migrate", results.len());
 
         let mut migrated_count = 0;
         let mut error_count = 0;
@@ -755,7 +1017,7 @@ impl SqliteRepository {
             migrated_count += 1;
         }
 
-        println!("    ✅ Migrated {} records ({} errors)", migrated_count, error_count);
+        eprintln!("    ✅ Migrated {} records ({} errors)", migrated_count, error_count);
         Ok(())
     }
 
@@ -847,55 +1109,227 @@ impl SqliteRepository {
         .await
     }
 
-    // ===== V3マイグレーション: 選手情報正規化 =====
-
-    /// V3マイグレーション: 選手情報を正規化（racesテーブル + race_participantsテーブル）
-    async fn migrate_to_v3(&self) -> Result<(), sqlx::Error> {
-        println!("🔄 Running V3 migration: Normalizing racer information");
+    /// 組番ごとの的中数・配当統計（的中数の多い順）
+    ///
+    /// 的中率の分母は結果のあるレース数。`venue_code` を指定するとその場のレースに限定する。
+    pub async fn get_combination_payout_stats(
+        &self,
+        bet_type: &str,
+        venue_code: Option<&str>,
+        limit: i32,
+    ) -> Result<Vec<CombinationPayoutStats>, sqlx::Error> {
+        sqlx::query_as(
+            r#"
+            WITH target_races AS (
+                SELECT id FROM races
+                WHERE result_data_json IS NOT NULL
+                  AND (?1 IS NULL OR venue_code = ?1)
+            )
+            SELECT
+                pay.bet_type,
+                pay.combination,
+                COUNT(DISTINCT pay.race_id) as hit_count,
+                CAST(COUNT(DISTINCT pay.race_id) AS REAL) / (SELECT COUNT(*) FROM target_races) as hit_rate,
+                AVG(pay.payout) as avg_payout,
+                MIN(pay.payout) as min_payout,
+                MAX(pay.payout) as max_payout
+            FROM race_payouts pay
+            INNER JOIN target_races t ON t.id = pay.race_id
+            WHERE pay.bet_type = ?2
+            GROUP BY pay.bet_type, pay.combination
+            ORDER BY hit_count DESC, pay.combination
+            LIMIT ?3
+            "#
+        )
+        .bind(venue_code)
+        .bind(bet_type)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+    }
 
-        // 冪等性チェック
-        let table_check: Result<(i64,), _> = sqlx::query_as(
-            "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='races'"
+    /// 競艇場の最新シーズン（結果のあるレースの年）
+    pub async fn get_latest_motor_season(&self, venue_code: &str) -> Result<Option<String>, sqlx::Error> {
+        let (season,): (Option<String>,) = sqlx::query_as(
+            "SELECT MAX(substr(race_date, 1, 4)) FROM races WHERE venue_code = ? AND result_data_json IS NOT NULL"
         )
+        .bind(venue_code)
         .fetch_one(&self.pool)
-        .await;
+        .await?;
+        Ok(season)
+    }
+
+    /// 競艇場・シーズンのモーター成績（2連率の高い順、ascending で低い順）
+    ///
+    /// `exclude` のモーター番号は除く（上位に入ったモーターを下位から外すため）。
+    pub async fn get_motor_stats(
+        &self,
+        venue_code: &str,
+        season: &str,
+        min_starts: i32,
+        ascending: bool,
+        limit: i32,
+        exclude: &[i32],
+    ) -> Result<Vec<MotorStats>, sqlx::Error> {
+        let mut query = QueryBuilder::new("SELECT * FROM motors WHERE venue_code = ");
+        query.push_bind(venue_code);
+        query.push(" AND season = ").push_bind(season);
+        Self::push_motor_ranking(&mut query, min_starts, ascending, limit, exclude);
+        query.build_query_as().fetch_all(&self.pool).await
+    }
+
+    /// 競艇場・期間（YYYYMMDD、両端を含む）のモーター成績（2連率の高い順、ascending で低い順）
+    ///
+    /// motors ビューの年単位の集計では、年の途中でモーターを入れ替えた場の前後の
+    /// モーターが同じ行になるため、入れ替え日以降の期間を指定して集計するためのもの。
+    /// 集計は motors ビューと同じ [`Self::push_motor_stats_select`] で行い、行の season は None になる。
+    #[allow(clippy::too_many_arguments)]
+    pub async fn get_motor_stats_between(
+        &self,
+        venue_code: &str,
+        date_from: &str,
+        date_to: &str,
+        min_starts: i32,
+        ascending: bool,
+        limit: i32,
+        exclude: &[i32],
+    ) -> Result<Vec<MotorStats>, sqlx::Error> {
+        let mut query = QueryBuilder::new("SELECT * FROM (");
+        Self::push_motor_stats_select(&mut query, false, |query| {
+            query.push(" AND r.venue_code = ").push_bind(venue_code);
+            query.push(" AND r.race_date >= ").push_bind(date_from);
+            query.push(" AND r.race_date <= ").push_bind(date_to);
+        });
+        query.push(") WHERE 1 = 1");
+        Self::push_motor_ranking(&mut query, min_starts, ascending, limit, exclude);
+        query.build_query_as().fetch_all(&self.pool).await
+    }
+
+    /// モーター成績の集計（motors ビューと期間指定の集計で共有する）
+    ///
+    /// `by_season` なら競艇場・モーター番号・年ごと、そうでなければ競艇場・モーター番号ごとに
+    /// 集計する（season は NULL）。`filter` で race_participants rp / races r への条件を
+    /// " AND ..." の形で追加する。出走表の2連率・3連率は集計範囲内で最新の出走表の値。
+    fn push_motor_stats_select<'a>(
+        query: &mut QueryBuilder<'a, sqlx::Sqlite>,
+        by_season: bool,
+        filter: impl FnOnce(&mut QueryBuilder<'a, sqlx::Sqlite>),
+    ) {
+        let latest_program_percent = |column: &str| {
+            format!(
+                r#"(
+                    SELECT rp2.{column}
+                    FROM race_participants rp2
+                    INNER JOIN races r2 ON r2.id = rp2.race_id
+                    WHERE r2.venue_code = m.venue_code
+                      AND rp2.assigned_motor_number = m.motor_number
+                      AND r2.race_date BETWEEN m.first_race_date AND m.last_race_date
+                      AND rp2.{column} IS NOT NULL
+                    ORDER BY r2.race_date DESC, r2.race_number DESC
+                    LIMIT 1
+                )"#,
+                column = column,
+            )
+        };
+        let (season, group_by_season) = if by_season {
+            ("substr(r.race_date, 1, 4)", ", substr(r.race_date, 1, 4)")
+        } else {
+            ("NULL", "")
+        };
+        query.push(format!(
+            r#"
+            SELECT
+                m.*,
+                CAST(m.wins AS REAL) / NULLIF(m.starts, 0) as win_rate,
+                CAST(m.top2_count AS REAL) / NULLIF(m.starts, 0) as top2_rate,
+                CAST(m.top3_count AS REAL) / NULLIF(m.starts, 0) as top3_rate,
+                {top2} as program_top_2_percent,
+                {top3} as program_top_3_percent
+            FROM (
+                SELECT
+                    r.venue_code,
+                    rp.assigned_motor_number as motor_number,
+                    {season} as season,
+                    SUM(CASE WHEN r.result_data_json IS NOT NULL THEN 1 ELSE 0 END) as starts,
+                    SUM(CASE WHEN r.result_data_json IS NOT NULL AND rp.place_number = 1 THEN 1 ELSE 0 END) as wins,
+                    SUM(CASE WHEN r.result_data_json IS NOT NULL AND rp.place_number <= 2 THEN 1 ELSE 0 END) as top2_count,
+                    SUM(CASE WHEN r.result_data_json IS NOT NULL AND rp.place_number <= 3 THEN 1 ELSE 0 END) as top3_count,
+                    COUNT(DISTINCT rp.racer_number) as racer_count,
+                    COUNT(CASE WHEN rp.exhibition_time > 0 THEN 1 END) as exhibition_count,
+                    AVG(CASE WHEN rp.exhibition_time > 0 THEN rp.exhibition_time END) as average_exhibition_time,
+                    MIN(CASE WHEN rp.exhibition_time > 0 THEN rp.exhibition_time END) as best_exhibition_time,
+                    AVG(CASE WHEN r.result_data_json IS NOT NULL THEN rp.start_timing END) as average_start_timing,
+                    MIN(r.race_date) as first_race_date,
+                    MAX(r.race_date) as last_race_date
+                FROM race_participants rp
+                INNER JOIN races r ON r.id = rp.race_id
+                WHERE rp.assigned_motor_number IS NOT NULL"#,
+            top2 = latest_program_percent("assigned_motor_top_2_percent"),
+            top3 = latest_program_percent("assigned_motor_top_3_percent"),
+            season = season,
+        ));
+        filter(query);
+        query.push(format!(
+            r#"
+                GROUP BY r.venue_code, rp.assigned_motor_number{group_by_season}
+            ) m
+            "#,
+            group_by_season = group_by_season,
+        ));
+    }
 
-        if let Ok((count,)) = table_check {
-            if count > 0 {
-                println!("✅ V3 migration already applied, skipping");
-                return Ok(());
+    /// モーター成績の絞り込み・並び順（2連率・3連率、同率は展示タイムの速い順）
+    fn push_motor_ranking<'a>(
+        query: &mut QueryBuilder<'a, sqlx::Sqlite>,
+        min_starts: i32,
+        ascending: bool,
+        limit: i32,
+        exclude: &'a [i32],
+    ) {
+        query.push(" AND starts >= ").push_bind(min_starts);
+        if !exclude.is_empty() {
+            query.push(" AND motor_number NOT IN (");
+            let mut separated = query.separated(", ");
+            for motor_number in exclude {
+                separated.push_bind(*motor_number);
             }
+            separated.push_unseparated(")");
         }
+        let (order, reverse) = if ascending { ("ASC", "DESC") } else { ("DESC", "ASC") };
+        query.push(format!(
+            " ORDER BY top2_rate {order}, top3_rate {order}, COALESCE(average_exhibition_time, 99.0) {reverse}, motor_number LIMIT ",
+            order = order,
+            reverse = reverse,
+        ));
+        query.push_bind(limit);
+    }
 
-        // トランザクション開始
-        let mut tx = self.pool.begin().await?;
+    // ===== V3マイグレーション: 選手情報正規化 =====
 
+    /// V3マイグレーション: 選手情報を正規化（racesテーブル + race_participantsテーブル）
+    async fn migrate_to_v3(&self, tx: &mut MigrationTx) -> Result<(), sqlx::Error> {
         // ステップ1: 新テーブル作成
-        println!("  📝 Creating new tables...");
-        self.create_v3_tables(&mut tx).await?;
+        eprintln!("  📝 Creating new tables...");
+        self.create_v3_tables(tx).await?;
 
         // ステップ2: データ移行
-        println!("  🔄 Migrating data from results table...");
-        self.migrate_results_to_v3(&mut tx).await?;
+        eprintln!("  🔄 Migrating data from results table...");
+        self.migrate_results_to_v3(tx).await?;
 
         // ステップ3: インデックス作成
-        println!("  📊 Creating indexes...");
-        self.create_v3_indexes(&mut tx).await?;
+        eprintln!("  📊 Creating indexes...");
+        self.create_v3_indexes(tx).await?;
 
         // ステップ4: 整合性検証
-        println!("  ✅ Verifying data integrity...");
-        self.verify_v3_migration(&mut tx).await?;
+        eprintln!("  ✅ Verifying data integrity...");
+        self.verify_v3_migration(tx).await?;
 
         // ステップ5: 旧テーブル削除
-        println!("  🗑️  Dropping old results table...");
+        eprintln!("  🗑️  Dropping old results table...");
         sqlx::query("DROP TABLE IF EXISTS results")
-            .execute(&mut *tx)
+            .execute(&mut **tx)
             .await?;
 
-        // コミット
-        tx.commit().await?;
-
-        println!("✅ V3 migration completed successfully");
         Ok(())
     }
 
@@ -1015,7 +1449,7 @@ impl SqliteRepository {
             .map(|p| ((p.date, p.venue_code, p.race_number), p.data_json))
             .collect();
 
-        println!("    📦 Found {} results and {} programs to migrate",
+        eprintln!("    📦 Found {} results and {} programs to migrate",
             results.len(), program_map.len());
 
         let mut migrated_races = 0;
@@ -1175,7 +1609,7 @@ impl SqliteRepository {
             }
         }
 
-        println!("    ✅ Migrated {} races and {} participants",
+        eprintln!("    ✅ Migrated {} races and {} participants",
             migrated_races, migrated_participants);
         Ok(())
     }
@@ -1259,59 +1693,514 @@ impl SqliteRepository {
             )));
         }
 
-        println!("    ✅ Data integrity verified: {} races, {} participants",
+        eprintln!("    ✅ Data integrity verified: {} races, {} participants",
             new_count.0, participant_count.0);
         Ok(())
     }
 
-    // ===== V3検索API: 正規化テーブルを使用した高度な検索 =====
+    // ===== V4マイグレーション: 直前情報の正規化 =====
+
+    /// V4マイグレーション: previews の展示タイム等を races / race_participants のカラムに展開
+    async fn migrate_to_v4(&self, tx: &mut MigrationTx) -> Result<(), sqlx::Error> {
+        eprintln!("  📝 Adding preview columns...");
+        let columns = [
+            ("races", "preview_wind REAL"),
+            ("races", "preview_wind_direction_number REAL"),
+            ("races", "preview_wave REAL"),
+            ("races", "preview_weather_number REAL"),
+            ("races", "preview_temperature REAL"),
+            ("races", "preview_water_temperature REAL"),
+            ("race_participants", "exhibition_time REAL"),
+            ("race_participants", "exhibition_course_number INTEGER"),
+            ("race_participants", "exhibition_start_timing REAL"),
+            ("race_participants", "tilt_adjustment REAL"),
+            ("race_participants", "weight_adjustment REAL"),
+        ];
+        for (table, column) in columns {
+            sqlx::query(&format!("ALTER TABLE {} ADD COLUMN {}", table, column))
+                .execute(&mut **tx)
+                .await?;
+        }
 
-    /// 複合条件検索（動的クエリビルダー使用）
-    pub async fn search_races_advanced(
-        &self,
-        params: SearchParams,
-    ) -> Result<Vec<(RaceRecord, Vec<RaceParticipantRecord>)>, sqlx::Error> {
-        // 選手条件がある場合はJOINが必要
-        let needs_participant_join = params.racer_number.is_some()
-            || params.racer_name.is_some()
-            || params.racer_class.is_some()
-            || params.place_number.is_some();
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_participants_exhibition_time ON race_participants(exhibition_time)")
+            .execute(&mut **tx)
+            .await?;
 
-        // QueryBuilder開始
-        let mut query = QueryBuilder::new("SELECT DISTINCT r.* FROM races r");
+        // 既存の previews を反映
+        eprintln!("  🔄 Backfilling from previews table...");
+        let previews: Vec<PreviewRecord> = sqlx::query_as(
+            "SELECT id, date, venue_code, race_number, data_json, created_at, updated_at FROM previews"
+        )
+        .fetch_all(&mut **tx)
+        .await?;
 
-        if needs_participant_join {
-            query.push(" INNER JOIN race_participants rp ON r.id = rp.race_id");
+        let mut migrated = 0;
+        for record in &previews {
+            let data: RacePreview = match serde_json::from_str(&record.data_json) {
+                Ok(data) => data,
+                Err(e) => {
+                    eprintln!("    ⚠️  Failed to parse preview JSON: {}", e);
+                    continue;
+                }
+            };
+            Self::upsert_preview_columns(tx, record, &data).await?;
+            migrated += 1;
         }
 
-        query.push(" WHERE 1=1");
-
-        // 選手条件
-        if let Some(racer_number) = params.racer_number {
-            query.push(" AND rp.racer_number = ");
-            query.push_bind(racer_number);
-        }
+        eprintln!("    ✅ Backfilled {}/{} previews", migrated, previews.len());
+        Ok(())
+    }
 
-        if let Some(racer_name) = &params.racer_name {
-            query.push(" AND rp.racer_name LIKE ");
-            query.push_bind(format!("%{}%", racer_name));
-        }
+    // ===== V5マイグレーション: 全賭式の払戻テーブル =====
 
-        if let Some(racer_class) = params.racer_class {
-            query.push(" AND rp.racer_class_number = ");
-            query.push_bind(racer_class);
-        }
+    /// V5マイグレーション: race_payouts テーブルを作成し、races.result_data_json から移行
+    async fn migrate_to_v5(&self, tx: &mut MigrationTx) -> Result<(), sqlx::Error> {
+        eprintln!("  📝 Creating race_payouts table...");
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS race_payouts (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                race_id INTEGER NOT NULL,
+                bet_type TEXT NOT NULL,
+                combination TEXT NOT NULL,
+                payout INTEGER,
+                popularity INTEGER,
+                FOREIGN KEY (race_id) REFERENCES races(id) ON DELETE CASCADE,
+                UNIQUE(race_id, bet_type, combination)
+            )
+            "#
+        )
+        .execute(&mut **tx)
+        .await?;
 
-        if let Some(place) = params.place_number {
-            query.push(" AND rp.place_number = ");
-            query.push_bind(place);
-        }
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_payouts_bet_type_combination ON race_payouts(bet_type, combination)")
+            .execute(&mut **tx)
+            .await?;
 
-        // 日付・会場条件
-        if let Some(date_from) = &params.date_from {
-            query.push(" AND r.race_date >= ");
-            query.push_bind(date_from);
-        }
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_payouts_bet_type_payout ON race_payouts(bet_type, payout)")
+            .execute(&mut **tx)
+            .await?;
+
+        eprintln!("  🔄 Backfilling from races.result_data_json...");
+        let rows: Vec<(i64, String)> = sqlx::query_as(
+            "SELECT id, result_data_json FROM races WHERE result_data_json IS NOT NULL AND result_data_json != ''"
+        )
+        .fetch_all(&mut **tx)
+        .await?;
+
+        let mut migrated = 0;
+        for (race_id, json) in &rows {
+            let result: RaceResult = match serde_json::from_str(json) {
+                Ok(result) => result,
+                Err(e) => {
+                    eprintln!("    ⚠️  Failed to parse result JSON: {}", e);
+                    continue;
+                }
+            };
+            Self::replace_race_payouts(tx, *race_id, &result.payouts).await?;
+            migrated += 1;
+        }
+
+        eprintln!("    ✅ Backfilled payouts for {}/{} races", migrated, rows.len());
+        Ok(())
+    }
+
+    // ===== V6マイグレーション: コードマスタ =====
+
+    /// V6マイグレーション: コード値のマスタテーブルを作成し、決まり手を1着艇の decision_hand に反映
+    async fn migrate_to_v6(&self, tx: &mut MigrationTx) -> Result<(), sqlx::Error> {
+        eprintln!("  📝 Seeding code master tables...");
+        Self::seed_master::<Weather>(tx).await?;
+        Self::seed_master::<WindDirection>(tx).await?;
+        Self::seed_master::<Technique>(tx).await?;
+        Self::seed_master::<RaceGrade>(tx).await?;
+        Self::seed_master::<RacerClass>(tx).await?;
+        Self::seed_master::<Prefecture>(tx).await?;
+
+        eprintln!("  🔄 Filling decision_hand from race_technique_number...");
+        let result = sqlx::query(
+            r#"
+            UPDATE race_participants
+            SET decision_hand = (
+                SELECT t.label_ja
+                FROM races r
+                INNER JOIN master_techniques t ON t.code = CAST(r.race_technique_number AS INTEGER)
+                WHERE r.id = race_participants.race_id
+            )
+            WHERE place_number = 1
+            "#
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        eprintln!("    ✅ Updated decision_hand for {} winners", result.rows_affected());
+        Ok(())
+    }
+
+    /// コード値のマスタテーブルを作成して全件を登録
+    async fn seed_master<E: CodeEnum>(conn: &mut SqliteConnection) -> Result<(), sqlx::Error> {
+        sqlx::query(&format!(
+            "CREATE TABLE IF NOT EXISTS {} (
+                code INTEGER PRIMARY KEY,
+                label_ja TEXT NOT NULL,
+                label_en TEXT NOT NULL
+            )",
+            E::MASTER_TABLE
+        ))
+        .execute(&mut *conn)
+        .await?;
+
+        for value in E::ALL {
+            sqlx::query(&format!(
+                "INSERT OR REPLACE INTO {} (code, label_ja, label_en) VALUES (?, ?, ?)",
+                E::MASTER_TABLE
+            ))
+            .bind(value.code())
+            .bind(value.label_ja())
+            .bind(value.label_en())
+            .execute(&mut *conn)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    // ===== V7マイグレーション: 選手マスタ =====
+
+    /// V7マイグレーション: racers / racer_class_history を作成し、race_participants から移行
+    async fn migrate_to_v7(&self, tx: &mut MigrationTx) -> Result<(), sqlx::Error> {
+        eprintln!("  📝 Creating racers tables...");
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS racers (
+                racer_number INTEGER PRIMARY KEY,
+                racer_name TEXT,
+                branch_number INTEGER,
+                birthplace_number INTEGER,
+                class_number INTEGER,
+                period INTEGER,
+                gender TEXT,
+                last_seen_date TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )
+            "#
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS racer_class_history (
+                racer_number INTEGER NOT NULL,
+                term TEXT NOT NULL,
+                class_number INTEGER NOT NULL,
+                first_seen_date TEXT NOT NULL,
+                last_seen_date TEXT NOT NULL,
+                PRIMARY KEY (racer_number, term)
+            )
+            "#
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_participants_racer ON race_participants(racer_number)")
+            .execute(&mut **tx)
+            .await?;
+
+        eprintln!("  🔄 Backfilling racers from race_participants...");
+        #[derive(sqlx::FromRow)]
+        struct ParticipantRow {
+            racer_number: i32,
+            racer_name: Option<String>,
+            racer_class_number: Option<i32>,
+            racer_branch_number: Option<i32>,
+            racer_birthplace_number: Option<i32>,
+            race_date: String,
+        }
+
+        let rows: Vec<ParticipantRow> = sqlx::query_as(
+            r#"
+            SELECT rp.racer_number, rp.racer_name, rp.racer_class_number,
+                   rp.racer_branch_number, rp.racer_birthplace_number, r.race_date
+            FROM race_participants rp
+            INNER JOIN races r ON r.id = rp.race_id
+            WHERE rp.racer_number IS NOT NULL
+            ORDER BY r.race_date
+            "#
+        )
+        .fetch_all(&mut **tx)
+        .await?;
+
+        let now = chrono::Utc::now().to_rfc3339();
+        for row in &rows {
+            let update = RacerUpdate {
+                racer_number: row.racer_number,
+                racer_name: row.racer_name.clone(),
+                branch_number: row.racer_branch_number,
+                birthplace_number: row.racer_birthplace_number,
+                class_number: row.racer_class_number,
+                seen_date: row.race_date.replace('-', ""),
+                ..Default::default()
+            };
+            Self::upsert_racer(tx, &update, &now).await?;
+        }
+
+        let (racer_count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM racers")
+            .fetch_one(&mut **tx)
+            .await?;
+        eprintln!("    ✅ Registered {} racers from {} entries", racer_count, rows.len());
+        Ok(())
+    }
+
+    // ===== V8マイグレーション: モーター成績 =====
+
+    /// V8マイグレーション: 競艇場・モーター番号・シーズン（年）ごとの成績を集計する motors ビューを作成
+    ///
+    /// シーズンはレース日の年で近似している。モーターの入れ替えは場ごとの日付で行われ、
+    /// 入れ替え日はデータに含まれないため、年の途中で入れ替えた場では同じ番号の新旧モーターが
+    /// 1行にまとまる。入れ替え後だけを見たい場合は `get_motor_stats_between` で期間を指定する。
+    async fn migrate_to_v8(&self, tx: &mut MigrationTx) -> Result<(), sqlx::Error> {
+        eprintln!("  📊 Creating motors view...");
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_participants_motor ON race_participants(assigned_motor_number)"
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        // 期間指定の集計（get_motor_stats_between）と同じクエリから作る
+        let mut view = QueryBuilder::new("CREATE VIEW IF NOT EXISTS motors AS");
+        Self::push_motor_stats_select(&mut view, true, |_| {});
+        view.build().execute(&mut **tx).await?;
+
+        Ok(())
+    }
+
+    // ===== V9マイグレーション: オッズの時系列 =====
+
+    /// V9マイグレーション: 取得時刻ごとの単勝・複勝オッズを保存する odds_snapshots を作成
+    async fn migrate_to_v9(&self, tx: &mut MigrationTx) -> Result<(), sqlx::Error> {
+        eprintln!("  📈 Creating odds_snapshots table...");
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS odds_snapshots (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                race_date TEXT NOT NULL,
+                venue_code TEXT NOT NULL,
+                race_number INTEGER NOT NULL,
+                captured_at TEXT NOT NULL,
+                bet_type TEXT NOT NULL,
+                boat_number INTEGER NOT NULL,
+                odds REAL NOT NULL,
+                odds_max REAL
+            )
+            "#
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_odds_snapshots_race ON odds_snapshots(race_date, venue_code, race_number, captured_at)"
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    // ===== V10マイグレーション: スクレイピング結果のキャッシュ =====
+
+    /// V10マイグレーション: kyoteibiyori のレースデータ・単勝複勝オッズのキャッシュを作成
+    ///
+    /// それまでの JSON ファイル（norimaki-db）のキャッシュに代わるもの。逃げ率などのレース統計は
+    /// 検索・集計に使えるよう列にも展開する。
+    async fn migrate_to_v10(&self, tx: &mut MigrationTx) -> Result<(), sqlx::Error> {
+        eprintln!("  🗃️ Creating biyori_race_data / biyori_odds tables...");
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS biyori_race_data (
+                race_date TEXT NOT NULL,
+                venue_code TEXT NOT NULL,
+                race_number INTEGER NOT NULL,
+                escape_last_year REAL,
+                escape_last_half_year REAL,
+                allow_escape_last_year REAL,
+                allow_escape_last_half_year REAL,
+                pierce_last_year REAL,
+                pierce_last_half_year REAL,
+                overtake_last_year REAL,
+                overtake_last_half_year REAL,
+                data_json TEXT NOT NULL,
+                fetched_at TEXT,
+                source_json TEXT,
+                updated_at TEXT NOT NULL,
+                PRIMARY KEY (race_date, venue_code, race_number)
+            )
+            "#
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS biyori_odds (
+                race_date TEXT NOT NULL,
+                venue_code TEXT NOT NULL,
+                race_number INTEGER NOT NULL,
+                betting_type TEXT NOT NULL,
+                data_json TEXT NOT NULL,
+                fetched_at TEXT,
+                source_json TEXT,
+                updated_at TEXT NOT NULL,
+                PRIMARY KEY (race_date, venue_code, race_number)
+            )
+            "#
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    // ===== V11マイグレーション: 一括取得ジョブ =====
+
+    /// V11マイグレーション: 一括取得ジョブと作業単位のテーブルを作成
+    ///
+    /// 以前は JobRepository が起動時に個別に作成していたため、既存のDBではテーブルが
+    /// すでにある場合がある（IF NOT EXISTS でそのまま使う）。
+    async fn migrate_to_v11(&self, tx: &mut MigrationTx) -> Result<(), sqlx::Error> {
+        eprintln!("  🗃️ Creating bulk_jobs / bulk_job_units tables...");
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS bulk_jobs (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                kind TEXT NOT NULL,
+                status TEXT NOT NULL,
+                params_json TEXT NOT NULL,
+                total_units INTEGER NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )
+            "#
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS bulk_job_units (
+                job_id INTEGER NOT NULL REFERENCES bulk_jobs(id) ON DELETE CASCADE,
+                seq INTEGER NOT NULL,
+                payload_json TEXT NOT NULL,
+                status TEXT NOT NULL,
+                error TEXT,
+                updated_at TEXT NOT NULL,
+                PRIMARY KEY (job_id, seq)
+            )
+            "#
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_bulk_job_units_status ON bulk_job_units(job_id, status)")
+            .execute(&mut **tx)
+            .await?;
+
+        Ok(())
+    }
+
+    // ===== V12マイグレーション: 未使用カラムの削除 =====
+
+    /// V12マイグレーション: racers から racer_name_kana を削除
+    ///
+    /// どのデータソースにもカナ氏名が含まれず常に NULL だったため。V7 の定義からも外したので、
+    /// 新規DBではカラムが存在しない。
+    async fn migrate_to_v12(&self, tx: &mut MigrationTx) -> Result<(), sqlx::Error> {
+        let (count,): (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM pragma_table_info('racers') WHERE name='racer_name_kana'"
+        )
+        .fetch_one(&mut **tx)
+        .await?;
+
+        if count > 0 {
+            eprintln!("  🗃️ Dropping racers.racer_name_kana...");
+            sqlx::query("ALTER TABLE racers DROP COLUMN racer_name_kana")
+                .execute(&mut **tx)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    // ===== V3検索API: 正規化テーブルを使用した高度な検索 =====
+
+    /// SearchParams の条件を WHERE 句にした races の検索クエリ（select は races を r として参照する）
+    fn search_query<'a>(select: &str, params: &'a SearchParams) -> QueryBuilder<'a, sqlx::Sqlite> {
+        // 選手条件・展示条件がある場合はJOINが必要
+        let needs_participant_join = params.racer_number.is_some()
+            || params.racer_name.is_some()
+            || params.racer_class.is_some()
+            || params.place_number.is_some()
+            || params.max_exhibition_time.is_some()
+            || params.exhibition_course_number.is_some()
+            || params.max_exhibition_start_timing.is_some()
+            || params.min_tilt_adjustment.is_some();
+
+        let mut query = QueryBuilder::new(select);
+
+        if needs_participant_join {
+            query.push(" INNER JOIN race_participants rp ON r.id = rp.race_id");
+        }
+
+        query.push(" WHERE 1=1");
+
+        // 選手条件
+        if let Some(racer_number) = params.racer_number {
+            query.push(" AND rp.racer_number = ");
+            query.push_bind(racer_number);
+        }
+
+        if let Some(racer_name) = &params.racer_name {
+            query.push(" AND rp.racer_name LIKE ");
+            query.push_bind(format!("%{}%", racer_name));
+        }
+
+        if let Some(racer_class) = params.racer_class {
+            query.push(" AND rp.racer_class_number = ");
+            query.push_bind(racer_class);
+        }
+
+        if let Some(place) = params.place_number {
+            query.push(" AND rp.place_number = ");
+            query.push_bind(place);
+        }
+
+        // 展示条件（選手条件と同じ艇に対して適用）
+        if let Some(max_time) = params.max_exhibition_time {
+            query.push(" AND rp.exhibition_time <= ");
+            query.push_bind(max_time);
+        }
+
+        if let Some(course) = params.exhibition_course_number {
+            query.push(" AND rp.exhibition_course_number = ");
+            query.push_bind(course);
+        }
+
+        if let Some(max_st) = params.max_exhibition_start_timing {
+            query.push(" AND rp.exhibition_start_timing <= ");
+            query.push_bind(max_st);
+        }
+
+        if let Some(min_tilt) = params.min_tilt_adjustment {
+            query.push(" AND rp.tilt_adjustment >= ");
+            query.push_bind(min_tilt);
+        }
+
+        // 日付・会場条件
+        if let Some(date_from) = &params.date_from {
+            query.push(" AND r.race_date >= ");
+            query.push_bind(date_from);
+        }
 
         if let Some(date_to) = &params.date_to {
             query.push(" AND r.race_date <= ");
@@ -1381,12 +2270,53 @@ impl SqliteRepository {
             query.push_bind(max_temp);
         }
 
+        // 払戻条件（同じ払戻行に対してすべての条件を適用）
+        if params.bet_type.is_some()
+            || params.combination.is_some()
+            || params.min_payout.is_some()
+            || params.max_payout.is_some()
+        {
+            query.push(" AND EXISTS (SELECT 1 FROM race_payouts pay WHERE pay.race_id = r.id");
+
+            if let Some(bet_type) = &params.bet_type {
+                query.push(" AND pay.bet_type = ");
+                query.push_bind(bet_type);
+            }
+
+            if let Some(combination) = &params.combination {
+                query.push(" AND pay.combination = ");
+                query.push_bind(combination);
+            }
+
+            if let Some(min_payout) = params.min_payout {
+                query.push(" AND pay.payout >= ");
+                query.push_bind(min_payout);
+            }
+
+            if let Some(max_payout) = params.max_payout {
+                query.push(" AND pay.payout <= ");
+                query.push_bind(max_payout);
+            }
+
+            query.push(")");
+        }
+
         // 勝者条件
         if let Some(winner_boat) = params.winner_boat_number {
             query.push(" AND r.winner_boat_number = ");
             query.push_bind(winner_boat);
         }
 
+        query
+    }
+
+    /// 複合条件検索（動的クエリビルダー使用）
+    pub async fn search_races_advanced(
+        &self,
+        params: SearchParams,
+    ) -> Result<Vec<(RaceRecord, Vec<RaceParticipantRecord>)>, sqlx::Error> {
+        let mut query = Self::search_query("SELECT DISTINCT r.* FROM races r", &params);
+
         // ソート
         query.push(" ORDER BY r.race_date DESC, r.venue_code, r.race_number");
 
@@ -1403,20 +2333,199 @@ impl SqliteRepository {
 
         // 各レースの選手情報を取得
         let mut results = Vec::new();
-        for race in races {
-            let participants = sqlx::query_as::<_, RaceParticipantRecord>(
+        for mut race in races {
+            let mut participants = sqlx::query_as::<_, RaceParticipantRecord>(
                 "SELECT * FROM race_participants WHERE race_id = ? ORDER BY boat_number"
             )
             .bind(race.id)
             .fetch_all(&self.pool)
             .await?;
 
+            race.decode_labels();
+            participants.iter_mut().for_each(RaceParticipantRecord::decode_labels);
             results.push((race, participants));
         }
 
         Ok(results)
     }
 
+    /// バックテスト対象のレース（結果のあるレースを古い順、指定賭式の払戻付き）
+    pub async fn get_backtest_races(
+        &self,
+        params: &SearchParams,
+        bet_type: &str,
+    ) -> Result<Vec<BacktestRace>, sqlx::Error> {
+        #[derive(sqlx::FromRow)]
+        struct BacktestRow {
+            race_id: i64,
+            race_date: String,
+            venue_code: String,
+            race_number: i32,
+            combination: Option<String>,
+            payout: Option<i32>,
+        }
+
+        let mut query = Self::search_query(
+            "WITH target AS (SELECT DISTINCT r.id, r.race_date, r.venue_code, r.race_number FROM races r",
+            params,
+        );
+        query.push(" AND r.result_data_json IS NOT NULL)");
+        query.push(
+            " SELECT t.id as race_id, t.race_date, t.venue_code, t.race_number, pay.combination, pay.payout
+              FROM target t
+              LEFT JOIN race_payouts pay ON pay.race_id = t.id AND pay.bet_type = ",
+        );
+        query.push_bind(bet_type);
+        query.push(" ORDER BY t.race_date, t.venue_code, t.race_number, pay.combination");
+
+        let rows = query
+            .build_query_as::<BacktestRow>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut races: Vec<BacktestRace> = Vec::new();
+        for row in rows {
+            if races.last().map(|race| race.race_id) != Some(row.race_id) {
+                races.push(BacktestRace {
+                    race_id: row.race_id,
+                    race_date: row.race_date,
+                    venue_code: row.venue_code,
+                    race_number: row.race_number,
+                    payouts: Vec::new(),
+                });
+            }
+            if let (Some(combination), Some(payout), Some(race)) = (row.combination, row.payout, races.last_mut()) {
+                race.payouts.push((combination, payout));
+            }
+        }
+
+        Ok(races)
+    }
+
+    /// 日付・会場・レース番号でレースを取得（コード値のラベル付き）
+    pub async fn get_race(
+        &self,
+        race_date: &str,
+        venue_code: &str,
+        race_number: i32,
+    ) -> Result<Option<RaceRecord>, sqlx::Error> {
+        let race = sqlx::query_as::<_, RaceRecord>(
+            "SELECT * FROM races WHERE race_date = ? AND venue_code = ? AND race_number = ?"
+        )
+        .bind(race_date)
+        .bind(venue_code)
+        .bind(race_number)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(race.map(|mut race| {
+            race.decode_labels();
+            race
+        }))
+    }
+
+    /// kyoteibiyori の逃げ率と結果の両方があるレース（日付は YYYYMMDD、範囲の両端を含む）
+    pub async fn get_escape_outcomes(
+        &self,
+        date_from: Option<&str>,
+        date_to: Option<&str>,
+        venue_code: Option<&str>,
+    ) -> Result<Vec<EscapeOutcomeRow>, sqlx::Error> {
+        let mut query = QueryBuilder::<sqlx::Sqlite>::new(
+            r#"
+            SELECT b.race_date, b.venue_code, b.race_number,
+                   b.escape_last_year, b.escape_last_half_year,
+                   r.winner_boat_number, r.race_technique_number
+            FROM biyori_race_data b
+            JOIN races r
+              ON r.race_date = b.race_date AND r.venue_code = b.venue_code AND r.race_number = b.race_number
+            WHERE r.result_data_json IS NOT NULL
+            "#,
+        );
+        if let Some(date_from) = date_from {
+            query.push(" AND b.race_date >= ").push_bind(date_from);
+        }
+        if let Some(date_to) = date_to {
+            query.push(" AND b.race_date <= ").push_bind(date_to);
+        }
+        if let Some(venue_code) = venue_code {
+            query.push(" AND b.venue_code = ").push_bind(venue_code);
+        }
+        query.push(" ORDER BY b.race_date, b.venue_code, b.race_number");
+
+        query.build_query_as::<EscapeOutcomeRow>().fetch_all(&self.pool).await
+    }
+
+    /// 日付・会場・レース番号で出走艇を取得（レースが未保存なら None）
+    pub async fn get_race_entries(
+        &self,
+        race_date: &str,
+        venue_code: &str,
+        race_number: i32,
+    ) -> Result<Option<(i64, Vec<RaceParticipantRecord>)>, sqlx::Error> {
+        let race_id: Option<(i64,)> = sqlx::query_as(
+            "SELECT id FROM races WHERE race_date = ? AND venue_code = ? AND race_number = ?"
+        )
+        .bind(race_date)
+        .bind(venue_code)
+        .bind(race_number)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let Some((race_id,)) = race_id else {
+            return Ok(None);
+        };
+        let participants = sqlx::query_as::<_, RaceParticipantRecord>(
+            "SELECT * FROM race_participants WHERE race_id = ? ORDER BY boat_number"
+        )
+        .bind(race_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(Some((race_id, participants)))
+    }
+
+    /// before_date（YYYYMMDD）より前に結果の出たレースの要素ごとの出走数・1着数
+    ///
+    /// 対象レース当日以降の結果を含めると過去のレースの推定に先の結果が混ざるため、前日までに限る。
+    pub async fn get_win_factor_buckets(
+        &self,
+        factor: WinFactor,
+        before_date: &str,
+    ) -> Result<Vec<FactorBucket>, sqlx::Error> {
+        let (bucket, column) = match factor {
+            WinFactor::Course => ("rp.course_number", "rp.course_number"),
+            WinFactor::RacerClass => ("rp.racer_class_number", "rp.racer_class_number"),
+            WinFactor::LocalWinRateRank => (
+                "RANK() OVER (PARTITION BY rp.race_id ORDER BY rp.local_top_1_percent DESC)",
+                "rp.local_top_1_percent",
+            ),
+            WinFactor::MotorTop2Rank => (
+                "RANK() OVER (PARTITION BY rp.race_id ORDER BY rp.assigned_motor_top_2_percent DESC)",
+                "rp.assigned_motor_top_2_percent",
+            ),
+        };
+        let query = format!(
+            r#"
+            SELECT bucket, COUNT(*) as starts, SUM(CASE WHEN place_number = 1 THEN 1 ELSE 0 END) as wins
+            FROM (
+                SELECT {bucket} as bucket, rp.place_number
+                FROM race_participants rp
+                INNER JOIN races r ON r.id = rp.race_id
+                WHERE r.result_data_json IS NOT NULL AND r.race_date < ? AND {column} IS NOT NULL
+            )
+            GROUP BY bucket
+            ORDER BY bucket
+            "#,
+            bucket = bucket,
+            column = column,
+        );
+        sqlx::query_as(&query)
+            .bind(before_date)
+            .fetch_all(&self.pool)
+            .await
+    }
+
     /// 選手番号での検索
     pub async fn search_races_by_racer(
         &self,
@@ -1549,6 +2658,302 @@ impl SqliteRepository {
         Ok(rows)
     }
 
+    // ===== 選手マスタ =====
+
+    /// 選手マスタを更新（級別は適用期ごとの履歴にも記録）
+    ///
+    /// 名前・級別・支部は情報の日付が最後に確認した日付以降の場合のみ上書きし、
+    /// それ以外の項目は値がある場合のみ更新する。
+    async fn upsert_racer(
+        conn: &mut SqliteConnection,
+        update: &RacerUpdate,
+        now: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO racers (
+                racer_number, racer_name, branch_number, birthplace_number,
+                class_number, period, gender, last_seen_date,
+                created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(racer_number)
+            DO UPDATE SET
+                racer_name = CASE WHEN excluded.last_seen_date >= COALESCE(racers.last_seen_date, '')
+                    THEN COALESCE(excluded.racer_name, racers.racer_name)
+                    ELSE COALESCE(racers.racer_name, excluded.racer_name) END,
+                class_number = CASE WHEN excluded.last_seen_date >= COALESCE(racers.last_seen_date, '')
+                    THEN COALESCE(excluded.class_number, racers.class_number)
+                    ELSE COALESCE(racers.class_number, excluded.class_number) END,
+                branch_number = CASE WHEN excluded.last_seen_date >= COALESCE(racers.last_seen_date, '')
+                    THEN COALESCE(excluded.branch_number, racers.branch_number)
+                    ELSE COALESCE(racers.branch_number, excluded.branch_number) END,
+                birthplace_number = COALESCE(excluded.birthplace_number, racers.birthplace_number),
+                period = COALESCE(excluded.period, racers.period),
+                gender = COALESCE(excluded.gender, racers.gender),
+                last_seen_date = MAX(COALESCE(racers.last_seen_date, ''), excluded.last_seen_date),
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(update.racer_number)
+        .bind(update.racer_name.as_ref())
+        .bind(update.branch_number)
+        .bind(update.birthplace_number)
+        .bind(update.class_number)
+        .bind(update.period)
+        .bind(update.gender.as_ref())
+        .bind(&update.seen_date)
+        .bind(now)
+        .bind(now)
+        .execute(&mut *conn)
+        .await?;
+
+        if let (Some(class_number), Some(term)) = (update.class_number, class_term(&update.seen_date)) {
+            sqlx::query(
+                r#"
+                INSERT INTO racer_class_history (racer_number, term, class_number, first_seen_date, last_seen_date)
+                VALUES (?, ?, ?, ?, ?)
+                ON CONFLICT(racer_number, term)
+                DO UPDATE SET
+                    class_number = CASE WHEN excluded.last_seen_date >= racer_class_history.last_seen_date
+                        THEN excluded.class_number ELSE racer_class_history.class_number END,
+                    first_seen_date = MIN(racer_class_history.first_seen_date, excluded.first_seen_date),
+                    last_seen_date = MAX(racer_class_history.last_seen_date, excluded.last_seen_date)
+                "#,
+            )
+            .bind(update.racer_number)
+            .bind(term)
+            .bind(class_number)
+            .bind(&update.seen_date)
+            .bind(&update.seen_date)
+            .execute(&mut *conn)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// スクレイピング結果などから選手マスタを更新
+    pub async fn save_racers(&self, updates: &[RacerUpdate]) -> Result<(), sqlx::Error> {
+        let now = chrono::Utc::now().to_rfc3339();
+        let mut tx = self.pool.begin().await?;
+        for update in updates {
+            Self::upsert_racer(&mut tx, update, &now).await?;
+        }
+        tx.commit().await
+    }
+
+    /// 選手マスタを取得
+    pub async fn get_racer(&self, racer_number: i32) -> Result<Option<RacerRecord>, sqlx::Error> {
+        let racer = sqlx::query_as::<_, RacerRecord>("SELECT * FROM racers WHERE racer_number = ?")
+            .bind(racer_number)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(racer.map(|mut racer| {
+            racer.decode_labels();
+            racer
+        }))
+    }
+
+    /// 級別の履歴（古い順）
+    pub async fn get_racer_class_history(
+        &self,
+        racer_number: i32,
+    ) -> Result<Vec<RacerClassHistoryRecord>, sqlx::Error> {
+        sqlx::query_as(
+            "SELECT * FROM racer_class_history WHERE racer_number = ? ORDER BY term"
+        )
+        .bind(racer_number)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// 結果のあるレースへの出走数・1〜3着率・平均ST
+    pub async fn get_racer_result_totals(
+        &self,
+        racer_number: i32,
+    ) -> Result<(i64, f64, f64, f64, Option<f64>), sqlx::Error> {
+        sqlx::query_as(
+            r#"
+            SELECT
+                COUNT(*),
+                COALESCE(AVG(CASE WHEN rp.place_number = 1 THEN 1.0 ELSE 0.0 END), 0.0),
+                COALESCE(AVG(CASE WHEN rp.place_number <= 2 THEN 1.0 ELSE 0.0 END), 0.0),
+                COALESCE(AVG(CASE WHEN rp.place_number <= 3 THEN 1.0 ELSE 0.0 END), 0.0),
+                AVG(rp.start_timing)
+            FROM race_participants rp
+            INNER JOIN races r ON r.id = rp.race_id
+            WHERE rp.racer_number = ? AND r.result_data_json IS NOT NULL
+            "#
+        )
+        .bind(racer_number)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// 着順分布
+    pub async fn get_racer_place_distribution(
+        &self,
+        racer_number: i32,
+    ) -> Result<Vec<PlaceCount>, sqlx::Error> {
+        sqlx::query_as(
+            r#"
+            SELECT rp.place_number, COUNT(*) as count
+            FROM race_participants rp
+            INNER JOIN races r ON r.id = rp.race_id
+            WHERE rp.racer_number = ? AND r.result_data_json IS NOT NULL AND rp.place_number IS NOT NULL
+            GROUP BY rp.place_number
+            ORDER BY rp.place_number
+            "#
+        )
+        .bind(racer_number)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// 最新の出走表時点のフライング・出遅れ回数
+    pub async fn get_racer_latest_fl_counts(
+        &self,
+        racer_number: i32,
+    ) -> Result<Option<(Option<i32>, Option<i32>)>, sqlx::Error> {
+        sqlx::query_as(
+            r#"
+            SELECT rp.flying_count, rp.late_count
+            FROM race_participants rp
+            INNER JOIN races r ON r.id = rp.race_id
+            WHERE rp.racer_number = ? AND r.program_data_json IS NOT NULL
+            ORDER BY r.race_date DESC, r.race_number DESC
+            LIMIT 1
+            "#
+        )
+        .bind(racer_number)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// 進入コース別成績
+    pub async fn get_racer_course_stats(
+        &self,
+        racer_number: i32,
+    ) -> Result<Vec<RacerCourseStats>, sqlx::Error> {
+        sqlx::query_as(
+            r#"
+            SELECT
+                rp.course_number,
+                COUNT(*) as starts,
+                SUM(CASE WHEN rp.place_number = 1 THEN 1 ELSE 0 END) as wins,
+                AVG(CASE WHEN rp.place_number = 1 THEN 1.0 ELSE 0.0 END) as win_rate,
+                AVG(CASE WHEN rp.place_number <= 2 THEN 1.0 ELSE 0.0 END) as top2_rate,
+                AVG(CASE WHEN rp.place_number <= 3 THEN 1.0 ELSE 0.0 END) as top3_rate,
+                AVG(rp.start_timing) as average_start_timing
+            FROM race_participants rp
+            INNER JOIN races r ON r.id = rp.race_id
+            WHERE rp.racer_number = ? AND r.result_data_json IS NOT NULL AND rp.course_number IS NOT NULL
+            GROUP BY rp.course_number
+            ORDER BY rp.course_number
+            "#
+        )
+        .bind(racer_number)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// 競艇場別成績
+    pub async fn get_racer_venue_stats(
+        &self,
+        racer_number: i32,
+    ) -> Result<Vec<RacerVenueStats>, sqlx::Error> {
+        sqlx::query_as(
+            r#"
+            SELECT
+                r.venue_code,
+                COUNT(*) as starts,
+                SUM(CASE WHEN rp.place_number = 1 THEN 1 ELSE 0 END) as wins,
+                AVG(CASE WHEN rp.place_number = 1 THEN 1.0 ELSE 0.0 END) as win_rate,
+                AVG(CASE WHEN rp.place_number <= 3 THEN 1.0 ELSE 0.0 END) as top3_rate
+            FROM race_participants rp
+            INNER JOIN races r ON r.id = rp.race_id
+            WHERE rp.racer_number = ? AND r.result_data_json IS NOT NULL
+            GROUP BY r.venue_code
+            ORDER BY starts DESC, r.venue_code
+            "#
+        )
+        .bind(racer_number)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    // ===== オッズの時系列 =====
+
+    /// 1回分の取得結果をスナップショットとして保存
+    pub async fn save_odds_snapshot(&self, snapshots: &[OddsSnapshotRecord]) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        for snapshot in snapshots {
+            sqlx::query(
+                r#"
+                INSERT INTO odds_snapshots (
+                    race_date, venue_code, race_number, captured_at, bet_type, boat_number, odds, odds_max
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+                "#
+            )
+            .bind(&snapshot.race_date)
+            .bind(&snapshot.venue_code)
+            .bind(snapshot.race_number)
+            .bind(&snapshot.captured_at)
+            .bind(&snapshot.bet_type)
+            .bind(snapshot.boat_number)
+            .bind(snapshot.odds)
+            .bind(snapshot.odds_max)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await
+    }
+
+    /// レースのスナップショットを取得時刻順に取得
+    pub async fn get_odds_snapshots(
+        &self,
+        race_date: &str,
+        venue_code: &str,
+        race_number: i32,
+    ) -> Result<Vec<OddsSnapshotRecord>, sqlx::Error> {
+        sqlx::query_as::<_, OddsSnapshotRecord>(
+            r#"
+            SELECT race_date, venue_code, race_number, captured_at, bet_type, boat_number, odds, odds_max
+            FROM odds_snapshots
+            WHERE race_date = ? AND venue_code = ? AND race_number = ?
+            ORDER BY captured_at, bet_type, boat_number
+            "#
+        )
+        .bind(race_date)
+        .bind(venue_code)
+        .bind(race_number)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// 保存済みの出走表から締切時刻を取得
+    pub async fn get_race_closed_at(
+        &self,
+        race_date: &str,
+        venue_code: &str,
+        race_number: i32,
+    ) -> Result<Option<String>, sqlx::Error> {
+        let closed_at: Option<(Option<String>,)> = sqlx::query_as(
+            r#"
+            SELECT json_extract(program_data_json, '$.race_closed_at')
+            FROM races
+            WHERE race_date = ? AND venue_code = ? AND race_number = ? AND program_data_json IS NOT NULL
+            "#
+        )
+        .bind(race_date)
+        .bind(venue_code)
+        .bind(race_number)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(closed_at.and_then(|(closed_at,)| closed_at))
+    }
+
     // ===== Bulk Fetch用ヘルパー関数 =====
 
     /// 指定日付のPreviewsレコード数をカウント（キャッシュチェック用）
@@ -1565,7 +2970,7 @@ impl SqliteRepository {
     /// 指定日付のResultsレコード数をカウント
     pub async fn count_results_by_date(&self, date: &str) -> Result<i64, sqlx::Error> {
         let count: (i64,) = sqlx::query_as(
-            "SELECT COUNT(*) FROM results WHERE date = ?"
+            "SELECT COUNT(*) FROM races WHERE race_date = ? AND result_data_json IS NOT NULL"
         )
         .bind(date)
         .fetch_one(&self.pool)
@@ -0,0 +1,421 @@
+use crate::error::AppError;
+use rand::Rng;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// 最大試行回数を上書きする環境変数
+pub const MAX_ATTEMPTS_ENV: &str = "BORT_MAX_ATTEMPTS";
+
+static GLOBAL_POLICY: OnceLock<Arc<RequestPolicy>> = OnceLock::new();
+
+/// ホストごとのレート制限（トークンバケット）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    /// 1秒あたりに補充されるトークン数
+    pub requests_per_second: f64,
+    /// バケットの容量（連続で送れるリクエスト数）
+    pub burst: u32,
+}
+
+impl RateLimit {
+    pub fn new(requests_per_second: f64, burst: u32) -> Self {
+        RateLimit {
+            requests_per_second,
+            burst: burst.max(1),
+        }
+    }
+}
+
+/// リクエストポリシーの設定
+#[derive(Debug, Clone)]
+pub struct RequestPolicyConfig {
+    /// 1リクエストあたりの最大試行回数（初回を含む）
+    pub max_attempts: u32,
+    /// バックオフの初期待機時間（試行ごとに2倍）
+    pub base_delay: Duration,
+    /// バックオフ・Retry-Afterの上限。これを超える Retry-After は待たずに諦める
+    pub max_delay: Duration,
+    /// ホスト個別の設定がない場合のレート制限
+    pub default_rate: RateLimit,
+    /// ホスト名ごとのレート制限
+    pub host_rates: HashMap<String, RateLimit>,
+}
+
+impl Default for RequestPolicyConfig {
+    fn default() -> Self {
+        let mut host_rates = HashMap::new();
+        // JavaScript描画でブラウザを使うため1秒に1回まで
+        host_rates.insert("kyoteibiyori.com".to_string(), RateLimit::new(1.0, 1));
+        host_rates.insert("www.boatrace.jp".to_string(), RateLimit::new(1.0, 1));
+        // 静的JSON（GitHub Pages）
+        host_rates.insert("boatraceopenapi.github.io".to_string(), RateLimit::new(4.0, 4));
+
+        RequestPolicyConfig {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            default_rate: RateLimit::new(2.0, 2),
+            host_rates,
+        }
+    }
+}
+
+impl RequestPolicyConfig {
+    /// デフォルト設定に環境変数（`BORT_MAX_ATTEMPTS`）を反映
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+        if let Some(max_attempts) = std::env::var(MAX_ATTEMPTS_ENV)
+            .ok()
+            .and_then(|value| value.parse::<u32>().ok())
+        {
+            config.max_attempts = max_attempts.max(1);
+        }
+        config
+    }
+}
+
+struct TokenBucket {
+    rate: RateLimit,
+    tokens: f64,
+    last_refill: Instant,
+    // Retry-After を受けたホストはこの時刻まで送信しない
+    blocked_until: Option<Instant>,
+}
+
+impl TokenBucket {
+    fn new(rate: RateLimit, now: Instant) -> Self {
+        TokenBucket {
+            rate,
+            tokens: rate.burst as f64,
+            last_refill: now,
+            blocked_until: None,
+        }
+    }
+
+    /// トークンを1つ予約し、送信可能になるまでの待機時間を返す
+    ///
+    /// トークンが足りない場合は残量をマイナスにして先の枠を予約するため、
+    /// 複数スレッドから同時に呼ばれても送信間隔が保たれる。
+    /// Retry-After による停止中は停止明けの時刻を起点に予約するため、停止中に来た
+    /// リクエストも明けた後に送信間隔を空けて順に送られる。
+    fn reserve(&mut self, now: Instant) -> Duration {
+        let blocked_wait = self
+            .blocked_until
+            .map(|until| until.saturating_duration_since(now))
+            .unwrap_or(Duration::ZERO);
+        let start = now + blocked_wait;
+
+        // 停止中はトークンを補充しない（補充は停止明け以降の分だけ）
+        if start > self.last_refill {
+            let elapsed = (start - self.last_refill).as_secs_f64();
+            self.tokens =
+                (self.tokens + elapsed * self.rate.requests_per_second).min(self.rate.burst as f64);
+            self.last_refill = start;
+        }
+        self.tokens -= 1.0;
+
+        let token_wait = if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.rate.requests_per_second)
+        };
+
+        blocked_wait + token_wait
+    }
+
+    fn block_for(&mut self, now: Instant, delay: Duration) {
+        let until = now + delay;
+        // 既存の停止期限より後に延びる場合だけ更新する
+        let extends = match self.blocked_until {
+            Some(current) => current < until,
+            None => true,
+        };
+        if extends {
+            self.blocked_until = Some(until);
+        }
+    }
+}
+
+/// 全フェッチ経路で共有するリクエストポリシー
+///
+/// - ホストごとのトークンバケットで送信間隔を制限する
+/// - タイムアウト・5xx・429 は指数バックオフ（ジッター付き）で再試行する
+/// - Retry-After が返された場合はその時間だけ同じホストへの送信を止める
+/// - 試行回数は `max_attempts` まで
+pub struct RequestPolicy {
+    config: RequestPolicyConfig,
+    buckets: Mutex<HashMap<String, TokenBucket>>,
+}
+
+impl RequestPolicy {
+    pub fn new(config: RequestPolicyConfig) -> Self {
+        RequestPolicy {
+            config,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// アプリ全体で共有するポリシー
+    pub fn global() -> Arc<RequestPolicy> {
+        GLOBAL_POLICY
+            .get_or_init(|| Arc::new(RequestPolicy::new(RequestPolicyConfig::from_env())))
+            .clone()
+    }
+
+    /// 同期版: レート制限・再試行を適用して `request` を実行する
+    pub fn execute_blocking<T, F>(&self, url: &str, mut request: F) -> Result<T, AppError>
+    where
+        F: FnMut() -> Result<T, AppError>,
+    {
+        let host = host_of(url);
+        let mut attempt = 1;
+        loop {
+            std::thread::sleep(self.reserve_slot(&host));
+            match request() {
+                Ok(value) => return Ok(value),
+                Err(error) => match self.retry_delay(&host, url, attempt, &error) {
+                    Some(delay) => std::thread::sleep(delay),
+                    None => return Err(error),
+                },
+            }
+            attempt += 1;
+        }
+    }
+
+    /// 非同期版: レート制限・再試行を適用して `request` を実行する
+    pub async fn execute<T, F, Fut>(&self, url: &str, mut request: F) -> Result<T, AppError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, AppError>>,
+    {
+        let host = host_of(url);
+        let mut attempt = 1;
+        loop {
+            tokio::time::sleep(self.reserve_slot(&host)).await;
+            match request().await {
+                Ok(value) => return Ok(value),
+                Err(error) => match self.retry_delay(&host, url, attempt, &error) {
+                    Some(delay) => tokio::time::sleep(delay).await,
+                    None => return Err(error),
+                },
+            }
+            attempt += 1;
+        }
+    }
+
+    fn lock_buckets(&self) -> std::sync::MutexGuard<'_, HashMap<String, TokenBucket>> {
+        self.buckets
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn rate_for(&self, host: &str) -> RateLimit {
+        self.config
+            .host_rates
+            .get(host)
+            .copied()
+            .unwrap_or(self.config.default_rate)
+    }
+
+    /// 送信枠を予約し、送信までの待機時間を返す
+    fn reserve_slot(&self, host: &str) -> Duration {
+        let now = Instant::now();
+        let rate = self.rate_for(host);
+        let mut buckets = self.lock_buckets();
+        buckets
+            .entry(host.to_string())
+            .or_insert_with(|| TokenBucket::new(rate, now))
+            .reserve(now)
+    }
+
+    /// 次の試行までの待機時間。再試行しない場合は None
+    fn retry_delay(
+        &self,
+        host: &str,
+        url: &str,
+        attempt: u32,
+        error: &AppError,
+    ) -> Option<Duration> {
+        if !error.is_transient() || attempt >= self.config.max_attempts {
+            return None;
+        }
+
+        let delay = match error.retry_after() {
+            Some(seconds) => {
+                let delay = Duration::from_secs(seconds);
+                if delay > self.config.max_delay {
+                    eprintln!(
+                        "⚠️ Retry-After が長すぎるため再試行しません: {}秒 ({})",
+                        seconds, url
+                    );
+                    return None;
+                }
+                // 同じホストへの他のリクエストも止める
+                let now = Instant::now();
+                let rate = self.rate_for(host);
+                self.lock_buckets()
+                    .entry(host.to_string())
+                    .or_insert_with(|| TokenBucket::new(rate, now))
+                    .block_for(now, delay);
+                delay
+            }
+            None => self.backoff_delay(attempt),
+        };
+
+        eprintln!(
+            "🔁 再試行します ({}/{}回目, {:.1}秒後): {} - {}",
+            attempt + 1,
+            self.config.max_attempts,
+            delay.as_secs_f64(),
+            url,
+            error
+        );
+        Some(delay)
+    }
+
+    /// 指数バックオフ（上限つき）に 50〜100% のジッターをかける
+    fn backoff_delay(&self, attempt: u32) -> Duration {
+        let exponential = self
+            .config
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)));
+        let capped = exponential.min(self.config.max_delay);
+        capped.mul_f64(rand::thread_rng().gen_range(0.5..=1.0))
+    }
+}
+
+/// URLからホスト名を取り出す（パースできない場合はURL全体をキーにする）
+fn host_of(url: &str) -> String {
+    url::Url::parse(url)
+        .ok()
+        .and_then(|parsed| parsed.host_str().map(|host| host.to_string()))
+        .unwrap_or_else(|| url.to_string())
+}
+
+/// Retry-After ヘッダー（秒数またはHTTP日付）を秒数に変換
+pub fn retry_after_secs(headers: &HeaderMap) -> Option<u64> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(seconds);
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let seconds = (date.timestamp() - chrono::Utc::now().timestamp()).max(0);
+    Some(seconds as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    fn fast_config(max_attempts: u32) -> RequestPolicyConfig {
+        RequestPolicyConfig {
+            max_attempts,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(50),
+            default_rate: RateLimit::new(1000.0, 10),
+            host_rates: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_token_bucket_spacing() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(RateLimit::new(2.0, 1), start);
+
+        // 1つ目はすぐ送れる、2つ目以降は0.5秒ずつ後ろにずれる
+        assert_eq!(bucket.reserve(start), Duration::ZERO);
+        assert_eq!(bucket.reserve(start), Duration::from_millis(500));
+        assert_eq!(bucket.reserve(start), Duration::from_millis(1000));
+
+        // 時間が経てば補充される
+        let later = start + Duration::from_secs(5);
+        assert_eq!(bucket.reserve(later), Duration::ZERO);
+
+        // Retry-After の間は送信しない
+        bucket.block_for(later, Duration::from_secs(3));
+        assert_eq!(bucket.reserve(later + Duration::from_secs(10)), Duration::ZERO);
+        assert!(bucket.reserve(later) >= Duration::from_secs(3));
+    }
+
+    #[test]
+    fn test_token_bucket_queues_reservations_after_block() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(RateLimit::new(2.0, 1), start);
+        assert_eq!(bucket.reserve(start), Duration::ZERO);
+
+        // 停止中に来たリクエストは停止明けから0.5秒ずつずらして送る
+        bucket.block_for(start, Duration::from_secs(3));
+        let during = start + Duration::from_secs(1);
+        let waits: Vec<Duration> = (0..4).map(|_| bucket.reserve(during)).collect();
+        assert_eq!(
+            waits,
+            vec![
+                Duration::from_millis(2000),
+                Duration::from_millis(2500),
+                Duration::from_millis(3000),
+                Duration::from_millis(3500),
+            ]
+        );
+
+        // 停止中に時間が経ってもトークンは貯まらず、予約済みの枠の後ろに並ぶ
+        let later = start + Duration::from_secs(2);
+        assert_eq!(bucket.reserve(later), Duration::from_millis(3000));
+    }
+
+    #[test]
+    fn test_retries_transient_errors_until_success() {
+        let policy = RequestPolicy::new(fast_config(3));
+        let calls = Cell::new(0);
+
+        let result = policy.execute_blocking("https://example.com/a", || {
+            calls.set(calls.get() + 1);
+            if calls.get() < 3 {
+                Err(AppError::http_status("https://example.com/a", 503, None))
+            } else {
+                Ok("ok")
+            }
+        });
+
+        assert_eq!(result, Ok("ok"));
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[test]
+    fn test_does_not_retry_permanent_errors() {
+        let policy = RequestPolicy::new(fast_config(5));
+        let calls = Cell::new(0);
+
+        let result: Result<(), AppError> = policy.execute_blocking("https://example.com/b", || {
+            calls.set(calls.get() + 1);
+            Err(AppError::http_status("https://example.com/b", 404, None))
+        });
+        assert!(result.is_err());
+        assert_eq!(calls.get(), 1);
+
+        // Retry-After が上限を超える場合も待たずに諦める
+        calls.set(0);
+        let result: Result<(), AppError> = policy.execute_blocking("https://example.com/c", || {
+            calls.set(calls.get() + 1);
+            Err(AppError::http_status("https://example.com/c", 429, Some(3600)))
+        });
+        assert!(result.is_err());
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn test_retry_after_header() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, "120".parse().unwrap());
+        assert_eq!(retry_after_secs(&headers), Some(120));
+
+        headers.insert(RETRY_AFTER, "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap());
+        assert_eq!(retry_after_secs(&headers), Some(0));
+
+        headers.insert(RETRY_AFTER, "soon".parse().unwrap());
+        assert_eq!(retry_after_secs(&headers), None);
+    }
+}
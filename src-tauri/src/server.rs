@@ -0,0 +1,697 @@
+//! ローカル HTTP サーバーモード（`server` フィーチャー）
+//!
+//! Tauri コマンドを呼べないノートブックや外部ツール向けに、`commands::open_api` /
+//! `commands::scraping` と同じ操作を JSON の REST API として公開する。
+//! `bort-cli serve` で起動し、デスクトップアプリと同じ DB を共有できる。
+//!
+//! エラーは Tauri コマンドと同じ `{code, message, details}` 形式で返す。
+
+use crate::error::AppError;
+use crate::models::job::{BulkJob, JobKind};
+use crate::models::open_api::{
+    ApiDataType, CombinationPayoutStats, DataSummaryRow, PayoutStats, RaceParticipantRecord, RaceRecord, RaceResult,
+    SearchParams,
+};
+use crate::models::race::{OddsData, RaceData};
+use crate::models::motor::MotorRanking;
+use crate::models::race_view::{EscapeCalibrationReport, RaceView};
+use crate::models::racer::RacerProfile;
+use crate::parse::official::RaceCard;
+use crate::repositories::local_db;
+use crate::services::job_service::JobService;
+use crate::services::open_api_service::{self, OpenApiService};
+use crate::services::schedule_service::ScheduleService;
+use crate::services::scraping_service::ScrapingService;
+use axum::extract::{FromRequest, FromRequestParts, Path, Query, Request, State};
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::sync::Arc;
+
+/// サーバーで共有するサービス
+pub struct ServerState {
+    open_api: Arc<OpenApiService>,
+    jobs: Arc<JobService>,
+    scraping: Arc<ScrapingService>,
+}
+
+type SharedState = Arc<ServerState>;
+type ApiResult<T> = Result<Json<T>, AppError>;
+
+impl ServerState {
+    /// Open API サービスとジョブ管理を初期化（db_path 省略時は data/open_api.db）
+    ///
+    /// スクレイピングのキャッシュとジョブ管理は Open API サービスの接続プールを共有する。
+    pub async fn new(db_path: Option<&str>) -> Result<Self, AppError> {
+        let open_api = OpenApiService::new(db_path).await?;
+        open_api
+            .scrape_cache()
+            .import_legacy_json_once(local_db::LEGACY_JSON_PATH)
+            .await;
+        Self::with_service(open_api).await
+    }
+
+    /// 初期化済みの Open API サービスから生成
+    pub async fn with_service(open_api: OpenApiService) -> Result<Self, AppError> {
+        let jobs = JobService::new(open_api.job_repository()).await?;
+        Ok(Self {
+            scraping: Arc::new(ScrapingService::new().with_cache(open_api.scrape_cache())),
+            open_api: Arc::new(open_api),
+            jobs: Arc::new(jobs),
+        })
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let status = match &self {
+            AppError::InvalidInput { .. } => StatusCode::BAD_REQUEST,
+            AppError::NotInitialized(_) => StatusCode::SERVICE_UNAVAILABLE,
+            AppError::NetworkError { .. }
+            | AppError::ElementNotFound { .. }
+            | AppError::ParseError { .. } => StatusCode::BAD_GATEWAY,
+            AppError::DbError(_) | AppError::IoError { .. } | AppError::Internal(_) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        };
+        (status, Json(self)).into_response()
+    }
+}
+
+// ===== リクエストの抽出 =====
+//
+// axum 標準の抽出エラーはプレーンテキストになるため、AppError（InvalidInput）に変換して
+// 他のエラーと同じ JSON で返す。
+
+/// クエリ文字列
+struct ApiQuery<T>(T);
+
+/// JSON ボディ
+struct ApiJson<T>(T);
+
+/// パスパラメータ
+struct ApiPath<T>(T);
+
+impl<T, S> FromRequestParts<S> for ApiQuery<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        Query::<T>::from_request_parts(parts, state)
+            .await
+            .map(|Query(value)| ApiQuery(value))
+            .map_err(|e| AppError::invalid_input("query", e.body_text()))
+    }
+}
+
+impl<T, S> FromRequest<S> for ApiJson<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        Json::<T>::from_request(req, state)
+            .await
+            .map(|Json(value)| ApiJson(value))
+            .map_err(|e| AppError::invalid_input("body", e.body_text()))
+    }
+}
+
+impl<T, S> FromRequestParts<S> for ApiPath<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        Path::<T>::from_request_parts(parts, state)
+            .await
+            .map(|Path(value)| ApiPath(value))
+            .map_err(|e| AppError::invalid_input("path", e.body_text()))
+    }
+}
+
+/// ルーティング
+pub fn router(state: ServerState) -> Router {
+    Router::new()
+        // Open API データ
+        .route("/api/summary", get(data_summary))
+        .route("/api/races/search", post(search_races))
+        .route("/api/races/high-payout", get(high_payout_races))
+        .route("/api/stats/payout", get(payout_statistics))
+        .route("/api/stats/payout/combinations", get(combination_payout_stats))
+        .route("/api/races/view", get(race_view))
+        .route("/api/stats/escape", get(escape_calibration))
+        .route("/api/racers/{racer_number}", get(racer_profile))
+        .route("/api/motors/{venue_code}", get(motor_ranking))
+        .route("/api/export/csv-v3", post(export_csv_v3))
+        // スクレイピング（レース詳細）
+        .route("/api/scraping/race", get(scrape_race))
+        .route("/api/scraping/odds", get(scrape_odds))
+        .route("/api/official/racelist", get(official_race_card))
+        // 一括取得ジョブ
+        .route("/api/jobs", get(list_jobs).post(start_bulk_job))
+        .route("/api/jobs/{id}", get(get_job))
+        .route("/api/jobs/{id}/pause", post(pause_job))
+        .route("/api/jobs/{id}/cancel", post(cancel_job))
+        .route("/api/jobs/{id}/resume", post(resume_job))
+        .with_state(Arc::new(state))
+}
+
+/// 指定アドレスで待ち受ける（終了するまで戻らない）
+pub async fn serve(addr: &str, db_path: Option<&str>) -> Result<(), AppError> {
+    let state = ServerState::new(db_path).await?;
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    eprintln!("🌐 HTTP server listening on http://{}", listener.local_addr()?);
+    axum::serve(listener, router(state)).await?;
+    Ok(())
+}
+
+// ===== Open API データ =====
+
+async fn data_summary(
+    State(state): State<SharedState>,
+) -> ApiResult<Vec<DataSummaryRow>> {
+    Ok(Json(state.open_api.get_data_summary().await?))
+}
+
+async fn search_races(
+    State(state): State<SharedState>,
+    ApiJson(params): ApiJson<SearchParams>,
+) -> ApiResult<Vec<(RaceRecord, Vec<RaceParticipantRecord>)>> {
+    Ok(Json(state.open_api.search_races_advanced(params).await?))
+}
+
+#[derive(Debug, Deserialize)]
+struct HighPayoutQuery {
+    min_payout: i32,
+    payout_type: String,
+    limit: Option<i32>,
+}
+
+async fn high_payout_races(
+    State(state): State<SharedState>,
+    ApiQuery(query): ApiQuery<HighPayoutQuery>,
+) -> ApiResult<Vec<RaceResult>> {
+    if !["win", "trifecta", "exacta", "place"].contains(&query.payout_type.as_str()) {
+        return Err(AppError::invalid_input(
+            "payout_type",
+            format!(
+                "Invalid payout_type: '{}'. Expected 'win', 'trifecta', 'exacta', or 'place'",
+                query.payout_type
+            ),
+        ));
+    }
+    if query.min_payout < 0 {
+        return Err(AppError::invalid_input("min_payout", "min_payout must be non-negative"));
+    }
+
+    let races = state
+        .open_api
+        .search_high_payout_races(query.min_payout, query.payout_type, query.limit)
+        .await?;
+    Ok(Json(races))
+}
+
+async fn payout_statistics(
+    State(state): State<SharedState>,
+) -> ApiResult<PayoutStats> {
+    Ok(Json(state.open_api.get_payout_statistics().await?))
+}
+
+#[derive(Debug, Deserialize)]
+struct CombinationStatsQuery {
+    bet_type: String,
+    venue_code: Option<String>,
+    limit: Option<i32>,
+}
+
+async fn combination_payout_stats(
+    State(state): State<SharedState>,
+    ApiQuery(query): ApiQuery<CombinationStatsQuery>,
+) -> ApiResult<Vec<CombinationPayoutStats>> {
+    let stats = state
+        .open_api
+        .get_combination_payout_stats(&query.bet_type, query.venue_code.as_deref(), query.limit)
+        .await?;
+    Ok(Json(stats))
+}
+
+#[derive(Debug, Deserialize)]
+struct RaceViewQuery {
+    date: String,
+    venue: String,
+    race: u32,
+}
+
+async fn race_view(
+    State(state): State<SharedState>,
+    ApiQuery(query): ApiQuery<RaceViewQuery>,
+) -> ApiResult<RaceView> {
+    let view = state
+        .open_api
+        .get_race_view(&query.date, &query.venue, query.race)
+        .await?;
+    Ok(Json(view))
+}
+
+#[derive(Debug, Deserialize)]
+struct EscapeCalibrationQuery {
+    date_from: Option<String>,
+    date_to: Option<String>,
+    venue_code: Option<String>,
+    rate_period: Option<String>,
+}
+
+async fn escape_calibration(
+    State(state): State<SharedState>,
+    ApiQuery(query): ApiQuery<EscapeCalibrationQuery>,
+) -> ApiResult<EscapeCalibrationReport> {
+    let report = state
+        .open_api
+        .get_escape_calibration(
+            query.date_from.as_deref(),
+            query.date_to.as_deref(),
+            query.venue_code.as_deref(),
+            query.rate_period.as_deref(),
+        )
+        .await?;
+    Ok(Json(report))
+}
+
+async fn racer_profile(
+    State(state): State<SharedState>,
+    ApiPath(racer_number): ApiPath<i32>,
+) -> ApiResult<RacerProfile> {
+    Ok(Json(state.open_api.get_racer_profile(racer_number).await?))
+}
+
+#[derive(Debug, Deserialize)]
+struct MotorRankingQuery {
+    season: Option<String>,
+    date_from: Option<String>,
+    date_to: Option<String>,
+    min_starts: Option<i32>,
+    limit: Option<i32>,
+}
+
+async fn motor_ranking(
+    State(state): State<SharedState>,
+    ApiPath(venue_code): ApiPath<String>,
+    ApiQuery(query): ApiQuery<MotorRankingQuery>,
+) -> ApiResult<MotorRanking> {
+    let ranking = state
+        .open_api
+        .get_motor_ranking(
+            &venue_code,
+            query.season.as_deref(),
+            query.date_from.as_deref(),
+            query.date_to.as_deref(),
+            query.min_starts,
+            query.limit,
+        )
+        .await?;
+    Ok(Json(ranking))
+}
+
+#[derive(Debug, Deserialize)]
+struct ExportRequest {
+    output_dir: String,
+}
+
+async fn export_csv_v3(
+    State(state): State<SharedState>,
+    ApiJson(request): ApiJson<ExportRequest>,
+) -> ApiResult<serde_json::Value> {
+    std::fs::create_dir_all(&request.output_dir)
+        .map_err(|e| AppError::io(format!("Failed to create output directory: {}", e)))?;
+    let (race_count, participant_count) = state.open_api.export_to_csv_v3(&request.output_dir).await?;
+    Ok(Json(serde_json::json!({
+        "output_dir": request.output_dir,
+        "race_count": race_count,
+        "participant_count": participant_count,
+    })))
+}
+
+// ===== スクレイピング =====
+
+#[derive(Debug, Deserialize)]
+struct RaceQuery {
+    date: String, // YYYY-MM-DD
+    venue: u32,
+    race: u32,
+    /// キャッシュの鮮度にかかわらず取得し直す
+    #[serde(default)]
+    force_refresh: bool,
+}
+
+async fn scrape_race(
+    State(state): State<SharedState>,
+    ApiQuery(query): ApiQuery<RaceQuery>,
+) -> ApiResult<RaceData> {
+    let race_data = state
+        .scraping
+        .get_race_info(&query.date, query.race, query.venue, query.force_refresh)
+        .await?;
+    if let Err(e) = state.open_api.update_racers_from_race_data(&query.date, &race_data).await {
+        eprintln!("⚠️ 選手マスタの更新に失敗: {}", e);
+    }
+    Ok(Json(race_data))
+}
+
+/// 公式サイトの出走表（date は YYYYMMDD / YYYY-MM-DD、venue は "01" / "1"）
+async fn official_race_card(ApiQuery(query): ApiQuery<RaceViewQuery>) -> ApiResult<RaceCard> {
+    let card = ScheduleService::new()
+        .get_race_card(&query.date, &query.venue, query.race)
+        .await?;
+    Ok(Json(card))
+}
+
+async fn scrape_odds(
+    State(state): State<SharedState>,
+    ApiQuery(query): ApiQuery<RaceQuery>,
+) -> ApiResult<OddsData> {
+    // 締切時刻は保存済みの出走表から（なければレース日の終わりを締切とみなす）
+    let closed_at = state
+        .open_api
+        .get_race_closed_at(&query.date, &format!("{:02}", query.venue), query.race as i32)
+        .await?;
+    let odds = state
+        .scraping
+        .get_win_place_odds(
+            &query.date,
+            query.race,
+            query.venue,
+            query.force_refresh,
+            closed_at.as_deref(),
+        )
+        .await?;
+    Ok(Json(odds))
+}
+
+// ===== 一括取得ジョブ =====
+
+#[derive(Debug, Deserialize)]
+struct BulkJobRequest {
+    /// "previews" | "results" | "programs" | "all"
+    data_type: String,
+    start_date: String, // YYYYMMDD
+    end_date: String,   // YYYYMMDD
+    concurrency: Option<usize>,
+}
+
+async fn list_jobs(State(state): State<SharedState>) -> ApiResult<Vec<BulkJob>> {
+    Ok(Json(state.jobs.list_jobs().await?))
+}
+
+async fn get_job(State(state): State<SharedState>, ApiPath(id): ApiPath<i64>) -> ApiResult<BulkJob> {
+    Ok(Json(state.jobs.get_job(id).await?))
+}
+
+/// Open API の期間一括取得ジョブを登録し、バックグラウンドで実行する
+///
+/// 登録直後のジョブを返すので、進捗は `GET /api/jobs/{id}` で確認する。
+async fn start_bulk_job(
+    State(state): State<SharedState>,
+    ApiJson(request): ApiJson<BulkJobRequest>,
+) -> Result<(StatusCode, Json<BulkJob>), AppError> {
+    for (field, value) in [("start_date", &request.start_date), ("end_date", &request.end_date)] {
+        if value.len() != 8 || !value.chars().all(|c| c.is_numeric()) {
+            return Err(AppError::invalid_input(
+                field,
+                format!("Invalid {} format. Expected YYYYMMDD", field),
+            ));
+        }
+    }
+    if request.start_date > request.end_date {
+        return Err(AppError::invalid_input(
+            "start_date",
+            "start_date must be less than or equal to end_date",
+        ));
+    }
+
+    let job = if request.data_type == "all" {
+        let concurrency = request
+            .concurrency
+            .unwrap_or(open_api_service::DEFAULT_BULK_CONCURRENCY);
+        if concurrency == 0 {
+            return Err(AppError::invalid_input("concurrency", "concurrency must be at least 1"));
+        }
+        OpenApiService::create_all_data_bulk_job(
+            &state.jobs,
+            &request.start_date,
+            &request.end_date,
+            concurrency,
+        )
+        .await?
+    } else {
+        let data_type = ApiDataType::from_name(&request.data_type).ok_or_else(|| {
+            AppError::invalid_input(
+                "data_type",
+                format!("Unknown data_type: {}", request.data_type),
+            )
+        })?;
+        OpenApiService::create_bulk_job(&state.jobs, data_type, &request.start_date, &request.end_date)
+            .await?
+    };
+
+    spawn_job(&state, job.id);
+    Ok((StatusCode::ACCEPTED, Json(job)))
+}
+
+async fn pause_job(State(state): State<SharedState>, ApiPath(id): ApiPath<i64>) -> ApiResult<BulkJob> {
+    Ok(Json(state.jobs.pause(id).await?))
+}
+
+async fn cancel_job(State(state): State<SharedState>, ApiPath(id): ApiPath<i64>) -> ApiResult<BulkJob> {
+    Ok(Json(state.jobs.cancel(id).await?))
+}
+
+/// 一時停止・中断したジョブをバックグラウンドで再開する
+async fn resume_job(
+    State(state): State<SharedState>,
+    ApiPath(id): ApiPath<i64>,
+) -> Result<(StatusCode, Json<BulkJob>), AppError> {
+    let job = state.jobs.get_job(id).await?;
+    if !job.status.is_resumable() {
+        return Err(AppError::invalid_input(
+            "job_id",
+            format!("Job {} cannot be resumed (status: {})", id, job.status.as_str()),
+        ));
+    }
+
+    match job.kind {
+        JobKind::OpenApiBulk => spawn_job(&state, id),
+        JobKind::ScrapingBulk => {
+            let jobs = state.jobs.clone();
+            let scraping = state.scraping.clone();
+            tokio::spawn(async move {
+                if let Err(e) = scraping.run_bulk_job(None, &jobs, id).await {
+                    eprintln!("❌ Bulk job #{} failed: {}", id, e);
+                }
+            });
+        }
+    }
+    Ok((StatusCode::ACCEPTED, Json(job)))
+}
+
+fn spawn_job(state: &ServerState, job_id: i64) {
+    let open_api = state.open_api.clone();
+    let jobs = state.jobs.clone();
+    tokio::spawn(async move {
+        if let Err(e) = open_api.run_bulk_job(None, &jobs, job_id).await {
+            eprintln!("❌ Bulk job #{} failed: {}", job_id, e);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::job::JobStatus;
+    use crate::test_support::{seeded_service, FIXTURE_DATE, FIXTURE_RACE_COUNT};
+    use axum::body::Body;
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    async fn send(app: &Router, method: &str, uri: &str, body: Option<Value>) -> (StatusCode, Value) {
+        let request = axum::http::Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .body(body.map_or_else(Body::empty, |body| Body::from(body.to_string())))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+    }
+
+    async fn wait_for_job(app: &Router, id: i64, status: &str) -> Value {
+        for _ in 0..100 {
+            let (_, job) = send(app, "GET", &format!("/api/jobs/{}", id), None).await;
+            if job["status"] == status {
+                return job;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        panic!("job #{} did not become {}", id, status);
+    }
+
+    fn assert_invalid_input(status: StatusCode, body: &Value, field: &str) {
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+        assert_eq!(body["code"], "INVALID_INPUT");
+        assert_eq!(body["details"]["field"], field, "{}", body);
+        assert!(body["message"].as_str().is_some_and(|m| !m.is_empty()));
+    }
+
+    #[test]
+    fn test_error_status_codes() {
+        let response = AppError::invalid_input("date", "bad").into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = AppError::http_status("https://example.com", 503, None).into_response();
+        assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+
+        let response = AppError::db("locked").into_response();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[tokio::test]
+    async fn test_summary_and_search_routes() {
+        let (service, _server, path) = seeded_service("server_routes").await;
+        let app = router(ServerState::with_service(service).await.unwrap());
+
+        let (status, summary) = send(&app, "GET", "/api/summary", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(!summary.as_array().unwrap().is_empty());
+
+        let (status, races) = send(
+            &app,
+            "POST",
+            "/api/races/search",
+            Some(json!({ "date_from": FIXTURE_DATE, "date_to": FIXTURE_DATE })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let races = races.as_array().unwrap();
+        assert_eq!(races.len(), FIXTURE_RACE_COUNT);
+        assert_eq!(races[0][0]["race_date"], FIXTURE_DATE, "{}", races[0]);
+
+        let (status, races) = send(
+            &app,
+            "POST",
+            "/api/races/search",
+            Some(json!({ "venue_code": "99" })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(races.as_array().unwrap().is_empty());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_parameters_return_app_error_json() {
+        let (service, _server, path) = seeded_service("server_invalid").await;
+        let app = router(ServerState::with_service(service).await.unwrap());
+
+        // 型の合わないクエリ・必須パラメータの欠落
+        let (status, body) = send(
+            &app,
+            "GET",
+            "/api/races/high-payout?min_payout=abc&payout_type=trifecta&start_date=20251228&end_date=20251228",
+            None,
+        )
+        .await;
+        assert_invalid_input(status, &body, "query");
+        let (status, body) = send(&app, "GET", "/api/stats/payout/combinations", None).await;
+        assert_invalid_input(status, &body, "query");
+
+        // パス・ボディの解釈エラー
+        let (status, body) = send(&app, "GET", "/api/jobs/abc", None).await;
+        assert_invalid_input(status, &body, "path");
+        let (status, body) =
+            send(&app, "POST", "/api/races/search", Some(json!({ "racer_number": "x" }))).await;
+        assert_invalid_input(status, &body, "body");
+
+        // ハンドラーでの検証エラー
+        let (status, body) = send(
+            &app,
+            "POST",
+            "/api/jobs",
+            Some(json!({ "data_type": "previews", "start_date": "2025-12-28", "end_date": FIXTURE_DATE })),
+        )
+        .await;
+        assert_invalid_input(status, &body, "start_date");
+        let (status, body) = send(
+            &app,
+            "POST",
+            "/api/jobs",
+            Some(json!({ "data_type": "odds", "start_date": FIXTURE_DATE, "end_date": FIXTURE_DATE })),
+        )
+        .await;
+        assert_invalid_input(status, &body, "data_type");
+        let (status, body) = send(&app, "GET", "/api/jobs/9999", None).await;
+        assert_invalid_input(status, &body, "job_id");
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_job_start_pause_and_resume_routes() {
+        let (service, _server, path) = seeded_service("server_jobs").await;
+        let state = ServerState::with_service(service).await.unwrap();
+        let jobs = state.jobs.clone();
+        let app = router(state);
+
+        // 登録したジョブはバックグラウンドで実行される（保存済みの日付なのでスキップで完了）
+        let (status, job) = send(
+            &app,
+            "POST",
+            "/api/jobs",
+            Some(json!({ "data_type": "previews", "start_date": FIXTURE_DATE, "end_date": FIXTURE_DATE })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::ACCEPTED, "{}", job);
+        let started = job["id"].as_i64().unwrap();
+        let job = wait_for_job(&app, started, "completed").await;
+        assert_eq!(job["skipped_units"], 1, "{}", job);
+
+        // 未実行のジョブを一時停止してから再開する
+        let pending =
+            OpenApiService::create_bulk_job(&jobs, ApiDataType::Results, FIXTURE_DATE, FIXTURE_DATE)
+                .await
+                .unwrap();
+        let (status, job) = send(&app, "POST", &format!("/api/jobs/{}/pause", pending.id), None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(job["status"], "paused");
+        assert_eq!(jobs.get_job(pending.id).await.unwrap().status, JobStatus::Paused);
+
+        let (status, _) = send(&app, "POST", &format!("/api/jobs/{}/resume", pending.id), None).await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+        wait_for_job(&app, pending.id, "completed").await;
+
+        // 完了したジョブは再開・一時停止できない
+        let (status, body) = send(&app, "POST", &format!("/api/jobs/{}/resume", pending.id), None).await;
+        assert_invalid_input(status, &body, "job_id");
+        let (status, body) = send(&app, "POST", &format!("/api/jobs/{}/pause", pending.id), None).await;
+        assert_invalid_input(status, &body, "job_id");
+
+        let (status, list) = send(&app, "GET", "/api/jobs", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(list.as_array().unwrap().len() >= 2);
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
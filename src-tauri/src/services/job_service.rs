@@ -0,0 +1,337 @@
+use crate::error::AppError;
+use crate::models::job::{BulkJob, JobKind, JobStatus, UnitStatus};
+use crate::repositories::job_repository::JobRepository;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
+
+const SIGNAL_NONE: u8 = 0;
+const SIGNAL_PAUSE: u8 = 1;
+const SIGNAL_CANCEL: u8 = 2;
+
+type RunningJobs = Arc<Mutex<HashMap<i64, Arc<AtomicU8>>>>;
+
+/// 一括取得ジョブの管理
+///
+/// ジョブの作業単位と進捗は SQLite に保存し、実行中ジョブへの一時停止・中止要求は
+/// メモリ上のシグナルで伝える。実行側は作業単位の区切りごとに [`JobRun::stop_requested`] を確認する。
+pub struct JobService {
+    repo: JobRepository,
+    running: RunningJobs,
+}
+
+/// 実行中ジョブのハンドル（破棄されると実行中リストから外れる）
+pub struct JobRun {
+    job_id: i64,
+    signal: Arc<AtomicU8>,
+    running: RunningJobs,
+}
+
+impl JobRun {
+    /// 一時停止・中止が要求されていれば、ジョブの次の状態を返す
+    pub fn stop_requested(&self) -> Option<JobStatus> {
+        match self.signal.load(Ordering::SeqCst) {
+            SIGNAL_PAUSE => Some(JobStatus::Paused),
+            SIGNAL_CANCEL => Some(JobStatus::Cancelled),
+            _ => None,
+        }
+    }
+}
+
+impl Drop for JobRun {
+    fn drop(&mut self) {
+        lock_running(&self.running).remove(&self.job_id);
+    }
+}
+
+fn lock_running(running: &RunningJobs) -> std::sync::MutexGuard<'_, HashMap<i64, Arc<AtomicU8>>> {
+    running
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+impl JobService {
+    /// サービスの初期化（リポジトリは [`OpenApiService::job_repository`] で Open API と同じ接続プールから作る）
+    pub async fn new(repo: JobRepository) -> Result<Self, AppError> {
+        // 前回終了時に実行中だったジョブは再開できるよう一時停止扱いにする
+        let interrupted = repo.pause_interrupted_jobs().await?;
+        if interrupted > 0 {
+            eprintln!("⏸️ 中断されたジョブ {} 件を一時停止にしました", interrupted);
+        }
+
+        Ok(Self {
+            repo,
+            running: Arc::new(Mutex::new(HashMap::new())),
+        })
+    }
+
+    /// ジョブと作業単位を登録
+    pub async fn create_job<P: Serialize, U: Serialize>(
+        &self,
+        kind: JobKind,
+        params: &P,
+        units: &[U],
+    ) -> Result<BulkJob, AppError> {
+        let params_json = serde_json::to_string(params)?;
+        let unit_payloads = units
+            .iter()
+            .map(serde_json::to_string)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let job_id = self.repo.create_job(kind, &params_json, &unit_payloads).await?;
+        eprintln!("🗂️ ジョブ #{} を登録しました（{} 件）", job_id, unit_payloads.len());
+        self.get_job(job_id).await
+    }
+
+    pub async fn get_job(&self, job_id: i64) -> Result<BulkJob, AppError> {
+        self.repo
+            .get_job(job_id)
+            .await?
+            .ok_or_else(|| AppError::invalid_input("job_id", format!("Job not found: {}", job_id)))
+    }
+
+    pub async fn list_jobs(&self) -> Result<Vec<BulkJob>, AppError> {
+        Ok(self.repo.list_jobs().await?)
+    }
+
+    /// ジョブの実行を開始（再開）する
+    pub async fn start(&self, job_id: i64) -> Result<JobRun, AppError> {
+        let job = self.get_job(job_id).await?;
+        // Running のままなのは異常終了したジョブ（このプロセスで実行中でなければ再開可）
+        let resumable = job.status.is_resumable() || job.status == JobStatus::Running;
+        if !resumable {
+            return Err(AppError::invalid_input(
+                "job_id",
+                format!("Job {} cannot be resumed (status: {})", job_id, job.status.as_str()),
+            ));
+        }
+
+        let signal = Arc::new(AtomicU8::new(SIGNAL_NONE));
+        {
+            let mut running = lock_running(&self.running);
+            if running.contains_key(&job_id) {
+                return Err(AppError::invalid_input(
+                    "job_id",
+                    format!("Job {} is already running", job_id),
+                ));
+            }
+            running.insert(job_id, signal.clone());
+        }
+
+        let run = JobRun {
+            job_id,
+            signal,
+            running: self.running.clone(),
+        };
+        self.repo.set_status(job_id, JobStatus::Running).await?;
+        Ok(run)
+    }
+
+    /// 実行を終えたジョブの状態を保存
+    pub async fn finish(&self, run: JobRun, status: JobStatus) -> Result<BulkJob, AppError> {
+        let job_id = run.job_id;
+        self.repo.set_status(job_id, status).await?;
+        drop(run);
+        self.get_job(job_id).await
+    }
+
+    /// 実行中のエラーでジョブを一時停止として保存し、元のエラーを返す
+    ///
+    /// `?` で途中終了したジョブが Running のまま残らないよう、実行側は処理結果をこれに通す。
+    /// 処理済みの作業単位は保存されているので、再開すると未処理分から続けられる。
+    pub async fn pause_on_error<T>(
+        &self,
+        run: &JobRun,
+        result: Result<T, AppError>,
+    ) -> Result<T, AppError> {
+        if let Err(e) = &result {
+            eprintln!("⏸️ ジョブ #{} をエラーで一時停止: {}", run.job_id, e);
+            if let Err(status_error) = self.repo.set_status(run.job_id, JobStatus::Paused).await {
+                eprintln!("⚠️ ジョブ #{} の状態を保存できません: {}", run.job_id, status_error);
+            }
+        }
+        result
+    }
+
+    /// 一時停止を要求する（実行中なら現在の作業単位の完了後に止まる）
+    pub async fn pause(&self, job_id: i64) -> Result<BulkJob, AppError> {
+        self.request_stop(job_id, SIGNAL_PAUSE, JobStatus::Paused).await
+    }
+
+    /// 中止を要求する（未処理の作業単位は実行されない）
+    pub async fn cancel(&self, job_id: i64) -> Result<BulkJob, AppError> {
+        self.request_stop(job_id, SIGNAL_CANCEL, JobStatus::Cancelled).await
+    }
+
+    async fn request_stop(
+        &self,
+        job_id: i64,
+        signal: u8,
+        status: JobStatus,
+    ) -> Result<BulkJob, AppError> {
+        let job = self.get_job(job_id).await?;
+        if job.status.is_finished() {
+            return Err(AppError::invalid_input(
+                "job_id",
+                format!("Job {} is already {}", job_id, job.status.as_str()),
+            ));
+        }
+
+        let running_signal = lock_running(&self.running).get(&job_id).cloned();
+        match running_signal {
+            // 実行中: ランナーが次の作業単位の前に状態を更新する
+            Some(running_signal) => running_signal.store(signal, Ordering::SeqCst),
+            None => self.repo.set_status(job_id, status).await?,
+        }
+        eprintln!("🛑 ジョブ #{} に {} を要求しました", job_id, status.as_str());
+        self.get_job(job_id).await
+    }
+
+    /// 未処理の作業単位を (seq, 作業内容) で取得
+    pub async fn pending_units<U: DeserializeOwned>(
+        &self,
+        job_id: i64,
+    ) -> Result<Vec<(i64, U)>, AppError> {
+        self.repo
+            .pending_units(job_id)
+            .await?
+            .into_iter()
+            .map(|unit| Ok((unit.seq, serde_json::from_str(&unit.payload_json)?)))
+            .collect()
+    }
+
+    /// 失敗した作業単位を (作業内容, エラー) で取得
+    pub async fn failed_units<U: DeserializeOwned>(
+        &self,
+        job_id: i64,
+    ) -> Result<Vec<(U, String)>, AppError> {
+        self.repo
+            .failed_units(job_id)
+            .await?
+            .into_iter()
+            .map(|unit| {
+                Ok((
+                    serde_json::from_str(&unit.payload_json)?,
+                    unit.error.unwrap_or_default(),
+                ))
+            })
+            .collect()
+    }
+
+    /// 作業単位の結果を記録
+    pub async fn complete_unit(
+        &self,
+        job_id: i64,
+        seq: i64,
+        status: UnitStatus,
+        error: Option<&str>,
+    ) -> Result<(), AppError> {
+        Ok(self.repo.update_unit(job_id, seq, status, error).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::job::OpenApiJobUnit;
+    use crate::repositories::sqlite_db::SqliteRepository;
+
+    async fn open(path: &std::path::Path) -> JobService {
+        let db = SqliteRepository::new(path.to_str().unwrap()).await.unwrap();
+        JobService::new(JobRepository::with_pool(db.pool().clone())).await.unwrap()
+    }
+
+    async fn temp_service(name: &str) -> (JobService, std::path::PathBuf) {
+        let path = std::env::temp_dir().join(format!("bort_jobs_{}_{}.db", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        (open(&path).await, path)
+    }
+
+    fn units(dates: &[&str]) -> Vec<OpenApiJobUnit> {
+        dates
+            .iter()
+            .map(|date| OpenApiJobUnit {
+                date: date.to_string(),
+                data_type: None,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_pause_and_resume_keeps_progress() {
+        let (service, path) = temp_service("pause_resume").await;
+        let job = service
+            .create_job(
+                JobKind::OpenApiBulk,
+                &"params",
+                &units(&["20250101", "20250102", "20250103"]),
+            )
+            .await
+            .unwrap();
+        assert_eq!(job.status, JobStatus::Pending);
+        assert_eq!(job.total_units, 3);
+
+        // 1件処理したところで一時停止を要求
+        let run = service.start(job.id).await.unwrap();
+        assert!(service.start(job.id).await.is_err(), "二重実行は不可");
+        let pending = service.pending_units::<OpenApiJobUnit>(job.id).await.unwrap();
+        service
+            .complete_unit(job.id, pending[0].0, UnitStatus::Done, None)
+            .await
+            .unwrap();
+        service.pause(job.id).await.unwrap();
+        assert_eq!(run.stop_requested(), Some(JobStatus::Paused));
+        let paused = service.finish(run, JobStatus::Paused).await.unwrap();
+        assert_eq!(paused.status, JobStatus::Paused);
+        assert_eq!(paused.done_units, 1);
+
+        // 再開すると未処理の2件だけが残っている
+        let run = service.start(job.id).await.unwrap();
+        let pending = service.pending_units::<OpenApiJobUnit>(job.id).await.unwrap();
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].1.date, "20250102");
+        service
+            .complete_unit(job.id, pending[0].0, UnitStatus::Failed, Some("HTTP 500"))
+            .await
+            .unwrap();
+        service
+            .complete_unit(job.id, pending[1].0, UnitStatus::Skipped, None)
+            .await
+            .unwrap();
+        let done = service.finish(run, JobStatus::Completed).await.unwrap();
+        assert_eq!(done.processed_units(), 3);
+
+        let failed = service.failed_units::<OpenApiJobUnit>(job.id).await.unwrap();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].1, "HTTP 500");
+
+        // 完了したジョブは再開・中止できない
+        assert!(service.start(job.id).await.is_err());
+        assert!(service.cancel(job.id).await.is_err());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_interrupted_job_becomes_paused_on_restart() {
+        let (service, path) = temp_service("interrupted").await;
+        let job = service
+            .create_job(JobKind::ScrapingBulk, &"params", &units(&["20250101"]))
+            .await
+            .unwrap();
+
+        // 実行中のままプロセスが終了した状態を再現
+        let run = service.start(job.id).await.unwrap();
+        std::mem::forget(run);
+        drop(service);
+
+        let restarted = open(&path).await;
+        let job = restarted.get_job(job.id).await.unwrap();
+        assert_eq!(job.status, JobStatus::Paused);
+        assert_eq!(restarted.list_jobs().await.unwrap().len(), 1);
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
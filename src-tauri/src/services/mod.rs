@@ -1,3 +1,5 @@
+pub mod job_service;
+pub mod odds_poller_service;
 pub mod open_api_service;
 pub mod schedule_service;
 pub mod scraping_service;
@@ -0,0 +1,353 @@
+use crate::error::AppError;
+use crate::models::odds_history::{OddsPollRaceStatus, OddsPollTarget, OddsPollerStatus};
+use crate::odds_history;
+use crate::services::open_api_service::OpenApiService;
+use crate::services::scraping_service::ScrapingService;
+use chrono::{NaiveDateTime, Utc};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::Emitter;
+
+/// 取得間隔（秒）のデフォルトと下限
+pub const DEFAULT_POLL_INTERVAL_SECONDS: u64 = 60;
+const MIN_POLL_INTERVAL_SECONDS: u64 = 10;
+
+/// 監視対象レース（締切時刻は解決済み）
+struct PollRace {
+    /// スクレイピング・キャッシュ用の日付（YYYY-MM-DD）
+    scrape_date: String,
+    place_number: u32,
+    race_number: u32,
+    venue_code: String,
+    closed_at: NaiveDateTime,
+}
+
+struct RunningPoller {
+    handle: tokio::task::JoinHandle<()>,
+    status: Arc<Mutex<OddsPollerStatus>>,
+}
+
+/// 単勝・複勝オッズの定期取得
+///
+/// 指定したレースのオッズを一定間隔で取得して odds_snapshots に追記し、締切時刻を過ぎたレースから
+/// 取得をやめる。同時に実行する監視は1つで、開始すると実行中の監視は止める。
+pub struct OddsPollerService {
+    service: Arc<OpenApiService>,
+    scraping: Arc<ScrapingService>,
+    current: Mutex<Option<RunningPoller>>,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+impl OddsPollerService {
+    /// サービスの初期化（Open API サービスと同じDB・接続プールに保存する）
+    pub fn new(service: OpenApiService) -> Self {
+        let scraping = ScrapingService::new().with_cache(service.scrape_cache());
+        Self::with_parts(Arc::new(service), Arc::new(scraping))
+    }
+
+    /// 保存先とオッズの取得手段を指定して生成
+    pub fn with_parts(service: Arc<OpenApiService>, scraping: Arc<ScrapingService>) -> Self {
+        Self {
+            service,
+            scraping,
+            current: Mutex::new(None),
+        }
+    }
+
+    /// 監視を開始（締切時刻が省略されたレースは保存済みの出走表から補う）
+    ///
+    /// 取得のたびに `odds-snapshot` イベントでレースの状態を通知する。
+    pub async fn start(
+        &self,
+        window: Option<tauri::Window>,
+        targets: &[OddsPollTarget],
+        interval_seconds: Option<u64>,
+    ) -> Result<OddsPollerStatus, AppError> {
+        let interval_seconds = interval_seconds.unwrap_or(DEFAULT_POLL_INTERVAL_SECONDS);
+        if interval_seconds < MIN_POLL_INTERVAL_SECONDS {
+            return Err(AppError::invalid_input(
+                "interval_seconds",
+                format!("Interval must be at least {} seconds", MIN_POLL_INTERVAL_SECONDS),
+            ));
+        }
+        if targets.is_empty() {
+            return Err(AppError::invalid_input("races", "No races to poll"));
+        }
+
+        let mut races = Vec::new();
+        for target in targets {
+            races.push(self.resolve_target(target).await?);
+        }
+
+        let status = Arc::new(Mutex::new(OddsPollerStatus {
+            running: true,
+            interval_seconds,
+            started_at: Some(Utc::now().to_rfc3339()),
+            races: races
+                .iter()
+                .map(|race| OddsPollRaceStatus {
+                    race_date: race.scrape_date.replace('-', ""),
+                    venue_code: race.venue_code.clone(),
+                    race_number: race.race_number as i32,
+                    race_closed_at: race.closed_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    snapshot_count: 0,
+                    last_captured_at: None,
+                    last_error: None,
+                    closed: false,
+                })
+                .collect(),
+        }));
+
+        self.stop();
+        eprintln!("⏱️ オッズ監視を開始: {} レース（{} 秒間隔）", races.len(), interval_seconds);
+        let handle = tokio::spawn(Self::poll_loop(
+            Arc::clone(&self.service),
+            Arc::clone(&self.scraping),
+            window,
+            races,
+            Duration::from_secs(interval_seconds),
+            Arc::clone(&status),
+        ));
+
+        let snapshot = lock(&status).clone();
+        *lock(&self.current) = Some(RunningPoller { handle, status });
+        Ok(snapshot)
+    }
+
+    /// 実行中の監視を止めて、止めた時点の状態を返す
+    pub fn stop(&self) -> OddsPollerStatus {
+        let Some(poller) = lock(&self.current).take() else {
+            return OddsPollerStatus::default();
+        };
+        poller.handle.abort();
+        let mut status = lock(&poller.status);
+        if status.running {
+            eprintln!("⏹️ オッズ監視を停止しました");
+        }
+        status.running = false;
+        status.clone()
+    }
+
+    pub fn status(&self) -> OddsPollerStatus {
+        lock(&self.current)
+            .as_ref()
+            .map(|poller| lock(&poller.status).clone())
+            .unwrap_or_default()
+    }
+
+    async fn resolve_target(&self, target: &OddsPollTarget) -> Result<PollRace, AppError> {
+        let digits = target.date.replace('-', "");
+        if digits.len() != 8 || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return Err(AppError::invalid_input("date", format!("Invalid date: {}", target.date)));
+        }
+        let scrape_date = format!("{}-{}-{}", &digits[0..4], &digits[4..6], &digits[6..8]);
+        let venue_code = format!("{:02}", target.place_number);
+        let race_number = target.race_number as i32;
+
+        let closed_at = match &target.race_closed_at {
+            Some(closed_at) => Some(closed_at.clone()),
+            None => {
+                self.service
+                    .get_race_closed_at(&digits, &venue_code, race_number)
+                    .await?
+            }
+        };
+        let closed_at = closed_at
+            .as_deref()
+            .and_then(odds_history::parse_closed_at)
+            .ok_or_else(|| {
+                AppError::invalid_input(
+                    "race_closed_at",
+                    format!(
+                        "No closing time for {}-{}-{}R (save the program or pass race_closed_at)",
+                        digits, venue_code, race_number
+                    ),
+                )
+            })?;
+
+        Ok(PollRace {
+            scrape_date,
+            place_number: target.place_number,
+            race_number: target.race_number,
+            venue_code,
+            closed_at,
+        })
+    }
+
+    async fn poll_loop(
+        service: Arc<OpenApiService>,
+        scraping: Arc<ScrapingService>,
+        window: Option<tauri::Window>,
+        races: Vec<PollRace>,
+        interval: Duration,
+        status: Arc<Mutex<OddsPollerStatus>>,
+    ) {
+        loop {
+            let mut active = 0;
+            for (index, race) in races.iter().enumerate() {
+                if odds_history::now_jst() >= race.closed_at {
+                    let mut status = lock(&status);
+                    if !status.races[index].closed {
+                        status.races[index].closed = true;
+                        eprintln!(
+                            "🏁 締切のためオッズ監視を終了: {}-{}-{}R",
+                            race.scrape_date, race.venue_code, race.race_number
+                        );
+                    }
+                    continue;
+                }
+                active += 1;
+
+                let result = Self::capture(&service, &scraping, race).await;
+                let race_status = {
+                    let mut status = lock(&status);
+                    let race_status = &mut status.races[index];
+                    match result {
+                        Ok(captured_at) => {
+                            race_status.snapshot_count += 1;
+                            race_status.last_captured_at = Some(captured_at);
+                            race_status.last_error = None;
+                        }
+                        Err(e) => {
+                            eprintln!(
+                                "⚠️ オッズ取得エラー {}-{}-{}R: {}",
+                                race.scrape_date, race.venue_code, race.race_number, e
+                            );
+                            race_status.last_error = Some(e.to_string());
+                        }
+                    }
+                    race_status.clone()
+                };
+                if let Some(ref w) = window {
+                    let _ = w.emit("odds-snapshot", race_status);
+                }
+            }
+
+            if active == 0 {
+                break;
+            }
+            tokio::time::sleep(interval).await;
+        }
+
+        lock(&status).running = false;
+        eprintln!("✅ オッズ監視が終了しました（全レース締切）");
+    }
+
+    /// オッズを取得してスナップショットを保存し、取得時刻を返す
+    async fn capture(
+        service: &OpenApiService,
+        scraping: &ScrapingService,
+        race: &PollRace,
+    ) -> Result<String, AppError> {
+        let odds = scraping
+            .refresh_win_place_odds(&race.scrape_date, race.race_number, race.place_number)
+            .await?;
+
+        service
+            .save_odds_snapshot(&race.scrape_date, &race.venue_code, race.race_number as i32, &odds)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::odds_history::OddsPollTarget;
+    use crate::page_fetcher::{PageRequest, ReplayPageFetcher};
+    use crate::repositories::sqlite_db::SqliteRepository;
+    use crate::test_support::seeded_service;
+
+    const WIN_PLACE_ODDS_HTML: &str = r#"<html><body><table class="odds_table">
+<tr><td class="table_top_title">艇</td><td class="table_top_title">選手</td><td class="table_top_title">単勝</td><td class="table_top_title">複勝</td></tr>
+<tr><td class="course1">1</td><td>選手A</td><td>1.8</td><td>1.1-1.6</td></tr>
+<tr><td class="course2">2</td><td>選手B</td><td>5.4</td><td>1.9-3.2</td></tr>
+<tr><td class="course3">3</td><td>選手C</td><td>9.6</td><td>2.4-4.8</td></tr>
+</table></body></html>"#;
+
+    fn target(place_number: u32, race_number: u32, race_closed_at: Option<&str>) -> OddsPollTarget {
+        OddsPollTarget {
+            date: "2025-12-28".to_string(),
+            place_number,
+            race_number,
+            race_closed_at: race_closed_at.map(str::to_string),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_odds_poller_saves_snapshots_until_stopped() {
+        let (service, _server, path) = seeded_service("odds_poller_capture").await;
+        let replay_dir = std::env::temp_dir()
+            .join(format!("bort_replay_odds_poller_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&replay_dir);
+        let replay = ReplayPageFetcher::new(&replay_dir);
+        let request = PageRequest::BiyoriWinPlaceOdds {
+            race_no: 5,
+            place_no: 23,
+            date: "20251228".to_string(),
+        };
+        replay.save_page(&request.url(), WIN_PLACE_ODDS_HTML).unwrap();
+
+        let scraping =
+            ScrapingService::with_fetcher(Arc::new(replay)).with_cache(service.scrape_cache());
+        let poller = OddsPollerService::with_parts(Arc::new(service), Arc::new(scraping));
+
+        // 締切前のレースは開始直後に1回取得して odds_snapshots に保存する
+        let status = poller
+            .start(None, &[target(23, 5, Some("2099-12-28 15:00:00"))], Some(10))
+            .await
+            .expect("Failed to start");
+        assert!(status.running);
+        for _ in 0..100 {
+            if poller.status().races[0].snapshot_count > 0 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        let status = poller.stop();
+        assert!(!status.running);
+        assert!(status.races[0].snapshot_count > 0, "{:?}", status.races[0].last_error);
+        assert!(status.races[0].last_captured_at.is_some());
+        assert!(!status.races[0].closed);
+
+        let db = SqliteRepository::new(path.to_str().unwrap()).await.unwrap();
+        let rows = db.get_odds_snapshots("20251228", "23", 5).await.unwrap();
+        // 単勝3艇 + 複勝3艇
+        assert_eq!(rows.len(), 6 * status.races[0].snapshot_count as usize);
+
+        let _ = std::fs::remove_dir_all(&replay_dir);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_odds_poller_stops_after_race_closed() {
+        let (service, _server, path) = seeded_service("odds_poller").await;
+        let scraping = ScrapingService::new().with_cache(service.scrape_cache());
+        let poller = OddsPollerService::with_parts(Arc::new(service), Arc::new(scraping));
+
+        // 締切時刻は保存済みの出走表から補う（締切済みなので取得せずに終わる）
+        let status = poller.start(None, &[target(23, 3, None)], Some(10)).await.expect("Failed to start");
+        assert_eq!(status.races.len(), 1);
+        assert_eq!(status.races[0].venue_code, "23");
+        for _ in 0..50 {
+            if !poller.status().running {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        let status = poller.status();
+        assert!(!status.running);
+        assert!(status.races[0].closed);
+        assert_eq!(status.races[0].snapshot_count, 0);
+
+        let error = poller.start(None, &[target(23, 9, None)], None).await;
+        assert!(matches!(error, Err(AppError::InvalidInput { .. })), "出走表がなく締切時刻が不明");
+        let error = poller.start(None, &[target(23, 3, None)], Some(1)).await;
+        assert!(matches!(error, Err(AppError::InvalidInput { .. })));
+        assert!(!poller.stop().running);
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -1,46 +1,112 @@
 use crate::models::open_api::{
-    ApiDataType, CsvExportRow, PayoutStats, PreviewRecord, PreviewsResponse, ProgramRecord,
+    ApiDataType, CombinationPayoutStats, CsvExportRow, PayoutStats, PreviewRecord, PreviewsResponse, ProgramRecord,
     ProgramsResponse, RaceResult, ResultRecord, ResultsResponse, SearchParams,
-    RaceRecord, RaceParticipantRecord, RaceCsvRow, RaceParticipantCsvRow, RacePreview, DataSummaryRow,
-    BulkFetchSummary, BulkFetchError, OpenApiBulkProgressPayload,
+    RaceRecord, RaceParticipantRecord, RaceCsvRow, RaceParticipantCsvRow, DataSummaryRow,
+    BulkFetchSummary, BulkFetchError, OpenApiBulkProgressPayload, BET_TYPES,
 };
+use crate::error::AppError;
+use crate::models::migration::{MigrationOptions, MigrationReport};
+use crate::backtest::BacktestEngine;
+use crate::expected_value::{self, FactorTable, WIN_FACTORS};
+use crate::models::expected_value::ExpectedValueReport;
+use crate::models::race::{BettingType, OddsData};
+use crate::models::backtest::{BacktestReport, BacktestStrategy};
+use crate::models::motor::{MotorRanking, MotorStats};
+use crate::models::odds_history::OddsMovementReport;
+use crate::odds_history;
+use crate::escape_analysis::{self, RatePeriod};
+use crate::models::race_key::{self, RaceKey};
+use crate::models::race_view::{EscapeCalibrationReport, RaceView};
+use crate::repositories::local_db::LocalDbRepository;
+use crate::models::race::RaceData;
+use crate::models::racer::{RacerProfile, RacerUpdate};
+use crate::models::job::{BulkJob, JobKind, JobStatus, OpenApiJobParams, OpenApiJobUnit, UnitStatus};
 use crate::repositories::sqlite_db::SqliteRepository;
+use crate::request_policy::{self, RequestPolicy};
+use crate::repositories::job_repository::JobRepository;
+use crate::services::job_service::{JobRun, JobService};
 use chrono::Utc;
 use std::env;
 use std::path::PathBuf;
+use std::sync::Arc;
 use tauri::Emitter;
 
-const BASE_URL: &str = "https://boatraceopenapi.github.io";
+const DEFAULT_BASE_URL: &str = "https://boatraceopenapi.github.io";
+/// API のベースURLを差し替える環境変数（ミラーやモックサーバー向け）
+const BASE_URL_ENV: &str = "BORT_OPEN_API_BASE_URL";
 const DEFAULT_DB_PATH: &str = "data/open_api.db";
 
+/// 全データ種別をまとめて取得するジョブの data_type
+const ALL_DATA_TYPES: &str = "all";
+/// 並行一括取得の同時取得数（デフォルト・上限）
+pub(crate) const DEFAULT_BULK_CONCURRENCY: usize = 4;
+const MAX_BULK_CONCURRENCY: usize = 16;
+
+/// クローンは接続プール・HTTPクライアント・リクエスト制御を共有する
+#[derive(Clone)]
 pub struct OpenApiService {
     repository: SqliteRepository,
     http_client: reqwest::Client,
+    policy: Arc<RequestPolicy>,
+    base_url: String,
 }
 
 impl OpenApiService {
     /// サービスの初期化（デフォルトパスまたは指定パスを使用）
-    pub async fn new(db_path: Option<&str>) -> Result<Self, String> {
+    ///
+    /// API のベースURLは `BORT_OPEN_API_BASE_URL` があればそれを、なければ公式の公開先を使う。
+    pub async fn new(db_path: Option<&str>) -> Result<Self, AppError> {
+        let base_url = env::var(BASE_URL_ENV).unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+        Self::with_base_url(db_path, &base_url).await
+    }
+
+    /// API のベースURLを指定して初期化
+    pub async fn with_base_url(db_path: Option<&str>, base_url: &str) -> Result<Self, AppError> {
         // パスの解決：指定されたパスまたはデフォルトパスを絶対パスに変換
         let resolved_path = Self::resolve_db_path(db_path.unwrap_or(DEFAULT_DB_PATH))?;
 
         let repository = SqliteRepository::new(&resolved_path)
             .await
-            .map_err(|e| format!("Failed to initialize SQLite repository: {}", e))?;
+            .map_err(|e| AppError::db(format!("Failed to initialize SQLite repository: {}", e)))?;
 
         let http_client = reqwest::Client::builder()
             .timeout(std::time::Duration::from_secs(30))
             .build()
-            .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+            .map_err(|e| AppError::internal(format!("Failed to create HTTP client: {}", e)))?;
 
         Ok(Self {
             repository,
             http_client,
+            policy: RequestPolicy::global(),
+            base_url: base_url.trim_end_matches('/').to_string(),
         })
     }
 
+    /// 同じDBにあるスクレイピング結果のキャッシュ（接続プールを共有する）
+    pub fn scrape_cache(&self) -> LocalDbRepository {
+        LocalDbRepository::with_pool(self.repository.pool().clone())
+    }
+
+    /// 同じDBにある一括取得ジョブのリポジトリ（接続プールを共有する）
+    pub fn job_repository(&self) -> JobRepository {
+        JobRepository::with_pool(self.repository.pool().clone())
+    }
+
+    /// DBのマイグレーションのみ実行（サービスは初期化しない）
+    ///
+    /// `dry_run` では未適用分をトランザクション内で検証してロールバックする。
+    pub async fn migrate(
+        db_path: Option<&str>,
+        options: MigrationOptions,
+    ) -> Result<MigrationReport, AppError> {
+        let resolved_path = Self::resolve_db_path(db_path.unwrap_or(DEFAULT_DB_PATH))?;
+        SqliteRepository::migrate(&resolved_path, options)
+            .await
+            .map_err(|e| AppError::db(format!("Migration failed: {}", e)))
+    }
+
     /// 相対パスを絶対パスに変換
-    fn resolve_db_path(path: &str) -> Result<String, String> {
+    fn resolve_db_path(path: &str) -> Result<String, AppError> {
         let path_buf = PathBuf::from(path);
 
         // すでに絶対パスの場合はそのまま返す
@@ -50,19 +116,19 @@ impl OpenApiService {
 
         // 相対パスの場合、カレントディレクトリから解決
         let current_dir = env::current_dir()
-            .map_err(|e| format!("Failed to get current directory: {}", e))?;
+            .map_err(|e| AppError::io(format!("Failed to get current directory: {}", e)))?;
 
         let absolute_path = current_dir.join(path_buf);
 
         // 親ディレクトリが存在しない場合は作成
         if let Some(parent) = absolute_path.parent() {
             std::fs::create_dir_all(parent)
-                .map_err(|e| format!("Failed to create directory: {}", e))?;
+                .map_err(|e| AppError::io(format!("Failed to create directory: {}", e)))?;
         }
 
         absolute_path
             .to_str()
-            .ok_or_else(|| "Invalid path".to_string())
+            .ok_or_else(|| AppError::invalid_input("db_path", "Invalid path"))
             .map(|s| s.to_string())
     }
 
@@ -71,7 +137,7 @@ impl OpenApiService {
         let year = &date[0..4];
         format!(
             "{}/{}/v2/{}/{}.json",
-            BASE_URL,
+            self.base_url,
             data_type.as_str(),
             year,
             date
@@ -83,25 +149,63 @@ impl OpenApiService {
         &self,
         data_type: ApiDataType,
         date: &str,
-    ) -> Result<String, String> {
+    ) -> Result<String, AppError> {
         let url = self.build_url(data_type, date);
-        println!("🔄 Fetching {} data for date: {} from {}", data_type.as_str(), date, url);
+        eprintln!("🔄 Fetching {} data for date: {} from {}", data_type.as_str(), date, url);
+
+        let json_text = Self::fetch_with_policy(&self.http_client, &self.policy, &url).await?;
+
+        eprintln!("✅ Successfully fetched {} data ({} bytes)", data_type.as_str(), json_text.len());
+        Ok(json_text)
+    }
 
-        let response = self.http_client.get(&url)
+    /// リクエストポリシー（レート制限・再試行）を適用して取得
+    async fn fetch_with_policy(
+        client: &reqwest::Client,
+        policy: &RequestPolicy,
+        url: &str,
+    ) -> Result<String, AppError> {
+        policy.execute(url, || Self::fetch_once(client, url)).await
+    }
+
+    async fn fetch_once(client: &reqwest::Client, url: &str) -> Result<String, AppError> {
+        let response = client.get(url)
             .send()
-            .await
-            .map_err(|e| format!("HTTP request failed: {}", e))?;
+            .await?;
 
         if !response.status().is_success() {
-            return Err(format!("HTTP error: {} - {}", response.status(), url));
+            return Err(AppError::http_status(
+                url,
+                response.status().as_u16(),
+                request_policy::retry_after_secs(response.headers()),
+            ));
         }
 
-        let json_text = response.text()
-            .await
-            .map_err(|e| format!("Failed to read response: {}", e))?;
+        Ok(response.text().await?)
+    }
 
-        println!("✅ Successfully fetched {} data ({} bytes)", data_type.as_str(), json_text.len());
-        Ok(json_text)
+    /// データ種別に応じてデータベースに保存
+    async fn save_data(
+        &self,
+        data_type: ApiDataType,
+        date: &str,
+        json_data: &str,
+    ) -> Result<usize, AppError> {
+        match data_type {
+            ApiDataType::Previews => self.save_previews_data(date, json_data).await,
+            ApiDataType::Results => self.save_results_data(date, json_data).await,
+            ApiDataType::Programs => self.save_programs_data(date, json_data).await,
+        }
+    }
+
+    /// 指定日のデータが既にDBにあるか（件数で判定）
+    async fn count_existing(&self, data_type: ApiDataType, date: &str) -> i64 {
+        let count = match data_type {
+            ApiDataType::Previews => self.repository.count_previews_by_date(date).await,
+            ApiDataType::Results => self.repository.count_results_by_date(date).await,
+            ApiDataType::Programs => self.repository.count_programs_by_date(date).await,
+        };
+        count.unwrap_or(0)
     }
 
     /// Previews データをデータベースに保存
@@ -109,11 +213,10 @@ impl OpenApiService {
         &self,
         date: &str,
         json_data: &str,
-    ) -> Result<usize, String> {
-        println!("💾 Saving previews data for date: {}", date);
+    ) -> Result<usize, AppError> {
+        eprintln!("💾 Saving previews data for date: {}", date);
 
-        let response: PreviewsResponse = serde_json::from_str(json_data)
-            .map_err(|e| format!("JSON parse error: {}", e))?;
+        let response: PreviewsResponse = serde_json::from_str(json_data)?;
 
         let now = Utc::now().to_rfc3339();
         let mut saved_count = 0;
@@ -125,26 +228,23 @@ impl OpenApiService {
                 date: date.to_string(),
                 venue_code,
                 race_number: preview.race_number,
-                data_json: serde_json::to_string(&preview)
-                    .map_err(|e| format!("Failed to serialize: {}", e))?,
+                data_json: serde_json::to_string(&preview)?,
                 created_at: now.clone(),
                 updated_at: now.clone(),
             };
-            self.repository.save_preview(&record).await
-                .map_err(|e| format!("Database error: {}", e))?;
+            self.repository.save_preview(&record).await?;
             saved_count += 1;
         }
 
-        println!("✅ Saved {} preview records", saved_count);
+        eprintln!("✅ Saved {} preview records", saved_count);
         Ok(saved_count)
     }
 
     /// Results データをデータベースに保存
-    pub async fn save_results_data(&self, date: &str, json_data: &str) -> Result<usize, String> {
-        println!("💾 Saving results data for date: {}", date);
+    pub async fn save_results_data(&self, date: &str, json_data: &str) -> Result<usize, AppError> {
+        eprintln!("💾 Saving results data for date: {}", date);
 
-        let response: ResultsResponse = serde_json::from_str(json_data)
-            .map_err(|e| format!("JSON parse error: {}", e))?;
+        let response: ResultsResponse = serde_json::from_str(json_data)?;
 
         let now = Utc::now().to_rfc3339();
         let mut saved_count = 0;
@@ -156,17 +256,15 @@ impl OpenApiService {
                 date: date.to_string(),
                 venue_code,
                 race_number: result.race_number,
-                data_json: serde_json::to_string(&result)
-                    .map_err(|e| format!("Failed to serialize: {}", e))?,
+                data_json: serde_json::to_string(&result)?,
                 created_at: now.clone(),
                 updated_at: now.clone(),
             };
-            self.repository.save_result(&record).await
-                .map_err(|e| format!("Database error: {}", e))?;
+            self.repository.save_result(&record).await?;
             saved_count += 1;
         }
 
-        println!("✅ Saved {} result records", saved_count);
+        eprintln!("✅ Saved {} result records", saved_count);
         Ok(saved_count)
     }
 
@@ -175,11 +273,10 @@ impl OpenApiService {
         &self,
         date: &str,
         json_data: &str,
-    ) -> Result<usize, String> {
-        println!("💾 Saving programs data for date: {}", date);
+    ) -> Result<usize, AppError> {
+        eprintln!("💾 Saving programs data for date: {}", date);
 
-        let response: ProgramsResponse = serde_json::from_str(json_data)
-            .map_err(|e| format!("JSON parse error: {}", e))?;
+        let response: ProgramsResponse = serde_json::from_str(json_data)?;
 
         let now = Utc::now().to_rfc3339();
         let mut saved_count = 0;
@@ -191,17 +288,15 @@ impl OpenApiService {
                 date: date.to_string(),
                 venue_code,
                 race_number: program.race_number,
-                data_json: serde_json::to_string(&program)
-                    .map_err(|e| format!("Failed to serialize: {}", e))?,
+                data_json: serde_json::to_string(&program)?,
                 created_at: now.clone(),
                 updated_at: now.clone(),
             };
-            self.repository.save_program(&record).await
-                .map_err(|e| format!("Database error: {}", e))?;
+            self.repository.save_program(&record).await?;
             saved_count += 1;
         }
 
-        println!("✅ Saved {} program records", saved_count);
+        eprintln!("✅ Saved {} program records", saved_count);
         Ok(saved_count)
     }
 
@@ -210,15 +305,14 @@ impl OpenApiService {
         &self,
         output_path: &str,
         data_type: Option<ApiDataType>,
-    ) -> Result<usize, String> {
-        println!("📊 Exporting to CSV: {} (type: {:?})", output_path, data_type);
+    ) -> Result<usize, AppError> {
+        eprintln!("📊 Exporting to CSV: {} (type: {:?})", output_path, data_type);
 
         let mut rows = Vec::new();
 
         match data_type {
             Some(ApiDataType::Previews) => {
-                let records = self.repository.get_all_previews().await
-                    .map_err(|e| format!("Database error: {}", e))?;
+                let records = self.repository.get_all_previews().await?;
                 for r in records {
                     rows.push(CsvExportRow {
                         date: r.date,
@@ -230,8 +324,7 @@ impl OpenApiService {
                 }
             },
             Some(ApiDataType::Results) => {
-                let records = self.repository.get_all_results().await
-                    .map_err(|e| format!("Database error: {}", e))?;
+                let records = self.repository.get_all_results().await?;
                 for r in records {
                     rows.push(CsvExportRow {
                         date: r.date,
@@ -243,8 +336,7 @@ impl OpenApiService {
                 }
             },
             Some(ApiDataType::Programs) => {
-                let records = self.repository.get_all_programs().await
-                    .map_err(|e| format!("Database error: {}", e))?;
+                let records = self.repository.get_all_programs().await?;
                 for r in records {
                     rows.push(CsvExportRow {
                         date: r.date,
@@ -257,8 +349,7 @@ impl OpenApiService {
             },
             None => {
                 // 全データをエクスポート
-                let previews = self.repository.get_all_previews().await
-                    .map_err(|e| format!("Database error: {}", e))?;
+                let previews = self.repository.get_all_previews().await?;
                 for r in previews {
                     rows.push(CsvExportRow {
                         date: r.date,
@@ -269,8 +360,7 @@ impl OpenApiService {
                     });
                 }
 
-                let results = self.repository.get_all_results().await
-                    .map_err(|e| format!("Database error: {}", e))?;
+                let results = self.repository.get_all_results().await?;
                 for r in results {
                     rows.push(CsvExportRow {
                         date: r.date,
@@ -281,8 +371,7 @@ impl OpenApiService {
                     });
                 }
 
-                let programs = self.repository.get_all_programs().await
-                    .map_err(|e| format!("Database error: {}", e))?;
+                let programs = self.repository.get_all_programs().await?;
                 for r in programs {
                     rows.push(CsvExportRow {
                         date: r.date,
@@ -296,17 +385,15 @@ impl OpenApiService {
         }
 
         let mut wtr = csv::Writer::from_path(output_path)
-            .map_err(|e| format!("Failed to create CSV file: {}", e))?;
+            .map_err(|e| AppError::io(format!("Failed to create CSV file: {}", e)))?;
 
         for row in &rows {
-            wtr.serialize(row)
-                .map_err(|e| format!("Failed to write CSV row: {}", e))?;
+            wtr.serialize(row)?;
         }
 
-        wtr.flush()
-            .map_err(|e| format!("Failed to flush CSV writer: {}", e))?;
+        wtr.flush()?;
 
-        println!("✅ Exported {} rows to CSV", rows.len());
+        eprintln!("✅ Exported {} rows to CSV", rows.len());
         Ok(rows.len())
     }
 
@@ -317,85 +404,54 @@ impl OpenApiService {
     pub async fn export_to_csv_v3(
         &self,
         output_dir: &str,
-    ) -> Result<(usize, usize), String> {
-        println!("📊 Exporting V3 normalized data to CSV: {}", output_dir);
+    ) -> Result<(usize, usize), AppError> {
+        eprintln!("📊 Exporting V3 normalized data to CSV: {}", output_dir);
 
         // 1. V3テーブルから全データ取得
         let race_data = self.repository
             .get_all_races_with_participants()
-            .await
-            .map_err(|e| format!("Database error: {}", e))?;
+            .await?;
 
         if race_data.is_empty() {
-            return Err("No race data found in database. Run V3 migration first.".to_string());
+            return Err(AppError::db("No race data found in database. Run V3 migration first."));
         }
 
-        println!("  📦 Fetched {} races from V3 tables", race_data.len());
+        eprintln!("  📦 Fetched {} races from V3 tables", race_data.len());
 
         // 2. races.csv 出力
         let races_csv_path = format!("{}/races.csv", output_dir);
         let mut races_writer = csv::Writer::from_path(&races_csv_path)
-            .map_err(|e| format!("Failed to create races.csv: {}", e))?;
+            .map_err(|e| AppError::io(format!("Failed to create races.csv: {}", e)))?;
 
         let mut race_count = 0;
-        for (race, _, _) in &race_data {
+        for (race, _) in &race_data {
             let csv_row = RaceCsvRow::from(race);
-            races_writer.serialize(&csv_row)
-                .map_err(|e| format!("Failed to write race row: {}", e))?;
+            races_writer.serialize(&csv_row)?;
             race_count += 1;
         }
 
-        races_writer.flush()
-            .map_err(|e| format!("Failed to flush races CSV: {}", e))?;
+        races_writer.flush()?;
 
-        println!("  ✅ Exported {} races to {}", race_count, races_csv_path);
+        eprintln!("  ✅ Exported {} races to {}", race_count, races_csv_path);
 
         // 3. race_participants.csv 出力
         let participants_csv_path = format!("{}/race_participants.csv", output_dir);
         let mut participants_writer = csv::Writer::from_path(&participants_csv_path)
-            .map_err(|e| format!("Failed to create race_participants.csv: {}", e))?;
+            .map_err(|e| AppError::io(format!("Failed to create race_participants.csv: {}", e)))?;
 
         let mut participant_count = 0;
-        for (race, participants, preview) in &race_data {
-            // previewsデータをパース（存在する場合）
-            let preview_map = if let Some(preview_record) = preview {
-                // JSONをパースしてRacePreview構造体に変換
-                match serde_json::from_str::<RacePreview>(&preview_record.data_json) {
-                    Ok(preview_data) => Some(preview_data.boats),
-                    Err(e) => {
-                        eprintln!("⚠️  Failed to parse preview JSON: {}", e);
-                        None
-                    }
-                }
-            } else {
-                None
-            };
-
+        for (race, participants) in &race_data {
             for participant in participants {
-                // 参加者の艇番号に対応するpreviewsデータを取得
-                let preview_data = preview_map.as_ref().and_then(|boats| {
-                    let boat_key = participant.boat_number.to_string();
-                    boats.get(&boat_key).map(|boat_info| {
-                        (
-                            boat_info.racer_weight_adjustment,
-                            boat_info.racer_exhibition_time,
-                            boat_info.racer_tilt_adjustment,
-                        )
-                    })
-                });
-
-                let csv_row = RaceParticipantCsvRow::from_record(participant, race, preview_data);
-                participants_writer.serialize(&csv_row)
-                    .map_err(|e| format!("Failed to write participant row: {}", e))?;
+                let csv_row = RaceParticipantCsvRow::from_record(participant, race);
+                participants_writer.serialize(&csv_row)?;
                 participant_count += 1;
             }
         }
 
-        participants_writer.flush()
-            .map_err(|e| format!("Failed to flush participants CSV: {}", e))?;
+        participants_writer.flush()?;
 
-        println!("  ✅ Exported {} participants to {}", participant_count, participants_csv_path);
-        println!("✅ CSV export completed: {} races, {} participants", race_count, participant_count);
+        eprintln!("  ✅ Exported {} participants to {}", participant_count, participants_csv_path);
+        eprintln!("✅ CSV export completed: {} races, {} participants", race_count, participant_count);
 
         Ok((race_count, participant_count))
     }
@@ -408,23 +464,414 @@ impl OpenApiService {
         min_payout: i32,
         payout_type: String,
         limit: Option<i32>,
-    ) -> Result<Vec<RaceResult>, String> {
+    ) -> Result<Vec<RaceResult>, AppError> {
         let records = self.repository
             .search_high_payout_races(min_payout, &payout_type, limit)
-            .await
-            .map_err(|e| format!("Database error: {}", e))?;
+            .await?;
 
         records.into_iter()
-            .map(|r| serde_json::from_str(&r.data_json)
-                .map_err(|e| format!("JSON parse error: {}", e)))
+            .map(|r| serde_json::from_str(&r.data_json).map_err(AppError::from))
             .collect()
     }
 
     /// 配当統計情報取得
-    pub async fn get_payout_statistics(&self) -> Result<PayoutStats, String> {
+    pub async fn get_payout_statistics(&self) -> Result<PayoutStats, AppError> {
         self.repository.get_payout_statistics()
             .await
-            .map_err(|e| format!("Database error: {}", e))
+            .map_err(AppError::from)
+    }
+
+    /// 組番ごとの的中数・配当統計
+    pub async fn get_combination_payout_stats(
+        &self,
+        bet_type: &str,
+        venue_code: Option<&str>,
+        limit: Option<i32>,
+    ) -> Result<Vec<CombinationPayoutStats>, AppError> {
+        if !BET_TYPES.contains(&bet_type) {
+            return Err(AppError::invalid_input(
+                "bet_type",
+                format!("Invalid bet_type: '{}'. Expected one of {:?}", bet_type, BET_TYPES),
+            ));
+        }
+
+        self.repository
+            .get_combination_payout_stats(bet_type, venue_code, limit.unwrap_or(120))
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// 単勝・複勝オッズと過去の結果から推定した確率で期待値・ケリー基準を計算
+    ///
+    /// 出走艇は保存済みの出走表（直前情報があれば展示進入コース）を使う。date は YYYYMMDD / YYYY-MM-DD。
+    /// 確率の推定には対象レースの前日までの結果だけを使う。
+    pub async fn calculate_expected_values(
+        &self,
+        date: &str,
+        venue_code: &str,
+        race_number: i32,
+        odds: &OddsData,
+    ) -> Result<ExpectedValueReport, AppError> {
+        if odds.betting_type != BettingType::WinPlace {
+            return Err(AppError::invalid_input(
+                "odds",
+                format!("Expected win/place odds, got {:?}", odds.betting_type),
+            ));
+        }
+
+        let race_date = date.replace('-', "");
+        let (_, entries) = self
+            .repository
+            .get_race_entries(&race_date, venue_code, race_number)
+            .await?
+            .filter(|(_, entries)| !entries.is_empty())
+            .ok_or_else(|| {
+                AppError::invalid_input(
+                    "race",
+                    format!("No saved entries for {}-{}-{}R", race_date, venue_code, race_number),
+                )
+            })?;
+
+        let mut tables = Vec::new();
+        for factor in WIN_FACTORS {
+            let buckets = self.repository.get_win_factor_buckets(factor, &race_date).await?;
+            tables.push(FactorTable::new(&buckets));
+        }
+
+        let boats = expected_value::estimate_probabilities(&entries, &tables);
+        let bets = expected_value::evaluate_bets(&boats, odds);
+        eprintln!(
+            "🎯 期待値計算: {}-{}-{}R（過去 {} 出走）overlay {} 件",
+            race_date,
+            venue_code,
+            race_number,
+            tables[0].total_starts(),
+            bets.iter().filter(|bet| bet.is_overlay).count()
+        );
+
+        Ok(ExpectedValueReport {
+            race_date,
+            venue_code: venue_code.to_string(),
+            race_number,
+            sample_starts: tables[0].total_starts(),
+            boats,
+            bets,
+        })
+    }
+
+    /// 単勝・複勝オッズを取得時刻付きのスナップショットとして保存し、取得時刻を返す
+    pub async fn save_odds_snapshot(
+        &self,
+        date: &str,
+        venue_code: &str,
+        race_number: i32,
+        odds: &OddsData,
+    ) -> Result<String, AppError> {
+        let captured_at = Utc::now().to_rfc3339();
+        let records = odds_history::snapshot_records(
+            &date.replace('-', ""),
+            venue_code,
+            race_number,
+            &captured_at,
+            odds,
+        );
+        self.repository.save_odds_snapshot(&records).await?;
+        Ok(captured_at)
+    }
+
+    /// 保存済みの出走表から締切時刻（"YYYY-MM-DD HH:MM:SS"）を取得
+    pub async fn get_race_closed_at(
+        &self,
+        date: &str,
+        venue_code: &str,
+        race_number: i32,
+    ) -> Result<Option<String>, AppError> {
+        Ok(self
+            .repository
+            .get_race_closed_at(&date.replace('-', ""), venue_code, race_number)
+            .await?)
+    }
+
+    /// レースのオッズの推移と下落率の大きい買い目（drop_limit 件）
+    pub async fn get_odds_movement(
+        &self,
+        date: &str,
+        venue_code: &str,
+        race_number: i32,
+        drop_limit: usize,
+    ) -> Result<OddsMovementReport, AppError> {
+        let race_date = date.replace('-', "");
+        let snapshots = self
+            .repository
+            .get_odds_snapshots(&race_date, venue_code, race_number)
+            .await?;
+        Ok(odds_history::build_movement(
+            &race_date,
+            venue_code,
+            race_number,
+            &snapshots,
+            drop_limit,
+        ))
+    }
+
+    // ===== レースビュー（Open API と kyoteibiyori の結合） =====
+
+    /// 1レースの出走表・直前情報・結果と kyoteibiyori のレースデータ
+    ///
+    /// date は YYYYMMDD / YYYY-MM-DD、venue_code は "01" / "1"。どちらのデータもなければエラー。
+    pub async fn get_race_view(
+        &self,
+        date: &str,
+        venue_code: &str,
+        race_number: u32,
+    ) -> Result<RaceView, AppError> {
+        let key = RaceKey::parse(date, venue_code, race_number)?;
+        let (race_date, venue_code, race_number) = (key.race_date(), key.venue_code(), race_number as i32);
+
+        let race = self.repository.get_race(&race_date, &venue_code, race_number).await?;
+        let mut participants = self
+            .repository
+            .get_race_entries(&race_date, &venue_code, race_number)
+            .await?
+            .map(|(_, participants)| participants)
+            .unwrap_or_default();
+        participants.iter_mut().for_each(RaceParticipantRecord::decode_labels);
+        let biyori = self
+            .scrape_cache()
+            .get_race_data(&key.scrape_date(), key.venue, key.race_number)
+            .await?;
+
+        if race.is_none() && biyori.is_none() {
+            return Err(AppError::invalid_input("race", format!("No saved data for {}", key)));
+        }
+
+        let has_preview = race.as_ref().is_some_and(|race| race.preview_wind.is_some())
+            || participants.iter().any(|p| p.exhibition_time.is_some());
+        let escape_check = match (&biyori, &race) {
+            (Some(biyori), Some(race)) => escape_analysis::escape_check(biyori, race),
+            _ => None,
+        };
+
+        Ok(RaceView {
+            has_program: race.as_ref().is_some_and(|race| race.program_data_json.is_some()),
+            has_preview,
+            has_result: race.as_ref().is_some_and(|race| race.result_data_json.is_some()),
+            race_date,
+            venue_code,
+            race_number,
+            race,
+            participants,
+            biyori,
+            escape_check,
+        })
+    }
+
+    /// kyoteibiyori の逃げ率が結果をどれだけ予想できたか（日付は YYYYMMDD、rate_period は
+    /// "last_half_year"（デフォルト）/ "last_year"）
+    pub async fn get_escape_calibration(
+        &self,
+        date_from: Option<&str>,
+        date_to: Option<&str>,
+        venue_code: Option<&str>,
+        rate_period: Option<&str>,
+    ) -> Result<EscapeCalibrationReport, AppError> {
+        let period = RatePeriod::parse(rate_period).ok_or_else(|| {
+            AppError::invalid_input(
+                "rate_period",
+                format!("Invalid rate_period: {:?}. Expected last_half_year or last_year", rate_period),
+            )
+        })?;
+        let venue_code = match venue_code {
+            Some(code) => Some(format!("{:02}", race_key::parse_venue(code)?)),
+            None => None,
+        };
+        let date_from = date_from.map(|date| date.replace('-', ""));
+        let date_to = date_to.map(|date| date.replace('-', ""));
+
+        let rows = self
+            .repository
+            .get_escape_outcomes(date_from.as_deref(), date_to.as_deref(), venue_code.as_deref())
+            .await?;
+        let report = escape_analysis::build_calibration(
+            &rows,
+            period,
+            date_from.as_deref(),
+            date_to.as_deref(),
+            venue_code.as_deref(),
+        );
+        eprintln!(
+            "🏁 逃げ率の検証: {} レース, 的中率 {:.1}%, ブライアスコア {:.3}",
+            report.races,
+            report.prediction_accuracy * 100.0,
+            report.brier_score
+        );
+        Ok(report)
+    }
+
+    /// 買い方戦略を保存済みのレース結果で検証（date_from / date_to は YYYYMMDD、指定時は filters の日付を上書き）
+    pub async fn run_backtest(
+        &self,
+        strategy: &BacktestStrategy,
+        date_from: Option<&str>,
+        date_to: Option<&str>,
+    ) -> Result<BacktestReport, AppError> {
+        let engine = BacktestEngine::new(strategy)?;
+
+        let mut filters = strategy.filters.clone();
+        if let Some(date_from) = date_from {
+            filters.date_from = Some(date_from.to_string());
+        }
+        if let Some(date_to) = date_to {
+            filters.date_to = Some(date_to.to_string());
+        }
+
+        let races = self
+            .repository
+            .get_backtest_races(&filters, &strategy.bet_type)
+            .await?;
+        eprintln!(
+            "📈 バックテスト: {} レース（{} {}点）",
+            races.len(),
+            strategy.bet_type,
+            engine.combinations().len()
+        );
+
+        let mut report = engine.run(&races);
+        report.date_from = filters.date_from;
+        report.date_to = filters.date_to;
+        Ok(report)
+    }
+
+    /// 競艇場のモーター上位・下位（2連率順）
+    ///
+    /// venue_code は "1"〜"24" / "01"〜"24"、season は年（省略時は結果のある最新の年）。
+    /// シーズンは年で近似しているため、年の途中でモーターを入れ替えた場では新旧のモーターが
+    /// 混ざる。date_from / date_to（YYYYMMDD / YYYY-MM-DD）を指定すると season の代わりに
+    /// その期間（入れ替え日以降など）で集計する。下位には上位に入ったモーターを含めない。
+    pub async fn get_motor_ranking(
+        &self,
+        venue_code: &str,
+        season: Option<&str>,
+        date_from: Option<&str>,
+        date_to: Option<&str>,
+        min_starts: Option<i32>,
+        limit: Option<i32>,
+    ) -> Result<MotorRanking, AppError> {
+        let venue_code = format!("{:02}", race_key::parse_venue(venue_code)?);
+        let min_starts = min_starts.unwrap_or(10).max(1);
+        let limit = limit.unwrap_or(10);
+
+        if date_from.is_some() || date_to.is_some() {
+            if season.is_some() {
+                return Err(AppError::invalid_input(
+                    "season",
+                    "season cannot be combined with date_from / date_to",
+                ));
+            }
+            let date_from = date_from.map(|date| date.replace('-', ""));
+            let date_to = date_to.map(|date| date.replace('-', ""));
+            let from = date_from.as_deref().unwrap_or("00000000");
+            let to = date_to.as_deref().unwrap_or("99999999");
+            let top = self
+                .repository
+                .get_motor_stats_between(&venue_code, from, to, min_starts, false, limit, &[])
+                .await?;
+            let bottom = self
+                .repository
+                .get_motor_stats_between(&venue_code, from, to, min_starts, true, limit, &motor_numbers(&top))
+                .await?;
+            return Ok(MotorRanking {
+                venue_code,
+                season: None,
+                date_from,
+                date_to,
+                min_starts,
+                top,
+                bottom,
+            });
+        }
+
+        let season = match season {
+            Some(season) => Some(season.to_string()),
+            None => self.repository.get_latest_motor_season(&venue_code).await?,
+        };
+
+        let (top, bottom) = match &season {
+            Some(season) => {
+                let top = self
+                    .repository
+                    .get_motor_stats(&venue_code, season, min_starts, false, limit, &[])
+                    .await?;
+                let bottom = self
+                    .repository
+                    .get_motor_stats(&venue_code, season, min_starts, true, limit, &motor_numbers(&top))
+                    .await?;
+                (top, bottom)
+            }
+            None => (Vec::new(), Vec::new()),
+        };
+
+        Ok(MotorRanking {
+            venue_code,
+            season,
+            date_from: None,
+            date_to: None,
+            min_starts,
+            top,
+            bottom,
+        })
+    }
+
+    // ===== 選手マスタ =====
+
+    /// 選手プロフィール（選手マスタ・級別履歴と保存済みレース結果の集計）
+    pub async fn get_racer_profile(&self, racer_number: i32) -> Result<RacerProfile, AppError> {
+        let racer = self.repository.get_racer(racer_number).await?;
+        let (starts, win_rate, top2_rate, top3_rate, average_start_timing) =
+            self.repository.get_racer_result_totals(racer_number).await?;
+        if racer.is_none() && starts == 0 {
+            return Err(AppError::invalid_input(
+                "racer_number",
+                format!("Racer not found: {}", racer_number),
+            ));
+        }
+
+        let (flying_count, late_count) = self
+            .repository
+            .get_racer_latest_fl_counts(racer_number)
+            .await?
+            .unwrap_or((None, None));
+
+        Ok(RacerProfile {
+            racer_number,
+            racer,
+            class_history: self.repository.get_racer_class_history(racer_number).await?,
+            starts,
+            place_distribution: self.repository.get_racer_place_distribution(racer_number).await?,
+            win_rate,
+            top2_rate,
+            top3_rate,
+            average_start_timing,
+            flying_count,
+            late_count,
+            course_stats: self.repository.get_racer_course_stats(racer_number).await?,
+            venue_stats: self.repository.get_racer_venue_stats(racer_number).await?,
+        })
+    }
+
+    /// kyoteibiyori のレースデータ（date は YYYY-MM-DD）から選手マスタを更新
+    pub async fn update_racers_from_race_data(
+        &self,
+        date: &str,
+        race_data: &RaceData,
+    ) -> Result<usize, AppError> {
+        let updates: Vec<RacerUpdate> = race_data
+            .boats
+            .iter()
+            .filter_map(|boat| RacerUpdate::from_player_info(&boat.player_basic_info, date))
+            .collect();
+        self.repository.save_racers(&updates).await?;
+        Ok(updates.len())
     }
 
     // ===== V3検索API: サービス層 =====
@@ -433,11 +880,11 @@ impl OpenApiService {
     pub async fn search_races_advanced(
         &self,
         params: SearchParams,
-    ) -> Result<Vec<(RaceRecord, Vec<RaceParticipantRecord>)>, String> {
+    ) -> Result<Vec<(RaceRecord, Vec<RaceParticipantRecord>)>, AppError> {
         self.repository
             .search_races_advanced(params)
             .await
-            .map_err(|e| format!("Database error: {}", e))
+            .map_err(AppError::from)
     }
 
     /// 選手番号での検索
@@ -445,11 +892,11 @@ impl OpenApiService {
         &self,
         racer_number: i32,
         limit: Option<i32>,
-    ) -> Result<Vec<(RaceRecord, Vec<RaceParticipantRecord>)>, String> {
+    ) -> Result<Vec<(RaceRecord, Vec<RaceParticipantRecord>)>, AppError> {
         self.repository
             .search_races_by_racer(racer_number, limit)
             .await
-            .map_err(|e| format!("Database error: {}", e))
+            .map_err(AppError::from)
     }
 
     /// 選手名での検索
@@ -457,11 +904,11 @@ impl OpenApiService {
         &self,
         racer_name: String,
         limit: Option<i32>,
-    ) -> Result<Vec<(RaceRecord, Vec<RaceParticipantRecord>)>, String> {
+    ) -> Result<Vec<(RaceRecord, Vec<RaceParticipantRecord>)>, AppError> {
         self.repository
             .search_races_by_racer_name(racer_name, limit)
             .await
-            .map_err(|e| format!("Database error: {}", e))
+            .map_err(AppError::from)
     }
 
     /// 級別での検索
@@ -469,11 +916,11 @@ impl OpenApiService {
         &self,
         racer_class: i32,
         limit: Option<i32>,
-    ) -> Result<Vec<(RaceRecord, Vec<RaceParticipantRecord>)>, String> {
+    ) -> Result<Vec<(RaceRecord, Vec<RaceParticipantRecord>)>, AppError> {
         self.repository
             .search_races_by_class(racer_class, limit)
             .await
-            .map_err(|e| format!("Database error: {}", e))
+            .map_err(AppError::from)
     }
 
     /// 日付範囲での検索
@@ -482,11 +929,11 @@ impl OpenApiService {
         date_from: String,
         date_to: String,
         limit: Option<i32>,
-    ) -> Result<Vec<(RaceRecord, Vec<RaceParticipantRecord>)>, String> {
+    ) -> Result<Vec<(RaceRecord, Vec<RaceParticipantRecord>)>, AppError> {
         self.repository
             .search_races_by_date_range(date_from, date_to, limit)
             .await
-            .map_err(|e| format!("Database error: {}", e))
+            .map_err(AppError::from)
     }
 
     /// 会場での検索
@@ -494,234 +941,975 @@ impl OpenApiService {
         &self,
         venue_code: String,
         limit: Option<i32>,
-    ) -> Result<Vec<(RaceRecord, Vec<RaceParticipantRecord>)>, String> {
+    ) -> Result<Vec<(RaceRecord, Vec<RaceParticipantRecord>)>, AppError> {
         self.repository
             .search_races_by_venue(venue_code, limit)
             .await
-            .map_err(|e| format!("Database error: {}", e))
+            .map_err(AppError::from)
     }
 
     /// 日付ごとのデータ取得状況サマリーを取得
-    pub async fn get_data_summary(&self) -> Result<Vec<DataSummaryRow>, String> {
+    pub async fn get_data_summary(&self) -> Result<Vec<DataSummaryRow>, AppError> {
         self.repository
             .get_data_summary_by_date()
             .await
-            .map_err(|e| format!("Failed to get data summary: {}", e))
+            .map_err(|e| AppError::db(format!("Failed to get data summary: {}", e)))
     }
 
     /// 期間を指定してデータを一括取得（Bulk Fetch）
+    ///
+    /// 日付ごとの作業単位をジョブとして登録してから実行する。
+    /// 一時停止・中断したジョブは [`Self::run_bulk_job`] で未処理の日付から再開できる。
     pub async fn fetch_data_bulk(
         &self,
         window: Option<tauri::Window>,
+        jobs: &JobService,
         data_type: ApiDataType,
         start_date: &str,  // YYYYMMDD形式
         end_date: &str,    // YYYYMMDD形式
-    ) -> Result<BulkFetchSummary, String> {
-        use chrono::{Duration, NaiveDate};
-        use tokio::time::{sleep, Duration as TokioDuration};
-
-        // 日付範囲のパース
-        let start = NaiveDate::parse_from_str(start_date, "%Y%m%d")
-            .map_err(|e| format!("Invalid start date: {}", e))?;
-        let end = NaiveDate::parse_from_str(end_date, "%Y%m%d")
-            .map_err(|e| format!("Invalid end date: {}", e))?;
-
-        let total_days = (end - start).num_days() + 1;
-        let mut current_date = start;
-        let mut current_day = 0;
-
-        let mut success_count = 0;
-        let mut error_count = 0;
-        let mut skipped_count = 0;
-        let mut errors = Vec::new();
-
-        println!(
-            "🔄 Starting bulk fetch: {} from {} to {} ({} days)",
+    ) -> Result<BulkFetchSummary, AppError> {
+        let job = Self::create_bulk_job(jobs, data_type, start_date, end_date).await?;
+        self.run_bulk_job(window, jobs, job.id).await
+    }
+
+    /// 期間一括取得ジョブを登録する（実行は [`Self::run_bulk_job`]）
+    pub async fn create_bulk_job(
+        jobs: &JobService,
+        data_type: ApiDataType,
+        start_date: &str,  // YYYYMMDD形式
+        end_date: &str,    // YYYYMMDD形式
+    ) -> Result<BulkJob, AppError> {
+        let units: Vec<OpenApiJobUnit> = date_range(start_date, end_date)?
+            .into_iter()
+            .map(|date| OpenApiJobUnit {
+                date,
+                data_type: None,
+            })
+            .collect();
+
+        let params = OpenApiJobParams {
+            data_type: data_type.as_str().to_string(),
+            start_date: start_date.to_string(),
+            end_date: end_date.to_string(),
+            concurrency: None,
+        };
+        let job = jobs.create_job(JobKind::OpenApiBulk, &params, &units).await?;
+
+        eprintln!(
+            "🔄 Starting bulk fetch job #{}: {} from {} to {} ({} days)",
+            job.id,
             data_type.as_str(),
             start_date,
             end_date,
-            total_days
+            units.len()
         );
+        Ok(job)
+    }
+
+    /// 登録済みの一括取得ジョブを未処理の日付から実行
+    pub async fn run_bulk_job(
+        &self,
+        window: Option<tauri::Window>,
+        jobs: &JobService,
+        job_id: i64,
+    ) -> Result<BulkFetchSummary, AppError> {
+        let run = jobs.start(job_id).await?;
+        // エラーで抜けてもジョブが Running のまま残らないよう、結果は pause_on_error に通す
+        let loaded = async {
+            let job = jobs.get_job(job_id).await?;
+            let params: OpenApiJobParams = serde_json::from_str(&job.params_json)?;
+            Ok((job, params))
+        }
+        .await;
+        let (job, params) = jobs.pause_on_error(&run, loaded).await?;
+        if params.data_type == ALL_DATA_TYPES {
+            return self
+                .run_concurrent_bulk_job(window, jobs, run, job, params)
+                .await;
+        }
+        let data_type = ApiDataType::from_name(&params.data_type).ok_or_else(|| {
+            AppError::invalid_input("data_type", format!("Unknown data_type: {}", params.data_type))
+        });
+        let data_type = jobs.pause_on_error(&run, data_type).await?;
+
+        let total_days = job.total_units as usize;
+        let mut current_day = job.processed_units() as usize;
+        let emit = |message: String, current: usize, date: &str, status: &str| {
+            if let Some(ref w) = window {
+                w.emit(
+                    "open-api-bulk-progress",
+                    OpenApiBulkProgressPayload {
+                        job_id,
+                        message,
+                        current,
+                        total: total_days,
+                        date: date.to_string(),
+                        data_type: data_type.as_str().to_string(),
+                        status: status.to_string(),
+                    },
+                )
+                .ok();
+            }
+        };
 
-        // 各日付を順次処理
-        while current_date <= end {
-            current_day += 1;
-            let date_str = current_date.format("%Y%m%d").to_string();
+        let outcome = async {
+            let mut final_status = JobStatus::Completed;
 
-            // STEP 1: データが既に存在するかチェック（キャッシュ優先戦略）
-            let existing_count = match data_type {
-                ApiDataType::Previews => self
-                    .repository
-                    .count_previews_by_date(&date_str)
-                    .await
-                    .unwrap_or(0),
-                ApiDataType::Results => self
-                    .repository
-                    .count_results_by_date(&date_str)
-                    .await
-                    .unwrap_or(0),
-                ApiDataType::Programs => self
-                    .repository
-                    .count_programs_by_date(&date_str)
-                    .await
-                    .unwrap_or(0),
-            };
+            // 未処理の日付を順次処理
+            for (seq, unit) in jobs.pending_units::<OpenApiJobUnit>(job_id).await? {
+                if let Some(stop) = run.stop_requested() {
+                    final_status = stop;
+                    break;
+                }
 
-            if existing_count > 0 {
-                // スキップ - 既にDBに存在
-                let message = format!("📦 Skipping {} (already in DB)", date_str);
-                println!("{}", message);
-
-                if let Some(ref w) = window {
-                    w.emit(
-                        "open-api-bulk-progress",
-                        OpenApiBulkProgressPayload {
-                            message,
-                            current: current_day as usize,
-                            total: total_days as usize,
-                            date: date_str.clone(),
-                            data_type: data_type.as_str().to_string(),
-                            status: "cached".to_string(),
-                        },
-                    )
-                    .ok();
+                current_day += 1;
+                let date_str = unit.date;
+
+                // STEP 1: データが既に存在するかチェック（キャッシュ優先戦略）
+                let existing_count = self.count_existing(data_type, &date_str).await;
+
+                if existing_count > 0 {
+                    // スキップ - 既にDBに存在
+                    let message = format!("📦 Skipping {} (already in DB)", date_str);
+                    eprintln!("{}", message);
+                    emit(message, current_day, &date_str, "cached");
+
+                    jobs.complete_unit(job_id, seq, UnitStatus::Skipped, None).await?;
+                    continue;
                 }
 
-                skipped_count += 1;
-                current_date += Duration::days(1);
-                continue;
+                // STEP 2: APIからデータ取得（レート制限・再試行はリクエストポリシーが行う）
+                let message = format!("🌐 Fetching {} for {}", data_type.as_str(), date_str);
+                eprintln!("{}", message);
+                emit(message, current_day, &date_str, "fetching");
+
+                let outcome = match self.fetch_data(data_type, &date_str).await {
+                    Ok(json_data) => {
+                        // STEP 3: データベースに保存
+                        match self.save_data(data_type, &date_str, &json_data).await {
+                            Ok(count) => {
+                                let message = format!("💾 Saved {} records for {}", count, date_str);
+                                eprintln!("{}", message);
+                                emit(message, current_day, &date_str, "saved");
+                                Ok(())
+                            }
+                            Err(e) => {
+                                let error_msg = format!("Database save error: {}", e);
+                                eprintln!("⚠️  {}: {}", date_str, error_msg);
+                                Err(error_msg)
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        // API取得失敗 - ログに記録して継続
+                        eprintln!("⚠️  Failed to fetch {}: {}", date_str, e);
+                        emit(format!("❌ Error: {}", e), current_day, &date_str, "error");
+                        Err(e.to_string())
+                    }
+                };
+
+                match outcome {
+                    Ok(()) => jobs.complete_unit(job_id, seq, UnitStatus::Done, None).await?,
+                    Err(error_msg) => {
+                        jobs.complete_unit(job_id, seq, UnitStatus::Failed, Some(&error_msg))
+                            .await?
+                    }
+                }
             }
+            Ok(final_status)
+        }
+        .await;
+        let final_status = jobs.pause_on_error(&run, outcome).await?;
 
-            // STEP 2: APIからデータ取得
-            let message = format!("🌐 Fetching {} for {}", data_type.as_str(), date_str);
-            println!("{}", message);
+        let job = jobs.finish(run, final_status).await?;
+
+        // 最終通知
+        let (completion_message, status) = Self::completion_message(&job, current_day);
+        eprintln!("{}", completion_message);
+        emit(completion_message, current_day, &params.end_date, status);
+
+        Self::bulk_summary(jobs, &job, total_days).await
+    }
+
+    /// 期間を指定して previews / results / programs をまとめて並行取得
+    ///
+    /// (日付, データ種別) ごとの作業単位をジョブとして登録してから実行する。
+    /// HTTP取得は最大 `concurrency` 件まで同時に行い、SQLite への書き込みは
+    /// 実行タスク（単一のライター）が取得の完了順に行う。
+    pub async fn fetch_all_data_bulk(
+        &self,
+        window: Option<tauri::Window>,
+        jobs: &JobService,
+        start_date: &str,  // YYYYMMDD形式
+        end_date: &str,    // YYYYMMDD形式
+        concurrency: usize,
+    ) -> Result<BulkFetchSummary, AppError> {
+        let job = Self::create_all_data_bulk_job(jobs, start_date, end_date, concurrency).await?;
+        self.run_bulk_job(window, jobs, job.id).await
+    }
 
+    /// 全データ種別の並行一括取得ジョブを登録する（実行は [`Self::run_bulk_job`]）
+    pub async fn create_all_data_bulk_job(
+        jobs: &JobService,
+        start_date: &str,  // YYYYMMDD形式
+        end_date: &str,    // YYYYMMDD形式
+        concurrency: usize,
+    ) -> Result<BulkJob, AppError> {
+        let dates = date_range(start_date, end_date)?;
+
+        let mut units = Vec::new();
+        for date in &dates {
+            for data_type in [ApiDataType::Previews, ApiDataType::Results, ApiDataType::Programs] {
+                units.push(OpenApiJobUnit {
+                    date: date.clone(),
+                    data_type: Some(data_type.as_str().to_string()),
+                });
+            }
+        }
+
+        let concurrency = concurrency.clamp(1, MAX_BULK_CONCURRENCY);
+        let params = OpenApiJobParams {
+            data_type: ALL_DATA_TYPES.to_string(),
+            start_date: start_date.to_string(),
+            end_date: end_date.to_string(),
+            concurrency: Some(concurrency),
+        };
+        let job = jobs.create_job(JobKind::OpenApiBulk, &params, &units).await?;
+
+        eprintln!(
+            "🔄 Starting concurrent bulk fetch job #{}: all types from {} to {} ({} days, concurrency {})",
+            job.id,
+            start_date,
+            end_date,
+            dates.len(),
+            concurrency
+        );
+        Ok(job)
+    }
+
+    /// 全データ種別の一括取得ジョブを並行実行
+    async fn run_concurrent_bulk_job(
+        &self,
+        window: Option<tauri::Window>,
+        jobs: &JobService,
+        run: JobRun,
+        job: BulkJob,
+        params: OpenApiJobParams,
+    ) -> Result<BulkFetchSummary, AppError> {
+        use tokio::sync::mpsc;
+
+        let job_id = job.id;
+        let concurrency = params
+            .concurrency
+            .unwrap_or(DEFAULT_BULK_CONCURRENCY)
+            .clamp(1, MAX_BULK_CONCURRENCY);
+        let total = job.total_units as usize;
+        let mut current = job.processed_units() as usize;
+        let emit = |message: String, current: usize, date: &str, data_type: &str, status: &str| {
             if let Some(ref w) = window {
                 w.emit(
                     "open-api-bulk-progress",
                     OpenApiBulkProgressPayload {
-                        message: message.clone(),
-                        current: current_day as usize,
-                        total: total_days as usize,
-                        date: date_str.clone(),
-                        data_type: data_type.as_str().to_string(),
-                        status: "fetching".to_string(),
+                        job_id,
+                        message,
+                        current,
+                        total,
+                        date: date.to_string(),
+                        data_type: data_type.to_string(),
+                        status: status.to_string(),
                     },
                 )
                 .ok();
             }
-
-            match self.fetch_data(data_type, &date_str).await {
-                Ok(json_data) => {
-                    // STEP 3: データベースに保存
-                    let save_result = match data_type {
-                        ApiDataType::Previews => {
-                            self.save_previews_data(&date_str, &json_data).await
-                        }
-                        ApiDataType::Results => {
-                            self.save_results_data(&date_str, &json_data).await
-                        }
-                        ApiDataType::Programs => {
-                            self.save_programs_data(&date_str, &json_data).await
-                        }
+        };
+
+        // 取得タスク → ライター（このタスク）への結果の受け渡し
+        let (tx, mut rx) = mpsc::channel(concurrency);
+        let pending = jobs.pending_units::<OpenApiJobUnit>(job_id).await;
+        let mut pending = jobs.pause_on_error(&run, pending).await?.into_iter();
+        let mut in_flight = 0;
+        let outcome = async {
+            let mut stop_status = None;
+
+            loop {
+                // 同時取得数の上限まで取得タスクを起動
+                while in_flight < concurrency && stop_status.is_none() {
+                    if let Some(stop) = run.stop_requested() {
+                        stop_status = Some(stop);
+                        break;
+                    }
+                    let Some((seq, unit)) = pending.next() else {
+                        break;
                     };
+                    let data_type = unit
+                        .data_type
+                        .as_deref()
+                        .and_then(ApiDataType::from_name)
+                        .ok_or_else(|| {
+                            AppError::invalid_input("data_type", format!("Unknown data_type in job unit {}", seq))
+                        })?;
+
+                    if self.count_existing(data_type, &unit.date).await > 0 {
+                        current += 1;
+                        let message = format!("📦 Skipping {} {} (already in DB)", data_type.as_str(), unit.date);
+                        eprintln!("{}", message);
+                        emit(message, current, &unit.date, data_type.as_str(), "cached");
+                        jobs.complete_unit(job_id, seq, UnitStatus::Skipped, None).await?;
+                        continue;
+                    }
 
-                    match save_result {
-                        Ok(count) => {
-                            let message = format!("💾 Saved {} records for {}", count, date_str);
-                            println!("{}", message);
-
-                            if let Some(ref w) = window {
-                                w.emit(
-                                    "open-api-bulk-progress",
-                                    OpenApiBulkProgressPayload {
-                                        message,
-                                        current: current_day as usize,
-                                        total: total_days as usize,
-                                        date: date_str.clone(),
-                                        data_type: data_type.as_str().to_string(),
-                                        status: "saved".to_string(),
-                                    },
-                                )
-                                .ok();
-                            }
+                    let message = format!("🌐 Fetching {} for {}", data_type.as_str(), unit.date);
+                    eprintln!("{}", message);
+                    emit(message, current, &unit.date, data_type.as_str(), "fetching");
+
+                    let url = self.build_url(data_type, &unit.date);
+                    let client = self.http_client.clone();
+                    let policy = self.policy.clone();
+                    let tx = tx.clone();
+                    tokio::spawn(async move {
+                        let result = Self::fetch_with_policy(&client, &policy, &url).await;
+                        // ライターがエラーで先に終了していれば結果は捨てる
+                        let _ = tx.send((seq, unit, data_type, result)).await;
+                    });
+                    in_flight += 1;
+                }
 
-                            success_count += 1;
-                        }
-                        Err(e) => {
-                            let error_msg = format!("Database save error: {}", e);
-                            println!("⚠️  {}: {}", date_str, error_msg);
-                            errors.push(BulkFetchError {
-                                date: date_str.clone(),
-                                error_message: error_msg,
-                            });
-                            error_count += 1;
-                        }
-                    }
+                if in_flight == 0 {
+                    break;
                 }
-                Err(e) => {
-                    // API取得失敗 - ログに記録して継続
-                    println!("⚠️  Failed to fetch {}: {}", date_str, e);
-
-                    if let Some(ref w) = window {
-                        w.emit(
-                            "open-api-bulk-progress",
-                            OpenApiBulkProgressPayload {
-                                message: format!("❌ Error: {}", e),
-                                current: current_day as usize,
-                                total: total_days as usize,
-                                date: date_str.clone(),
-                                data_type: data_type.as_str().to_string(),
-                                status: "error".to_string(),
-                            },
-                        )
-                        .ok();
-                    }
 
-                    errors.push(BulkFetchError {
-                        date: date_str.clone(),
-                        error_message: e,
-                    });
-                    error_count += 1;
+                // 単一ライター: 取得が終わった順に SQLite へ書き込む
+                let Some((seq, unit, data_type, result)) = rx.recv().await else {
+                    break;
+                };
+                in_flight -= 1;
+                current += 1;
+
+                let outcome = match result {
+                    Ok(json_data) => match self.save_data(data_type, &unit.date, &json_data).await {
+                        Ok(count) => {
+                            let message = format!(
+                                "💾 Saved {} {} records for {}",
+                                count,
+                                data_type.as_str(),
+                                unit.date
+                            );
+                            eprintln!("{}", message);
+                            emit(message, current, &unit.date, data_type.as_str(), "saved");
+                            Ok(())
+                        }
+                        Err(e) => Err(format!("Database save error: {}", e)),
+                    },
+                    Err(e) => Err(e.to_string()),
+                };
+
+                match outcome {
+                    Ok(()) => jobs.complete_unit(job_id, seq, UnitStatus::Done, None).await?,
+                    Err(error_msg) => {
+                        eprintln!("⚠️  {} {}: {}", unit.date, data_type.as_str(), error_msg);
+                        emit(
+                            format!("❌ Error: {}", error_msg),
+                            current,
+                            &unit.date,
+                            data_type.as_str(),
+                            "error",
+                        );
+                        jobs.complete_unit(job_id, seq, UnitStatus::Failed, Some(&error_msg))
+                            .await?
+                    }
                 }
             }
+            Ok(stop_status.unwrap_or(JobStatus::Completed))
+        }
+        .await;
+        let final_status = jobs.pause_on_error(&run, outcome).await?;
 
-            // STEP 4: レート制限（API負荷を避けるため）
-            if current_day < total_days {
-                sleep(TokioDuration::from_millis(500)).await;
-            }
+        let job = jobs.finish(run, final_status).await?;
 
-            current_date += Duration::days(1);
-        }
+        let (completion_message, status) = Self::completion_message(&job, current);
+        eprintln!("{}", completion_message);
+        emit(completion_message, current, &params.end_date, ALL_DATA_TYPES, status);
 
-        // 最終完了通知
-        let completion_message = format!(
-            "✅ Bulk fetch completed: {} success, {} skipped, {} errors",
-            success_count, skipped_count, error_count
-        );
-        println!("{}", completion_message);
-
-        if let Some(ref w) = window {
-            w.emit(
-                "open-api-bulk-progress",
-                OpenApiBulkProgressPayload {
-                    message: completion_message,
-                    current: total_days as usize,
-                    total: total_days as usize,
-                    date: end_date.to_string(),
-                    data_type: data_type.as_str().to_string(),
-                    status: "completed".to_string(),
-                },
-            )
-            .ok();
+        Self::bulk_summary(jobs, &job, total).await
+    }
+
+    /// ジョブ終了時の通知メッセージとステータス
+    fn completion_message(job: &BulkJob, current: usize) -> (String, &'static str) {
+        match job.status {
+            JobStatus::Paused => (
+                format!("⏸️ Bulk fetch job #{} paused at {}/{}", job.id, current, job.total_units),
+                "paused",
+            ),
+            JobStatus::Cancelled => (
+                format!("⏹️ Bulk fetch job #{} cancelled at {}/{}", job.id, current, job.total_units),
+                "cancelled",
+            ),
+            _ => (
+                format!(
+                    "✅ Bulk fetch completed: {} success, {} skipped, {} errors",
+                    job.done_units, job.skipped_units, job.failed_units
+                ),
+                "completed",
+            ),
         }
+    }
+
+    async fn bulk_summary(
+        jobs: &JobService,
+        job: &BulkJob,
+        total_days: usize,
+    ) -> Result<BulkFetchSummary, AppError> {
+        let errors = jobs
+            .failed_units::<OpenApiJobUnit>(job.id)
+            .await?
+            .into_iter()
+            .map(|(unit, error_message)| BulkFetchError {
+                date: unit.date,
+                data_type: unit.data_type,
+                error_message,
+            })
+            .collect();
 
         Ok(BulkFetchSummary {
-            total_days: total_days as usize,
-            success_count,
-            error_count,
-            skipped_count,
+            job_id: job.id,
+            status: job.status,
+            total_days,
+            success_count: job.done_units as usize,
+            error_count: job.failed_units as usize,
+            skipped_count: job.skipped_units as usize,
             errors,
         })
     }
 }
+
+/// モーター番号の一覧（上位に入ったモーターを下位から除くため）
+fn motor_numbers(motors: &[MotorStats]) -> Vec<i32> {
+    motors.iter().map(|motor| motor.motor_number).collect()
+}
+
+/// YYYYMMDD 形式の期間を日付のリストに展開
+fn date_range(start_date: &str, end_date: &str) -> Result<Vec<String>, AppError> {
+    use chrono::{Duration, NaiveDate};
+
+    let start = NaiveDate::parse_from_str(start_date, "%Y%m%d")
+        .map_err(|e| AppError::invalid_input("start_date", format!("Invalid start date: {}", e)))?;
+    let end = NaiveDate::parse_from_str(end_date, "%Y%m%d")
+        .map_err(|e| AppError::invalid_input("end_date", format!("Invalid end date: {}", e)))?;
+
+    let mut dates = Vec::new();
+    let mut current_date = start;
+    while current_date <= end {
+        dates.push(current_date.format("%Y%m%d").to_string());
+        current_date += Duration::days(1);
+    }
+    Ok(dates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{seeded_service, FIXTURE_DATE, FIXTURE_RACE_COUNT};
+
+    #[test]
+    fn test_date_range_spans_month_boundary() {
+        let dates = date_range("20250130", "20250202").unwrap();
+        assert_eq!(dates, vec!["20250130", "20250131", "20250201", "20250202"]);
+
+        assert!(date_range("20250202", "20250130").unwrap().is_empty());
+        assert!(date_range("2025-01-30", "20250202").is_err());
+    }
+
+    // ===== 展示データ検索テスト =====
+
+    #[tokio::test]
+    async fn test_bulk_job_is_paused_when_unit_update_fails() {
+        let (service, _server, path) = seeded_service("bulk_unit_error").await;
+        let jobs = JobService::new(service.job_repository()).await.unwrap();
+        // 作業単位の更新だけを失敗させる
+        sqlx::query(
+            "CREATE TRIGGER fail_unit_update BEFORE UPDATE ON bulk_job_units
+             BEGIN SELECT RAISE(ABORT, 'unit update failed'); END",
+        )
+        .execute(service.repository.pool())
+        .await
+        .unwrap();
+
+        let error = service
+            .fetch_data_bulk(None, &jobs, ApiDataType::Previews, FIXTURE_DATE, FIXTURE_DATE)
+            .await
+            .expect_err("complete_unit should fail");
+        assert!(matches!(error, AppError::DbError(_)));
+        let error = service
+            .fetch_all_data_bulk(None, &jobs, FIXTURE_DATE, FIXTURE_DATE, 2)
+            .await
+            .expect_err("complete_unit should fail");
+        assert!(matches!(error, AppError::DbError(_)));
+
+        // どちらのジョブも Running のまま残らず、再開できる
+        let failed: Vec<_> = jobs.list_jobs().await.unwrap().into_iter().take(2).collect();
+        for job in &failed {
+            assert_eq!(job.status, JobStatus::Paused, "job #{}", job.id);
+        }
+
+        sqlx::query("DROP TRIGGER fail_unit_update")
+            .execute(service.repository.pool())
+            .await
+            .unwrap();
+        for job in &failed {
+            service.run_bulk_job(None, &jobs, job.id).await.unwrap();
+            assert_eq!(jobs.get_job(job.id).await.unwrap().status, JobStatus::Completed);
+        }
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_search_races_by_exhibition_data() {
+        use crate::models::open_api::SearchParams;
+
+        let (service, _server, path) = seeded_service("exhibition_search").await;
+
+        // 展示タイム6.55秒以下の艇がいるレース: 07場12R（6.54）と 23場3R（6.52）
+        let params = SearchParams {
+            max_exhibition_time: Some(6.55),
+            ..Default::default()
+        };
+        let races = service.search_races_advanced(params).await.expect("Failed to search");
+        assert_eq!(races.len(), 2);
+
+        // 同じ艇に条件がかかる: チルト+2.0 かつ 展示6.52 は 23場3R の5号艇のみ
+        let params = SearchParams {
+            min_tilt_adjustment: Some(2.0),
+            max_exhibition_time: Some(6.52),
+            ..Default::default()
+        };
+        let races = service.search_races_advanced(params).await.expect("Failed to search");
+        assert_eq!(races.len(), 1);
+        let (race, participants) = &races[0];
+        assert_eq!((race.venue_code.as_str(), race.race_number), ("23", 3));
+        assert_eq!(race.preview_wind, Some(3.0));
+        assert!(race.trifecta_payout.is_some(), "結果のカラムは直前情報で上書きされない");
+
+        let boat5 = participants.iter().find(|p| p.boat_number == 5).unwrap();
+        assert_eq!(boat5.exhibition_time, Some(6.52));
+        assert_eq!(boat5.exhibition_course_number, Some(5));
+        assert_eq!(boat5.exhibition_start_timing, Some(0.31));
+        assert!(boat5.racer_name.is_some(), "選手情報は直前情報で上書きされない");
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    // ===== 全賭式の払戻テスト =====
+
+    #[tokio::test]
+    async fn test_search_and_stats_by_any_payout_combination() {
+        use crate::models::open_api::SearchParams;
+
+        let (service, _server, path) = seeded_service("race_payouts").await;
+
+        // 拡連複の2番目以降の組番でも検索できる（07場12R: 2=6, 5=6, 2=5）
+        let params = SearchParams {
+            bet_type: Some("quinella_place".to_string()),
+            combination: Some("5=6".to_string()),
+            min_payout: Some(2000),
+            ..Default::default()
+        };
+        let races = service.search_races_advanced(params).await.expect("Failed to search");
+        assert_eq!(races.len(), 1);
+        assert_eq!((races[0].0.venue_code.as_str(), races[0].0.race_number), ("07", 12));
+
+        // 複勝は2行とも保存されている
+        let stats = service
+            .get_combination_payout_stats("place", Some("07"), None)
+            .await
+            .expect("Failed to get combination stats");
+        assert_eq!(stats.len(), 2);
+        assert!(stats.iter().all(|s| s.hit_count == 1 && s.hit_rate == 1.0));
+
+        let stats = service.get_combination_payout_stats("trifecta", None, None).await.unwrap();
+        assert_eq!(stats.iter().map(|s| s.hit_count).sum::<i64>(), FIXTURE_RACE_COUNT as i64);
+        assert!(stats.iter().any(|s| s.combination == "6-2-5" && s.max_payout == Some(390200)));
+
+        let error = service.get_combination_payout_stats("trifecta_box", None, None).await;
+        assert!(matches!(error, Err(AppError::InvalidInput { .. })));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    // ===== コード値のラベルテスト =====
+
+    #[tokio::test]
+    async fn test_search_results_have_decoded_labels_and_decision_hand() {
+        use crate::models::open_api::SearchParams;
+
+        let (service, _server, path) = seeded_service("code_labels").await;
+
+        let params = SearchParams {
+            venue_code: Some("07".to_string()),
+            race_number: Some(12),
+            ..Default::default()
+        };
+        let races = service.search_races_advanced(params).await.expect("Failed to search");
+        let (race, participants) = &races[0];
+
+        assert_eq!(race.race_technique_label.as_ref().unwrap().ja, "まくり差し");
+        assert_eq!(race.race_wind_direction_label.as_ref().unwrap().en, "Calm");
+        assert_eq!(race.race_weather_label.as_ref().unwrap().ja, "晴");
+
+        // 決まり手は1着艇（6号艇）にのみ記録される
+        for participant in participants {
+            let expected = (participant.place_number == Some(1)).then_some("まくり差し");
+            assert_eq!(participant.decision_hand.as_deref(), expected);
+            assert!(participant.racer_class_label.is_some(), "級別は出走表から付与される");
+        }
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    // ===== 選手マスタテスト =====
+
+    #[tokio::test]
+    async fn test_racer_profile_from_saved_races() {
+        use crate::models::open_api::SearchParams;
+        use crate::models::race::{BoatRaceData, RaceData};
+
+        let (service, _server, path) = seeded_service("racer_profile").await;
+
+        let params = SearchParams {
+            venue_code: Some("07".to_string()),
+            race_number: Some(12),
+            ..Default::default()
+        };
+        let races = service.search_races_advanced(params).await.expect("Failed to search");
+        let winner = races[0]
+            .1
+            .iter()
+            .find(|participant| participant.place_number == Some(1))
+            .expect("winner should exist");
+        let racer_number = winner.racer_number.unwrap();
+
+        let profile = service.get_racer_profile(racer_number).await.expect("Failed to get profile");
+        let racer = profile.racer.as_ref().expect("出走表から選手マスタに登録される");
+        assert_eq!(racer.racer_name, winner.racer_name);
+        assert_eq!(racer.class_number, winner.racer_class_number);
+        assert_eq!(racer.last_seen_date.as_deref(), Some(FIXTURE_DATE));
+        assert_eq!(profile.class_history.len(), 1);
+        assert!(profile.starts >= 1);
+        assert!(profile.win_rate > 0.0);
+        assert!(profile.place_distribution.iter().any(|p| p.place_number == 1));
+        assert!(!profile.course_stats.is_empty());
+        assert_eq!(profile.venue_stats[0].venue_code, "07");
+
+        // 古い日付のスクレイピング結果では最新の級別を上書きせず、級別履歴にのみ追加される
+        let mut boat = BoatRaceData::new(1);
+        boat.player_basic_info.registration_number = racer_number.to_string();
+        boat.player_basic_info.class_level = "B2".to_string();
+        boat.player_basic_info.period = "100期".to_string();
+        let mut race_data = RaceData::new();
+        race_data.boats.push(boat);
+        let updated = service
+            .update_racers_from_race_data("2025-01-05", &race_data)
+            .await
+            .expect("Failed to update racers");
+        assert_eq!(updated, 1);
+
+        let profile = service.get_racer_profile(racer_number).await.unwrap();
+        let racer = profile.racer.unwrap();
+        assert_eq!(racer.class_number, winner.racer_class_number);
+        assert_eq!(racer.period, Some(100));
+        assert_eq!(profile.class_history.len(), 2);
+        assert_eq!(profile.class_history[0].term, "2025H1");
+
+        let error = service.get_racer_profile(1).await;
+        assert!(matches!(error, Err(AppError::InvalidInput { .. })));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    // ===== モーター成績テスト =====
+
+    #[tokio::test]
+    async fn test_motor_ranking_by_venue() {
+        let (service, _server, path) = seeded_service("motor_ranking").await;
+
+        // 23場は2レース分（12基）のモーターが集計される
+        let ranking = service
+            .get_motor_ranking("23", None, None, None, Some(1), Some(6))
+            .await
+            .expect("Failed to get motor ranking");
+        assert_eq!(ranking.venue_code, "23");
+        assert_eq!(ranking.season.as_deref(), Some("2025"));
+        assert_eq!(ranking.top.len(), 6);
+        assert_eq!(ranking.bottom.len(), 6);
+        assert_eq!(ranking.top[0].top2_rate, Some(1.0));
+        assert_eq!(ranking.bottom[0].top2_rate, Some(0.0));
+        let top_numbers = motor_numbers(&ranking.top);
+        assert!(ranking.bottom.iter().all(|motor| !top_numbers.contains(&motor.motor_number)));
+
+        // 上位に入ったモーターは下位に含めない（モーター数が limit の2倍未満の場合）
+        let ranking = service
+            .get_motor_ranking("23", None, None, None, Some(1), Some(12))
+            .await
+            .unwrap();
+        assert_eq!(ranking.top.len(), 12);
+        assert!(ranking.bottom.is_empty());
+        assert!(ranking.top.iter().all(|motor| motor.starts == 1 && motor.racer_count == 1));
+
+        // 展示タイムは直前情報から集計される（23場3R 5号艇 = モーター1番）
+        let season_motor = ranking.top.iter().find(|motor| motor.motor_number == 1).unwrap().clone();
+        assert_eq!(season_motor.best_exhibition_time, Some(6.52));
+
+        // 競艇場コードは0埋めなしでも受け付ける
+        let ranking = service.get_motor_ranking("7", Some("2025"), None, None, Some(1), Some(3)).await.unwrap();
+        assert_eq!(ranking.venue_code, "07");
+        assert_eq!(ranking.top.len(), 3);
+
+        // 出走数が足りないモーターは除外される
+        let ranking = service.get_motor_ranking("23", None, None, None, None, None).await.unwrap();
+        assert!(ranking.top.is_empty() && ranking.bottom.is_empty());
+
+        // 期間を指定すると年ではなくその期間で集計する（入れ替え後のモーターだけを見る場合）
+        let ranking = service
+            .get_motor_ranking("23", None, Some("2025-12-28"), None, Some(1), Some(12))
+            .await
+            .unwrap();
+        assert_eq!(ranking.season, None);
+        assert_eq!(ranking.date_from.as_deref(), Some(FIXTURE_DATE));
+        assert_eq!(ranking.top.len(), 12);
+        assert!(ranking.bottom.is_empty());
+        let motor = ranking.top.iter().find(|motor| motor.motor_number == 1).unwrap();
+        assert_eq!(motor.season, None);
+        assert_eq!(motor.top2_rate, season_motor.top2_rate);
+        assert_eq!(motor.best_exhibition_time, Some(6.52));
+        assert_eq!(motor.program_top_2_percent, season_motor.program_top_2_percent);
+        let ranking = service
+            .get_motor_ranking("23", None, None, Some("20251227"), Some(1), None)
+            .await
+            .unwrap();
+        assert!(ranking.top.is_empty(), "期間外のレースは集計しない");
+
+        let error = service.get_motor_ranking("23", Some("2025"), Some(FIXTURE_DATE), None, None, None).await;
+        assert!(matches!(error, Err(AppError::InvalidInput { .. })));
+        let error = service.get_motor_ranking("25", None, None, None, None, None).await;
+        assert!(matches!(error, Err(AppError::InvalidInput { .. })));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    // ===== バックテストテスト =====
+
+    #[tokio::test]
+    async fn test_backtest_over_saved_results() {
+        use crate::backtest::{BacktestStrategy, CombinationRule, StakeRule};
+
+        let (service, _server, path) = seeded_service("backtest").await;
+
+        // 2=6 / 5=6 の拡連複を全4レースで100円ずつ購入（07場12Rのみ2点とも的中）
+        let strategy = BacktestStrategy {
+            filters: Default::default(),
+            bet_type: "quinella_place".to_string(),
+            combinations: CombinationRule::Fixed {
+                combinations: vec!["2=6".to_string(), "5=6".to_string()],
+            },
+            stake: StakeRule::Flat { amount: 100 },
+            initial_bankroll: Some(10_000),
+        };
+        let report = service
+            .run_backtest(&strategy, Some(FIXTURE_DATE), Some(FIXTURE_DATE))
+            .await
+            .expect("Failed to run backtest");
+        assert_eq!(report.race_count, FIXTURE_RACE_COUNT as i64);
+        assert_eq!(report.bet_count, 2 * FIXTURE_RACE_COUNT as i64);
+        assert_eq!(report.total_stake, 200 * FIXTURE_RACE_COUNT as i64);
+        assert!(report.hit_count >= 1);
+        assert_eq!(report.profit, report.total_return - report.total_stake);
+        assert_eq!(report.final_bankroll, Some(10_000 + report.profit));
+        assert_eq!(report.venues.iter().map(|venue| venue.race_count).sum::<i64>(), report.race_count);
+        let venue = report.venues.iter().find(|venue| venue.venue_code == "07").unwrap();
+        assert!(venue.total_return >= 2630);
+
+        // 条件は複合条件検索と同じ（範囲外の日付は対象なし）
+        let report = service.run_backtest(&strategy, Some("20250101"), Some("20250131")).await.unwrap();
+        assert_eq!(report.race_count, 0);
+        assert_eq!(report.roi, None);
+
+        let mut invalid = strategy.clone();
+        invalid.bet_type = "trifecta".to_string();
+        let error = service.run_backtest(&invalid, None, None).await;
+        assert!(matches!(error, Err(AppError::InvalidInput { .. })), "拡連複の組番は3連単には使えない");
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    // ===== 期待値計算テスト =====
+
+    #[tokio::test]
+    async fn test_expected_values_from_saved_history() {
+        use crate::models::race::{BettingType, OddsCombination, OddsData};
+
+        let (service, _server, path) = seeded_service("expected_value").await;
+        // 対象の23場3Rを翌日に移し、残りの3レースを前日までの結果として使う
+        let move_race = |venue_code: &'static str, race_number: i32, race_date: &'static str| {
+            let pool = service.repository.pool().clone();
+            async move {
+                sqlx::query("UPDATE races SET race_date = ? WHERE venue_code = ? AND race_number = ?")
+                    .bind(race_date)
+                    .bind(venue_code)
+                    .bind(race_number)
+                    .execute(&pool)
+                    .await
+                    .unwrap();
+            }
+        };
+        move_race("23", 3, "20251229").await;
+
+        let odds = OddsData {
+            betting_type: BettingType::WinPlace,
+            combinations: (1..=6)
+                .flat_map(|boat| {
+                    [
+                        OddsCombination { first: boat, second: 0, third: None, odds: 10.0, is_combined: false, range_text: None },
+                        OddsCombination {
+                            first: boat,
+                            second: 1,
+                            third: None,
+                            odds: 2.0,
+                            is_combined: true,
+                            range_text: Some("1.5-2.5".to_string()),
+                        },
+                    ]
+                })
+                .collect(),
+        };
+        let report = service
+            .calculate_expected_values("2025-12-29", "23", 3, &odds)
+            .await
+            .expect("Failed to calculate expected values");
+
+        assert_eq!(report.race_date, "20251229");
+        assert_eq!(report.boats.len(), 6);
+        assert!(report.sample_starts > 0, "対象レース以外の結果から集計される");
+        let total: f64 = report.boats.iter().map(|boat| boat.win_probability).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        // 展示進入コースがあればそれを使う
+        assert!(report.boats.iter().all(|boat| (1..=6).contains(&boat.course_number)));
+
+        assert_eq!(report.bets.len(), 12);
+        assert!(report.bets.windows(2).all(|w| w[0].expected_value >= w[1].expected_value));
+        for bet in &report.bets {
+            assert_eq!(bet.is_overlay, bet.expected_value > 1.0);
+            assert_eq!(bet.kelly_fraction > 0.0, bet.is_overlay);
+            if bet.bet_type == "place" {
+                assert_eq!(bet.odds, 1.5);
+            }
+        }
+
+        // 対象レースより後の結果は推定に使わない（使うと過去のレースの評価に先の結果が混ざる）
+        move_race("07", 12, "20251230").await;
+        let later = service.calculate_expected_values("20251229", "23", 3, &odds).await.unwrap();
+        assert!(later.sample_starts < report.sample_starts);
+        assert_ne!(
+            later.boats.iter().map(|boat| boat.win_probability).collect::<Vec<_>>(),
+            report.boats.iter().map(|boat| boat.win_probability).collect::<Vec<_>>()
+        );
+
+        let error = service.calculate_expected_values(FIXTURE_DATE, "23", 12, &odds).await;
+        assert!(matches!(error, Err(AppError::InvalidInput { .. })));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    // ===== オッズ時系列テスト =====
+
+    #[tokio::test]
+    async fn test_odds_snapshots_and_movement() {
+        use crate::models::race::{BettingType, OddsCombination, OddsData};
+
+        let (service, _server, path) = seeded_service("odds_movement").await;
+
+        let odds = |win: [f64; 2]| OddsData {
+            betting_type: BettingType::WinPlace,
+            combinations: (1..=2)
+                .map(|boat| OddsCombination {
+                    first: boat,
+                    second: 0,
+                    third: None,
+                    odds: win[boat as usize - 1],
+                    is_combined: false,
+                    range_text: None,
+                })
+                .collect(),
+        };
+        let first = service
+            .save_odds_snapshot("2025-12-28", "23", 3, &odds([3.0, 5.0]))
+            .await
+            .expect("Failed to save snapshot");
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        service.save_odds_snapshot(FIXTURE_DATE, "23", 3, &odds([1.5, 6.0])).await.unwrap();
+
+        let report = service
+            .get_odds_movement(FIXTURE_DATE, "23", 3, 5)
+            .await
+            .expect("Failed to get odds movement");
+        assert_eq!(report.snapshot_count, 2);
+        assert_eq!(report.first_captured_at.as_deref(), Some(first.as_str()));
+        assert_eq!(report.series.len(), 2);
+        assert_eq!(report.series[1].points.len(), 2);
+        // 下がった買い目のみ（1号艇 3.0 → 1.5）
+        assert_eq!(report.biggest_drops.len(), 1);
+        assert_eq!(report.biggest_drops[0].boat_number, 1);
+
+        let closed_at = service.get_race_closed_at(FIXTURE_DATE, "23", 3).await.unwrap();
+        assert!(closed_at.as_deref().and_then(crate::odds_history::parse_closed_at).is_some());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    // ===== レースビューテスト =====
+
+    #[tokio::test]
+    async fn test_race_view_joins_biyori_stats() {
+        use crate::models::race::RaceData;
+        use crate::repositories::local_db::LocalDbRepository;
+
+        let (service, _server, path) = seeded_service("race_view").await;
+        let cache = LocalDbRepository::open(path.to_str().unwrap()).await.unwrap();
+        let biyori = |escape: f64| RaceData {
+            escape_last_year: escape,
+            escape_last_half_year: escape,
+            ..Default::default()
+        };
+        // スクレイピング側の日付・場番号の形式で保存
+        cache.save_race_data("2025-12-28", 23, 3, &biyori(0.6)).await.unwrap();
+        cache.save_race_data("2025-12-28", 23, 1, &biyori(0.2)).await.unwrap();
+        cache.save_race_data("2025-12-28", 5, 1, &biyori(0.5)).await.unwrap();
+
+        let view = service.get_race_view(FIXTURE_DATE, "23", 3).await.expect("Failed to get race view");
+        assert_eq!((view.race_date.as_str(), view.venue_code.as_str(), view.race_number), ("20251228", "23", 3));
+        assert!(view.has_program && view.has_result);
+        assert_eq!(view.participants.len(), 6);
+        assert_eq!(view.biyori.as_ref().map(|b| b.escape_last_half_year), Some(0.6));
+        // 6号艇のまくり差し：逃げると予想して外れ
+        let check = view.escape_check.expect("escape check");
+        assert!(check.predicted_escape && !check.actual_escape && !check.prediction_hit);
+        assert_eq!(check.winner_boat_number, Some(6));
+
+        // kyoteibiyori のデータだけのレース
+        let view = service.get_race_view("2025-12-28", "5", 1).await.unwrap();
+        assert!(view.race.is_none() && view.biyori.is_some() && view.escape_check.is_none());
+        assert!(matches!(
+            service.get_race_view(FIXTURE_DATE, "05", 2).await,
+            Err(AppError::InvalidInput { .. })
+        ));
+
+        let report = service
+            .get_escape_calibration(Some("2025-12-28"), Some(FIXTURE_DATE), Some("23"), None)
+            .await
+            .unwrap();
+        assert_eq!((report.races, report.escapes), (2, 0));
+        assert!((report.prediction_accuracy - 0.5).abs() < 1e-9);
+        assert!(service.get_escape_calibration(None, None, None, Some("weekly")).await.is_err());
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -1,34 +1,65 @@
+use crate::error::AppError;
 use crate::fetcher;
+use crate::page_fetcher::{self, PageFetcher};
 use crate::parse::official;
+use crate::models::race_key::RaceKey;
 use crate::models::venue::{ActiveRace, RaceVenue, AllVenuesResponse, VenueStatus};
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
-pub struct ScheduleService;
+pub struct ScheduleService {
+    fetcher: Arc<dyn PageFetcher>,
+}
+
+impl Default for ScheduleService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl ScheduleService {
-    pub async fn get_monthly_schedule() -> Result<official::MonthlySchedule, String> {
-        let current_month = chrono::Local::now().format("%Y%m").to_string();
-        let file_path = format!("bort-html/monthly_schedule_{}.html", current_month);
+    pub fn new() -> Self {
+        Self::with_fetcher(page_fetcher::default_http_fetcher())
+    }
+
+    /// ページ取得手段を指定して生成（リプレイでのテストなど）
+    pub fn with_fetcher(fetcher: Arc<dyn PageFetcher>) -> Self {
+        ScheduleService { fetcher }
+    }
+
+    pub async fn get_monthly_schedule(&self) -> Result<official::MonthlySchedule, AppError> {
+        let file_path = format!("bort-html/{}", fetcher::monthly_schedule_file_name());
 
         // 1. 必要に応じてHTMLを取得
         if std::fs::metadata(&file_path).is_err() {
-            fetcher::fetch_and_cache_monthly_schedule().await?;
+            fetcher::fetch_and_cache_monthly_schedule(Arc::clone(&self.fetcher)).await?;
         }
 
         // 2. ファイルを直接パース（ファイルI/Oは別スレッドで実行）
         tokio::task::spawn_blocking(move || {
             let html = std::fs::read_to_string(&file_path)
-                .map_err(|e| format!("ファイル読み込みエラー: {}", e))?;
+                .map_err(|e| AppError::io(format!("ファイル読み込みエラー: {}", e)))?;
 
-            official::parse_monthly_schedule(&html).map_err(|e| format!("パースエラー: {}", e))
+            official::parse_monthly_schedule(&html)
+                .map_err(|e| AppError::from_parser("monthly_schedule", e))
         })
-        .await
-        .map_err(|e| format!("Task execution error: {}", e))?
+        .await?
+    }
+
+    /// 公式サイトの出走表を取得（date は YYYYMMDD / YYYY-MM-DD、venue は "01" / "1"）
+    pub async fn get_race_card(
+        &self,
+        date: &str,
+        venue: &str,
+        race_number: u32,
+    ) -> Result<official::RaceCard, AppError> {
+        let key = RaceKey::parse(date, venue, race_number)?;
+        fetcher::fetch_racelist(Arc::clone(&self.fetcher), key).await
     }
 
-    pub async fn get_active_races() -> Result<ActiveRace, String> {
+    pub async fn get_active_races(&self) -> Result<ActiveRace, AppError> {
         // 月間スケジュールを取得してパース
-        let monthly_schedule = Self::get_monthly_schedule().await?;
+        let monthly_schedule = self.get_monthly_schedule().await?;
 
         // 今日開催中の競艇場を抽出
         let today = chrono::Local::now().date_naive();
@@ -39,7 +70,7 @@ impl ScheduleService {
         for event in monthly_schedule.events {
             // イベントの開始日と終了日を計算
             let start_date = chrono::NaiveDate::parse_from_str(&event.start_date, "%Y-%m-%d")
-                .map_err(|e| format!("日付パースエラー: {}", e))?;
+                .map_err(|e| AppError::parse("start_date", e))?;
             let end_date = start_date + chrono::Duration::days(event.duration_days as i64 - 1);
 
             // 今日がイベント期間内かチェック
@@ -64,9 +95,9 @@ impl ScheduleService {
         })
     }
 
-    pub async fn get_all_venues_with_status() -> Result<AllVenuesResponse, String> {
+    pub async fn get_all_venues_with_status(&self) -> Result<AllVenuesResponse, AppError> {
         // 今日開催中の競艇場を取得
-        let active_races = Self::get_active_races().await?;
+        let active_races = self.get_active_races().await?;
         let today = active_races.date;
 
         // 全競艇場マスターデータを取得
@@ -1,392 +1,925 @@
+use crate::error::AppError;
+use crate::repositories::html_archive::HtmlArchive;
 use crate::repositories::local_db::LocalDbRepository;
-use crate::headress;
+use crate::page_fetcher::{self, PageFetcher, PageRequest};
 use crate::parse::biyori::flame;
+use crate::services::job_service::JobService;
+use crate::headress::WIN_PLACE_ODDS_SLIDER;
+use crate::cache_policy::{self, CacheKind, CachePolicy};
+use crate::odds_history;
+use crate::models::archive::{ArchiveEntry, ReparseSummary};
+use crate::models::job::{JobKind, JobStatus, ScrapingJobParams, ScrapingJobUnit, UnitStatus};
 use crate::models::race::{RaceData, OddsData, BulkRaceData};
 use crate::models::venue::BulkProgressPayload;
+use chrono::NaiveDateTime;
+use std::collections::BTreeMap;
+use std::sync::Arc;
 use tauri::Emitter;
 
-pub struct ScrapingService;
+pub struct ScrapingService {
+    fetcher: Arc<dyn PageFetcher>,
+    archive: Arc<HtmlArchive>,
+    cache_policy: CachePolicy,
+    /// スクレイピング結果のキャッシュ（Open API サービスと同じ接続プールを使う）
+    cache: Option<LocalDbRepository>,
+}
+
+impl Default for ScrapingService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl ScrapingService {
-    pub fn get_race_info(
+    pub fn new() -> Self {
+        Self::with_fetcher(page_fetcher::default_browser_fetcher())
+    }
+
+    /// ページ取得手段を指定して生成（リプレイでのテストなど）
+    pub fn with_fetcher(fetcher: Arc<dyn PageFetcher>) -> Self {
+        ScrapingService {
+            fetcher,
+            archive: Arc::new(HtmlArchive::default()),
+            cache_policy: CachePolicy::default(),
+            cache: None,
+        }
+    }
+
+    /// 取得元HTMLのアーカイブ先を指定（テストで一時ディレクトリに保存する）
+    #[cfg(test)]
+    pub(crate) fn with_archive(mut self, archive: HtmlArchive) -> Self {
+        self.archive = Arc::new(archive);
+        self
+    }
+
+    /// キャッシュを指定（`OpenApiService::scrape_cache` で開いているDBの接続を共有する）
+    pub fn with_cache(mut self, cache: LocalDbRepository) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    fn cache(&self) -> Result<&LocalDbRepository, AppError> {
+        self.cache.as_ref().ok_or_else(|| {
+            AppError::NotInitialized(
+                "Scrape cache not configured. Call init_open_api_service first.".to_string(),
+            )
+        })
+    }
+
+    /// ページを取得して生HTMLをアーカイブする（アーカイブ失敗は取得結果に影響させない）
+    fn fetch_and_archive(
+        fetcher: &dyn PageFetcher,
+        archive: &HtmlArchive,
+        request: &PageRequest,
+    ) -> Result<(String, Option<ArchiveEntry>), AppError> {
+        let html = fetcher.fetch(request)?;
+        let source = match archive.store(request, &html) {
+            Ok(entry) => {
+                eprintln!("🗄️ HTMLをアーカイブ: {}", entry.path);
+                Some(entry)
+            }
+            Err(e) => {
+                eprintln!("⚠️ HTMLアーカイブエラー: {}", e);
+                None
+            }
+        };
+        Ok((html, source))
+    }
+
+    fn fetch_page(&self, request: &PageRequest) -> Result<(String, Option<ArchiveEntry>), AppError> {
+        Self::fetch_and_archive(self.fetcher.as_ref(), &self.archive, request)
+    }
+
+    /// ページ取得は同期処理なので、非同期処理の中では別スレッドで実行する
+    async fn fetch_page_blocking(
+        &self,
+        request: PageRequest,
+    ) -> Result<(String, Option<ArchiveEntry>), AppError> {
+        let fetcher = Arc::clone(&self.fetcher);
+        let archive = Arc::clone(&self.archive);
+        tokio::task::spawn_blocking(move || {
+            Self::fetch_and_archive(fetcher.as_ref(), &archive, &request)
+        })
+        .await?
+    }
+
+    /// 保存したパース結果と取得元HTMLを紐付ける
+    async fn record_source(
+        repo: &LocalDbRepository,
+        date: &str,
+        place_number: u32,
+        race_number: u32,
+        kind: &str,
+        source: Option<&ArchiveEntry>,
+    ) {
+        if let Some(entry) = source {
+            if let Err(e) = repo.save_source_page(date, place_number, race_number, kind, entry).await {
+                eprintln!("⚠️ 取得元HTMLの記録エラー: {}", e);
+            }
+        }
+    }
+
+    /// 保存済みデータが鮮度ポリシー上まだ使えるか（closes_at はオッズの締切時刻）
+    async fn is_cache_fresh(
+        &self,
+        repo: &LocalDbRepository,
+        date: &str,
+        place_number: u32,
+        race_number: u32,
+        kind: CacheKind,
+        closes_at: Option<NaiveDateTime>,
+    ) -> bool {
+        let Some(race_date) = cache_policy::parse_cache_date(date) else {
+            return false;
+        };
+        let fetched_at = repo
+            .get_fetched_at(date, place_number, race_number, kind.as_str())
+            .await
+            .ok()
+            .flatten()
+            .and_then(|fetched_at| cache_policy::fetched_at_jst(&fetched_at));
+        self.cache_policy
+            .is_fresh(kind, race_date, fetched_at, closes_at, odds_history::now_jst())
+    }
+
+    /// レースデータを取得（鮮度ポリシー上まだ使えるキャッシュがあればそれを返す）
+    ///
+    /// `force_refresh` ではキャッシュを使わずに取得し直す。
+    pub async fn get_race_info(
+        &self,
         date: &str,
         race_number: u32,
         place_number: u32,
-    ) -> Result<RaceData, String> {
-        let repo = LocalDbRepository::new()
-            .map_err(|e| format!("Database initialization error: {}", e))?;
+        force_refresh: bool,
+    ) -> Result<RaceData, AppError> {
+        let repo = self.cache()?;
 
         // 1. まずデータベースから取得を試行
-        match repo.get_race_data(date, place_number, race_number) {
-            Ok(Some(cached_data)) => {
-                println!(
-                    "📦 キャッシュからレースデータを取得: {}-{}-{}",
-                    date, place_number, race_number
-                );
-                return Ok(cached_data);
-            }
-            Ok(None) => {
-                println!(
-                    "🌐 キャッシュにデータなし、スクレイピング実行: {}-{}-{}",
-                    date, place_number, race_number
-                );
-            }
-            Err(err) => {
-                println!("⚠️ データベース取得エラー、スクレイピング実行: {}", err);
+        if force_refresh {
+            eprintln!(
+                "🔄 キャッシュを使わずにスクレイピング実行: {}-{}-{}",
+                date, place_number, race_number
+            );
+        } else {
+            match repo.get_race_data(date, place_number, race_number).await {
+                Ok(Some(cached_data))
+                    if self.is_cache_fresh(repo, date, place_number, race_number, CacheKind::RaceData, None).await =>
+                {
+                    eprintln!(
+                        "📦 キャッシュからレースデータを取得: {}-{}-{}",
+                        date, place_number, race_number
+                    );
+                    return Ok(cached_data);
+                }
+                Ok(Some(_)) => {
+                    eprintln!(
+                        "♻️ キャッシュの期限切れ、スクレイピング実行: {}-{}-{}",
+                        date, place_number, race_number
+                    );
+                }
+                Ok(None) => {
+                    eprintln!(
+                        "🌐 キャッシュにデータなし、スクレイピング実行: {}-{}-{}",
+                        date, place_number, race_number
+                    );
+                }
+                Err(err) => {
+                    eprintln!("⚠️ データベース取得エラー、スクレイピング実行: {}", err);
+                }
             }
         }
 
         // 2. キャッシュにない場合はスクレイピング実行
         let date_str = date.replace("-", "");
         let slider = 1; // 枠別情報
-        let result =
-            headress::fetch_shusso_info_from_kyoteibiyori(race_number, place_number, &date_str, slider);
-        if result.is_err() {
-            return Err(format!("an error occurred: {}", result.unwrap_err()));
-        }
+        let (html_content, source) = self
+            .fetch_page_blocking(PageRequest::BiyoriShusso {
+                race_no: race_number,
+                place_no: place_number,
+                date: date_str,
+                slider,
+            })
+            .await?;
 
-        let race_data = flame::get_escaped_flame_info(&result.unwrap());
-        match race_data {
-            Ok(data) => {
-                // 3. 取得したデータをデータベースに保存
-                if let Err(save_err) = repo.save_race_data(date, place_number, race_number, &data) {
-                    println!("⚠️ データベース保存エラー: {}", save_err);
-                } else {
-                    println!(
-                        "💾 レースデータをデータベースに保存: {}-{}-{}",
-                        date, place_number, race_number
-                    );
-                }
-                Ok(data)
-            }
-            Err(err) => Err(format!("an error occurred: {}", err)),
+        let data = flame::get_escaped_flame_info(&html_content)
+            .map_err(|err| AppError::from_parser("race_data", err))?;
+
+        // 3. 取得したデータをデータベースに保存
+        if let Err(save_err) = repo.save_race_data(date, place_number, race_number, &data).await {
+            eprintln!("⚠️ データベース保存エラー: {}", save_err);
+        } else {
+            Self::record_source(repo, date, place_number, race_number, "data", source.as_ref()).await;
+            eprintln!(
+                "💾 レースデータをデータベースに保存: {}-{}-{}",
+                date, place_number, race_number
+            );
         }
+        Ok(data)
     }
 
     pub fn get_odds_html(
+        &self,
         date: &str,
         race_number: u32,
         place_number: u32,
-    ) -> Result<String, String> {
+    ) -> Result<String, AppError> {
         let date_str = date.replace("-", "");
-        let result = headress::fetch_odds_info_from_kyoteibiyori(race_number, place_number, &date_str);
-        match result {
-            Ok(html_content) => Ok(html_content),
-            Err(err) => Err(format!("an error occurred: {}", err)),
-        }
+        self.fetch_page(&PageRequest::BiyoriWinPlaceOdds {
+            race_no: race_number,
+            place_no: place_number,
+            date: date_str,
+        })
+        .map(|(html_content, _)| html_content)
     }
 
-    pub fn get_win_place_odds(
+    /// 単勝・複勝オッズを取得（締切前のキャッシュは短い期限で取得し直す）
+    ///
+    /// `race_closed_at`（"YYYY-MM-DD HH:MM:SS"）が不明な場合はレース日の終わりを締切とみなす。
+    pub async fn get_win_place_odds(
+        &self,
         date: &str,
         race_number: u32,
         place_number: u32,
-    ) -> Result<OddsData, String> {
-        let repo = LocalDbRepository::new()
-            .map_err(|e| format!("Database initialization error: {}", e))?;
+        force_refresh: bool,
+        race_closed_at: Option<&str>,
+    ) -> Result<OddsData, AppError> {
+        let repo = self.cache()?;
 
         // 1. まずデータベースから取得を試行
-        match repo.get_odds_data(date, place_number, race_number) {
-            Ok(Some(cached_odds)) => {
-                println!(
-                    "📦 キャッシュからオッズデータを取得: {}-{}-{}",
-                    date, place_number, race_number
-                );
-                return Ok(cached_odds);
-            }
-            Ok(None) => {
-                println!(
-                    "🌐 キャッシュにデータなし、スクレイピング実行: {}-{}-{}",
-                    date, place_number, race_number
-                );
-            }
-            Err(err) => {
-                println!("⚠️ データベース取得エラー、スクレイピング実行: {}", err);
+        if force_refresh {
+            eprintln!(
+                "🔄 キャッシュを使わずにスクレイピング実行: {}-{}-{}",
+                date, place_number, race_number
+            );
+        } else {
+            let closes_at = race_closed_at.and_then(odds_history::parse_closed_at);
+            match repo.get_odds_data(date, place_number, race_number).await {
+                Ok(Some(cached_odds))
+                    if self.is_cache_fresh(repo, date, place_number, race_number, CacheKind::Odds, closes_at).await =>
+                {
+                    eprintln!(
+                        "📦 キャッシュからオッズデータを取得: {}-{}-{}",
+                        date, place_number, race_number
+                    );
+                    return Ok(cached_odds);
+                }
+                Ok(Some(_)) => {
+                    eprintln!(
+                        "♻️ キャッシュの期限切れ、スクレイピング実行: {}-{}-{}",
+                        date, place_number, race_number
+                    );
+                }
+                Ok(None) => {
+                    eprintln!(
+                        "🌐 キャッシュにデータなし、スクレイピング実行: {}-{}-{}",
+                        date, place_number, race_number
+                    );
+                }
+                Err(err) => {
+                    eprintln!("⚠️ データベース取得エラー、スクレイピング実行: {}", err);
+                }
             }
         }
 
-        // 2. キャッシュにない場合はスクレイピング実行
+        // 2. キャッシュにない・期限切れの場合はスクレイピング実行
+        self.scrape_win_place_odds(repo, date, race_number, place_number).await
+    }
+
+    /// キャッシュを使わずに単勝・複勝オッズを取得し、キャッシュを最新の値で更新する
+    ///
+    /// オッズは締切まで変動するため、時系列の記録（オッズ監視）ではこちらを使う。
+    pub async fn refresh_win_place_odds(
+        &self,
+        date: &str,
+        race_number: u32,
+        place_number: u32,
+    ) -> Result<OddsData, AppError> {
+        let repo = self.cache()?;
+        self.scrape_win_place_odds(repo, date, race_number, place_number).await
+    }
+
+    async fn scrape_win_place_odds(
+        &self,
+        repo: &LocalDbRepository,
+        date: &str,
+        race_number: u32,
+        place_number: u32,
+    ) -> Result<OddsData, AppError> {
         let date_str = date.replace("-", "");
-        let html_result = headress::fetch_odds_info_from_kyoteibiyori(race_number, place_number, &date_str);
-        let html_content = match html_result {
-            Ok(content) => content,
-            Err(err) => return Err(format!("単勝・複勝HTML取得エラー: {}", err)),
-        };
+        let (html_content, source) = self
+            .fetch_page_blocking(PageRequest::BiyoriWinPlaceOdds {
+                race_no: race_number,
+                place_no: place_number,
+                date: date_str,
+            })
+            .await?;
 
         // 単勝・複勝オッズデータを解析
-        let odds_result = flame::parse_win_place_odds_from_html(&html_content);
-        match odds_result {
-            Ok(odds_data) => {
-                // 3. 取得したデータをデータベースに保存
-                if let Err(save_err) =
-                    repo.save_odds_data(date, place_number, race_number, &odds_data)
-                {
-                    println!("⚠️ データベース保存エラー: {}", save_err);
-                } else {
-                    println!(
-                        "💾 オッズデータをデータベースに保存: {}-{}-{}",
-                        date, place_number, race_number
-                    );
-                }
-                Ok(odds_data)
-            }
-            Err(err) => Err(format!("単勝・複勝オッズ解析エラー: {}", err)),
+        let odds_data = flame::parse_win_place_odds_from_html(&html_content)
+            .map_err(|err| AppError::from_parser("win_place_odds", err))?;
+
+        // 3. 取得したデータをデータベースに保存
+        if let Err(save_err) =
+            repo.save_odds_data(date, place_number, race_number, &odds_data).await
+        {
+            eprintln!("⚠️ データベース保存エラー: {}", save_err);
+        } else {
+            Self::record_source(repo, date, place_number, race_number, "odds", source.as_ref()).await;
+            eprintln!(
+                "💾 オッズデータをデータベースに保存: {}-{}-{}",
+                date, place_number, race_number
+            );
         }
+        Ok(odds_data)
     }
 
+    /// 日付・会場・レースを指定して一括取得する
+    ///
+    /// レースごとの作業単位をジョブとして登録してから実行する。
+    /// 一時停止・中断したジョブは [`Self::run_bulk_job`] で未処理のレースから再開できる。
     pub async fn get_bulk_race_data(
+        &self,
         window: Option<tauri::Window>,
-        start_date: &str,
-        end_date: &str,
-        place_numbers: Vec<u32>,
-        race_numbers: Vec<u32>,
-    ) -> Result<Vec<BulkRaceData>, String> {
+        jobs: &JobService,
+        params: ScrapingJobParams,
+    ) -> Result<Vec<BulkRaceData>, AppError> {
         use chrono::{Duration, NaiveDate};
-        use tokio::time::{sleep, Duration as TokioDuration};
-
-        let repo = LocalDbRepository::new()
-            .map_err(|e| format!("Database initialization error: {}", e))?;
-
-        let mut all_results = Vec::new();
 
         // 日付範囲を生成
-        let start = NaiveDate::parse_from_str(start_date, "%Y-%m-%d")
-            .map_err(|e| format!("Invalid start date format: {}", e))?;
-        let end = NaiveDate::parse_from_str(end_date, "%Y-%m-%d")
-            .map_err(|e| format!("Invalid end date format: {}", e))?;
+        let start = NaiveDate::parse_from_str(&params.start_date, "%Y-%m-%d")
+            .map_err(|e| AppError::invalid_input("start_date", format!("Invalid start date format: {}", e)))?;
+        let end = NaiveDate::parse_from_str(&params.end_date, "%Y-%m-%d")
+            .map_err(|e| AppError::invalid_input("end_date", format!("Invalid end date format: {}", e)))?;
 
+        // 各日付・競艇場・レースの組み合わせを作業単位にする
+        let mut units = Vec::new();
         let mut current_date = start;
-
-        // 総処理数を計算
-        let days = (end - start).num_days() + 1;
-        let total_tasks = (days as usize) * place_numbers.len() * race_numbers.len();
-        let mut current_task = 0;
-
         while current_date <= end {
             let date_str = current_date.format("%Y-%m-%d").to_string();
-            let date_str_no_dash = current_date.format("%Y%m%d").to_string();
-
-            // 各競艇場とレースの組み合わせを処理
-            for &place_number in &place_numbers {
-                for &race_number in &race_numbers {
-                    current_task += 1;
-
-                    let mut bulk_data = BulkRaceData {
+            for &place_number in &params.place_numbers {
+                for &race_number in &params.race_numbers {
+                    units.push(ScrapingJobUnit {
                         date: date_str.clone(),
                         place_number,
                         race_number,
-                        race_data: None,
-                        win_place_odds_data: None,
-                        error: None,
-                    };
-
-                    // レースデータを取得（キャッシュ優先）
-                    match repo.get_race_data(&date_str, place_number, race_number) {
-                        Ok(Some(cached_race_data)) => {
-                            let message = format!(
-                                "📦 キャッシュからレースデータを取得: {}-{}-{}",
-                                date_str, place_number, race_number
-                            );
-                            println!("{}", message);
-                            if let Some(ref w) = window {
-                                w.emit("bulk-progress", BulkProgressPayload {
-                                    message,
-                                    current: current_task,
-                                    total: total_tasks,
-                                    date: date_str.clone(),
-                                    place_number,
-                                    race_number,
-                                    status: "cache_hit".to_string(),
-                                }).ok();
-                            }
-                            bulk_data.race_data = Some(cached_race_data);
-                        }
-                        Ok(None) => {
-                            // キャッシュにない場合はスクレイピング
-                            let message = format!(
-                                "🌐 レースデータをスクレイピング: {}-{}-{}",
-                                date_str, place_number, race_number
-                            );
-                            println!("{}", message);
-                            if let Some(ref w) = window {
-                                w.emit("bulk-progress", BulkProgressPayload {
-                                    message,
-                                    current: current_task,
-                                    total: total_tasks,
-                                    date: date_str.clone(),
-                                    place_number,
-                                    race_number,
-                                    status: "scraping".to_string(),
-                                }).ok();
-                            }
+                    });
+                }
+            }
+            current_date += Duration::days(1);
+        }
+
+        let job = jobs.create_job(JobKind::ScrapingBulk, &params, &units).await?;
+
+        self.run_bulk_job(window, jobs, job.id).await
+    }
+
+    /// 登録済みの一括取得ジョブを未処理のレースから実行
+    ///
+    /// 戻り値はこの実行で処理したレースのみ（以前の実行分はキャッシュに保存済み）。
+    pub async fn run_bulk_job(
+        &self,
+        window: Option<tauri::Window>,
+        jobs: &JobService,
+        job_id: i64,
+    ) -> Result<Vec<BulkRaceData>, AppError> {
+        let repo = self.cache()?;
+
+        let run = jobs.start(job_id).await?;
+        let mut all_results = Vec::new();
+        let mut current_task = 0;
+        // エラーで抜けてもジョブが Running のまま残らないよう、結果は pause_on_error に通す
+        let outcome = async {
+            let job = jobs.get_job(job_id).await?;
+            let params: ScrapingJobParams = serde_json::from_str(&job.params_json)?;
+            let total_tasks = job.total_units as usize;
+            current_task = job.processed_units() as usize;
+            let mut final_status = JobStatus::Completed;
+
+            for (seq, unit) in jobs.pending_units::<ScrapingJobUnit>(job_id).await? {
+                if let Some(stop) = run.stop_requested() {
+                    final_status = stop;
+                    break;
+                }
 
-                            match headress::fetch_shusso_info_from_kyoteibiyori(
+                current_task += 1;
+                let date_str = unit.date;
+                let date_str_no_dash = date_str.replace("-", "");
+                let place_number = unit.place_number;
+                let race_number = unit.race_number;
+                // キャッシュだけで完結したか（作業単位の状態に使う）
+                let mut from_cache = true;
+
+                let mut bulk_data = BulkRaceData {
+                    date: date_str.clone(),
+                    place_number,
+                    race_number,
+                    race_data: None,
+                    win_place_odds_data: None,
+                    error: None,
+                };
+
+                // レースデータを取得（期限内のキャッシュ優先、期限切れは取得し直す）
+                let cached_race_data = match repo.get_race_data(&date_str, place_number, race_number).await {
+                    Ok(Some(_))
+                        if params.force_refresh
+                            || !self.is_cache_fresh(repo, &date_str, place_number, race_number, CacheKind::RaceData, None).await =>
+                    {
+                        eprintln!(
+                            "♻️ レースデータを再取得: {}-{}-{}",
+                            date_str, place_number, race_number
+                        );
+                        Ok(None)
+                    }
+                    cached => cached,
+                };
+                match cached_race_data {
+                    Ok(Some(cached_race_data)) => {
+                        let message = format!(
+                            "📦 キャッシュからレースデータを取得: {}-{}-{}",
+                            date_str, place_number, race_number
+                        );
+                        eprintln!("{}", message);
+                        if let Some(ref w) = window {
+                            w.emit("bulk-progress", BulkProgressPayload {
+                                job_id,
+                                message,
+                                current: current_task,
+                                total: total_tasks,
+                                date: date_str.clone(),
+                                place_number,
                                 race_number,
+                                status: "cache_hit".to_string(),
+                            }).ok();
+                        }
+                        bulk_data.race_data = Some(cached_race_data);
+                    }
+                    Ok(None) => {
+                        // キャッシュにない場合はスクレイピング
+                        from_cache = false;
+                        let message = format!(
+                            "🌐 レースデータをスクレイピング: {}-{}-{}",
+                            date_str, place_number, race_number
+                        );
+                        eprintln!("{}", message);
+                        if let Some(ref w) = window {
+                            w.emit("bulk-progress", BulkProgressPayload {
+                                job_id,
+                                message,
+                                current: current_task,
+                                total: total_tasks,
+                                date: date_str.clone(),
                                 place_number,
-                                &date_str_no_dash,
-                                1,
-                            ) {
-                                Ok(html_content) => {
-                                    match flame::get_escaped_flame_info(&html_content) {
-                                        Ok(race_data) => {
-                                            // データベースに保存
-                                            if let Err(save_err) = repo.save_race_data(
+                                race_number,
+                                status: "scraping".to_string(),
+                            }).ok();
+                        }
+
+                        match self
+                            .fetch_page_blocking(PageRequest::BiyoriShusso {
+                                race_no: race_number,
+                                place_no: place_number,
+                                date: date_str_no_dash.clone(),
+                                slider: 1,
+                            })
+                            .await
+                        {
+                            Ok((html_content, source)) => {
+                                // パーサーのエラーは Send でないため、await をまたぐ前に AppError にする
+                                match flame::get_escaped_flame_info(&html_content)
+                                    .map_err(|e| AppError::from_parser("race_data", e))
+                                {
+                                    Ok(race_data) => {
+                                        // データベースに保存
+                                        if let Err(save_err) = repo.save_race_data(
+                                            &date_str,
+                                            place_number,
+                                            race_number,
+                                            &race_data,
+                                        ).await {
+                                            eprintln!("⚠️ データベース保存エラー: {}", save_err);
+                                        } else {
+                                            Self::record_source(
+                                                repo,
                                                 &date_str,
                                                 place_number,
                                                 race_number,
-                                                &race_data,
-                                            ) {
-                                                println!("⚠️ データベース保存エラー: {}", save_err);
-                                            } else {
-                                                let message = format!(
-                                                    "💾 レースデータを保存: {}-{}-{}",
-                                                    date_str, place_number, race_number
-                                                );
-                                                println!("{}", message);
-                                                if let Some(ref w) = window {
-                                                    w.emit("bulk-progress", BulkProgressPayload {
-                                                        message,
-                                                        current: current_task,
-                                                        total: total_tasks,
-                                                        date: date_str.clone(),
-                                                        place_number,
-                                                        race_number,
-                                                        status: "saved".to_string(),
-                                                    }).ok();
-                                                }
+                                                "data",
+                                                source.as_ref(),
+                                            ).await;
+                                            let message = format!(
+                                                "💾 レースデータを保存: {}-{}-{}",
+                                                date_str, place_number, race_number
+                                            );
+                                            eprintln!("{}", message);
+                                            if let Some(ref w) = window {
+                                                w.emit("bulk-progress", BulkProgressPayload {
+                                                    job_id,
+                                                    message,
+                                                    current: current_task,
+                                                    total: total_tasks,
+                                                    date: date_str.clone(),
+                                                    place_number,
+                                                    race_number,
+                                                    status: "saved".to_string(),
+                                                }).ok();
                                             }
-                                            bulk_data.race_data = Some(race_data);
-                                        }
-                                        Err(e) => {
-                                            bulk_data.error =
-                                                Some(format!("Race data parse error: {}", e))
                                         }
+                                        bulk_data.race_data = Some(race_data);
+                                    }
+                                    Err(e) => {
+                                        bulk_data.error = Some(e);
                                     }
                                 }
-                                Err(e) => {
-                                    bulk_data.error = Some(format!("Race data fetch error: {}", e))
-                                }
+                            }
+                            Err(e) => {
+                                bulk_data.error = Some(e);
                             }
                         }
-                        Err(e) => {
-                            println!(
-                                "⚠️ データベース取得エラー、スクレイピングにフォールバック: {}",
-                                e
-                            );
-                            // Retry with scraping on DB error
-                            match headress::fetch_shusso_info_from_kyoteibiyori(
-                                race_number,
-                                place_number,
-                                &date_str_no_dash,
-                                1,
-                            ) {
-                                Ok(html_content) => {
-                                    match flame::get_escaped_flame_info(&html_content) {
-                                        Ok(race_data) => {
-                                            bulk_data.race_data = Some(race_data);
-                                        }
-                                        Err(e) => {
-                                            bulk_data.error =
-                                                Some(format!("Race data parse error: {}", e))
-                                        }
+                    }
+                    Err(e) => {
+                        eprintln!(
+                            "⚠️ データベース取得エラー、スクレイピングにフォールバック: {}",
+                            e
+                        );
+                        // Retry with scraping on DB error
+                        from_cache = false;
+                        match self
+                            .fetch_page_blocking(PageRequest::BiyoriShusso {
+                                race_no: race_number,
+                                place_no: place_number,
+                                date: date_str_no_dash.clone(),
+                                slider: 1,
+                            })
+                            .await
+                        {
+                            Ok((html_content, _)) => {
+                                match flame::get_escaped_flame_info(&html_content)
+                                    .map_err(|e| AppError::from_parser("race_data", e))
+                                {
+                                    Ok(race_data) => {
+                                        bulk_data.race_data = Some(race_data);
+                                    }
+                                    Err(e) => {
+                                        bulk_data.error = Some(e);
                                     }
                                 }
-                                Err(e) => {
-                                    bulk_data.error = Some(format!("Race data fetch error: {}", e))
-                                }
+                            }
+                            Err(e) => {
+                                bulk_data.error = Some(e);
                             }
                         }
                     }
+                }
 
-                    // オッズデータを取得（キャッシュ優先）
-                    match repo.get_odds_data(&date_str, place_number, race_number) {
-                        Ok(Some(cached_odds_data)) => {
-                            bulk_data.win_place_odds_data = Some(cached_odds_data);
-                        }
-                        Ok(None) => {
-                            // キャッシュにない場合はスクレイピング
-                            match headress::fetch_odds_info_from_kyoteibiyori(
-                                race_number,
-                                place_number,
-                                &date_str_no_dash,
-                            ) {
-                                Ok(html_content) => {
-                                    match flame::parse_win_place_odds_from_html(&html_content) {
-                                        Ok(odds_data) => {
-                                            // データベースに保存
-                                            if let Err(save_err) = repo.save_odds_data(
+                // オッズデータを取得（期限内のキャッシュ優先、期限切れは取得し直す）
+                let cached_odds_data = match repo.get_odds_data(&date_str, place_number, race_number).await {
+                    Ok(Some(_))
+                        if params.force_refresh
+                            || !self.is_cache_fresh(repo, &date_str, place_number, race_number, CacheKind::Odds, None).await =>
+                    {
+                        Ok(None)
+                    }
+                    cached => cached,
+                };
+                match cached_odds_data {
+                    Ok(Some(cached_odds_data)) => {
+                        bulk_data.win_place_odds_data = Some(cached_odds_data);
+                    }
+                    Ok(None) => {
+                        // キャッシュにない場合はスクレイピング
+                        from_cache = false;
+                        match self
+                            .fetch_page_blocking(PageRequest::BiyoriWinPlaceOdds {
+                                race_no: race_number,
+                                place_no: place_number,
+                                date: date_str_no_dash.clone(),
+                            })
+                            .await
+                        {
+                            Ok((html_content, source)) => {
+                                match flame::parse_win_place_odds_from_html(&html_content)
+                                    .map_err(|e| AppError::from_parser("win_place_odds", e))
+                                {
+                                    Ok(odds_data) => {
+                                        // データベースに保存
+                                        if let Err(save_err) = repo.save_odds_data(
+                                            &date_str,
+                                            place_number,
+                                            race_number,
+                                            &odds_data,
+                                        ).await {
+                                            eprintln!("⚠️ オッズデータベース保存エラー: {}", save_err);
+                                        } else {
+                                            Self::record_source(
+                                                repo,
                                                 &date_str,
                                                 place_number,
                                                 race_number,
-                                                &odds_data,
-                                            ) {
-                                                println!("⚠️ オッズデータベース保存エラー: {}", save_err);
-                                            }
-                                            bulk_data.win_place_odds_data = Some(odds_data);
+                                                "odds",
+                                                source.as_ref(),
+                                            ).await;
                                         }
-                                        Err(e) => {
-                                            if bulk_data.error.is_none() {
-                                                bulk_data.error =
-                                                    Some(format!("Odds data parse error: {}", e));
-                                            }
+                                        bulk_data.win_place_odds_data = Some(odds_data);
+                                    }
+                                    Err(e) => {
+                                        if bulk_data.error.is_none() {
+                                            bulk_data.error = Some(e);
                                         }
                                     }
                                 }
-                                Err(e) => {
-                                    if bulk_data.error.is_none() {
-                                        bulk_data.error =
-                                            Some(format!("Odds data fetch error: {}", e));
-                                    }
+                            }
+                            Err(e) => {
+                                if bulk_data.error.is_none() {
+                                    bulk_data.error = Some(e);
                                 }
                             }
                         }
-                        Err(e) => {
-                            // DB errorのみログ出力、スクレイピングは試行しない（オッズはオプション）
-                            println!("⚠️ オッズデータベース取得エラー: {}", e);
-                        }
                     }
+                    Err(e) => {
+                        // DB errorのみログ出力、スクレイピングは試行しない（オッズはオプション）
+                        eprintln!("⚠️ オッズデータベース取得エラー: {}", e);
+                    }
+                }
 
-                    all_results.push(bulk_data);
+                let unit_status = match (&bulk_data.error, from_cache) {
+                    (Some(_), _) => UnitStatus::Failed,
+                    (None, true) => UnitStatus::Skipped,
+                    (None, false) => UnitStatus::Done,
+                };
+                let unit_error = bulk_data.error.as_ref().map(|e| e.to_string());
+                jobs.complete_unit(job_id, seq, unit_status, unit_error.as_deref())
+                    .await?;
 
-                    // レート制限を適用
-                    let delay = if current_task < total_tasks {
-                        // スクレイピングが発生した場合は長めの遅延
-                        if repo.get_race_data(&date_str, place_number, race_number)
-                            .ok()
-                            .flatten()
-                            .is_none()
-                        {
-                            TokioDuration::from_secs(1)
-                        } else {
-                            TokioDuration::from_millis(100)
-                        }
-                    } else {
-                        TokioDuration::from_millis(0)
-                    };
-                    sleep(delay).await;
-                }
+                all_results.push(bulk_data);
             }
-
-            current_date += Duration::days(1);
+            Ok::<_, AppError>((final_status, params, total_tasks))
         }
+        .await;
+        let (final_status, params, total_tasks) = jobs.pause_on_error(&run, outcome).await?;
+
+        jobs.finish(run, final_status).await?;
 
         // 完了通知
+        let (message, status) = match final_status {
+            JobStatus::Paused => (format!("⏸️ 一括取得を一時停止 (ジョブ #{})", job_id), "paused"),
+            JobStatus::Cancelled => (format!("⏹️ 一括取得を中止 (ジョブ #{})", job_id), "cancelled"),
+            _ => ("✅ 一括取得完了".to_string(), "completed"),
+        };
+        eprintln!("{}", message);
         if let Some(ref w) = window {
             w.emit("bulk-progress", BulkProgressPayload {
-                message: "✅ 一括取得完了".to_string(),
-                current: total_tasks,
+                job_id,
+                message,
+                current: current_task,
                 total: total_tasks,
-                date: end_date.to_string(),
+                date: params.end_date.clone(),
                 place_number: 0,
                 race_number: 0,
-                status: "completed".to_string(),
+                status: status.to_string(),
             }).ok();
         }
 
         Ok(all_results)
     }
 
-    pub fn scrape_html(url: &str) -> Result<String, String> {
-        headress::scrape_html_from_url(url)
-            .map_err(|e| format!("スクレイピングエラー: {}", e))
+    /// アーカイブ済みHTMLを現在のパーサーで再パースし、キャッシュを上書きする
+    ///
+    /// レース・ページ種別ごとに最新の取得記録だけを対象にする。
+    pub async fn reparse_archive(&self) -> Result<ReparseSummary, AppError> {
+        let repo = self.cache()?;
+
+        let mut latest: BTreeMap<(String, u32, u32, u32), ArchiveEntry> = BTreeMap::new();
+        for entry in self.archive.entries()? {
+            if let (Some(date), Some(place), Some(race), Some(slider)) = (
+                entry.date.clone(),
+                entry.place_number,
+                entry.race_number,
+                entry.slider,
+            ) {
+                latest.insert((date, place, race, slider), entry);
+            }
+        }
+
+        let mut summary = ReparseSummary::default();
+
+        for ((date, place_number, race_number, slider), entry) in latest {
+            // アーカイブはYYYYMMDD、キャッシュはYYYY-MM-DD
+            let cache_date = match chrono::NaiveDate::parse_from_str(&date, "%Y%m%d") {
+                Ok(d) => d.format("%Y-%m-%d").to_string(),
+                Err(e) => {
+                    summary.errors.push(format!("{}: 日付パースエラー: {}", entry.path, e));
+                    continue;
+                }
+            };
+
+            let html = match self.archive.read(&entry) {
+                Ok(html) => html,
+                Err(e) => {
+                    summary.errors.push(e.to_string());
+                    continue;
+                }
+            };
+
+            let (kind, result) = match slider {
+                1 => (
+                    "data",
+                    Self::reparse_race_data(repo, &cache_date, place_number, race_number, &html).await,
+                ),
+                WIN_PLACE_ODDS_SLIDER => (
+                    "odds",
+                    Self::reparse_odds_data(repo, &cache_date, place_number, race_number, &html).await,
+                ),
+                _ => continue,
+            };
+            summary.pages_processed += 1;
+
+            match result {
+                Ok(changed) => {
+                    Self::record_source(repo, &cache_date, place_number, race_number, kind, Some(&entry)).await;
+                    // 取得時刻は再パースした時刻ではなくHTMLの取得時刻
+                    if let Err(e) = repo.save_fetched_at(&cache_date, place_number, race_number, kind, &entry.fetched_at).await {
+                        eprintln!("⚠️ 取得時刻の記録エラー: {}", e);
+                    }
+                    if changed > 0 {
+                        eprintln!(
+                            "🔁 再パースで{}件の値が変化: {}-{}-{} ({})",
+                            changed, cache_date, place_number, race_number, kind
+                        );
+                        summary.values_changed += changed;
+                        if kind == "data" {
+                            summary.race_data_updated += 1;
+                        } else {
+                            summary.odds_updated += 1;
+                        }
+                    }
+                }
+                Err(e) => summary.errors.push(format!("{}: {}", entry.path, e)),
+            }
+        }
+
+        eprintln!(
+            "✅ 再パース完了: {}ページ, レースデータ更新{}件, オッズ更新{}件, 変化した値{}件, エラー{}件",
+            summary.pages_processed,
+            summary.race_data_updated,
+            summary.odds_updated,
+            summary.values_changed,
+            summary.errors.len()
+        );
+
+        Ok(summary)
+    }
+
+    async fn reparse_race_data(
+        repo: &LocalDbRepository,
+        date: &str,
+        place_number: u32,
+        race_number: u32,
+        html: &str,
+    ) -> Result<usize, AppError> {
+        let new_data = flame::get_escaped_flame_info(html)
+            .map_err(|e| AppError::from_parser("race_data", e))?;
+        let old_data = repo
+            .get_race_data(date, place_number, race_number)
+            .await?;
+
+        let changed = count_changed_values(&to_json(old_data.as_ref())?, &to_json(Some(&new_data))?);
+        repo.save_race_data(date, place_number, race_number, &new_data)
+            .await?;
+        Ok(changed)
+    }
+
+    async fn reparse_odds_data(
+        repo: &LocalDbRepository,
+        date: &str,
+        place_number: u32,
+        race_number: u32,
+        html: &str,
+    ) -> Result<usize, AppError> {
+        let new_data = flame::parse_win_place_odds_from_html(html)
+            .map_err(|e| AppError::from_parser("win_place_odds", e))?;
+        let old_data = repo
+            .get_odds_data(date, place_number, race_number)
+            .await?;
+
+        let changed = count_changed_values(&to_json(old_data.as_ref())?, &to_json(Some(&new_data))?);
+        repo.save_odds_data(date, place_number, race_number, &new_data)
+            .await?;
+        Ok(changed)
+    }
+
+    pub fn scrape_html(&self, url: &str) -> Result<String, AppError> {
+        self.fetch_page(&PageRequest::Url(url.to_string()))
+            .map(|(html, _)| html)
+    }
+}
+
+fn to_json<T: serde::Serialize>(value: Option<&T>) -> Result<serde_json::Value, AppError> {
+    Ok(serde_json::to_value(value)?)
+}
+
+/// 2つのJSON値を比較し、値が異なる末端要素の数を数える
+///
+/// 片側にしか存在しないオブジェクトや配列要素は、その中の末端要素をすべて変化として数える。
+fn count_changed_values(old: &serde_json::Value, new: &serde_json::Value) -> usize {
+    use serde_json::Value;
+
+    match (old, new) {
+        (Value::Object(old_map), Value::Object(new_map)) => {
+            let mut changed = 0;
+            for (key, new_value) in new_map {
+                changed += count_changed_values(old_map.get(key).unwrap_or(&Value::Null), new_value);
+            }
+            for (key, old_value) in old_map {
+                if !new_map.contains_key(key) {
+                    changed += count_changed_values(old_value, &Value::Null);
+                }
+            }
+            changed
+        }
+        (Value::Array(old_items), Value::Array(new_items)) => {
+            let len = old_items.len().max(new_items.len());
+            (0..len)
+                .map(|i| {
+                    count_changed_values(
+                        old_items.get(i).unwrap_or(&Value::Null),
+                        new_items.get(i).unwrap_or(&Value::Null),
+                    )
+                })
+                .sum()
+        }
+        (Value::Object(_) | Value::Array(_), Value::Null) => count_leaves(old),
+        (Value::Null, Value::Object(_) | Value::Array(_)) => count_leaves(new),
+        _ => usize::from(old != new),
+    }
+}
+
+fn count_leaves(value: &serde_json::Value) -> usize {
+    match value {
+        serde_json::Value::Object(map) => map.values().map(count_leaves).sum(),
+        serde_json::Value::Array(items) => items.iter().map(count_leaves).sum(),
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::sqlite_db::SqliteRepository;
+    use crate::test_support::{replay_fetcher, temp_db_path};
+    use serde_json::json;
+
+    #[test]
+    fn test_count_changed_values() {
+        let old = json!({
+            "escape_last_year": 0.5,
+            "player_basic_info": { "name": "テスト選手", "class_level": "A1" },
+            "boats": [{ "boat_number": 1, "rate": 0.2 }]
+        });
+        let new = json!({
+            "escape_last_year": 0.55,
+            "player_basic_info": { "name": "テスト選手", "class_level": "A1" },
+            "boats": [{ "boat_number": 1, "rate": 0.25 }, { "boat_number": 2, "rate": 0.1 }]
+        });
+
+        // escape_last_year, boats[0].rate, boats[1]の2値
+        assert_eq!(count_changed_values(&old, &new), 4);
+        assert_eq!(count_changed_values(&new, &new), 0);
+
+        // キャッシュがない場合は全ての値を変化として数える
+        assert_eq!(count_changed_values(&serde_json::Value::Null, &old), 5);
+    }
+
+    #[tokio::test]
+    async fn test_get_race_info_from_replay() {
+        let db_path = temp_db_path("scraping_replay");
+        let archive_dir = std::env::temp_dir().join(format!("bort_archive_replay_{}", std::process::id()));
+        let db = SqliteRepository::new(db_path.to_str().unwrap()).await.unwrap();
+        let service = ScrapingService::with_fetcher(Arc::new(replay_fetcher()))
+            .with_archive(HtmlArchive::new(&archive_dir))
+            .with_cache(LocalDbRepository::with_pool(db.pool().clone()));
+
+        let data = service.get_race_info("2025-12-28", 1, 1, false).await.unwrap();
+        assert_eq!(data.escape_last_half_year, 0.55);
+        assert_eq!(data.escape_last_year, 0.525);
+        assert_eq!(data.pierce_last_year, 0.16);
+        assert_eq!(data.overtake_last_year, 0.11);
+        assert_eq!(data.boats.len(), 6);
+        assert_eq!(data.boats[5].player_basic_info.registration_number, "3948");
+        assert_eq!(data.boats[0].player_basic_info.support_group, "群馬");
+        assert_eq!(data.boats[0].detailed_performance.first_place_rate.this_period, Some(0.4));
+        assert_eq!(data.boats[3].st_data.average_st.this_period, Some(0.17));
+        assert_eq!(data.winning_hand.escape_rate_6months, Some(0.55));
+
+        // 保存したデータと取得元HTMLがキャッシュに残る
+        let repo = service.cache().unwrap();
+        let cached = repo.get_race_data("2025-12-28", 1, 1).await.unwrap().unwrap();
+        assert_eq!(cached.escape_last_year, data.escape_last_year);
+        assert!(repo.get_source_page("2025-12-28", 1, 1, "data").await.unwrap().is_some());
+
+        // リプレイにないレースは取得エラー
+        assert!(service.get_race_info("2025-12-28", 2, 1, false).await.is_err());
+
+        let _ = std::fs::remove_dir_all(&archive_dir);
+        let _ = std::fs::remove_file(&db_path);
     }
 }
@@ -1,4 +1,7 @@
+use crate::error::AppError;
+use crate::repositories::html_archive::HtmlArchive;
 use crate::repositories::local_db::LocalDbRepository;
+use crate::models::archive::ArchiveEntry;
 use crate::models::race::{RaceData, OddsData};
 
 pub struct StorageService {
@@ -6,51 +9,85 @@ pub struct StorageService {
 }
 
 impl StorageService {
-    pub fn new() -> Result<Self, String> {
-        Ok(Self {
-            repo: LocalDbRepository::new().map_err(|e| e.to_string())?,
-        })
+    /// スクレイピング結果のキャッシュ（`OpenApiService::scrape_cache`）を使う
+    pub fn new(repo: LocalDbRepository) -> Self {
+        Self { repo }
     }
 
-    pub fn save_race(&self, date: &str, place: u32, race: u32, data: &RaceData) -> Result<(), String> {
+    pub async fn save_race(&self, date: &str, place: u32, race: u32, data: &RaceData) -> Result<(), AppError> {
         self.repo
             .save_race_data(date, place, race, data)
-            .map_err(|e| e.to_string())
+            .await
+            .map_err(AppError::db)
     }
 
-    pub fn get_race(&self, date: &str, place: u32, race: u32) -> Result<Option<RaceData>, String> {
+    pub async fn get_race(&self, date: &str, place: u32, race: u32) -> Result<Option<RaceData>, AppError> {
         self.repo
             .get_race_data(date, place, race)
-            .map_err(|e| e.to_string())
+            .await
+            .map_err(AppError::db)
     }
 
-    pub fn save_odds(&self, date: &str, place: u32, race: u32, data: &OddsData) -> Result<(), String> {
+    pub async fn save_odds(&self, date: &str, place: u32, race: u32, data: &OddsData) -> Result<(), AppError> {
         self.repo
             .save_odds_data(date, place, race, data)
-            .map_err(|e| e.to_string())
+            .await
+            .map_err(AppError::db)
     }
 
-    pub fn get_odds(&self, date: &str, place: u32, race: u32) -> Result<Option<OddsData>, String> {
+    pub async fn get_odds(&self, date: &str, place: u32, race: u32) -> Result<Option<OddsData>, AppError> {
         self.repo
             .get_odds_data(date, place, race)
-            .map_err(|e| e.to_string())
+            .await
+            .map_err(AppError::db)
     }
 
-    pub fn get_all_race_keys(&self) -> Result<Vec<String>, String> {
+    /// 保存済みデータ（kind: "data" / "odds"）の取得元HTML
+    pub async fn get_source_page(
+        &self,
+        date: &str,
+        place: u32,
+        race: u32,
+        kind: &str,
+    ) -> Result<Option<ArchiveEntry>, AppError> {
+        self.repo
+            .get_source_page(date, place, race, kind)
+            .await
+            .map_err(AppError::db)
+    }
+
+    /// コンテンツハッシュからアーカイブ済みHTMLを復元
+    pub fn get_archived_html(&self, content_hash: &str) -> Result<String, AppError> {
+        let archive = HtmlArchive::default();
+        let entry = archive
+            .find_by_hash(content_hash)?
+            .ok_or_else(|| {
+                AppError::invalid_input(
+                    "content_hash",
+                    format!("アーカイブにHTMLがありません: {}", content_hash),
+                )
+            })?;
+        archive.read(&entry)
+    }
+
+    pub async fn get_all_race_keys(&self) -> Result<Vec<String>, AppError> {
         self.repo
             .get_all_race_keys()
-            .map_err(|e| e.to_string())
+            .await
+            .map_err(AppError::db)
     }
 
-    pub fn delete_race(&self, date: &str, place: u32, race: u32) -> Result<(), String> {
+    pub async fn delete_race(&self, date: &str, place: u32, race: u32) -> Result<(), AppError> {
         self.repo
             .delete_race_data(date, place, race)
-            .map_err(|e| e.to_string())
+            .await
+            .map_err(AppError::db)
     }
 
-    pub fn clear_all(&self) -> Result<(), String> {
+    pub async fn clear_all(&self) -> Result<(), AppError> {
         self.repo
             .clear_all_data()
-            .map_err(|e| e.to_string())
+            .await
+            .map_err(AppError::db)
     }
 }
@@ -0,0 +1,124 @@
+//! テスト用のヘルパー（ネットワークに依存しない Open API モックサーバー）
+
+use crate::services::job_service::JobService;
+use crate::services::open_api_service::OpenApiService;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// fixture が用意されている日付（YYYYMMDD）
+pub const FIXTURE_DATE: &str = "20251228";
+/// fixture のレース数（場・レース番号の組）
+pub const FIXTURE_RACE_COUNT: usize = 4;
+
+/// `tests/fixtures/open_api` の JSON を本番と同じパス構成
+/// （`/{previews|results|programs}/v2/{year}/{date}.json`）で返すプロセス内 HTTP サーバー
+///
+/// fixture のないパスには 404 を返す。テスト終了時にタスクごと破棄される。
+pub struct MockOpenApiServer {
+    base_url: String,
+    requests: Arc<AtomicUsize>,
+}
+
+impl MockOpenApiServer {
+    pub async fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("failed to bind mock server");
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(AtomicUsize::new(0));
+
+        let counter = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let counter = counter.clone();
+                tokio::spawn(async move {
+                    let mut buf = vec![0u8; 8192];
+                    let mut len = 0;
+                    // ヘッダー終端まで読む（GET のみなので本文はない）
+                    while !buf[..len].windows(4).any(|w| w == b"\r\n\r\n") && len < buf.len() {
+                        match stream.read(&mut buf[len..]).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => len += n,
+                        }
+                    }
+                    counter.fetch_add(1, Ordering::SeqCst);
+
+                    let request = String::from_utf8_lossy(&buf[..len]);
+                    let path = request.split_whitespace().nth(1).unwrap_or("/");
+                    let response = match fixture(path) {
+                        Some(body) => format!(
+                            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                            body.len(),
+                            body
+                        ),
+                        None => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                            .to_string(),
+                    };
+                    let _ = stream.write_all(response.as_bytes()).await;
+                    let _ = stream.shutdown().await;
+                });
+            }
+        });
+
+        Self { base_url, requests }
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// 受け付けたリクエスト数
+    pub fn request_count(&self) -> usize {
+        self.requests.load(Ordering::SeqCst)
+    }
+}
+
+fn fixture(path: &str) -> Option<String> {
+    let relative = path.trim_start_matches('/');
+    if relative.is_empty() || relative.split('/').any(|part| part == "..") {
+        return None;
+    }
+    std::fs::read_to_string(fixtures_dir().join(relative)).ok()
+}
+
+fn fixtures_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/open_api")
+}
+
+/// `tests/fixtures/replay` の保存済みHTMLを返すフェッチャー（ファイル名は `replay_file_name`）
+pub fn replay_fetcher() -> crate::page_fetcher::ReplayPageFetcher {
+    crate::page_fetcher::ReplayPageFetcher::new(
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/replay"),
+    )
+}
+
+/// テストごとに独立した一時 DB パス（既存ファイルは削除済み）
+pub fn temp_db_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("bort_{}_{}.db", name, std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+/// モックサーバーの fixture を一括取得で保存したサービスを用意
+pub async fn seeded_service(name: &str) -> (OpenApiService, MockOpenApiServer, PathBuf) {
+    let server = MockOpenApiServer::start().await;
+    let path = temp_db_path(name);
+    let db = path.to_str().unwrap();
+    let service = OpenApiService::with_base_url(Some(db), server.base_url())
+        .await
+        .expect("Failed to initialize service");
+    let jobs = JobService::new(service.job_repository())
+        .await
+        .expect("Failed to initialize jobs");
+
+    let summary = service
+        .fetch_all_data_bulk(None, &jobs, FIXTURE_DATE, FIXTURE_DATE, 3)
+        .await
+        .expect("Failed to fetch fixtures");
+    assert_eq!(summary.success_count, 3, "previews/results/programs should be saved");
+
+    (service, server, path)
+}
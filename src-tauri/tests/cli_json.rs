@@ -0,0 +1,52 @@
+//! bort-cli の標準出力が結果の JSON だけになっていることの確認
+
+use std::path::PathBuf;
+use std::process::Command;
+
+fn temp_db_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("bort_cli_{}_{}.db", name, std::process::id()))
+}
+
+fn run_cli(db: &PathBuf, args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_bort-cli"))
+        .arg("--db")
+        .arg(db)
+        .args(args)
+        .output()
+        .expect("bort-cli を起動できません")
+}
+
+#[test]
+fn test_stdout_is_json_only() {
+    let db = temp_db_path("stdout");
+    let _ = std::fs::remove_file(&db);
+
+    // 新しいDBではマイグレーションのログが出るが、標準出力には混ざらない
+    for args in [&["migrate", "--no-backup"][..], &["summary"][..]] {
+        let output = run_cli(&db, args);
+        assert!(output.status.success(), "{:?}: {}", args, String::from_utf8_lossy(&output.stderr));
+
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&stdout)
+            .unwrap_or_else(|e| panic!("{:?} の標準出力が JSON ではありません: {}\n{}", args, e, stdout));
+        assert!(value.is_object() || value.is_array());
+    }
+
+    let _ = std::fs::remove_file(&db);
+}
+
+#[test]
+fn test_error_is_json_on_stderr() {
+    let db = temp_db_path("stderr");
+    let _ = std::fs::remove_file(&db);
+
+    let output = run_cli(&db, &["import-scrape-cache", "--json", "/nonexistent/bort_race_data.json"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let error: serde_json::Value = serde_json::from_str(stderr.lines().last().unwrap()).unwrap();
+    assert_eq!(error["code"], "INVALID_INPUT");
+
+    let _ = std::fs::remove_file(&db);
+}
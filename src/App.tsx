@@ -6,6 +6,7 @@ import ScrapingTool from "./pages/ScrapingTool";
 import OpenApiTool from "./pages/OpenApiTool";
 import HighPayoutSearch from "./pages/HighPayoutSearch";
 import Manual from "./pages/Manual";
+import { toErrorMessage } from "./utils/errors";
 import "./App.css";
 
 function App() {
@@ -17,9 +18,11 @@ function App() {
       try {
         await invoke("init_open_api_service");
         console.log("Open API service initialized");
+        await invoke("init_odds_poller");
+        console.log("Odds poller initialized");
         setIsInitialized(true);
       } catch (error) {
-        const errorMessage = error instanceof Error ? error.message : String(error);
+        const errorMessage = toErrorMessage(error);
         console.error("Failed to initialize Open API service:", errorMessage);
         setInitError(errorMessage);
       }
@@ -28,7 +28,7 @@ export function BulkResultsContainer({ bulkData }: BulkResultsContainerProps) {
             </div>
             {item.error ? (
               <div className="bulk-item-error">
-                エラー: {item.error}
+                エラー: {item.error.message}
               </div>
             ) : (
               <div className="bulk-item-success">
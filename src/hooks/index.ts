@@ -2,4 +2,5 @@ export * from './useHelloWorld';
 export * from './useActiveRaces';
 export * from './useRaceData';
 export * from './useOddsData';
-export * from './useBulkData';
\ No newline at end of file
+export * from './useBulkData';
+export * from './useBulkJobs';
\ No newline at end of file
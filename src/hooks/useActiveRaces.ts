@@ -1,6 +1,7 @@
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { AllVenuesResponse } from "../types";
+import { toErrorMessage } from "../utils/errors";
 
 export function useActiveRaces() {
   const [allVenues, setAllVenues] = useState<AllVenuesResponse | null>(null);
@@ -15,7 +16,7 @@ export function useActiveRaces() {
       const result = await invoke<AllVenuesResponse>("get_all_venues_with_status");
       setAllVenues(result);
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err));
+      setError(toErrorMessage(err));
     } finally {
       setLoading(false);
     }
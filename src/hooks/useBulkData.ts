@@ -2,6 +2,7 @@ import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
 import { BulkRaceData, BulkProgressPayload } from "../types";
+import { toErrorMessage } from "../utils/errors";
 
 export function useBulkData() {
   const [bulkData, setBulkData] = useState<BulkRaceData[]>([]);
@@ -68,7 +69,7 @@ export function useBulkData() {
       console.log(`一括取得完了: 成功${successCount}件, エラー${errorCount}件`);
     } catch (err) {
       console.error("一括取得エラー:", err);
-      setError(err instanceof Error ? err.message : String(err));
+      setError(toErrorMessage(err));
     } finally {
       setLoading(false);
     }
@@ -0,0 +1,50 @@
+import { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { BulkJob } from "../types";
+import { toErrorMessage } from "../utils/errors";
+
+export function useBulkJobs() {
+  const [jobs, setJobs] = useState<BulkJob[]>([]);
+  const [error, setError] = useState("");
+
+  const refreshJobs = async () => {
+    try {
+      const result = await invoke<BulkJob[]>("list_bulk_jobs");
+      setJobs(result);
+    } catch (err) {
+      console.error("❌ ジョブ一覧取得エラー:", err);
+      setError(toErrorMessage(err));
+    }
+  };
+
+  const runJobCommand = async (command: string, jobId: number) => {
+    setError("");
+    try {
+      await invoke<BulkJob>(command, { jobId });
+    } catch (err) {
+      console.error(`❌ ${command} エラー:`, err);
+      setError(toErrorMessage(err));
+    } finally {
+      await refreshJobs();
+    }
+  };
+
+  // 実行中のジョブは処理中のレース・日付が終わった時点で止まる
+  const pauseJob = (jobId: number) => runJobCommand("pause_bulk_job", jobId);
+  const cancelJob = (jobId: number) => runJobCommand("cancel_bulk_job", jobId);
+  // 再開したジョブが完了・一時停止・中止するまで待つ
+  const resumeJob = (jobId: number) => runJobCommand("resume_bulk_job", jobId);
+
+  useEffect(() => {
+    refreshJobs();
+  }, []);
+
+  return {
+    jobs,
+    error,
+    refreshJobs,
+    pauseJob,
+    cancelJob,
+    resumeJob,
+  };
+}
@@ -1,6 +1,7 @@
 import { useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { OddsData } from "../types";
+import { toErrorMessage } from "../utils/errors";
 
 export function useOddsData() {
   const [oddsData, setOddsData] = useState<OddsData | null>(null);
@@ -18,7 +19,7 @@ export function useOddsData() {
       setOddsData(result);
     } catch (err) {
       console.error("❌ 単勝・複勝オッズデータ取得エラー:", err);
-      setError(err instanceof Error ? err.message : String(err));
+      setError(toErrorMessage(err));
     } finally {
       setLoading(false);
     }
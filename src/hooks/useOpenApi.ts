@@ -3,6 +3,7 @@ import { invoke } from "@tauri-apps/api/core";
 import { save } from "@tauri-apps/plugin-dialog";
 import { DataType, OpenApiState, PayoutType, RaceResult, PayoutStats, SearchState, StatsState, SummaryState, DataSummaryRow, BulkFetchState, BulkFetchSummary, OpenApiBulkProgressPayload } from "../types/OpenApiData";
 import { SearchParams, AdvancedSearchResult, AdvancedSearchState } from "../types/AdvancedSearch";
+import { toErrorMessage } from "../utils/errors";
 
 export const useOpenApi = () => {
   const [state, setState] = useState<OpenApiState>({
@@ -134,7 +135,7 @@ export const useOpenApi = () => {
         status: { ...prev.status, [dataType]: "success" },
       }));
     } catch (error) {
-      const errorMessage = error instanceof Error ? error.message : String(error);
+      const errorMessage = toErrorMessage(error);
       console.error(`Failed to fetch/save ${dataType}:`, errorMessage);
 
       setState((prev) => ({
@@ -197,7 +198,7 @@ export const useOpenApi = () => {
         exportStatus: "success",
       }));
     } catch (error) {
-      const errorMessage = error instanceof Error ? error.message : String(error);
+      const errorMessage = toErrorMessage(error);
       console.error("Failed to export CSV:", errorMessage);
 
       setState((prev) => ({
@@ -237,7 +238,7 @@ export const useOpenApi = () => {
 
       return results;
     } catch (error) {
-      const errorMessage = error instanceof Error ? error.message : String(error);
+      const errorMessage = toErrorMessage(error);
       console.error("Failed to search high payout races:", errorMessage);
 
       setSearchState({
@@ -271,7 +272,7 @@ export const useOpenApi = () => {
 
       return stats;
     } catch (error) {
-      const errorMessage = error instanceof Error ? error.message : String(error);
+      const errorMessage = toErrorMessage(error);
       console.error("Failed to get payout statistics:", errorMessage);
 
       setStatsState({
@@ -308,7 +309,7 @@ export const useOpenApi = () => {
 
       return results;
     } catch (error) {
-      const errorMessage = error instanceof Error ? error.message : String(error);
+      const errorMessage = toErrorMessage(error);
       console.error("Failed to search races:", errorMessage);
 
       setAdvancedSearchState({
@@ -340,7 +341,7 @@ export const useOpenApi = () => {
 
       return summary;
     } catch (error) {
-      const errorMessage = error instanceof Error ? error.message : String(error);
+      const errorMessage = toErrorMessage(error);
       console.error("Failed to fetch data summary:", errorMessage);
 
       setSummaryState({
@@ -354,10 +355,12 @@ export const useOpenApi = () => {
   };
 
   // 期間一括取得
+  // dataType に "all" を指定すると3種別をまとめて並行取得する
   const fetchDataBulk = async (
-    dataType: DataType,
+    dataType: DataType | "all",
     startDate: string,  // YYYYMMDD形式
-    endDate: string     // YYYYMMDD形式
+    endDate: string,    // YYYYMMDD形式
+    concurrency?: number
   ) => {
     setBulkFetchState({
       status: "loading",
@@ -371,10 +374,12 @@ export const useOpenApi = () => {
       if (dataType === "previews") command = "fetch_previews_data_bulk";
       else if (dataType === "results") command = "fetch_results_data_bulk";
       else if (dataType === "programs") command = "fetch_programs_data_bulk";
+      else if (dataType === "all") command = "fetch_all_data_bulk";
 
       const summary = await invoke<BulkFetchSummary>(command, {
         startDate,
         endDate,
+        ...(dataType === "all" ? { concurrency } : {}),
       });
 
       console.log(
@@ -390,7 +395,7 @@ export const useOpenApi = () => {
 
       return summary;
     } catch (error) {
-      const errorMessage = error instanceof Error ? error.message : String(error);
+      const errorMessage = toErrorMessage(error);
       console.error("Bulk fetch failed:", errorMessage);
 
       setBulkFetchState({
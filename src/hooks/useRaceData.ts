@@ -1,6 +1,7 @@
 import { useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { RaceData } from "../types";
+import { toErrorMessage } from "../utils/errors";
 
 export function useRaceData() {
   const [raceData, setRaceData] = useState<RaceData | null>(null);
@@ -18,7 +19,7 @@ export function useRaceData() {
       setRaceData(result);
     } catch (err) {
       console.error("❌ レースデータ取得エラー:", err);
-      setError(err instanceof Error ? err.message : String(err));
+      setError(toErrorMessage(err));
     } finally {
       setLoading(false);
     }
@@ -42,6 +42,37 @@ function Manual() {
 // venues.桐生.race_number === 12`,
       implementation: "src-tauri/src/commands/schedule.rs:15-18",
     },
+    {
+      title: "公式サイトの出走表",
+      commandName: "get_official_race_card",
+      description: "公式サイト（boatrace.jp）の出走表ページを HTTP で取得し、6艇分の登録番号・級別・支部・年齢・体重・F/L数・全国/当地勝率・モーター/ボートの番号と2連率・3連率を返します。Chrome は使いません。Open API の出走表の公開が遅れているときの予備の取得元です。",
+      parameters: [
+        {
+          name: "date",
+          type: "string",
+          description: "日付（YYYYMMDD または YYYY-MM-DD）",
+          required: true,
+        },
+        {
+          name: "venue_code",
+          type: "string",
+          description: "競艇場コード（\"01\"〜\"24\"、\"1\" も可）",
+          required: true,
+        },
+        {
+          name: "race_number",
+          type: "number",
+          description: "レース番号（1〜12）",
+          required: true,
+        },
+      ],
+      returnType: "RaceCard",
+      example: `const card = await invoke<RaceCard>("get_official_race_card", {
+  date: "20251228", venueCode: "01", raceNumber: 1,
+});
+// card.entries[0].assigned_motor_top_2_percent => 1号艇のモーター2連率`,
+      implementation: "src-tauri/src/commands/schedule.rs:21-31",
+    },
     {
       title: "レース情報の取得",
       commandName: "get_biyori_info",
@@ -66,6 +97,12 @@ function Manual() {
           description: "競艇場番号（01-24）",
           required: true,
         },
+        {
+          name: "force_refresh",
+          type: "boolean",
+          description: "キャッシュを使わずに取得し直す（省略時は当日30分・レース日を過ぎたら一度だけ再取得）",
+          required: false,
+        },
       ],
       returnType: "RaceData",
       example: `const raceData = await invoke<RaceData>("get_biyori_info", {
@@ -73,7 +110,7 @@ function Manual() {
   raceNumber: "12",
   placeNumber: "01",
 });`,
-      implementation: "src-tauri/src/commands/scraping.rs:4-25",
+      implementation: "src-tauri/src/commands/scraping.rs:38-62",
     },
     {
       title: "単勝・複勝オッズの取得",
@@ -99,6 +136,12 @@ function Manual() {
           description: "競艇場番号（01-24）",
           required: true,
         },
+        {
+          name: "force_refresh",
+          type: "boolean",
+          description: "キャッシュを使わずに取得し直す（省略時は締切まで60秒で再取得、締切は保存済みの出走表から）",
+          required: false,
+        },
       ],
       returnType: "OddsData",
       example: `const oddsData = await invoke<OddsData>("get_win_place_odds_info", {
@@ -106,7 +149,7 @@ function Manual() {
   raceNumber: "12",
   placeNumber: "01",
 });`,
-      implementation: "src-tauri/src/commands/scraping.rs:37-58",
+      implementation: "src-tauri/src/commands/scraping.rs:82-112",
     },
     {
       title: "データの一括取得",
@@ -138,6 +181,12 @@ function Manual() {
           description: "レース番号の配列（例: [1, 2, 12]）",
           required: true,
         },
+        {
+          name: "force_refresh",
+          type: "boolean",
+          description: "期限内のキャッシュも含めてすべて取得し直す（省略時は期限切れのみ再取得）",
+          required: false,
+        },
       ],
       returnType: "BulkRaceData[]",
       example: `// プログレスイベントのリスニング
@@ -152,7 +201,7 @@ const results = await invoke<BulkRaceData[]>("get_bulk_race_data", {
   placeNumbers: [1, 2, 3],
   raceNumbers: [12],
 });`,
-      implementation: "src-tauri/src/commands/scraping.rs:60-75",
+      implementation: "src-tauri/src/commands/scraping.rs:114-144",
     },
   ];
 
@@ -355,6 +404,328 @@ const results = await invoke<BulkRaceData[]>("get_bulk_race_data", {
 // stats.max_trifecta_payout => 最高3連単配当`,
       implementation: "src-tauri/src/commands/open_api.rs:223-233",
     },
+    {
+      title: "組番ごとの配当統計",
+      commandName: "get_combination_payout_stats",
+      description: "賭式を指定して、組番ごとの的中数・的中率・平均/最小/最高配当を取得します。同着や拡連複の全組番を含みます。",
+      parameters: [
+        {
+          name: "bet_type",
+          type: "string",
+          description: "賭式（win, place, exacta, quinella, quinella_place, trifecta, trio）",
+          required: true,
+        },
+        {
+          name: "venue_code",
+          type: "string",
+          description: "競艇場コード（\"01\"〜\"24\"、省略時は全場）",
+          required: false,
+        },
+        {
+          name: "limit",
+          type: "number",
+          description: "取得件数の上限（デフォルト: 120）",
+          required: false,
+        },
+      ],
+      returnType: "CombinationPayoutStats[]",
+      example: `const stats = await invoke<CombinationPayoutStats[]>("get_combination_payout_stats", {
+  betType: "trifecta",
+  venueCode: "01",
+});
+// stats[0].hit_rate => 最も出現した組番の的中率`,
+      implementation: "src-tauri/src/commands/open_api.rs:252-268",
+    },
+    {
+      title: "オッズ監視サービスの初期化",
+      commandName: "init_odds_poller",
+      description: "オッズの定期取得サービスを初期化します。Open APIサービスと同じデータベースに保存するため、init_open_api_service の後に呼び出します。アプリケーション起動時に自動実行されます。",
+      parameters: [],
+      returnType: "string",
+      example: `const result = await invoke<string>("init_odds_poller");
+// => "Odds poller initialized successfully"`,
+      implementation: "src-tauri/src/commands/odds.rs:18-40",
+    },
+    {
+      title: "オッズの定期取得",
+      commandName: "start_odds_polling",
+      description: "指定したレースの単勝・複勝オッズを一定間隔で取得し、取得時刻付きのスナップショットとして保存します。締切時刻を過ぎたレースから取得をやめ、取得のたびに odds-snapshot イベントでレースの状態を通知します。stop_odds_polling で停止、get_odds_polling_status で状態を取得できます。",
+      parameters: [
+        {
+          name: "races",
+          type: "OddsPollTarget[]",
+          description: "対象レース（date, place_number, race_number、race_closed_at 省略時は保存済みの出走表から取得）",
+          required: true,
+        },
+        {
+          name: "interval_seconds",
+          type: "number",
+          description: "取得間隔（秒、デフォルト: 60、最小: 10）",
+          required: false,
+        },
+      ],
+      returnType: "OddsPollerStatus",
+      example: `await listen<OddsPollRaceStatus>("odds-snapshot", (event) => {
+  console.log(event.payload.snapshot_count);
+});
+const status = await invoke<OddsPollerStatus>("start_odds_polling", {
+  races: [{ date: "2025-07-05", place_number: 1, race_number: 12 }],
+  intervalSeconds: 30,
+});`,
+      implementation: "src-tauri/src/commands/odds.rs:42-56",
+    },
+    {
+      title: "オッズの推移",
+      commandName: "get_odds_movement",
+      description: "保存済みのオッズスナップショットから、艇・賭式ごとのオッズの推移と、最初の取得から下落率の大きい買い目を返します。複勝は範囲の下限で比較します。",
+      parameters: [
+        {
+          name: "date",
+          type: "string",
+          description: "日付（YYYYMMDD または YYYY-MM-DD）",
+          required: true,
+        },
+        {
+          name: "venue_code",
+          type: "string",
+          description: "競艇場コード（\"01\"〜\"24\"）",
+          required: true,
+        },
+        {
+          name: "race_number",
+          type: "number",
+          description: "レース番号（1〜12）",
+          required: true,
+        },
+        {
+          name: "drop_limit",
+          type: "number",
+          description: "下落の大きい買い目の件数（デフォルト: 5）",
+          required: false,
+        },
+      ],
+      returnType: "OddsMovementReport",
+      example: `const movement = await invoke<OddsMovementReport>("get_odds_movement", {
+  date: "2025-07-05", venueCode: "01", raceNumber: 12,
+});
+// movement.biggest_drops[0].change_rate => 最も下がった買い目の変化率`,
+      implementation: "src-tauri/src/commands/open_api.rs:295-312",
+    },
+    {
+      title: "レースの統合ビュー",
+      commandName: "get_race_view",
+      description: "1レースについて、Open API の出走表・直前情報・結果（races / race_participants）と、スクレイピング済みの kyoteibiyori のレースデータをまとめて返します。結果と kyoteibiyori の逃げ率の両方がある場合は、逃げの予想と実際の比較も含みます。どちらのデータもない場合はエラーになります。",
+      parameters: [
+        {
+          name: "date",
+          type: "string",
+          description: "日付（YYYYMMDD または YYYY-MM-DD）",
+          required: true,
+        },
+        {
+          name: "venue_code",
+          type: "string",
+          description: "競艇場コード（\"01\"〜\"24\"、\"1\" も可）",
+          required: true,
+        },
+        {
+          name: "race_number",
+          type: "number",
+          description: "レース番号（1〜12）",
+          required: true,
+        },
+      ],
+      returnType: "RaceView",
+      example: `const view = await invoke<RaceView>("get_race_view", {
+  date: "20251228", venueCode: "01", raceNumber: 12,
+});
+// view.escape_check?.prediction_hit => 逃げ率の予想が当たったか`,
+      implementation: "src-tauri/src/commands/open_api.rs:315-329",
+    },
+    {
+      title: "逃げ率の検証",
+      commandName: "get_escape_rate_calibration",
+      description: "kyoteibiyori の1号艇の逃げ率と、保存済みの結果（1号艇が逃げで1着か）を比較します。逃げ率の区間ごとの予想と実際の割合、逃げ率 0.5 以上を逃げと予想したときの的中率、ブライアスコアを返します。",
+      parameters: [
+        {
+          name: "date_from",
+          type: "string",
+          description: "開始日（YYYYMMDD）",
+          required: false,
+        },
+        {
+          name: "date_to",
+          type: "string",
+          description: "終了日（YYYYMMDD）",
+          required: false,
+        },
+        {
+          name: "venue_code",
+          type: "string",
+          description: "競艇場コード（\"01\"〜\"24\"）",
+          required: false,
+        },
+        {
+          name: "rate_period",
+          type: "string",
+          description: "比較に使う逃げ率（\"last_half_year\" | \"last_year\"、デフォルト: last_half_year）",
+          required: false,
+        },
+      ],
+      returnType: "EscapeCalibrationReport",
+      example: `const report = await invoke<EscapeCalibrationReport>("get_escape_rate_calibration", {
+  dateFrom: "20251201", dateTo: "20251231",
+});
+// report.buckets => 逃げ率の区間ごとの予想と実際`,
+      implementation: "src-tauri/src/commands/open_api.rs:331-353",
+    },
+    {
+      title: "単勝・複勝の期待値計算",
+      commandName: "calculate_expected_values",
+      description: "対象レースの前日までに保存された結果から進入コース・級別・当地勝率・モーター2連率ごとの1着率を集計して各艇の1着/2着以内の確率を推定し、現在の単勝・複勝オッズと組み合わせて期待値・ケリー基準の賭け金割合・割安（overlay）フラグを返します。対象レースの出走表が保存済みである必要があります。",
+      parameters: [
+        {
+          name: "date",
+          type: "string",
+          description: "日付（YYYYMMDD または YYYY-MM-DD）",
+          required: true,
+        },
+        {
+          name: "venue_code",
+          type: "string",
+          description: "競艇場コード（\"01\"〜\"24\"）",
+          required: true,
+        },
+        {
+          name: "race_number",
+          type: "number",
+          description: "レース番号（1〜12）",
+          required: true,
+        },
+        {
+          name: "odds",
+          type: "OddsData",
+          description: "get_win_place_odds_info で取得した単勝・複勝オッズ",
+          required: true,
+        },
+      ],
+      returnType: "ExpectedValueReport",
+      example: `const odds = await invoke<OddsData>("get_win_place_odds_info", {
+  date: "2025-07-05", raceNumber: "12", placeNumber: "1",
+});
+const report = await invoke<ExpectedValueReport>("calculate_expected_values", {
+  date: "2025-07-05", venueCode: "01", raceNumber: 12, odds,
+});
+// report.bets.filter((bet) => bet.is_overlay) => 割安な買い目`,
+      implementation: "src-tauri/src/commands/open_api.rs:276-293",
+    },
+    {
+      title: "買い方戦略のバックテスト",
+      commandName: "run_backtest",
+      description: "対象レースの条件（複合条件検索と同じ SearchParams）・賭式・買い目（組番指定/ボックス/フォーメーション）・賭け金ルールを指定し、保存済みの結果と払戻で古い順に再生します。的中率・回収率・最大ドローダウン・最長連敗・競艇場別の成績を返します。",
+      parameters: [
+        {
+          name: "strategy",
+          type: "BacktestStrategy",
+          description: "戦略（filters, bet_type, combinations, stake, initial_bankroll）",
+          required: true,
+        },
+        {
+          name: "date_from",
+          type: "string",
+          description: "開始日（YYYYMMDD、指定時は filters の日付を上書き）",
+          required: false,
+        },
+        {
+          name: "date_to",
+          type: "string",
+          description: "終了日（YYYYMMDD）",
+          required: false,
+        },
+      ],
+      returnType: "BacktestReport",
+      example: `const report = await invoke<BacktestReport>("run_backtest", {
+  strategy: {
+    filters: { venue_code: "01", exhibition_course_number: 1 },
+    bet_type: "trifecta",
+    combinations: { kind: "formation", first: [1], second: [2, 3], third: [2, 3, 4] },
+    stake: { kind: "flat", amount: 100 },
+  },
+  dateFrom: "20250101",
+  dateTo: "20251231",
+});
+// report.roi => 回収率、report.max_drawdown => 最大ドローダウン（円）`,
+      implementation: "src-tauri/src/commands/open_api.rs:274-290",
+    },
+    {
+      title: "モーター上位・下位",
+      commandName: "get_motor_ranking",
+      description: "競艇場・シーズン（年）ごとにモーターの実際の成績（1〜3着率・展示タイム・平均ST）を集計し、2連率の上位と下位を取得します。集計は motors ビューで、モーターを使用したすべての選手の出走を含みます。シーズンはレース日の年で近似しているため、年の途中でモーターを入れ替えた場では同じ番号の新旧モーターが1行にまとまります。その場合は date_from / date_to で入れ替え日以降の期間を指定してください。",
+      parameters: [
+        {
+          name: "venue_code",
+          type: "string",
+          description: "競艇場コード（\"01\"〜\"24\"）",
+          required: true,
+        },
+        {
+          name: "season",
+          type: "string",
+          description: "シーズン（年、例: \"2025\"。省略時は結果のある最新の年）。date_from / date_to とは同時に指定できません",
+          required: false,
+        },
+        {
+          name: "date_from",
+          type: "string",
+          description: "集計期間の開始日（YYYYMMDD、モーターの入れ替え日など）。指定するとシーズンの代わりにこの期間で集計します",
+          required: false,
+        },
+        {
+          name: "date_to",
+          type: "string",
+          description: "集計期間の終了日（YYYYMMDD、省略時は最新まで）",
+          required: false,
+        },
+        {
+          name: "min_starts",
+          type: "number",
+          description: "集計対象の最低出走数（デフォルト: 10）",
+          required: false,
+        },
+        {
+          name: "limit",
+          type: "number",
+          description: "上位・下位それぞれの件数（デフォルト: 10）",
+          required: false,
+        },
+      ],
+      returnType: "MotorRanking",
+      example: `const ranking = await invoke<MotorRanking>("get_motor_ranking", {
+  venueCode: "01",
+  minStarts: 20,
+});
+// ranking.top[0].top2_rate => 最も2連率の高いモーター`,
+      implementation: "src-tauri/src/commands/open_api.rs:371-402",
+    },
+    {
+      title: "選手プロフィール",
+      commandName: "get_racer_profile",
+      description: "選手マスタ（氏名・支部・級別・期別）と級別の履歴、保存済みレース結果から集計した着順分布・勝率・平均ST・コース別/場別成績を取得します。選手マスタは出走表の保存時と kyoteibiyori のスクレイピング時に更新されます。",
+      parameters: [
+        {
+          name: "racer_number",
+          type: "number",
+          description: "登録番号",
+          required: true,
+        },
+      ],
+      returnType: "RacerProfile",
+      example: `const profile = await invoke<RacerProfile>("get_racer_profile", {
+  racerNumber: 4444,
+});
+// profile.course_stats[0].win_rate => 1コースの1着率`,
+      implementation: "src-tauri/src/commands/open_api.rs:272-283",
+    },
     {
       title: "高配当レースの検索",
       commandName: "search_high_payout_races",
@@ -33,10 +33,29 @@ export interface SearchParams {
   // 着順条件
   place_number?: number;
 
+  // 払戻条件（同じ払戻行に適用）
+  bet_type?: string; // "win" | "place" | "exacta" | "quinella" | "quinella_place" | "trifecta" | "trio"
+  combination?: string; // 例: "1-2-3", "2=6"
+  min_payout?: number;
+  max_payout?: number;
+
+  // 展示条件（選手条件と同じ艇に適用）
+  max_exhibition_time?: number;
+  exhibition_course_number?: number;
+  max_exhibition_start_timing?: number;
+  min_tilt_adjustment?: number;
+
   // 結果数制限
   limit?: number;
 }
 
+// コード値とラベル（天候・決まり手・級別など）
+export interface CodeLabel {
+  code: number;
+  ja: string;
+  en: string;
+}
+
 // 正規化DBから返されるレース情報
 export interface RaceRecord {
   id: number;
@@ -62,6 +81,12 @@ export interface RaceRecord {
   winner_boat_number?: number;
   winner_racer_number?: number;
 
+  // 直前気象条件
+  preview_wind?: number;
+  preview_wave?: number;
+  preview_temperature?: number;
+  preview_water_temperature?: number;
+
   // レース詳細
   race_grade_number?: number;
   race_title?: string;
@@ -70,6 +95,12 @@ export interface RaceRecord {
 
   created_at: string;
   updated_at: string;
+
+  // コード値のラベル
+  race_weather_label?: CodeLabel;
+  race_wind_direction_label?: CodeLabel;
+  race_technique_label?: CodeLabel;
+  race_grade_label?: CodeLabel;
 }
 
 // 選手情報
@@ -94,6 +125,7 @@ export interface RaceParticipantRecord {
 
   // レース結果
   place_number?: number;
+  decision_hand?: string; // 1着艇の決まり手
 
   // 成績統計
   flying_count?: number;
@@ -114,8 +146,20 @@ export interface RaceParticipantRecord {
   assigned_boat_top_2_percent?: number;
   assigned_boat_top_3_percent?: number;
 
+  // 展示情報
+  exhibition_time?: number;
+  exhibition_course_number?: number;
+  exhibition_start_timing?: number;
+  tilt_adjustment?: number;
+  weight_adjustment?: number;
+
   created_at: string;
   updated_at: string;
+
+  // コード値のラベル
+  racer_class_label?: CodeLabel;
+  racer_branch_label?: CodeLabel;
+  racer_birthplace_label?: CodeLabel;
 }
 
 // バックエンドの戻り値型 Vec<(RaceRecord, Vec<RaceParticipantRecord>)>
@@ -0,0 +1,26 @@
+// バックエンド（Tauriコマンド）から返されるエラー
+export type AppErrorCode =
+  | "NETWORK_ERROR"
+  | "ELEMENT_NOT_FOUND"
+  | "PARSE_ERROR"
+  | "DB_ERROR"
+  | "IO_ERROR"
+  | "INVALID_INPUT"
+  | "NOT_INITIALIZED"
+  | "INTERNAL_ERROR";
+
+export interface AppError {
+  code: AppErrorCode;
+  message: string;
+  // エラー種別ごとの補足情報（url, status, selector, field, path など）
+  details: Record<string, unknown> | null;
+}
+
+export const isAppError = (value: unknown): value is AppError => {
+  return (
+    typeof value === "object" &&
+    value !== null &&
+    "code" in value &&
+    "message" in value
+  );
+};
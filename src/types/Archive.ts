@@ -0,0 +1,21 @@
+// 生HTMLアーカイブのエントリ（取得元ページの追跡用）
+export interface ArchiveEntry {
+  url: string;
+  date: string | null; // YYYYMMDD
+  place_number: number | null;
+  race_number: number | null;
+  slider: number | null;
+  content_hash: string; // SHA-256
+  fetched_at: string; // RFC3339
+  path: string; // アーカイブルートからの相対パス
+  size_bytes: number;
+}
+
+// アーカイブからの再パース結果
+export interface ReparseSummary {
+  pages_processed: number;
+  race_data_updated: number;
+  odds_updated: number;
+  values_changed: number;
+  errors: string[];
+}
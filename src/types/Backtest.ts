@@ -0,0 +1,53 @@
+import type { SearchParams } from './AdvancedSearch';
+import type { BetType } from './OpenApiData';
+
+// 買い目の指定（艇番は1〜6）
+export type CombinationRule =
+  | { kind: "fixed"; combinations: string[] } // 例: ["1-2-3"], ["2=6"]
+  | { kind: "box"; boats: number[] }
+  | { kind: "formation"; first: number[]; second?: number[]; third?: number[] };
+
+// 1点あたりの賭け金
+export type StakeRule =
+  | { kind: "flat"; amount: number } // 100円単位
+  | { kind: "proportional"; fraction: number }; // 資金に対する割合（initial_bankroll が必要）
+
+export interface BacktestStrategy {
+  filters?: SearchParams; // 着順・払戻の条件は先読みになるため使わない
+  bet_type: BetType;
+  combinations: CombinationRule;
+  stake: StakeRule;
+  initial_bankroll?: number;
+}
+
+export interface VenueBacktestStats {
+  venue_code: string;
+  race_count: number;
+  hit_count: number;
+  hit_rate: number; // 0〜1
+  total_stake: number;
+  total_return: number;
+  roi?: number; // 払戻 / 賭け金
+}
+
+// バックテスト結果（run_backtest）
+export interface BacktestReport {
+  bet_type: BetType;
+  date_from?: string; // YYYYMMDD
+  date_to?: string;
+  combinations: string[];
+  race_count: number;
+  skipped_race_count: number;
+  bet_count: number;
+  hit_count: number;
+  hit_rate: number;
+  total_stake: number;
+  total_return: number;
+  profit: number;
+  roi?: number;
+  max_drawdown: number;
+  max_drawdown_rate?: number;
+  longest_losing_streak: number;
+  final_bankroll?: number;
+  venues: VenueBacktestStats[];
+}
@@ -0,0 +1,17 @@
+// 一括取得ジョブ（一時停止・再開・中止が可能）
+export type JobKind = "scraping_bulk" | "open_api_bulk";
+
+export type JobStatus = "pending" | "running" | "paused" | "cancelled" | "completed";
+
+export interface BulkJob {
+  id: number;
+  kind: JobKind;
+  status: JobStatus;
+  params_json: string;
+  total_units: number;
+  done_units: number;
+  skipped_units: number;
+  failed_units: number;
+  created_at: string; // RFC3339
+  updated_at: string; // RFC3339
+}
@@ -1,9 +1,10 @@
 export interface BulkProgressPayload {
+  job_id: number;
   message: string;
   current: number;
   total: number;
   date: string;
   place_number: number;
   race_number: number;
-  status: "cache_hit" | "scraping" | "saved" | "error" | "completed";
+  status: "cache_hit" | "scraping" | "saved" | "error" | "completed" | "paused" | "cancelled";
 }
@@ -0,0 +1,31 @@
+// 艇ごとの推定確率（保存済みの結果から推定）
+export interface BoatProbability {
+  boat_number: number;
+  racer_number?: number;
+  racer_name?: string;
+  course_number: number; // 展示進入コース（なければ枠番）
+  win_probability: number; // 0〜1
+  place_probability: number; // 2着以内
+}
+
+// 買い目ごとの期待値
+export interface ExpectedValueBet {
+  bet_type: "win" | "place";
+  boat_number: number;
+  odds: number; // 複勝は範囲の下限
+  odds_range?: string; // 例: "1.5-3.3"
+  probability: number;
+  expected_value: number; // 確率 × オッズ（1を超えると割安）
+  kelly_fraction: number;
+  is_overlay: boolean;
+}
+
+// 期待値計算の結果（calculate_expected_values）
+export interface ExpectedValueReport {
+  race_date: string; // YYYYMMDD
+  venue_code: string;
+  race_number: number;
+  sample_starts: number;
+  boats: BoatProbability[];
+  bets: ExpectedValueBet[]; // 期待値の高い順
+}
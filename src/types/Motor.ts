@@ -0,0 +1,34 @@
+// モーター成績（motors ビュー、競艇場・モーター番号・シーズンごと）
+// シーズンは年で近似しているため、年の途中でモーターを入れ替えた場では新旧のモーターが1行にまとまる
+export interface MotorStats {
+  venue_code: string;
+  motor_number: number;
+  season?: string; // レース日の年（例: "2025"）。期間を指定した集計では省略
+  starts: number;
+  wins: number;
+  top2_count: number;
+  top3_count: number;
+  win_rate?: number; // 0〜1
+  top2_rate?: number;
+  top3_rate?: number;
+  racer_count: number;
+  exhibition_count: number;
+  average_exhibition_time?: number;
+  best_exhibition_time?: number;
+  average_start_timing?: number;
+  program_top_2_percent?: number; // 最新の出走表のモーター2連率（%）
+  program_top_3_percent?: number;
+  first_race_date: string; // YYYYMMDD
+  last_race_date: string; // YYYYMMDD
+}
+
+// 競艇場のモーター上位・下位（get_motor_ranking）
+export interface MotorRanking {
+  venue_code: string;
+  season?: string;
+  date_from?: string; // 期間を指定した場合（YYYYMMDD）
+  date_to?: string;
+  min_starts: number;
+  top: MotorStats[];
+  bottom: MotorStats[];
+}
@@ -1,3 +1,4 @@
+import type { AppError } from './AppError';
 import { RaceData } from './RaceData';
 
 export interface OddsCombination {
@@ -22,5 +23,5 @@ export interface BulkRaceData {
   race_number: number;
   race_data?: RaceData;
   win_place_odds_data?: OddsData;
-  error?: string;
+  error?: AppError; // 取得・解析に失敗した場合
 }
\ No newline at end of file
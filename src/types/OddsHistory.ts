@@ -0,0 +1,55 @@
+// オッズ監視の対象レース（start_odds_polling）
+export interface OddsPollTarget {
+  date: string; // YYYY-MM-DD または YYYYMMDD
+  place_number: number;
+  race_number: number;
+  race_closed_at?: string; // "YYYY-MM-DD HH:MM:SS"（省略時は保存済みの出走表から取得）
+}
+
+// 監視中レースの状態（odds-snapshot イベントのペイロード）
+export interface OddsPollRaceStatus {
+  race_date: string; // YYYYMMDD
+  venue_code: string;
+  race_number: number;
+  race_closed_at: string;
+  snapshot_count: number;
+  last_captured_at?: string;
+  last_error?: string;
+  closed: boolean; // 締切を過ぎて監視を終えた
+}
+
+// オッズ監視の状態
+export interface OddsPollerStatus {
+  running: boolean;
+  interval_seconds: number;
+  started_at?: string;
+  races: OddsPollRaceStatus[];
+}
+
+export interface OddsPoint {
+  captured_at: string; // RFC3339
+  odds: number; // 複勝は範囲の下限
+  odds_max?: number;
+}
+
+// 1艇・1賭式のオッズの推移
+export interface OddsSeries {
+  bet_type: "win" | "place";
+  boat_number: number;
+  points: OddsPoint[];
+  first_odds: number;
+  last_odds: number;
+  change_rate: number; // (最新 - 最初) / 最初、下落は負
+}
+
+// オッズの推移と下落の大きい買い目（get_odds_movement）
+export interface OddsMovementReport {
+  race_date: string; // YYYYMMDD
+  venue_code: string;
+  race_number: number;
+  snapshot_count: number;
+  first_captured_at?: string;
+  last_captured_at?: string;
+  series: OddsSeries[];
+  biggest_drops: OddsSeries[]; // 下落率の大きい順
+}
@@ -0,0 +1,38 @@
+// 公式サイト出走表の1艇分（get_official_race_card、項目名は Open API の出走表に合わせている）
+export interface RaceCardEntry {
+  boat_number: number;
+  racer_number: number;
+  racer_name: string;
+  racer_class: string; // "A1" 〜 "B2"
+  racer_class_number?: number;
+  racer_branch: string; // 支部
+  racer_branch_number?: number;
+  racer_birthplace: string;
+  racer_birthplace_number?: number;
+  racer_age?: number;
+  racer_weight?: number;
+  flying_count: number;
+  late_count: number;
+  average_start_timing?: number;
+  national_top_1_percent?: number; // 全国勝率
+  national_top_2_percent?: number;
+  national_top_3_percent?: number;
+  local_top_1_percent?: number; // 当地勝率
+  local_top_2_percent?: number;
+  local_top_3_percent?: number;
+  assigned_motor_number?: number;
+  assigned_motor_top_2_percent?: number;
+  assigned_motor_top_3_percent?: number;
+  assigned_boat_number?: number;
+  assigned_boat_top_2_percent?: number;
+  assigned_boat_top_3_percent?: number;
+}
+
+// 公式サイトの出走表
+export interface RaceCard {
+  race_date: string; // YYYYMMDD
+  venue_code: string;
+  race_number: number;
+  source_url: string;
+  entries: RaceCardEntry[];
+}
@@ -1,3 +1,5 @@
+import { JobStatus } from "./BulkJob";
+
 // Open API データ管理用の型定義
 
 export type DataType = "previews" | "results" | "programs";
@@ -29,6 +31,7 @@ export type PayoutType = "win" | "trifecta" | "exacta" | "place";
 export interface PayoutEntry {
   combination?: string;
   payout?: number;
+  popularity?: number;
 }
 
 export interface PayoutInfo {
@@ -72,6 +75,19 @@ export interface PayoutStats {
   max_win?: number;
 }
 
+// 組番ごとの配当統計（race_payouts から集計）
+export type BetType = "win" | "place" | "exacta" | "quinella" | "quinella_place" | "trifecta" | "trio";
+
+export interface CombinationPayoutStats {
+  bet_type: BetType;
+  combination: string;
+  hit_count: number;
+  hit_rate: number; // 0〜1
+  avg_payout?: number;
+  min_payout?: number;
+  max_payout?: number;
+}
+
 export interface SearchState {
   status: "idle" | "loading" | "success" | "error";
   results: RaceResult[];
@@ -104,6 +120,8 @@ export interface SummaryState {
 // Bulk Fetch用の型定義
 
 export interface BulkFetchSummary {
+  job_id: number;
+  status: JobStatus;
   total_days: number;
   success_count: number;
   error_count: number;
@@ -113,16 +131,18 @@ export interface BulkFetchSummary {
 
 export interface BulkFetchError {
   date: string;
+  data_type: DataType | null; // 全種別の並行取得時のみ
   error_message: string;
 }
 
 export interface OpenApiBulkProgressPayload {
+  job_id: number;
   message: string;
   current: number;
   total: number;
   date: string;
-  data_type: "previews" | "results" | "programs";
-  status: "fetching" | "cached" | "saved" | "error" | "completed";
+  data_type: DataType | "all";
+  status: "fetching" | "cached" | "saved" | "error" | "completed" | "paused" | "cancelled";
 }
 
 export interface BulkFetchState {
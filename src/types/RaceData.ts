@@ -61,6 +61,13 @@ export interface WinningHandData {
   overtake_rate_6months?: number;
 }
 
+export interface BoatRaceData {
+  boat_number: number;
+  player_basic_info: PlayerBasicInfo;
+  detailed_performance: DetailedPerformanceData;
+  st_data: STRelatedData;
+}
+
 export interface RaceData {
   escape_last_year: number;
   escape_last_half_year: number;
@@ -74,4 +81,5 @@ export interface RaceData {
   detailed_performance: DetailedPerformanceData;
   st_data: STRelatedData;
   winning_hand: WinningHandData;
+  boats: BoatRaceData[];
 }
\ No newline at end of file
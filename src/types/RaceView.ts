@@ -0,0 +1,52 @@
+import type { CodeLabel, RaceParticipantRecord, RaceRecord } from './AdvancedSearch';
+import type { RaceData } from './RaceData';
+
+// 逃げ率（1号艇の逃げ）と実際の結果の比較
+export interface EscapeCheck {
+  escape_last_year: number; // 0〜1
+  escape_last_half_year: number;
+  predicted_escape: boolean; // 直近半年の逃げ率が 0.5 以上
+  winner_boat_number?: number;
+  technique?: CodeLabel; // 決まり手
+  actual_escape: boolean; // 1号艇が逃げで1着
+  prediction_hit: boolean;
+}
+
+// 1レースの Open API データと kyoteibiyori の統計（get_race_view）
+export interface RaceView {
+  race_date: string; // YYYYMMDD
+  venue_code: string;
+  race_number: number;
+  has_program: boolean;
+  has_preview: boolean;
+  has_result: boolean;
+  race?: RaceRecord;
+  participants: RaceParticipantRecord[]; // 艇番順
+  biyori?: RaceData; // スクレイピング済みでなければ省略
+  escape_check?: EscapeCheck;
+}
+
+// 逃げ率の区間ごとの予想と実際
+export interface EscapeCalibrationBucket {
+  rate_from: number;
+  rate_to: number;
+  races: number;
+  escapes: number;
+  average_escape_rate: number; // 区間内の逃げ率の平均（予想）
+  actual_escape_rate: number; // 実際に逃げた割合
+}
+
+// 逃げ率が結果をどれだけ予想できたか（get_escape_rate_calibration）
+export interface EscapeCalibrationReport {
+  date_from?: string;
+  date_to?: string;
+  venue_code?: string;
+  rate_period: "last_half_year" | "last_year";
+  races: number;
+  escapes: number;
+  average_escape_rate: number;
+  actual_escape_rate: number;
+  prediction_accuracy: number; // 逃げ率 0.5 以上で逃げると予想したときの的中率
+  brier_score: number; // 小さいほど良い
+  buckets: EscapeCalibrationBucket[];
+}
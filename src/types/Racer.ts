@@ -0,0 +1,67 @@
+import type { CodeLabel } from './AdvancedSearch';
+
+// 選手マスタ（racers テーブル、登録番号ごとに1行）
+export interface RacerRecord {
+  racer_number: number;
+  racer_name?: string;
+  branch_number?: number;
+  birthplace_number?: number;
+  class_number?: number; // 最新の級別
+  period?: number; // 期別
+  gender?: string;
+  last_seen_date?: string; // YYYYMMDD
+  created_at: string; // RFC3339
+  updated_at: string; // RFC3339
+  class_label?: CodeLabel;
+  branch_label?: CodeLabel;
+  birthplace_label?: CodeLabel;
+}
+
+// 級別の適用期ごとの履歴
+export interface RacerClassHistory {
+  racer_number: number;
+  term: string; // 例: "2025H2"（2025年後期）
+  class_number: number;
+  first_seen_date: string; // YYYYMMDD
+  last_seen_date: string; // YYYYMMDD
+}
+
+export interface PlaceCount {
+  place_number: number;
+  count: number;
+}
+
+export interface RacerCourseStats {
+  course_number: number;
+  starts: number;
+  wins: number;
+  win_rate: number; // 0〜1
+  top2_rate: number;
+  top3_rate: number;
+  average_start_timing?: number;
+}
+
+export interface RacerVenueStats {
+  venue_code: string;
+  starts: number;
+  wins: number;
+  win_rate: number; // 0〜1
+  top3_rate: number;
+}
+
+// 選手プロフィール（get_racer_profile）
+export interface RacerProfile {
+  racer_number: number;
+  racer?: RacerRecord;
+  class_history: RacerClassHistory[];
+  starts: number;
+  place_distribution: PlaceCount[];
+  win_rate: number; // 0〜1
+  top2_rate: number;
+  top3_rate: number;
+  average_start_timing?: number;
+  flying_count?: number; // 最新の出走表時点
+  late_count?: number;
+  course_stats: RacerCourseStats[];
+  venue_stats: RacerVenueStats[];
+}
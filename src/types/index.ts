@@ -1,4 +1,14 @@
 export * from './RaceData';
 export * from './OddsData';
 export * from './ActiveRace';
-export * from './BulkProgress';
\ No newline at end of file
+export * from './BulkProgress';
+export * from './Archive';
+export * from './AppError';
+export * from './BulkJob';
+export * from './Racer';
+export * from './Motor';
+export * from './Backtest';
+export * from './ExpectedValue';
+export * from './OddsHistory';
+export * from './RaceView';
+export * from './OfficialRaceCard';
@@ -0,0 +1,8 @@
+import { isAppError } from "../types/AppError";
+
+// invoke の例外を表示用メッセージに変換
+export const toErrorMessage = (error: unknown): string => {
+  if (isAppError(error)) return error.message;
+  if (error instanceof Error) return error.message;
+  return String(error);
+};